@@ -0,0 +1,259 @@
+//! The serde attributes and doc comments of items, fields and variants.
+
+use syn::meta::ParseNestedMeta;
+use syn::{Attribute, Expr, ExprLit, Lit, LitStr, Meta, Result, Token};
+
+/// # Attrs
+/// The serde attributes which change the schema of an item, a field or a variant, along with its doc comment.
+#[derive(Default)]
+pub(crate) struct Attrs {
+    pub rename: Option<String>,
+    pub rename_all: Option<RenameRule>,
+    pub tag: Option<String>,
+    pub content: Option<String>,
+    pub untagged: bool,
+    pub default: bool,
+    pub skip: bool,
+    pub skip_serializing: bool,
+    pub skip_deserializing: bool,
+    pub skip_serializing_if: bool,
+    pub flatten: bool,
+    pub doc: Option<String>,
+}
+
+impl Attrs {
+    pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut parsed = Attrs::default();
+        let mut doc = Vec::new();
+        for attr in attrs {
+            if attr.path().is_ident("doc") {
+                if let Meta::NameValue(meta) = &attr.meta {
+                    if let Expr::Lit(ExprLit {
+                        lit: Lit::Str(line),
+                        ..
+                    }) = &meta.value
+                    {
+                        let line = line.value();
+                        doc.push(
+                            line.strip_prefix(' ')
+                                .unwrap_or(&line)
+                                .trim_end()
+                                .to_string(),
+                        );
+                    }
+                }
+            } else if attr.path().is_ident("serde") {
+                attr.parse_nested_meta(|meta| parsed.parse_meta(meta))?;
+            }
+        }
+        let doc = doc.join("\n");
+        let doc = doc.trim();
+        parsed.doc = (!doc.is_empty()).then(|| doc.to_string());
+        Ok(parsed)
+    }
+
+    fn parse_meta(&mut self, meta: ParseNestedMeta) -> Result<()> {
+        if meta.path.is_ident("rename") {
+            self.rename = Some(serialized(&meta)?);
+        } else if meta.path.is_ident("rename_all") {
+            let rule = serialized_lit(&meta)?;
+            self.rename_all = Some(
+                RenameRule::from_name(&rule.value())
+                    .ok_or_else(|| syn::Error::new(rule.span(), "unknown rename rule"))?,
+            );
+        } else if meta.path.is_ident("tag") {
+            self.tag = Some(meta.value()?.parse::<LitStr>()?.value());
+        } else if meta.path.is_ident("content") {
+            self.content = Some(meta.value()?.parse::<LitStr>()?.value());
+        } else if meta.path.is_ident("untagged") {
+            self.untagged = true;
+        } else if meta.path.is_ident("default") {
+            self.default = true;
+            skip(&meta)?;
+        } else if meta.path.is_ident("skip") {
+            self.skip = true;
+        } else if meta.path.is_ident("skip_serializing") {
+            self.skip_serializing = true;
+        } else if meta.path.is_ident("skip_deserializing") {
+            self.skip_deserializing = true;
+        } else if meta.path.is_ident("skip_serializing_if") {
+            self.skip_serializing_if = true;
+            skip(&meta)?;
+        } else if meta.path.is_ident("flatten") {
+            self.flatten = true;
+        } else {
+            skip(&meta)?;
+        }
+        Ok(())
+    }
+}
+
+/// The serialized value of `rename = "..."` or `rename(serialize = "...")`.
+fn serialized(meta: &ParseNestedMeta) -> Result<String> {
+    Ok(serialized_lit(meta)?.value())
+}
+
+fn serialized_lit(meta: &ParseNestedMeta) -> Result<LitStr> {
+    if meta.input.peek(Token![=]) {
+        return meta.value()?.parse();
+    }
+    let mut serialize = None;
+    meta.parse_nested_meta(|inner| {
+        let value = inner.value()?.parse::<LitStr>()?;
+        if inner.path.is_ident("serialize") {
+            serialize = Some(value);
+        }
+        Ok(())
+    })?;
+    serialize.ok_or_else(|| meta.error("expected a `serialize` value"))
+}
+
+/// Consume the value of an attribute which does not change the schema.
+fn skip(meta: &ParseNestedMeta) -> Result<()> {
+    if meta.input.peek(Token![=]) {
+        meta.value()?.parse::<Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.parse_nested_meta(|inner| skip(&inner))?;
+    }
+    Ok(())
+}
+
+/// # RenameRule
+/// The case of `rename_all`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "lowercase" => RenameRule::Lower,
+            "UPPERCASE" => RenameRule::Upper,
+            "PascalCase" => RenameRule::Pascal,
+            "camelCase" => RenameRule::Camel,
+            "snake_case" => RenameRule::Snake,
+            "SCREAMING_SNAKE_CASE" => RenameRule::ScreamingSnake,
+            "kebab-case" => RenameRule::Kebab,
+            "SCREAMING-KEBAB-CASE" => RenameRule::ScreamingKebab,
+            _ => return None,
+        })
+    }
+
+    /// Rename a field, whose name is in snake case.
+    pub fn apply_to_field(self, field: &str) -> String {
+        match self {
+            RenameRule::Lower | RenameRule::Snake => field.to_string(),
+            RenameRule::Upper | RenameRule::ScreamingSnake => field.to_ascii_uppercase(),
+            RenameRule::Pascal | RenameRule::Camel => {
+                let mut renamed = String::new();
+                let mut capitalize = self == RenameRule::Pascal;
+                for c in field.chars() {
+                    if c == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        renamed.push(c.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        renamed.push(c);
+                    }
+                }
+                renamed
+            }
+            RenameRule::Kebab => field.replace('_', "-"),
+            RenameRule::ScreamingKebab => field.replace('_', "-").to_ascii_uppercase(),
+        }
+    }
+
+    /// Rename a variant, whose name is in Pascal case.
+    pub fn apply_to_variant(self, variant: &str) -> String {
+        match self {
+            RenameRule::Pascal => variant.to_string(),
+            RenameRule::Lower => variant.to_ascii_lowercase(),
+            RenameRule::Upper => variant.to_ascii_uppercase(),
+            RenameRule::Camel => {
+                let mut chars = variant.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                    None => String::new(),
+                }
+            }
+            RenameRule::Snake
+            | RenameRule::ScreamingSnake
+            | RenameRule::Kebab
+            | RenameRule::ScreamingKebab => {
+                let mut snake = String::new();
+                for (i, c) in variant.char_indices() {
+                    if i > 0 && c.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(c.to_ascii_lowercase());
+                }
+                match self {
+                    RenameRule::Snake => snake,
+                    rule => rule.apply_to_field(&snake),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rename_field() {
+        let renamed: Vec<String> = [
+            "lowercase",
+            "UPPERCASE",
+            "PascalCase",
+            "camelCase",
+            "SCREAMING-KEBAB-CASE",
+        ]
+        .iter()
+        .map(|rule| {
+            RenameRule::from_name(rule)
+                .unwrap()
+                .apply_to_field("pet_id")
+        })
+        .collect();
+        assert_eq!(renamed, ["pet_id", "PET_ID", "PetId", "petId", "PET-ID"]);
+    }
+
+    #[test]
+    fn test_rename_variant() {
+        let renamed: Vec<String> = [
+            "lowercase",
+            "camelCase",
+            "snake_case",
+            "SCREAMING_SNAKE_CASE",
+            "kebab-case",
+        ]
+        .iter()
+        .map(|rule| {
+            RenameRule::from_name(rule)
+                .unwrap()
+                .apply_to_variant("NotFound")
+        })
+        .collect();
+        assert_eq!(
+            renamed,
+            [
+                "notfound",
+                "notFound",
+                "not_found",
+                "NOT_FOUND",
+                "not-found"
+            ]
+        );
+        assert!(RenameRule::from_name("Title Case").is_none());
+    }
+}
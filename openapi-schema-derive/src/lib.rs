@@ -0,0 +1,368 @@
+//! `#[derive(ToSchema)]`, which builds the `v3::Schema` of a struct or an enum for `openapi-schema`.
+//!
+//! The macro is re-exported by `openapi-schema` with its `derive` feature, see `openapi_schema::v3::ToSchema`. The
+//! schema follows the serde attributes of the type, so that it describes its JSON:
+//!
+//! - `rename` and `rename_all` rename the properties, the variants and the schema itself;
+//! - `skip` leaves a field or a variant out, and `flatten` merges the properties of a field into the object;
+//! - a field which is only deserialized is `writeOnly`, and one which is only serialized is `readOnly`;
+//! - a field is required unless it is an `Option`, it has a `default` or its struct has one, or it has a
+//!   `skip_serializing_if`;
+//! - an enum is externally tagged, internally tagged by its `tag`, adjacently tagged by its `tag` and `content`, or
+//!   `untagged`, and an enum of unit variants is an enum of strings.
+//!
+//! Doc comments become the descriptions of the schema and of its properties.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, parse_quote, Data, DataEnum, DeriveInput, Fields, Ident, Type};
+
+mod attr;
+
+use attr::{Attrs, RenameRule};
+
+#[proc_macro_derive(ToSchema, attributes(serde))]
+pub fn derive_to_schema(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let attrs = Attrs::parse(&input.attrs)?;
+    let ident = &input.ident;
+    let (schema, dependencies) = match &input.data {
+        Data::Struct(data) => struct_schema(&data.fields, &attrs)?,
+        Data::Enum(data) => enum_schema(data, &attrs)?,
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "ToSchema cannot be derived for a union",
+            ));
+        }
+    };
+
+    // a generic type is inlined, as its schema depends on its parameters
+    let name = if input.generics.type_params().next().is_none() {
+        let name = attrs.rename.clone().unwrap_or_else(|| unraw(ident));
+        quote!(::core::option::Option::Some(::std::string::ToString::to_string(#name)))
+    } else {
+        quote!(::core::option::Option::None)
+    };
+    let mut generics = input.generics.clone();
+    for param in generics.type_params_mut() {
+        param
+            .bounds
+            .push(parse_quote!(::openapi_schema::v3::ToSchema));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let description = doc(&attrs.doc);
+    let components = if dependencies.is_empty() {
+        quote!(_components)
+    } else {
+        quote!(components)
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::openapi_schema::v3::ToSchema for #ident #ty_generics #where_clause {
+            fn name() -> ::core::option::Option<::std::string::String> {
+                #name
+            }
+
+            fn schema() -> ::openapi_schema::v3::Schema {
+                ::openapi_schema::v3::__private::with_description(#schema, #description)
+            }
+
+            fn register_dependencies(#components: &mut ::openapi_schema::v3::Components) {
+                #(#dependencies)*
+            }
+        }
+    })
+}
+
+/// The schema of the fields of a struct, and the statements registering their types.
+fn struct_schema(fields: &Fields, attrs: &Attrs) -> syn::Result<(TokenStream2, Vec<TokenStream2>)> {
+    let mut dependencies = Vec::new();
+    let schema = match fields {
+        Fields::Named(_) => named(
+            fields,
+            attrs.rename_all,
+            attrs.default,
+            Vec::new(),
+            &mut dependencies,
+        )?,
+        Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => {
+            let ty = &unnamed.unnamed[0].ty;
+            dependencies.push(register(ty));
+            quote!(::openapi_schema::v3::__private::inline::<#ty>())
+        }
+        Fields::Unnamed(_) => tuple(fields, &mut dependencies),
+        Fields::Unit => quote!(::openapi_schema::v3::__private::unit()),
+    };
+    Ok((schema, dependencies))
+}
+
+/// The object of named fields, whose properties follow `properties`.
+fn named(
+    fields: &Fields,
+    rename_all: Option<RenameRule>,
+    default: bool,
+    mut properties: Vec<TokenStream2>,
+    dependencies: &mut Vec<TokenStream2>,
+) -> syn::Result<TokenStream2> {
+    let mut flattened = Vec::new();
+    for field in fields {
+        let attrs = Attrs::parse(&field.attrs)?;
+        if attrs.skip || (attrs.skip_serializing && attrs.skip_deserializing) {
+            continue;
+        }
+        let ty = &field.ty;
+        if attrs.flatten {
+            flattened.push(quote!(::openapi_schema::v3::__private::flatten::<#ty>(&mut schema);));
+            dependencies.push(
+                quote!(<#ty as ::openapi_schema::v3::ToSchema>::register_dependencies(components);),
+            );
+            continue;
+        }
+        let ident = field.ident.as_ref().map(unraw).unwrap_or_default();
+        let name = match (attrs.rename, rename_all) {
+            (Some(name), _) => name,
+            (None, Some(rule)) => rule.apply_to_field(&ident),
+            (None, None) => ident,
+        };
+        let required = !(default || attrs.default || attrs.skip_serializing_if || is_option(ty));
+        let description = doc(&attrs.doc);
+        let (read_only, write_only) = (attrs.skip_deserializing, attrs.skip_serializing);
+        properties.push(quote! {
+            (
+                #name,
+                ::openapi_schema::v3::__private::access(
+                    ::openapi_schema::v3::__private::describe(
+                        <#ty as ::openapi_schema::v3::ToSchema>::reference(),
+                        #description,
+                    ),
+                    #read_only,
+                    #write_only,
+                ),
+                #required,
+            )
+        });
+        dependencies.push(register(ty));
+    }
+    Ok(quote! {
+        {
+            #[allow(unused_mut)]
+            let mut schema = ::openapi_schema::v3::__private::object(::std::vec![#(#properties),*]);
+            #(#flattened)*
+            schema
+        }
+    })
+}
+
+/// The fixed-length array of unnamed fields.
+fn tuple(fields: &Fields, dependencies: &mut Vec<TokenStream2>) -> TokenStream2 {
+    let types: Vec<&Type> = fields.iter().map(|field| &field.ty).collect();
+    dependencies.extend(types.iter().map(|ty| register(ty)));
+    quote! {
+        ::openapi_schema::v3::__private::tuple(::std::vec![
+            #(<#types as ::openapi_schema::v3::ToSchema>::reference()),*
+        ])
+    }
+}
+
+/// How the variants of an enum are represented, see <https://serde.rs/enum-representations.html>.
+enum Tagging<'a> {
+    External,
+    Internal { tag: &'a str },
+    Adjacent { tag: &'a str, content: &'a str },
+    Untagged,
+}
+
+fn enum_schema(data: &DataEnum, attrs: &Attrs) -> syn::Result<(TokenStream2, Vec<TokenStream2>)> {
+    let tagging = match (&attrs.tag, &attrs.content) {
+        _ if attrs.untagged => Tagging::Untagged,
+        (Some(tag), Some(content)) => Tagging::Adjacent { tag, content },
+        (Some(tag), None) => Tagging::Internal { tag },
+        (None, _) => Tagging::External,
+    };
+
+    let mut units = Vec::new();
+    let mut schemas = Vec::new();
+    let mut dependencies = Vec::new();
+    for variant in &data.variants {
+        let variant_attrs = Attrs::parse(&variant.attrs)?;
+        if variant_attrs.skip
+            || (variant_attrs.skip_serializing && variant_attrs.skip_deserializing)
+        {
+            continue;
+        }
+        let name = match (&variant_attrs.rename, attrs.rename_all) {
+            (Some(name), _) => name.clone(),
+            (None, Some(rule)) => rule.apply_to_variant(&unraw(&variant.ident)),
+            (None, None) => unraw(&variant.ident),
+        };
+        let fields = &variant.fields;
+        let newtype = match fields {
+            Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => Some(&unnamed.unnamed[0].ty),
+            _ => None,
+        };
+        // the schema of the content of the variant, `None` for a unit variant
+        let content = match (fields, newtype) {
+            (Fields::Unit, _) => None,
+            (_, Some(ty)) => {
+                dependencies.push(register(ty));
+                Some(quote!(<#ty as ::openapi_schema::v3::ToSchema>::reference()))
+            }
+            (Fields::Named(_), _) => {
+                let object = named(
+                    fields,
+                    variant_attrs.rename_all,
+                    false,
+                    Vec::new(),
+                    &mut dependencies,
+                )?;
+                Some(quote!(::openapi_schema::v3::RefOrObject::Object(#object)))
+            }
+            (Fields::Unnamed(_), _) => {
+                let tuple = tuple(fields, &mut dependencies);
+                Some(quote!(::openapi_schema::v3::RefOrObject::Object(#tuple)))
+            }
+        };
+        let tag_property = |tag: &str| {
+            quote! {
+                (
+                    #tag,
+                    ::openapi_schema::v3::RefOrObject::Object(
+                        ::openapi_schema::v3::__private::string_enum(&[#name]),
+                    ),
+                    true,
+                )
+            }
+        };
+
+        let schema = match (&tagging, content) {
+            (Tagging::External, None) => {
+                units.push(name);
+                continue;
+            }
+            (Tagging::External, Some(content)) => {
+                quote!(::openapi_schema::v3::__private::object(
+                    ::std::vec![(#name, #content, true)]
+                ))
+            }
+            (Tagging::Internal { tag }, None) | (Tagging::Adjacent { tag, .. }, None) => {
+                let tag = tag_property(tag);
+                quote!(::openapi_schema::v3::__private::object(::std::vec![#tag]))
+            }
+            (Tagging::Internal { tag }, Some(_)) => match (fields, newtype) {
+                (_, Some(ty)) => {
+                    let tag = tag_property(tag);
+                    quote! {
+                        ::openapi_schema::v3::__private::all_of(::std::vec![
+                            ::openapi_schema::v3::RefOrObject::Object(
+                                ::openapi_schema::v3::__private::object(::std::vec![#tag]),
+                            ),
+                            <#ty as ::openapi_schema::v3::ToSchema>::reference(),
+                        ])
+                    }
+                }
+                (Fields::Named(_), _) => named(
+                    fields,
+                    variant_attrs.rename_all,
+                    false,
+                    vec![tag_property(tag)],
+                    &mut Vec::new(),
+                )?,
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        &variant.ident,
+                        "an internally tagged enum cannot have tuple variants",
+                    ));
+                }
+            },
+            (Tagging::Adjacent { tag, content: key }, Some(content)) => {
+                let tag = tag_property(tag);
+                quote!(::openapi_schema::v3::__private::object(
+                    ::std::vec![#tag, (#key, #content, true)]
+                ))
+            }
+            (Tagging::Untagged, None) => quote!(::openapi_schema::v3::__private::unit()),
+            (Tagging::Untagged, Some(content)) => match newtype {
+                Some(ty) => quote!(::openapi_schema::v3::__private::inline::<#ty>()),
+                None => quote! {
+                    match #content {
+                        ::openapi_schema::v3::RefOrObject::Object(schema) => schema,
+                        reference => ::openapi_schema::v3::__private::all_of(::std::vec![reference]),
+                    }
+                },
+            },
+        };
+        let description = doc(&variant_attrs.doc);
+        schemas
+            .push(quote!(::openapi_schema::v3::__private::with_description(#schema, #description)));
+    }
+
+    let schema = match tagging {
+        Tagging::External if schemas.is_empty() => {
+            quote!(::openapi_schema::v3::__private::string_enum(&[#(#units),*]))
+        }
+        Tagging::External => {
+            if !units.is_empty() {
+                schemas.insert(
+                    0,
+                    quote!(::openapi_schema::v3::__private::string_enum(&[#(#units),*])),
+                );
+            }
+            quote!(::openapi_schema::v3::__private::one_of(
+                ::std::vec![#(#schemas),*],
+                ::core::option::Option::None
+            ))
+        }
+        Tagging::Internal { tag } | Tagging::Adjacent { tag, .. } => quote! {
+            ::openapi_schema::v3::__private::one_of(::std::vec![#(#schemas),*], ::core::option::Option::Some(#tag))
+        },
+        Tagging::Untagged => {
+            quote!(::openapi_schema::v3::__private::one_of(
+                ::std::vec![#(#schemas),*],
+                ::core::option::Option::None
+            ))
+        }
+    };
+    Ok((schema, dependencies))
+}
+
+/// The statement registering the named types of a field.
+fn register(ty: &Type) -> TokenStream2 {
+    quote!(<#ty as ::openapi_schema::v3::ToSchema>::register(components);)
+}
+
+/// Whether the type is an `Option`, which is not required.
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option"),
+        _ => false,
+    }
+}
+
+/// The `Option<&str>` of a doc comment.
+fn doc(doc: &Option<String>) -> TokenStream2 {
+    match doc {
+        Some(doc) => quote!(::core::option::Option::Some(#doc)),
+        None => quote!(::core::option::Option::None),
+    }
+}
+
+/// The name of an identifier, without the `r#` of a raw identifier.
+fn unraw(ident: &Ident) -> String {
+    let ident = ident.to_string();
+    ident
+        .strip_prefix("r#")
+        .map(str::to_string)
+        .unwrap_or(ident)
+}
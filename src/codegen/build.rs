@@ -0,0 +1,195 @@
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::loader::{is_url, Loader};
+use crate::{Doc, Error};
+
+use super::Generator;
+
+/// # Build
+/// Generates Rust source from a document and its references in a build script.
+///
+/// The document is loaded from a path along with every document it references, which are bundled into it; a Swagger
+/// 2.0 document is converted to OpenAPI 3.0 first. The source is written to `types.rs`, and to `client.rs` and
+/// `server.rs` when asked for, in the output directory.
+pub struct Build {
+    spec: PathBuf,
+    loader: Loader,
+    out_dir: Option<PathBuf>,
+    client: bool,
+    server: bool,
+    rustfmt: bool,
+}
+
+/// The result of a [`Build`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Generated {
+    /// The files which were generated, in the output directory.
+    pub files: Vec<PathBuf>,
+    /// The files the source was generated from, the document and every local document it references, sorted.
+    pub dependencies: Vec<PathBuf>,
+}
+
+impl Build {
+    /// A build of the document at `spec`, whose source is written to the `OUT_DIR` of the build script.
+    pub fn new<P>(spec: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        Build {
+            spec: spec.as_ref().to_path_buf(),
+            loader: Loader::new(),
+            out_dir: None,
+            client: false,
+            server: false,
+            rustfmt: true,
+        }
+    }
+
+    /// Write the source to another directory than `OUT_DIR`.
+    pub fn with_out_dir<P>(mut self, out_dir: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        self.out_dir = Some(out_dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Load the documents with another loader, such as one with a fetcher for remote documents.
+    pub fn with_loader(mut self, loader: Loader) -> Self {
+        self.loader = loader;
+        self
+    }
+
+    /// Generate `client.rs`, see [`Generator::client`].
+    pub fn with_client(mut self, client: bool) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Generate `server.rs`, see [`Generator::server`].
+    pub fn with_server(mut self, server: bool) -> Self {
+        self.server = server;
+        self
+    }
+
+    /// Format the source with `rustfmt`, which is the default. The source is written as generated when `rustfmt`
+    /// is not installed.
+    pub fn with_rustfmt(mut self, rustfmt: bool) -> Self {
+        self.rustfmt = rustfmt;
+        self
+    }
+
+    /// Generate the source and print the `cargo:rerun-if-changed` lines of its dependencies.
+    ///
+    /// ```no_run
+    /// // in the `main` of build.rs
+    /// openapi_schema::codegen::Build::new("openapi.yaml")
+    ///     .with_client(true)
+    ///     .run()
+    ///     .unwrap();
+    /// ```
+    pub fn run(&self) -> Result<Generated, Error> {
+        let generated = self.generate()?;
+        for dependency in &generated.dependencies {
+            println!("cargo:rerun-if-changed={}", dependency.display());
+        }
+        Ok(generated)
+    }
+
+    /// Generate the source without printing anything.
+    ///
+    /// Files whose content did not change are left untouched, so that they are not rebuilt.
+    pub fn generate(&self) -> Result<Generated, Error> {
+        let out_dir = match &self.out_dir {
+            Some(out_dir) => out_dir.clone(),
+            None => env::var_os("OUT_DIR").map(PathBuf::from).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "OUT_DIR is not set, the build is not run from a build script",
+                )
+            })?,
+        };
+
+        let mut set = self.loader.load(&self.spec)?;
+        let dependencies = set
+            .documents()
+            .map(|(document, _)| document)
+            .filter(|document| !is_url(document))
+            .map(PathBuf::from)
+            .collect();
+        let generator = match set.doc()? {
+            Doc::V3(mut openapi) => {
+                openapi.bundle(&set)?;
+                Generator::new(openapi)
+            }
+            Doc::V2(swagger) => {
+                // references to other documents are kept by the conversion and bundled against the converted root
+                let (mut openapi, _) = swagger.to_v3();
+                set.replace_root(serde_json::to_value(&openapi)?);
+                openapi.bundle(&set)?;
+                Generator::new(openapi)
+            }
+            Doc::V31(openapi) => {
+                return Err(Error::UnsupportedVersion {
+                    path: Some(self.spec.clone()),
+                    version: openapi.openapi,
+                })
+            }
+        };
+
+        let mut outputs = vec![("types.rs", generator.types()?)];
+        if self.client {
+            outputs.push(("client.rs", generator.client()?));
+        }
+        if self.server {
+            outputs.push(("server.rs", generator.server()?));
+        }
+
+        fs::create_dir_all(&out_dir).map_err(|e| Error::from(e).with_path(&out_dir))?;
+        let mut files = Vec::new();
+        for (name, source) in outputs {
+            let source = if self.rustfmt {
+                rustfmt(source)
+            } else {
+                source
+            };
+            let path = out_dir.join(name);
+            if fs::read_to_string(&path).ok().as_deref() != Some(source.as_str()) {
+                fs::write(&path, source).map_err(|e| Error::from(e).with_path(&path))?;
+            }
+            files.push(path);
+        }
+        Ok(Generated {
+            files,
+            dependencies,
+        })
+    }
+}
+
+/// Format source with `rustfmt`, or return it as is if that fails.
+fn rustfmt(source: String) -> String {
+    let child = Command::new("rustfmt")
+        .args(["--edition", "2021"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn();
+    let Ok(mut child) = child else {
+        return source;
+    };
+    let written = child
+        .stdin
+        .take()
+        .map(|mut stdin| stdin.write_all(source.as_bytes()))
+        .transpose();
+    match (written, child.wait_with_output()) {
+        (Ok(_), Ok(output)) if output.status.success() => {
+            String::from_utf8(output.stdout).unwrap_or(source)
+        }
+        _ => source,
+    }
+}
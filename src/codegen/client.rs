@@ -0,0 +1,561 @@
+//! The typed HTTP client of the operations of a document.
+
+use crate::v3::OpenApi;
+
+use super::names::doc_comment;
+use super::operations::{
+    render_definitions, uses_map, BodyDef, BodyKind, OperationDef, ParamDef, ResponseDef, Status,
+};
+use super::types::Types;
+
+/// The types and traits the client defines or imports, which the generated types cannot be named after.
+pub(crate) const NAMES: &[&str] = &[
+    "Client",
+    "ClientError",
+    "Future",
+    "HttpRequest",
+    "HttpResponse",
+    "Style",
+    "Transport",
+];
+
+/// The request and response types, the transport trait and the error of the client, which do not depend on the
+/// document.
+const TRANSPORT: &str = r#"/// A request sent by the [`Client`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct HttpRequest {
+    /// The uppercase method, such as `GET`.
+    pub method: &'static str,
+    /// The URL, with its query string.
+    pub url: String,
+    /// The headers, with lowercase names.
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Vec<u8>>,
+}
+
+/// A response received by the [`Client`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+/// Sends the requests of the [`Client`], such as over HTTP, or to an in-memory server in tests.
+pub trait Transport {
+    type Error;
+
+    fn send(&self, request: HttpRequest) -> impl Future<Output = Result<HttpResponse, Self::Error>>;
+}
+
+/// Why an operation failed.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ClientError<E> {
+    /// The transport could not send the request.
+    Transport(E),
+    /// A parameter or the body could not be encoded.
+    Encode(String),
+    /// The body of a response does not match its type.
+    Decode { status: u16, message: String },
+    /// The status of a response is not one the operation describes.
+    UnexpectedStatus(HttpResponse),
+}
+
+impl<E: fmt::Display> fmt::Display for ClientError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Transport(error) => write!(f, "the request could not be sent: {}", error),
+            ClientError::Encode(message) => write!(f, "the request could not be encoded: {}", message),
+            ClientError::Decode { status, message } => {
+                write!(f, "the {} response could not be decoded: {}", status, message)
+            }
+            ClientError::UnexpectedStatus(response) => {
+                write!(f, "the {} status is not described", response.status)
+            }
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> std::error::Error for ClientError<E> {}
+"#;
+
+/// The functions the generated methods share, emitted only when one of them needs it.
+const ENCODE_VALUE: &str = r#"fn encode_value<V: serde::Serialize + ?Sized>(
+    name: &str,
+    style: Style,
+    explode: bool,
+    value: &V,
+) -> Result<String, String> {
+    let value = serde_json::to_value(value).map_err(|error| error.to_string())?;
+    style.encode(name, explode, &value).map_err(|error| error.to_string())
+}
+"#;
+
+const ENCODE_PAIRS: &str = r#"fn encode_pairs<V: serde::Serialize + ?Sized>(
+    name: &str,
+    style: Style,
+    explode: bool,
+    allow_reserved: bool,
+    value: &V,
+) -> Result<Vec<(String, String)>, String> {
+    let value = serde_json::to_value(value).map_err(|error| error.to_string())?;
+    style
+        .encode_pairs(name, explode, allow_reserved, &value)
+        .map_err(|error| error.to_string())
+}
+"#;
+
+const JOIN_PAIRS: &str = r#"fn join_pairs(pairs: &[(String, String)], separator: &str) -> String {
+    pairs
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+"#;
+
+const ENCODE_JSON_TEXT: &str = r#"fn encode_json_text<V: serde::Serialize + ?Sized>(value: &V) -> Result<String, String> {
+    serde_json::to_string(value).map_err(|error| error.to_string())
+}
+"#;
+
+const ENCODE_JSON: &str = r#"fn encode_json<V: serde::Serialize + ?Sized>(value: &V) -> Result<Vec<u8>, String> {
+    serde_json::to_vec(value).map_err(|error| error.to_string())
+}
+"#;
+
+const ENCODE_FORM: &str = r#"fn encode_form<V: serde::Serialize + ?Sized>(value: &V) -> Result<Vec<u8>, String> {
+    let serde_json::Value::Object(properties) =
+        serde_json::to_value(value).map_err(|error| error.to_string())?
+    else {
+        return Err("a form must be an object".to_string());
+    };
+    let mut pairs = Vec::new();
+    for (name, value) in properties.iter().filter(|(_, value)| !value.is_null()) {
+        pairs.extend(
+            Style::Form
+                .encode_pairs(name, true, false, value)
+                .map_err(|error| error.to_string())?,
+        );
+    }
+    Ok(join_pairs(&pairs, "&").into_bytes())
+}
+"#;
+
+const DECODE_JSON: &str = r#"fn decode_json<V: serde::de::DeserializeOwned>(body: &[u8]) -> Result<V, String> {
+    serde_json::from_slice(body).map_err(|error| error.to_string())
+}
+"#;
+
+const DECODE_TEXT: &str = r#"fn decode_text(body: &[u8]) -> Result<String, String> {
+    String::from_utf8(body.to_vec()).map_err(|error| error.to_string())
+}
+"#;
+
+/// Render the client module: the types, the transport and a `Client` with one method for each operation.
+pub(crate) fn render(openapi: &OpenApi, types: &Types, operations: &[OperationDef]) -> String {
+    let params = || operations.iter().flat_map(|operation| &operation.params);
+    let bodies = || {
+        operations
+            .iter()
+            .flat_map(|operation| &operation.body)
+            .map(|body| body.kind)
+    };
+    let responses = || {
+        operations
+            .iter()
+            .flat_map(|operation| &operation.responses)
+            .flat_map(|response| &response.body)
+            .map(|body| body.kind)
+    };
+    let encode_value = params().any(|param| matches!(param.location.as_str(), "path" | "header"));
+    let encode_pairs = params().any(|param| matches!(param.location.as_str(), "query" | "cookie"));
+    let encode_form = bodies().any(|kind| kind == BodyKind::Form);
+    let helpers = [
+        (encode_value, ENCODE_VALUE),
+        (encode_pairs, ENCODE_PAIRS),
+        (encode_pairs || encode_form, JOIN_PAIRS),
+        (params().any(|param| param.json), ENCODE_JSON_TEXT),
+        (bodies().any(|kind| kind == BodyKind::Json), ENCODE_JSON),
+        (encode_form, ENCODE_FORM),
+        (responses().any(|kind| kind == BodyKind::Json), DECODE_JSON),
+        (responses().any(|kind| kind == BodyKind::Text), DECODE_TEXT),
+    ];
+
+    let mut out = String::from("\n");
+    if encode_value || encode_pairs || encode_form {
+        out.push_str("use openapi_schema::style::Style;\n");
+    }
+    if !types.definitions().is_empty() {
+        out.push_str("use serde::{Deserialize, Serialize};\n");
+    }
+    if uses_map(types, operations) {
+        out.push_str("use std::collections::BTreeMap;\n");
+    }
+    out.push_str("use std::fmt;\nuse std::future::Future;\n\n");
+    out.push_str(&render_definitions(types, operations));
+    out.push_str(TRANSPORT);
+    out.push('\n');
+
+    let base_url = openapi
+        .servers
+        .iter()
+        .flatten()
+        .next()
+        .map(|server| server.default_url().trim_end_matches('/').to_string())
+        .unwrap_or_default();
+    out.push_str("/// A client of the operations of the document.\n");
+    out.push_str("#[derive(Clone, Debug)]\n");
+    out.push_str("pub struct Client<T> {\n    transport: T,\n    base_url: String,\n}\n\n");
+    out.push_str("impl<T: Transport> Client<T> {\n");
+    out.push_str(&format!(
+        "    /// A client sending its requests to the first server of the document, `{}`.\n",
+        base_url
+    ));
+    out.push_str("    pub fn new(transport: T) -> Self {\n");
+    out.push_str(&format!(
+        "        Client {{\n            transport,\n            base_url: {:?}.to_string(),\n        }}\n",
+        base_url
+    ));
+    out.push_str("    }\n\n");
+    out.push_str("    /// Send the requests to another server, such as a staging one.\n");
+    out.push_str("    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {\n");
+    out.push_str("        self.base_url = base_url.into().trim_end_matches('/').to_string();\n");
+    out.push_str("        self\n    }\n\n");
+    out.push_str("    pub fn base_url(&self) -> &str {\n        &self.base_url\n    }\n\n");
+    out.push_str("    pub fn transport(&self) -> &T {\n        &self.transport\n    }\n");
+    for operation in operations {
+        out.push('\n');
+        out.push_str(&render_method(operation));
+    }
+    out.push_str("}\n");
+
+    for (_, helper) in helpers.iter().filter(|(used, _)| *used) {
+        out.push('\n');
+        out.push_str(helper);
+    }
+    out
+}
+
+/// The type of the argument of a body, such as `&Pet` or `Option<&str>`.
+fn body_argument(body: &BodyDef) -> String {
+    let ty = match body.kind {
+        BodyKind::Json | BodyKind::Form => format!("&{}", body.ty),
+        BodyKind::Text => "&str".to_string(),
+        BodyKind::Bytes => "&[u8]".to_string(),
+    };
+    if body.required {
+        ty
+    } else {
+        format!("Option<{}>", ty)
+    }
+}
+
+/// The expression of the value of a parameter, written as JSON text when it is described by a `content` map.
+fn param_value(param: &ParamDef, value: &str) -> String {
+    if param.json {
+        format!("&encode_json_text({}).map_err(ClientError::Encode)?", value)
+    } else {
+        value.to_string()
+    }
+}
+
+/// The statement sending a parameter, with `value` referring to its value, such as `params.limit`.
+fn param_statement(param: &ParamDef, value: &str) -> String {
+    let value = param_value(param, value);
+    let style = format!("Style::{:?}", param.style);
+    match param.location.as_str() {
+        "query" | "cookie" => format!(
+            "{}.extend(encode_pairs({:?}, {}, {}, {}, {}).map_err(ClientError::Encode)?);",
+            if param.location == "query" {
+                "query"
+            } else {
+                "cookies"
+            },
+            param.name,
+            style,
+            param.explode,
+            param.allow_reserved,
+            value
+        ),
+        _ => format!(
+            "headers.push(({:?}.to_string(), {}));",
+            param.name.to_ascii_lowercase(),
+            header_value(param, &value)
+        ),
+    }
+}
+
+fn header_value(param: &ParamDef, value: &str) -> String {
+    format!(
+        "encode_value({:?}, Style::{:?}, {}, {}).map_err(ClientError::Encode)?",
+        param.name, param.style, param.explode, value
+    )
+}
+
+/// Render the method of an operation.
+fn render_method(operation: &OperationDef) -> String {
+    let mut out = doc_comment(operation.doc.as_deref(), "    ");
+    if !out.is_empty() {
+        out.push_str("    ///\n");
+    }
+    out.push_str(&format!("    /// `{}`\n", operation.signature()));
+    out.push_str(&format!(
+        "    pub async fn {}(\n        &self,\n",
+        operation.ident
+    ));
+    if let Some(params_type) = &operation.params_type {
+        out.push_str(&format!("        params: &{},\n", params_type));
+    }
+    if let Some(body) = &operation.body {
+        out.push_str(&format!("        body: {},\n", body_argument(body)));
+    }
+    out.push_str(&format!(
+        "    ) -> Result<{}, ClientError<T::Error>> {{\n",
+        operation.response_type
+    ));
+
+    let mut lines: Vec<String> = Vec::new();
+    let located = |location: &'static str| {
+        operation
+            .params
+            .iter()
+            .filter(move |param| param.location == location)
+    };
+
+    // The URL, with the values of the path parameters.
+    let mut format = "{}".to_string();
+    let mut arguments = vec!["self.base_url".to_string()];
+    let mut rest = operation.template.as_str();
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+            break;
+        };
+        format.push_str(&rest[..start].replace('{', "{{").replace('}', "}}"));
+        let name = &rest[start + 1..end];
+        match located("path").find(|param| param.name == name) {
+            Some(param) => {
+                format.push_str("{}");
+                let value = param_value(param, &format!("&params.{}", param.ident));
+                arguments.push(format!(
+                    "encode_value({:?}, Style::{:?}, {}, {}).map_err(ClientError::Encode)?",
+                    param.name, param.style, param.explode, value
+                ));
+            }
+            None => format.push_str(&format!("{{{{{}}}}}", name)),
+        }
+        rest = &rest[end + 1..];
+    }
+    format.push_str(&rest.replace('{', "{{").replace('}', "}}"));
+    let has_query = located("query").next().is_some();
+    lines.push(format!(
+        "let {}url = format!({:?}, {});",
+        if has_query { "mut " } else { "" },
+        format,
+        arguments.join(", ")
+    ));
+
+    // The query string.
+    if has_query {
+        lines.push("let mut query = Vec::new();".to_string());
+        push_params(&mut lines, located("query"));
+        lines.push("if !query.is_empty() {".to_string());
+        lines.push("    url.push('?');".to_string());
+        lines.push("    url.push_str(&join_pairs(&query, \"&\"));".to_string());
+        lines.push("}".to_string());
+    }
+
+    // The headers: those which are always sent are listed first, then the optional ones are pushed.
+    let mut fixed: Vec<String> = Vec::new();
+    let mut pushed: Vec<String> = Vec::new();
+    let mut accept: Vec<&str> = Vec::new();
+    for body in operation
+        .responses
+        .iter()
+        .flat_map(|response| &response.body)
+    {
+        if !accept.contains(&body.media_type.as_str()) {
+            accept.push(&body.media_type);
+        }
+    }
+    if !accept.is_empty() {
+        fixed.push(format!(
+            "(\"accept\".to_string(), {:?}.to_string())",
+            accept.join(", ")
+        ));
+    }
+    if let Some(body) = &operation.body {
+        let header = format!(
+            "(\"content-type\".to_string(), {:?}.to_string())",
+            body.media_type
+        );
+        if body.required {
+            fixed.push(header);
+        } else {
+            pushed.push("if body.is_some() {".to_string());
+            pushed.push(format!("    headers.push({});", header));
+            pushed.push("}".to_string());
+        }
+    }
+    for param in located("header") {
+        if param.required {
+            let value = param_value(param, &format!("&params.{}", param.ident));
+            fixed.push(format!(
+                "({:?}.to_string(), {})",
+                param.name.to_ascii_lowercase(),
+                header_value(param, &value)
+            ));
+        } else {
+            push_params(&mut pushed, std::iter::once(param));
+        }
+    }
+    if located("cookie").next().is_some() {
+        pushed.push("let mut cookies = Vec::new();".to_string());
+        push_params(&mut pushed, located("cookie"));
+        pushed.push("if !cookies.is_empty() {".to_string());
+        pushed.push(
+            "    headers.push((\"cookie\".to_string(), join_pairs(&cookies, \"; \")));".to_string(),
+        );
+        pushed.push("}".to_string());
+    }
+    let has_headers = !fixed.is_empty() || !pushed.is_empty();
+    if has_headers {
+        let mutable = if pushed.is_empty() { "" } else { "mut " };
+        if fixed.is_empty() {
+            lines.push(format!("let {}headers = Vec::new();", mutable));
+        } else {
+            lines.push(format!("let {}headers = vec![", mutable));
+            lines.extend(fixed.iter().map(|header| format!("    {},", header)));
+            lines.push("];".to_string());
+        }
+        lines.extend(pushed);
+    }
+
+    // The body.
+    let body = match &operation.body {
+        Some(body) => {
+            let encoded = match (body.kind, body.required) {
+                (BodyKind::Json, true) => "Some(encode_json(body).map_err(ClientError::Encode)?)",
+                (BodyKind::Form, true) => "Some(encode_form(body).map_err(ClientError::Encode)?)",
+                (BodyKind::Text, true) => "Some(body.as_bytes().to_vec())",
+                (BodyKind::Bytes, true) => "Some(body.to_vec())",
+                (BodyKind::Json, false) => {
+                    "body.map(encode_json).transpose().map_err(ClientError::Encode)?"
+                }
+                (BodyKind::Form, false) => {
+                    "body.map(encode_form).transpose().map_err(ClientError::Encode)?"
+                }
+                (BodyKind::Text, false) => "body.map(|body| body.as_bytes().to_vec())",
+                (BodyKind::Bytes, false) => "body.map(|body| body.to_vec())",
+            };
+            lines.push(format!("let body = {};", encoded));
+            "body"
+        }
+        None => "body: None",
+    };
+
+    lines.push("let response = self".to_string());
+    lines.push("    .transport".to_string());
+    lines.push("    .send(HttpRequest {".to_string());
+    lines.push(format!(
+        "        method: {:?},",
+        operation.method.to_ascii_uppercase()
+    ));
+    lines.push("        url,".to_string());
+    lines.push(if has_headers {
+        "        headers,".to_string()
+    } else {
+        "        headers: Vec::new(),".to_string()
+    });
+    lines.push(format!("        {},", body));
+    lines.push("    })".to_string());
+    lines.push("    .await".to_string());
+    lines.push("    .map_err(ClientError::Transport)?;".to_string());
+
+    // The response, matched on exact statuses, then ranges, then `default`.
+    match operation.responses.as_slice() {
+        [] => lines.push("Err(ClientError::UnexpectedStatus(response))".to_string()),
+        [only] if only.status == Status::Default => {
+            lines.push("let status = response.status;".to_string());
+            lines.push(format!("let decoded = {};", decode(operation, only)));
+            lines.push(DECODED.to_string());
+        }
+        responses => {
+            lines.push("let status = response.status;".to_string());
+            lines.push("let decoded = match status {".to_string());
+            for response in responses {
+                let pattern = match response.status {
+                    Status::Default => "_".to_string(),
+                    _ => response.pattern(),
+                };
+                lines.push(format!(
+                    "    {} => {},",
+                    pattern,
+                    decode(operation, response)
+                ));
+            }
+            if responses
+                .iter()
+                .all(|response| response.status != Status::Default)
+            {
+                lines.push(
+                    "    _ => return Err(ClientError::UnexpectedStatus(response)),".to_string(),
+                );
+            }
+            lines.push("};".to_string());
+            lines.push(DECODED.to_string());
+        }
+    }
+
+    for line in lines {
+        out.push_str("        ");
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out.push_str("    }\n");
+    out
+}
+
+/// The statement turning the errors of decoding into those of the client.
+const DECODED: &str = "decoded.map_err(|message| ClientError::Decode { status, message })";
+
+/// The expression decoding a response into its variant, as a `Result<_, String>`.
+fn decode(operation: &OperationDef, response: &ResponseDef) -> String {
+    let variant = format!("{}::{}", operation.response_type, response.variant);
+    let Some(body) = &response.body else {
+        return if response.has_status() {
+            format!("Ok({} {{ status }})", variant)
+        } else {
+            format!("Ok({})", variant)
+        };
+    };
+    let decoded = match body.kind {
+        BodyKind::Bytes => {
+            return if response.has_status() {
+                format!("Ok({} {{ status, body: response.body.clone() }})", variant)
+            } else {
+                format!("Ok({}(response.body.clone()))", variant)
+            };
+        }
+        BodyKind::Text => "decode_text(&response.body)",
+        BodyKind::Json | BodyKind::Form => "decode_json(&response.body)",
+    };
+    if response.has_status() {
+        format!("{}.map(|body| {} {{ status, body }})", decoded, variant)
+    } else {
+        format!("{}.map({})", decoded, variant)
+    }
+}
+
+/// Push the statements sending parameters, an optional one only being sent when it has a value.
+fn push_params<'a>(lines: &mut Vec<String>, params: impl Iterator<Item = &'a ParamDef>) {
+    for param in params {
+        if param.required {
+            lines.push(param_statement(param, &format!("&params.{}", param.ident)));
+        } else {
+            lines.push(format!("if let Some(value) = &params.{} {{", param.ident));
+            lines.push(format!("    {}", param_statement(param, "value")));
+            lines.push("}".to_string());
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! Generation of Rust source from a document.
+//!
+//! A [`Generator`] turns the schemas of the `components` of an OpenAPI 3.0 document, or the `definitions` of a Swagger
+//! 2.0 document, into serde-annotated Rust types:
+//!
+//! - an object becomes a struct, whose properties which are not `required` are `Option`s;
+//! - an `enum` of strings becomes an enum;
+//! - a `oneOf` with a `discriminator` becomes an enum tagged by the discriminator property, and any other `oneOf` or
+//!   `anyOf` an untagged enum;
+//! - the properties of the schemas of an `allOf` are flattened into a single struct;
+//! - a `$ref` to a schema of the components becomes the name of its type.
+//!
+//! Inline schemas which need a type of their own get one named after where they appear, such as `PetOwner` for the
+//! `owner` property of `Pet`. `readOnly` and `writeOnly` properties are always `Option`s, as they are only sent one
+//! way. The generated source is deterministic, so it can be committed.
+//!
+//! [`Generator::client`] adds a typed HTTP client of the operations of the paths: a `Client` with one method for each
+//! operation, taking a struct of its parameters and its body and returning an enum of its responses, which sends its
+//! requests through a `Transport` trait.
+//! [`Generator::server`] adds the other side: an `Api` trait with one method for each operation, and a `Server`
+//! answering requests by routing them from the paths, checking them against the document, then calling the trait.
+//!
+//! In a build script, a [`Build`] loads a document split across several files, generates the source into `OUT_DIR`,
+//! and tells cargo to run it again when any of the files changes.
+//!
+//! ```
+//! # use openapi_schema::codegen::Generator;
+//! # use openapi_schema::v3::OpenApi;
+//! # fn example(openapi: OpenApi) -> Result<(), openapi_schema::Error> {
+//! let source = Generator::new(openapi).types()?;
+//! std::fs::write("src/types.rs", source)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::v2::Swagger;
+use crate::v3::OpenApi;
+use crate::Error;
+
+mod build;
+mod client;
+mod names;
+mod operations;
+mod server;
+mod types;
+
+pub use build::{Build, Generated};
+use types::Types;
+
+/// The first line of the generated files.
+const HEADER: &str = "// Generated from an OpenAPI document, do not edit by hand.\n";
+
+/// # Generator
+/// Generates Rust source from an OpenAPI 3.0 document.
+#[derive(Clone, Debug)]
+pub struct Generator {
+    openapi: OpenApi,
+}
+
+impl Generator {
+    pub fn new(openapi: OpenApi) -> Self {
+        Generator { openapi }
+    }
+
+    /// A generator for a Swagger 2.0 document, which is converted to OpenAPI 3.0 first: its `definitions` become the
+    /// schemas of the components.
+    pub fn from_swagger(swagger: &Swagger) -> Self {
+        Generator::new(swagger.to_v3().0)
+    }
+
+    /// The document the source is generated from.
+    pub fn openapi(&self) -> &OpenApi {
+        &self.openapi
+    }
+
+    /// The Rust types of the schemas of the components.
+    ///
+    /// The source uses `serde` and `serde_json`, which the crate including it must depend on.
+    pub fn types(&self) -> Result<String, Error> {
+        let types = Types::new(&self.openapi, &[])?;
+        let mut source = HEADER.to_string();
+        if types.definitions().is_empty() {
+            return Ok(source);
+        }
+        source.push_str("\nuse serde::{Deserialize, Serialize};\n");
+        if types.uses_map() {
+            source.push_str("use std::collections::BTreeMap;\n");
+        }
+        source.push('\n');
+        source.push_str(&types::render(types.definitions()));
+        Ok(source)
+    }
+
+    /// A typed HTTP client of the operations of the paths, along with the types it needs.
+    ///
+    /// The `Client` has one method for each operation, named from its `operationId`, which takes a struct of its
+    /// parameters and its body, and returns an enum with a variant for each of its responses. Requests are sent
+    /// through a `Transport`, which the crate including the source implements, so that tests can answer them in
+    /// memory. The source uses `serde`, `serde_json` and `openapi_schema`, which encodes the parameters.
+    pub fn client(&self) -> Result<String, Error> {
+        let mut types = Types::new(&self.openapi, client::NAMES)?;
+        let operations = operations::operations(&self.openapi, &mut types)?;
+        let mut source = HEADER.to_string();
+        source.push_str(&client::render(&self.openapi, &types, &operations));
+        Ok(source)
+    }
+
+    /// The server side of the operations of the paths, along with the types it needs.
+    ///
+    /// The `Api` trait has one method for each operation, which takes the struct of its parameters and its body, and
+    /// returns the enum of its responses. A `Server` answers requests by calling it: the operation of a request is
+    /// routed from the paths of the document, the request is checked against it, its parameters and body are decoded,
+    /// and the response is encoded according to its variant. The source embeds the document, and uses `serde`,
+    /// `serde_json` and `openapi_schema`.
+    pub fn server(&self) -> Result<String, Error> {
+        let mut types = Types::new(&self.openapi, server::NAMES)?;
+        let operations = operations::operations(&self.openapi, &mut types)?;
+        let mut source = HEADER.to_string();
+        source.push_str(&server::render(&self.openapi, &types, &operations));
+        Ok(source)
+    }
+}
@@ -0,0 +1,150 @@
+//! The Rust identifiers of the names of a document.
+
+/// The keywords which can be escaped as raw identifiers, such as `r#type`.
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+/// The keywords which cannot be raw identifiers, and get a trailing `_` instead.
+const RESERVED: &[&str] = &["crate", "self", "Self", "super"];
+
+/// The types and traits the generated source refers to, which the generated types cannot be named after.
+pub(crate) const PRELUDE: &[&str] = &[
+    "BTreeMap",
+    "Box",
+    "Deserialize",
+    "Option",
+    "Result",
+    "Serialize",
+    "String",
+    "Vec",
+];
+
+/// Split a name into words, on any character which is not alphanumeric and where the case changes, so that
+/// `petType`, `pet_type`, `PetType` and `pet-type` all give `pet` and `type`. Digits stay with the word before them.
+fn words(name: &str) -> Vec<String> {
+    let chars: Vec<char> = name.chars().collect();
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for (index, &char) in chars.iter().enumerate() {
+        if !char.is_alphanumeric() {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            continue;
+        }
+        let previous = index.checked_sub(1).map(|index| chars[index]);
+        let next = chars.get(index + 1);
+        let boundary = char.is_uppercase()
+            && previous.is_some_and(|previous| {
+                previous.is_lowercase()
+                    || previous.is_ascii_digit()
+                    || (previous.is_uppercase() && next.is_some_and(|next| next.is_lowercase()))
+            });
+        if boundary && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        word.push(char);
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+/// The `UpperCamelCase` form of a name, such as `PetType` for `pet_type`.
+pub(crate) fn pascal_case(name: &str) -> String {
+    words(name)
+        .iter()
+        .map(|word| {
+            let mut chars = word.chars();
+            let first = chars.next().map(|first| first.to_uppercase().to_string());
+            first.unwrap_or_default() + &chars.as_str().to_lowercase()
+        })
+        .collect()
+}
+
+/// The `snake_case` form of a name, such as `pet_type` for `petType`.
+pub(crate) fn snake_case(name: &str) -> String {
+    words(name)
+        .iter()
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// The identifier of a type, a variant or a trait for a name.
+pub(crate) fn type_ident(name: &str) -> String {
+    let ident = pascal_case(name);
+    match ident.chars().next() {
+        None => "Type".to_string(),
+        Some(first) if first.is_ascii_digit() => format!("V{}", ident),
+        _ if RESERVED.contains(&ident.as_str()) => format!("{}_", ident),
+        _ => ident,
+    }
+}
+
+/// The identifier of a field, a function or an argument for a name, escaping keywords.
+pub(crate) fn field_ident(name: &str) -> String {
+    let ident = snake_case(name);
+    match ident.chars().next() {
+        None => "value".to_string(),
+        Some(first) if first.is_ascii_digit() => format!("_{}", ident),
+        _ if RESERVED.contains(&ident.as_str()) => format!("{}_", ident),
+        _ if KEYWORDS.contains(&ident.as_str()) => format!("r#{}", ident),
+        _ => ident,
+    }
+}
+
+/// The name of a serialized field or variant, which is the identifier without its raw prefix.
+pub(crate) fn unraw(ident: &str) -> &str {
+    ident.strip_prefix("r#").unwrap_or(ident)
+}
+
+/// The doc comment of a description, one `///` line for each of its lines.
+pub(crate) fn doc_comment(description: Option<&str>, indent: &str) -> String {
+    let Some(description) = description
+        .map(str::trim)
+        .filter(|description| !description.is_empty())
+    else {
+        return String::new();
+    };
+    description
+        .lines()
+        .map(|line| match line.trim_end() {
+            "" => format!("{}///\n", indent),
+            line => format!("{}/// {}\n", indent, line),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cases() {
+        assert_eq!(pascal_case("pet_type"), "PetType");
+        assert_eq!(pascal_case("HTTPServer"), "HttpServer");
+        assert_eq!(pascal_case("api-key v2"), "ApiKeyV2");
+        assert_eq!(snake_case("petType"), "pet_type");
+        assert_eq!(snake_case("X-Rate-Limit"), "x_rate_limit");
+        assert_eq!(snake_case("int32Value"), "int32_value");
+        assert_eq!(snake_case("userID"), "user_id");
+    }
+
+    #[test]
+    fn test_idents() {
+        assert_eq!(type_ident("2xx"), "V2xx");
+        assert_eq!(type_ident("self"), "Self_");
+        assert_eq!(type_ident("!"), "Type");
+        assert_eq!(field_ident("type"), "r#type");
+        assert_eq!(field_ident("self"), "self_");
+        assert_eq!(field_ident("3d"), "_3d");
+        assert_eq!(unraw("r#type"), "type");
+    }
+}
@@ -0,0 +1,428 @@
+use std::collections::BTreeMap;
+
+use crate::http::{essence, RESERVED_HEADERS};
+use crate::style::Style;
+use crate::v3::{Media, OpenApi, Parameter, RefOrObject, Response};
+use crate::Error;
+
+use super::names::{doc_comment, field_ident, pascal_case, snake_case, type_ident, unraw};
+use super::types::{render, RustType, Types};
+
+/// The methods of the generated client which operations cannot be named after.
+const RESERVED_METHODS: &[&str] = &["new", "with_base_url", "base_url", "transport"];
+
+/// An operation of the document, with the types of its parameters, body and responses.
+pub(crate) struct OperationDef {
+    /// The identifier of its method, from its `operationId`.
+    pub(crate) ident: String,
+    /// The lowercase HTTP method.
+    pub(crate) method: &'static str,
+    pub(crate) template: String,
+    pub(crate) doc: Option<String>,
+    pub(crate) params: Vec<ParamDef>,
+    /// The struct of the parameters, `None` when the operation has none.
+    pub(crate) params_type: Option<String>,
+    pub(crate) body: Option<BodyDef>,
+    pub(crate) responses: Vec<ResponseDef>,
+    /// The enum of the responses.
+    pub(crate) response_type: String,
+}
+
+pub(crate) struct ParamDef {
+    pub(crate) name: String,
+    /// Where the parameter is sent: `path`, `query`, `header` or `cookie`.
+    pub(crate) location: String,
+    pub(crate) ident: String,
+    /// The type of the field, an `Option` when the parameter is not required.
+    pub(crate) ty: RustType,
+    pub(crate) required: bool,
+    pub(crate) style: Style,
+    pub(crate) explode: bool,
+    pub(crate) allow_reserved: bool,
+    /// Whether the parameter is described by a `content` map, and is written as JSON text.
+    pub(crate) json: bool,
+    pub(crate) doc: Option<String>,
+}
+
+/// How a body is written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum BodyKind {
+    Json,
+    Form,
+    Text,
+    Bytes,
+}
+
+pub(crate) struct BodyDef {
+    pub(crate) media_type: String,
+    pub(crate) kind: BodyKind,
+    pub(crate) ty: RustType,
+    pub(crate) required: bool,
+}
+
+/// The statuses a response describes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Status {
+    Code(u16),
+    /// A range such as `2XX`, by its first digit.
+    Range(u16),
+    Default,
+}
+
+pub(crate) struct ResponseDef {
+    pub(crate) status: Status,
+    pub(crate) variant: String,
+    pub(crate) body: Option<BodyDef>,
+    pub(crate) doc: Option<String>,
+}
+
+impl OperationDef {
+    /// The method and the path template of the operation, such as `GET /pets/{petId}`.
+    pub(crate) fn signature(&self) -> String {
+        format!("{} {}", self.method.to_ascii_uppercase(), self.template)
+    }
+}
+
+impl ResponseDef {
+    /// Whether the variant carries the status, which is the case of ranges and `default`.
+    pub(crate) fn has_status(&self) -> bool {
+        !matches!(self.status, Status::Code(_))
+    }
+
+    /// The pattern matching the statuses of the response, such as `200` or `200..=299`.
+    pub(crate) fn pattern(&self) -> String {
+        match self.status {
+            Status::Code(code) => code.to_string(),
+            Status::Range(digit) => format!("{}..={}", digit * 100, digit * 100 + 99),
+            Status::Default => "status".to_string(),
+        }
+    }
+}
+
+/// Describe the operations of a document in the order of their paths and methods, generating the types they need.
+pub(crate) fn operations(openapi: &OpenApi, types: &mut Types) -> Result<Vec<OperationDef>, Error> {
+    let mut operations: Vec<OperationDef> = Vec::new();
+    for (template, item) in &openapi.paths {
+        let item = openapi.resolve_path_item(item)?;
+        for (method, operation) in item.operations() {
+            let name = match &operation.operation_id {
+                Some(operation_id) => operation_id.clone(),
+                None => format!("{} {}", method, template),
+            };
+            let mut ident = field_ident(&name);
+            while RESERVED_METHODS.contains(&ident.as_str())
+                || operations.iter().any(|existing| existing.ident == ident)
+            {
+                ident.push('_');
+            }
+            let prefix = type_ident(&name);
+
+            let mut parameters: Vec<&Parameter> = Vec::new();
+            for parameter in item
+                .parameters
+                .iter()
+                .flatten()
+                .chain(operation.parameters.iter().flatten())
+            {
+                let parameter = openapi.resolve(parameter)?;
+                parameters.retain(|existing| {
+                    (&existing.name, &existing.r#in) != (&parameter.name, &parameter.r#in)
+                });
+                parameters.push(parameter);
+            }
+            let mut params: Vec<ParamDef> = Vec::new();
+            for parameter in parameters {
+                let reserved = parameter.r#in == "header"
+                    && RESERVED_HEADERS.contains(&parameter.name.to_ascii_lowercase().as_str());
+                // A path parameter which the template does not use cannot be sent.
+                let unused = parameter.r#in == "path"
+                    && !template.contains(&format!("{{{}}}", parameter.name));
+                if reserved || unused {
+                    continue;
+                }
+                params.push(param(types, parameter, &prefix, &params)?);
+            }
+            let params_type =
+                (!params.is_empty()).then(|| types.unique(&format!("{}Params", prefix)));
+
+            let body = match &operation.request_body {
+                Some(request_body) => {
+                    let request_body = openapi.resolve(request_body)?;
+                    body(
+                        types,
+                        &request_body.content,
+                        &format!("{}Body", prefix),
+                        true,
+                    )?
+                    .map(|mut body| {
+                        body.required = request_body.required == Some(true);
+                        body
+                    })
+                }
+                None => None,
+            };
+
+            let response_type = types.unique(&format!("{}Response", prefix));
+            let responses = responses(openapi, types, &operation.responses, &prefix)?;
+            let doc = operation
+                .summary
+                .clone()
+                .or_else(|| operation.description.clone());
+            operations.push(OperationDef {
+                ident,
+                method,
+                template: template.clone(),
+                doc,
+                params,
+                params_type,
+                body,
+                responses,
+                response_type,
+            });
+        }
+    }
+    Ok(operations)
+}
+
+fn param(
+    types: &mut Types,
+    parameter: &Parameter,
+    prefix: &str,
+    params: &[ParamDef],
+) -> Result<ParamDef, Error> {
+    let hint = format!("{}{}", prefix, pascal_case(&parameter.name));
+    let (schema, json) = match (&parameter.schema, parameter.content.iter().flatten().next()) {
+        (Some(schema), _) => (Some(schema), false),
+        (None, Some((_, media))) => (media.schema.as_ref(), true),
+        (None, None) => (None, false),
+    };
+    let ty = match schema {
+        Some(schema) => types.type_of(schema, &hint)?,
+        None => RustType::String,
+    };
+    let required = parameter.r#in == "path" || parameter.required == Some(true);
+    let style = parameter
+        .style
+        .as_deref()
+        .and_then(Style::from_name)
+        .unwrap_or_else(|| Style::default_for(&parameter.r#in));
+    let mut ident = field_ident(&parameter.name);
+    if params.iter().any(|existing| existing.ident == ident) {
+        ident = format!("{}_{}", unraw(&ident), snake_case(&parameter.r#in));
+    }
+    Ok(ParamDef {
+        name: parameter.name.clone(),
+        location: parameter.r#in.clone(),
+        ident,
+        ty: if required { ty } else { ty.optional() },
+        required,
+        style,
+        explode: parameter.explode.unwrap_or(style.explode_by_default()),
+        allow_reserved: parameter.allow_reserved == Some(true),
+        json,
+        doc: parameter.description.clone(),
+    })
+}
+
+/// The body of a `content` map: JSON when it is described, else a form when `form` is set, else text, else raw bytes.
+fn body(
+    types: &mut Types,
+    content: &BTreeMap<String, Media>,
+    hint: &str,
+    form: bool,
+) -> Result<Option<BodyDef>, Error> {
+    let kind = |media_type: &str| {
+        let essence = essence(media_type);
+        if essence == "application/json" || essence.ends_with("+json") {
+            BodyKind::Json
+        } else if form && essence == "application/x-www-form-urlencoded" {
+            BodyKind::Form
+        } else if essence.starts_with("text/") {
+            BodyKind::Text
+        } else {
+            BodyKind::Bytes
+        }
+    };
+    let selected = [
+        BodyKind::Json,
+        BodyKind::Form,
+        BodyKind::Text,
+        BodyKind::Bytes,
+    ]
+    .into_iter()
+    .find_map(|wanted| {
+        content
+            .iter()
+            .find(|(media_type, _)| kind(media_type) == wanted)
+    });
+    let Some((media_type, media)) = selected else {
+        return Ok(None);
+    };
+    let kind = kind(media_type);
+    let ty = match (kind, &media.schema) {
+        (BodyKind::Text, _) => RustType::String,
+        (BodyKind::Bytes, _) => RustType::Bytes,
+        (_, Some(schema)) => types.type_of(schema, hint)?,
+        (_, None) => RustType::Value,
+    };
+    Ok(Some(BodyDef {
+        media_type: media_type.clone(),
+        kind,
+        ty,
+        required: true,
+    }))
+}
+
+fn responses(
+    openapi: &OpenApi,
+    types: &mut Types,
+    responses: &BTreeMap<String, RefOrObject<Response>>,
+    prefix: &str,
+) -> Result<Vec<ResponseDef>, Error> {
+    let mut defs: Vec<ResponseDef> = Vec::new();
+    for (key, response) in responses {
+        let status = match key.as_str() {
+            "default" => Status::Default,
+            key => match key.parse::<u16>() {
+                Ok(code) => Status::Code(code),
+                Err(_) => match key.as_bytes() {
+                    [digit @ b'1'..=b'5', b'x' | b'X', b'x' | b'X'] => {
+                        Status::Range(u16::from(digit - b'0'))
+                    }
+                    _ => continue,
+                },
+            },
+        };
+        let mut variant = status_variant(status);
+        while defs.iter().any(|existing| existing.variant == variant) {
+            variant.push('_');
+        }
+        let response = openapi.resolve(response)?;
+        let body = match &response.content {
+            Some(content) => body(types, content, &format!("{}{}Body", prefix, variant), false)?,
+            None => None,
+        };
+        defs.push(ResponseDef {
+            status,
+            variant,
+            body,
+            doc: Some(response.description.clone()),
+        });
+    }
+    // Exact statuses are matched before ranges, and ranges before `default`.
+    defs.sort_by_key(|response| match response.status {
+        Status::Code(_) => 0,
+        Status::Range(_) => 1,
+        Status::Default => 2,
+    });
+    Ok(defs)
+}
+
+/// The variant of a status, such as `NotFound` for `404` or `ClientError` for `4XX`.
+fn status_variant(status: Status) -> String {
+    let name = match status {
+        Status::Default => "Default",
+        Status::Range(1) => "Informational",
+        Status::Range(2) => "Success",
+        Status::Range(3) => "Redirection",
+        Status::Range(4) => "ClientError",
+        Status::Range(_) => "ServerError",
+        Status::Code(code) => match code {
+            200 => "Ok",
+            201 => "Created",
+            202 => "Accepted",
+            204 => "NoContent",
+            206 => "PartialContent",
+            301 => "MovedPermanently",
+            302 => "Found",
+            303 => "SeeOther",
+            304 => "NotModified",
+            307 => "TemporaryRedirect",
+            308 => "PermanentRedirect",
+            400 => "BadRequest",
+            401 => "Unauthorized",
+            403 => "Forbidden",
+            404 => "NotFound",
+            405 => "MethodNotAllowed",
+            406 => "NotAcceptable",
+            409 => "Conflict",
+            410 => "Gone",
+            412 => "PreconditionFailed",
+            415 => "UnsupportedMediaType",
+            422 => "UnprocessableEntity",
+            429 => "TooManyRequests",
+            500 => "InternalServerError",
+            501 => "NotImplemented",
+            502 => "BadGateway",
+            503 => "ServiceUnavailable",
+            504 => "GatewayTimeout",
+            code => return format!("Status{}", code),
+        },
+    };
+    name.to_string()
+}
+
+/// Whether the types of the components or of the operations use `BTreeMap`.
+pub(crate) fn uses_map(types: &Types, operations: &[OperationDef]) -> bool {
+    types.uses_map()
+        || operations.iter().any(|operation| {
+            let bodies = operation
+                .responses
+                .iter()
+                .flat_map(|response| &response.body)
+                .chain(&operation.body);
+            operation.params.iter().any(|param| param.ty.uses_map())
+                || bodies.into_iter().any(|body| body.ty.uses_map())
+        })
+}
+
+/// Render the types of the components, then the parameters and the responses of each operation.
+pub(crate) fn render_definitions(types: &Types, operations: &[OperationDef]) -> String {
+    let mut out = String::new();
+    if !types.definitions().is_empty() {
+        out.push_str(&render(types.definitions()));
+        out.push('\n');
+    }
+    for operation in operations {
+        out.push_str(&render_types(operation));
+        out.push('\n');
+    }
+    out
+}
+
+/// Render the struct of the parameters and the enum of the responses of an operation.
+fn render_types(operation: &OperationDef) -> String {
+    let mut out = String::new();
+    if let Some(params_type) = &operation.params_type {
+        out.push_str(&format!(
+            "/// The parameters of `{}`.\n",
+            operation.signature()
+        ));
+        out.push_str("#[derive(Clone, Debug, PartialEq)]\n");
+        out.push_str(&format!("pub struct {} {{\n", params_type));
+        for param in &operation.params {
+            out.push_str(&doc_comment(param.doc.as_deref(), "    "));
+            out.push_str(&format!("    pub {}: {},\n", param.ident, param.ty));
+        }
+        out.push_str("}\n\n");
+    }
+    out.push_str(&format!(
+        "/// The responses of `{}`.\n",
+        operation.signature()
+    ));
+    out.push_str("#[derive(Clone, Debug, PartialEq)]\n");
+    out.push_str(&format!("pub enum {} {{\n", operation.response_type));
+    for response in &operation.responses {
+        out.push_str(&doc_comment(response.doc.as_deref(), "    "));
+        let body = response.body.as_ref().map(|body| &body.ty);
+        let variant = match (response.has_status(), body) {
+            (false, None) => response.variant.clone(),
+            (false, Some(ty)) => format!("{}({})", response.variant, ty),
+            (true, None) => format!("{} {{ status: u16 }}", response.variant),
+            (true, Some(ty)) => format!("{} {{ status: u16, body: {} }}", response.variant, ty),
+        };
+        out.push_str(&format!("    {},\n", variant));
+    }
+    out.push_str("}\n");
+    out
+}
@@ -0,0 +1,411 @@
+//! The server trait of the operations of a document, with the glue routing requests to it.
+
+use crate::v3::OpenApi;
+
+use super::names::doc_comment;
+use super::operations::{
+    render_definitions, uses_map, BodyDef, BodyKind, OperationDef, ResponseDef, Status,
+};
+use super::types::Types;
+
+/// The types, traits and constants the server defines or imports, which the generated types cannot be named after.
+pub(crate) const NAMES: &[&str] = &[
+    "Api",
+    "DOCUMENT",
+    "Extracted",
+    "Future",
+    "HttpRequest",
+    "HttpResponse",
+    "OnceLock",
+    "OpenApi",
+    "Request",
+    "Server",
+    "Validator",
+];
+
+/// The request and response types of the server, which do not depend on the document.
+const MESSAGES: &str = r#"/// A request received by the [`Server`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HttpRequest {
+    /// The method, such as `GET`.
+    pub method: String,
+    /// The path, with its query string, such as `/v1/pets?limit=10`.
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+/// A response sent by the [`Server`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct HttpResponse {
+    pub status: u16,
+    /// The headers, with lowercase names.
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+"#;
+
+/// The functions the generated glue shares, emitted only when one of them needs it.
+const PARAMETER: &str = r#"fn parameter<V: serde::de::DeserializeOwned>(
+    extracted: &Extracted<'_>,
+    location: &str,
+    name: &str,
+) -> Result<Option<V>, HttpResponse> {
+    extracted
+        .parameter(location, name)
+        .map(|value| serde_json::from_value(value.clone()))
+        .transpose()
+        .map_err(|error| {
+            let message = format!("the {} parameter `{}` is invalid: {}", location, name, error);
+            text_response(400, message)
+        })
+}
+"#;
+
+const REQUIRED_PARAMETER: &str = r#"fn required_parameter<V: serde::de::DeserializeOwned>(
+    extracted: &Extracted<'_>,
+    location: &str,
+    name: &str,
+) -> Result<V, HttpResponse> {
+    parameter(extracted, location, name)?.ok_or_else(|| {
+        let message = format!("the {} parameter `{}` is required", location, name);
+        text_response(400, message)
+    })
+}
+"#;
+
+const BODY: &str = r#"fn body<V: serde::de::DeserializeOwned>(extracted: &Extracted<'_>) -> Result<Option<V>, HttpResponse> {
+    extracted
+        .body
+        .clone()
+        .map(serde_json::from_value)
+        .transpose()
+        .map_err(|error| text_response(400, format!("the request body is invalid: {}", error)))
+}
+"#;
+
+const REQUIRED_BODY: &str = r#"fn required_body<V: serde::de::DeserializeOwned>(extracted: &Extracted<'_>) -> Result<V, HttpResponse> {
+    body(extracted)?.ok_or_else(|| text_response(400, "the request body is required".to_string()))
+}
+"#;
+
+const JSON_RESPONSE: &str = r#"fn json_response<V: serde::Serialize>(status: u16, media_type: &str, body: &V) -> HttpResponse {
+    match serde_json::to_vec(body) {
+        Ok(body) => bytes_response(status, media_type, body),
+        Err(error) => text_response(500, error.to_string()),
+    }
+}
+"#;
+
+const EMPTY_RESPONSE: &str = r#"fn empty_response(status: u16) -> HttpResponse {
+    HttpResponse {
+        status,
+        headers: Vec::new(),
+        body: Vec::new(),
+    }
+}
+"#;
+
+const BYTES_RESPONSE: &str = r#"fn bytes_response(status: u16, media_type: &str, body: Vec<u8>) -> HttpResponse {
+    HttpResponse {
+        status,
+        headers: vec![("content-type".to_string(), media_type.to_string())],
+        body,
+    }
+}
+"#;
+
+const TEXT_RESPONSE: &str = r#"fn text_response(status: u16, message: String) -> HttpResponse {
+    bytes_response(status, "text/plain", message.into_bytes())
+}
+"#;
+
+/// Render the server module: the types, an `Api` trait with one method for each operation, and a `Server` calling it.
+pub(crate) fn render(openapi: &OpenApi, types: &Types, operations: &[OperationDef]) -> String {
+    let params = || operations.iter().flat_map(|operation| &operation.params);
+    let bodies = || operations.iter().flat_map(|operation| &operation.body);
+    let responses = || operations.iter().flat_map(|operation| &operation.responses);
+    // Bytes are passed as they were received, other bodies are decoded from their parsed value.
+    let raw_body = bodies().any(|body| body.kind == BodyKind::Bytes);
+    let decoded = || bodies().filter(|body| body.kind != BodyKind::Bytes);
+    let helpers = [
+        (params().next().is_some(), PARAMETER),
+        (params().any(|param| param.required), REQUIRED_PARAMETER),
+        (decoded().next().is_some(), BODY),
+        (decoded().any(|body| body.required), REQUIRED_BODY),
+        (
+            responses().any(|response| {
+                response
+                    .body
+                    .as_ref()
+                    .is_some_and(|body| body.kind == BodyKind::Json)
+            }),
+            JSON_RESPONSE,
+        ),
+        (
+            responses().any(|response| response.body.is_none()),
+            EMPTY_RESPONSE,
+        ),
+        (true, BYTES_RESPONSE),
+        (true, TEXT_RESPONSE),
+    ];
+
+    let mut out = String::from("\n");
+    out.push_str("use openapi_schema::http::{Extracted, Request, Validator};\n");
+    out.push_str("use openapi_schema::v3::OpenApi;\n");
+    if !types.definitions().is_empty() {
+        out.push_str("use serde::{Deserialize, Serialize};\n");
+    }
+    if uses_map(types, operations) {
+        out.push_str("use std::collections::BTreeMap;\n");
+    }
+    out.push_str("use std::future::Future;\n");
+    out.push_str("use std::sync::OnceLock;\n\n");
+    out.push_str(&render_definitions(types, operations));
+    for operation in operations {
+        out.push_str(&render_into_response(operation));
+        out.push('\n');
+    }
+    out.push_str(MESSAGES);
+    out.push('\n');
+
+    out.push_str("/// The operations of the document, which the [`Server`] calls once their requests are checked.\n");
+    out.push_str("pub trait Api {\n");
+    for (index, operation) in operations.iter().enumerate() {
+        if index > 0 {
+            out.push('\n');
+        }
+        out.push_str(&render_signature(operation));
+    }
+    out.push_str("}\n\n");
+
+    let document = serde_json::to_string(openapi).unwrap_or_default();
+    let hashes = "#".repeat(
+        (1..)
+            .find(|&count| !document.contains(&format!("\"{}", "#".repeat(count))))
+            .unwrap_or(1),
+    );
+    out.push_str("/// The document the requests are checked against.\n");
+    out.push_str(&format!(
+        "const DOCUMENT: &str = r{}\"{}\"{};\n\n",
+        hashes, document, hashes
+    ));
+
+    out.push_str(SERVER);
+    let request = if raw_body { "request" } else { "_request" };
+    out.push_str(&format!(
+        "    async fn dispatch(\n        &self,\n        {}: &HttpRequest,\n        extracted: &Extracted<'_>,\n    ) -> Result<HttpResponse, HttpResponse> {{\n",
+        request
+    ));
+    if operations.is_empty() {
+        out.push_str(
+            "        Err(text_response(404, format!(\"`{}` has no operation\", extracted.template)))\n",
+        );
+    } else {
+        out.push_str("        match (extracted.method, extracted.template) {\n");
+        for operation in operations {
+            out.push_str(&render_arm(operation));
+        }
+        out.push_str("            (method, template) => {\n");
+        out.push_str("                let message = format!(\"`{}` has no `{}` operation\", template, method);\n");
+        out.push_str("                Err(text_response(405, message))\n");
+        out.push_str("            }\n");
+        out.push_str("        }\n");
+    }
+    out.push_str("    }\n}\n");
+
+    for (_, helper) in helpers.iter().filter(|(used, _)| *used) {
+        out.push('\n');
+        out.push_str(helper);
+    }
+    out
+}
+
+/// The `Server` and the methods which do not depend on the operations, up to its dispatch.
+const SERVER: &str = r#"/// Serves the operations of an [`Api`]: routes each request to its operation, checks it against the document, decodes
+/// its parameters and body, then encodes the response of the operation.
+#[derive(Clone, Debug)]
+pub struct Server<A> {
+    api: A,
+    validator: Validator<'static>,
+}
+
+/// The document, parsed once for every server.
+fn document() -> &'static OpenApi {
+    static OPENAPI: OnceLock<OpenApi> = OnceLock::new();
+    OPENAPI.get_or_init(|| serde_json::from_str(DOCUMENT).expect("the document is valid"))
+}
+
+impl<A: Api> Server<A> {
+    pub fn new(api: A) -> Self {
+        Server {
+            api,
+            validator: Validator::new(document()),
+        }
+    }
+
+    pub fn api(&self) -> &A {
+        &self.api
+    }
+
+    /// The document the requests are checked against.
+    pub fn openapi(&self) -> &OpenApi {
+        self.validator.openapi()
+    }
+
+    /// Answer a request. A request which does not follow the document is answered with its errors, as a `404` when no
+    /// path matches, a `405` when the path has no operation for the method, a `415` for an unsupported media type and
+    /// a `400` otherwise.
+    pub async fn handle(&self, request: &HttpRequest) -> HttpResponse {
+        let mut checked = Request::new(&request.method, &request.path).with_body(&request.body);
+        for (name, value) in &request.headers {
+            checked = checked.with_header(name, value);
+        }
+        let extracted = match self.validator.extract_request(&checked) {
+            Ok(extracted) => extracted,
+            Err(errors) => {
+                let message = errors
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                return text_response(errors[0].status(), message);
+            }
+        };
+        self.dispatch(request, &extracted)
+            .await
+            .unwrap_or_else(|response| response)
+    }
+
+"#;
+
+/// The type of the argument of a body, such as `Pet` or `Option<String>`.
+fn body_argument(body: &BodyDef) -> String {
+    let ty = match body.kind {
+        BodyKind::Json | BodyKind::Form => body.ty.to_string(),
+        BodyKind::Text => "String".to_string(),
+        BodyKind::Bytes => "Vec<u8>".to_string(),
+    };
+    if body.required {
+        ty
+    } else {
+        format!("Option<{}>", ty)
+    }
+}
+
+/// Render the method of the `Api` trait of an operation.
+fn render_signature(operation: &OperationDef) -> String {
+    let mut out = doc_comment(operation.doc.as_deref(), "    ");
+    if !out.is_empty() {
+        out.push_str("    ///\n");
+    }
+    out.push_str(&format!("    /// `{}`\n", operation.signature()));
+    out.push_str(&format!("    fn {}(\n        &self,\n", operation.ident));
+    if let Some(params_type) = &operation.params_type {
+        out.push_str(&format!("        params: {},\n", params_type));
+    }
+    if let Some(body) = &operation.body {
+        out.push_str(&format!("        body: {},\n", body_argument(body)));
+    }
+    out.push_str(&format!(
+        "    ) -> impl Future<Output = {}> + Send;\n",
+        operation.response_type
+    ));
+    out
+}
+
+/// Render the arm of the dispatch of an operation, which decodes its arguments and calls its method.
+fn render_arm(operation: &OperationDef) -> String {
+    let mut out = format!(
+        "            ({:?}, {:?}) => {{\n",
+        operation.method, operation.template
+    );
+    let mut arguments = Vec::new();
+    if let Some(params_type) = &operation.params_type {
+        out.push_str(&format!(
+            "                let params = {} {{\n",
+            params_type
+        ));
+        for param in &operation.params {
+            // The type of an optional parameter is already an `Option`.
+            let function = if param.required {
+                "required_parameter"
+            } else {
+                "parameter"
+            };
+            out.push_str(&format!(
+                "                    {}: {}(extracted, {:?}, {:?})?,\n",
+                param.ident, function, param.location, param.name
+            ));
+        }
+        out.push_str("                };\n");
+        arguments.push("params");
+    }
+    if let Some(body) = &operation.body {
+        let decoded = match (body.kind, body.required) {
+            (BodyKind::Bytes, true) => "request.body.clone()",
+            (BodyKind::Bytes, false) => "(!request.body.is_empty()).then(|| request.body.clone())",
+            (_, true) => "required_body(extracted)?",
+            (_, false) => "body(extracted)?",
+        };
+        out.push_str(&format!("                let body = {};\n", decoded));
+        arguments.push("body");
+    }
+    out.push_str(&format!(
+        "                let response = self.api.{}({}).await;\n",
+        operation.ident,
+        arguments.join(", ")
+    ));
+    out.push_str("                Ok(response.into_response())\n");
+    out.push_str("            }\n");
+    out
+}
+
+/// Render the encoding of the responses of an operation.
+fn render_into_response(operation: &OperationDef) -> String {
+    let name = &operation.response_type;
+    let mut out = format!("impl {} {{\n", name);
+    out.push_str("    /// The HTTP response of the variant.\n");
+    out.push_str("    pub fn into_response(self) -> HttpResponse {\n");
+    out.push_str("        match self {\n");
+    for response in &operation.responses {
+        out.push_str(&format!(
+            "            {} => {},\n",
+            pattern(name, response),
+            encode(response)
+        ));
+    }
+    out.push_str("        }\n    }\n}\n");
+    out
+}
+
+fn pattern(name: &str, response: &ResponseDef) -> String {
+    match (response.has_status(), response.body.is_some()) {
+        (false, false) => format!("{}::{}", name, response.variant),
+        (false, true) => format!("{}::{}(body)", name, response.variant),
+        (true, false) => format!("{}::{} {{ status }}", name, response.variant),
+        (true, true) => format!("{}::{} {{ status, body }}", name, response.variant),
+    }
+}
+
+/// The expression encoding the body of a variant, with `status` and `body` in scope.
+fn encode(response: &ResponseDef) -> String {
+    let status = match response.status {
+        Status::Code(code) => code.to_string(),
+        _ => "status".to_string(),
+    };
+    let Some(body) = &response.body else {
+        return format!("empty_response({})", status);
+    };
+    match body.kind {
+        // Forms are only read from requests, a response body is never one.
+        BodyKind::Json | BodyKind::Form => {
+            format!("json_response({}, {:?}, &body)", status, body.media_type)
+        }
+        BodyKind::Text => format!(
+            "bytes_response({}, {:?}, body.into_bytes())",
+            status, body.media_type
+        ),
+        BodyKind::Bytes => format!("bytes_response({}, {:?}, body)", status, body.media_type),
+    }
+}
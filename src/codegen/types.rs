@@ -0,0 +1,979 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use serde_json::Value;
+
+use crate::loader::collect_references;
+use crate::pointer::split_reference;
+use crate::v3::{AdditionalProperties, OpenApi, RefOrObject, Reference, Schema};
+use crate::validate::V3_LITERALS;
+use crate::Error;
+
+use super::names::{doc_comment, field_ident, pascal_case, type_ident, unraw, PRELUDE};
+
+const SCHEMAS: &str = "/components/schemas/";
+
+/// The Rust type of a schema.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum RustType {
+    /// A type generated for a schema.
+    Named(String),
+    String,
+    I32,
+    I64,
+    F32,
+    F64,
+    Bool,
+    /// Any JSON value, for schemas without a type.
+    Value,
+    Vec(Box<RustType>),
+    /// A map with string keys, for `additionalProperties`.
+    Map(Box<RustType>),
+    Option(Box<RustType>),
+    Box(Box<RustType>),
+    /// Raw bytes, for bodies which are neither JSON, forms nor text.
+    Bytes,
+}
+
+impl RustType {
+    pub(crate) fn optional(self) -> RustType {
+        match self {
+            RustType::Option(_) => self,
+            _ => RustType::Option(Box::new(self)),
+        }
+    }
+
+    /// Whether the type holds a map, which needs `BTreeMap` in scope.
+    pub(crate) fn uses_map(&self) -> bool {
+        match self {
+            RustType::Map(_) => true,
+            RustType::Vec(inner) | RustType::Option(inner) | RustType::Box(inner) => {
+                inner.uses_map()
+            }
+            _ => false,
+        }
+    }
+
+    /// Box a type held by value whose name is `cyclic`, so that a type holding itself has a size.
+    fn boxed(self, cyclic: &impl Fn(&str) -> bool) -> RustType {
+        match self {
+            RustType::Named(named) if cyclic(&named) => {
+                RustType::Box(Box::new(RustType::Named(named)))
+            }
+            RustType::Option(inner) => RustType::Option(Box::new(inner.boxed(cyclic))),
+            ty => ty,
+        }
+    }
+
+    /// The generated types held by value, without the indirection of a `Vec`, a map or a `Box`.
+    fn held_by_value(&self, held: &mut Vec<String>) {
+        match self {
+            RustType::Named(name) => held.push(name.clone()),
+            RustType::Option(inner) => inner.held_by_value(held),
+            _ => {}
+        }
+    }
+}
+
+impl fmt::Display for RustType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RustType::Named(name) => f.write_str(name),
+            RustType::String => f.write_str("String"),
+            RustType::I32 => f.write_str("i32"),
+            RustType::I64 => f.write_str("i64"),
+            RustType::F32 => f.write_str("f32"),
+            RustType::F64 => f.write_str("f64"),
+            RustType::Bool => f.write_str("bool"),
+            RustType::Value => f.write_str("serde_json::Value"),
+            RustType::Vec(inner) => write!(f, "Vec<{}>", inner),
+            RustType::Map(inner) => write!(f, "BTreeMap<String, {}>", inner),
+            RustType::Option(inner) => write!(f, "Option<{}>", inner),
+            RustType::Box(inner) => write!(f, "Box<{}>", inner),
+            RustType::Bytes => f.write_str("Vec<u8>"),
+        }
+    }
+}
+
+/// A generated type.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct TypeDef {
+    pub(crate) name: String,
+    pub(crate) doc: Option<String>,
+    pub(crate) kind: Kind,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Kind {
+    Struct(Vec<Field>),
+    /// An enum of strings, with the identifier and the value of each variant.
+    Enum(Vec<(String, String)>),
+    /// An enum of the schemas of a `oneOf` told apart by the property of their discriminator, with the value, the
+    /// identifier and the type of each variant.
+    Tagged {
+        tag: String,
+        variants: Vec<(String, String, RustType)>,
+    },
+    /// An enum of the schemas of a `oneOf` or an `anyOf`, the first one matching a value being picked.
+    Untagged(Vec<(String, RustType)>),
+    Alias(RustType),
+}
+
+impl Kind {
+    /// The types held by the fields or the variants.
+    fn types(&self) -> Vec<&RustType> {
+        match self {
+            Kind::Struct(fields) => fields.iter().map(|field| &field.ty).collect(),
+            Kind::Tagged { variants, .. } => variants.iter().map(|(_, _, ty)| ty).collect(),
+            Kind::Untagged(variants) => variants.iter().map(|(_, ty)| ty).collect(),
+            Kind::Alias(ty) => vec![ty],
+            Kind::Enum(_) => Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Field {
+    pub(crate) ident: String,
+    /// The name of the property, when it differs from the identifier.
+    pub(crate) rename: Option<String>,
+    pub(crate) doc: Option<String>,
+    pub(crate) ty: RustType,
+    /// Whether the property may be missing, which leaves it out when it is `None`.
+    pub(crate) optional: bool,
+    /// Whether the field holds the additional properties of the object.
+    pub(crate) flatten: bool,
+}
+
+/// The types generated for the schemas of a document.
+///
+/// Every schema of the `components` gets a type named after it. Inline schemas which need a type of their own, such as
+/// objects, string enums and `oneOf`s, get one named after where they appear, such as `PetOwner` for the `owner`
+/// property of `Pet`.
+pub(crate) struct Types<'a> {
+    openapi: &'a OpenApi,
+    /// The type names of the schemas of the components, by schema name.
+    names: BTreeMap<String, String>,
+    /// The type names which are taken, starting with those the generated source refers to.
+    taken: BTreeSet<String>,
+    /// The discriminator property of the schemas which are only variants of tagged enums, by type name. The tag is
+    /// written by the enum, so it is left out of their structs.
+    tags: BTreeMap<String, String>,
+    definitions: Vec<TypeDef>,
+}
+
+impl<'a> Types<'a> {
+    /// Generate the types of the schemas of the components, which are not named after the `reserved` names the rest of
+    /// the source defines.
+    pub(crate) fn new(openapi: &'a OpenApi, reserved: &[&str]) -> Result<Self, Error> {
+        let schemas = openapi
+            .components
+            .as_ref()
+            .and_then(|components| components.schemas.as_ref());
+        let mut types = Types {
+            openapi,
+            names: BTreeMap::new(),
+            taken: PRELUDE
+                .iter()
+                .chain(reserved)
+                .map(|name| name.to_string())
+                .collect(),
+            tags: BTreeMap::new(),
+            definitions: Vec::new(),
+        };
+        for name in schemas.into_iter().flat_map(BTreeMap::keys) {
+            let ident = types.unique(&type_ident(name));
+            types.names.insert(name.clone(), ident);
+        }
+        types.tags = types.variant_tags();
+        for (name, schema) in schemas.into_iter().flatten() {
+            let ident = types.names[name].clone();
+            match schema {
+                RefOrObject::Ref(Reference { reference }) => {
+                    let ty = types.reference(reference, &ident)?;
+                    types.definitions.push(TypeDef {
+                        name: ident,
+                        doc: None,
+                        kind: Kind::Alias(ty),
+                    });
+                }
+                RefOrObject::Object(schema) => types.define(ident, schema)?,
+            }
+        }
+        types.break_cycles();
+        Ok(types)
+    }
+
+    /// The discriminator property of the schemas which are only used as variants of tagged enums, by type name.
+    ///
+    /// A serde tagged enum writes the tag itself and hides it from its variants, so their structs leave it out. A schema
+    /// which is also used on its own, such as the type of a property, keeps its discriminator property, and the enums it
+    /// is a variant of are untagged, so that it is written once either way. Their other variants keep theirs too.
+    fn variant_tags(&self) -> BTreeMap<String, String> {
+        let schemas = self
+            .openapi
+            .components
+            .as_ref()
+            .and_then(|components| components.schemas.as_ref());
+        let mut enums: Vec<(&str, Vec<Option<String>>)> = Vec::new();
+        let mut tags: BTreeMap<String, Option<String>> = BTreeMap::new();
+        let mut variant_uses: BTreeMap<String, usize> = BTreeMap::new();
+        for schema in schemas.into_iter().flat_map(BTreeMap::values) {
+            let RefOrObject::Object(schema) = schema else {
+                continue;
+            };
+            let (Some(discriminator), Some(variants)) = (&schema.discriminator, &schema.one_of)
+            else {
+                continue;
+            };
+            let tag = discriminator.property_name.as_str();
+            let mut names = Vec::new();
+            for variant in variants {
+                let name = match variant.as_ref() {
+                    RefOrObject::Ref(Reference { reference }) => self.component_type(reference),
+                    RefOrObject::Object(_) => None,
+                };
+                if let Some(name) = &name {
+                    *variant_uses.entry(name.clone()).or_default() += 1;
+                    let variant_tag = tags
+                        .entry(name.clone())
+                        .or_insert_with(|| Some(tag.to_string()));
+                    // the variant of enums with different tags keeps all its properties
+                    if variant_tag.as_deref() != Some(tag) {
+                        *variant_tag = None;
+                    }
+                }
+                names.push(name);
+            }
+            enums.push((tag, names));
+        }
+
+        let document = serde_json::to_value(self.openapi).unwrap_or_default();
+        let mut references = Vec::new();
+        collect_references(&document, "", V3_LITERALS, &mut references);
+        let mut uses: BTreeMap<String, usize> = BTreeMap::new();
+        for name in references
+            .iter()
+            .filter_map(|reference| self.component_type(reference))
+        {
+            *uses.entry(name).or_default() += 1;
+        }
+        let mut tags: BTreeMap<String, String> = tags
+            .into_iter()
+            .filter(|(name, _)| uses.get(name) == variant_uses.get(name))
+            .filter_map(|(name, tag)| Some((name, tag?)))
+            .collect();
+
+        // an enum with a variant keeping its tag is untagged, so all its variants keep theirs, until none changes
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (tag, names) in &enums {
+                let tagged = names.iter().all(|name| {
+                    name.as_ref()
+                        .is_some_and(|name| tags.get(name).map(String::as_str) == Some(*tag))
+                });
+                if !tagged {
+                    for name in names.iter().flatten() {
+                        changed |= tags.remove(name).is_some();
+                    }
+                }
+            }
+        }
+        tags
+    }
+
+    /// Box the fields and variants which close a cycle of types holding each other by value, such as `Person` holding a
+    /// `Company` holding a `Person`, which would have no size.
+    ///
+    /// Every field or variant between two types of the same strongly connected component of the graph of the types
+    /// held by value is boxed, which leaves no such cycle.
+    fn break_cycles(&mut self) {
+        let indices: BTreeMap<&str, usize> = self
+            .definitions
+            .iter()
+            .enumerate()
+            .map(|(index, definition)| (definition.name.as_str(), index))
+            .collect();
+        let edges: Vec<Vec<usize>> = self
+            .definitions
+            .iter()
+            .map(|definition| {
+                let mut held = Vec::new();
+                for ty in definition.kind.types() {
+                    ty.held_by_value(&mut held);
+                }
+                held.iter()
+                    .filter_map(|name| indices.get(name.as_str()).copied())
+                    .collect()
+            })
+            .collect();
+        let components = strongly_connected(&edges);
+        let component_of: BTreeMap<String, usize> = indices
+            .iter()
+            .map(|(name, index)| (name.to_string(), components[*index]))
+            .collect();
+        for (index, definition) in self.definitions.iter_mut().enumerate() {
+            let cyclic = |name: &str| component_of.get(name) == Some(&components[index]);
+            match &mut definition.kind {
+                Kind::Struct(fields) => {
+                    for field in fields {
+                        field.ty = field.ty.clone().boxed(&cyclic);
+                    }
+                }
+                Kind::Tagged { variants, .. } => {
+                    for (_, _, ty) in variants {
+                        *ty = ty.clone().boxed(&cyclic);
+                    }
+                }
+                Kind::Untagged(variants) => {
+                    for (_, ty) in variants {
+                        *ty = ty.clone().boxed(&cyclic);
+                    }
+                }
+                // an alias is the type itself, a cycle through it is broken by the other types
+                Kind::Alias(_) | Kind::Enum(_) => {}
+            }
+        }
+    }
+
+    /// The generated types, the types of the components coming in the order of their names, each followed by the inline
+    /// types it needs.
+    pub(crate) fn definitions(&self) -> &[TypeDef] {
+        &self.definitions
+    }
+
+    /// The type of a schema, generating the types its inline schemas need, named from `hint`.
+    pub(crate) fn type_of(
+        &mut self,
+        schema: &RefOrObject<Schema>,
+        hint: &str,
+    ) -> Result<RustType, Error> {
+        match schema {
+            RefOrObject::Ref(Reference { reference }) => self.reference(reference, hint),
+            RefOrObject::Object(schema) => {
+                let ty = self.inline(schema, hint)?;
+                Ok(if schema.nullable == Some(true) {
+                    ty.optional()
+                } else {
+                    ty
+                })
+            }
+        }
+    }
+
+    /// Whether the generated types use `BTreeMap`.
+    pub(crate) fn uses_map(&self) -> bool {
+        self.definitions
+            .iter()
+            .any(|definition| match &definition.kind {
+                Kind::Struct(fields) => fields.iter().any(|field| field.ty.uses_map()),
+                Kind::Tagged { variants, .. } => variants.iter().any(|(_, _, ty)| ty.uses_map()),
+                Kind::Untagged(variants) => variants.iter().any(|(_, ty)| ty.uses_map()),
+                Kind::Alias(ty) => ty.uses_map(),
+                Kind::Enum(_) => false,
+            })
+    }
+
+    /// A type name which is not taken yet, such as `Pet2` when `Pet` is.
+    pub(crate) fn unique(&mut self, name: &str) -> String {
+        let mut unique = name.to_string();
+        let mut index = 1;
+        while self.taken.contains(&unique) {
+            index += 1;
+            unique = format!("{}{}", name, index);
+        }
+        self.taken.insert(unique.clone());
+        unique
+    }
+
+    /// The type name of a reference to a schema of the components.
+    fn component_type(&self, reference: &str) -> Option<String> {
+        let (file, fragment) = split_reference(reference);
+        let name = fragment.strip_prefix(SCHEMAS)?;
+        file.is_empty().then(|| self.names.get(name).cloned())?
+    }
+
+    fn reference(&mut self, reference: &str, hint: &str) -> Result<RustType, Error> {
+        if let Some(name) = self.component_type(reference) {
+            return Ok(RustType::Named(name));
+        }
+        // A reference into a schema, such as to one of its properties, is generated as if it was inline.
+        let schema = self.openapi.resolve_reference::<Schema>(reference)?;
+        self.type_of(&RefOrObject::Object(schema.clone()), hint)
+    }
+
+    /// The type of an inline schema, without its nullability.
+    fn inline(&mut self, schema: &Schema, hint: &str) -> Result<RustType, Error> {
+        if let [single] = schema.all_of.as_deref().unwrap_or_default() {
+            if schema.properties.is_none() {
+                return self.type_of(single, hint);
+            }
+        }
+        if needs_definition(schema) {
+            let name = self.unique(&type_ident(hint));
+            self.define(name.clone(), schema)?;
+            return Ok(RustType::Named(name));
+        }
+        Ok(match schema.r#type.as_deref() {
+            Some("string") => RustType::String,
+            Some("integer") if schema.format.as_deref() == Some("int32") => RustType::I32,
+            Some("integer") => RustType::I64,
+            Some("number") if schema.format.as_deref() == Some("float") => RustType::F32,
+            Some("number") => RustType::F64,
+            Some("boolean") => RustType::Bool,
+            Some("array") => {
+                let items = match &schema.items {
+                    Some(items) => self.type_of(items, &format!("{}Item", hint))?,
+                    None => RustType::Value,
+                };
+                RustType::Vec(Box::new(items))
+            }
+            _ => match self.additional(schema, hint)? {
+                Some(values) => RustType::Map(Box::new(values)),
+                None => RustType::Value,
+            },
+        })
+    }
+
+    /// The type of the additional properties of an object, if it allows some explicitly.
+    fn additional(&mut self, schema: &Schema, hint: &str) -> Result<Option<RustType>, Error> {
+        match &schema.additional_properties {
+            Some(AdditionalProperties::Bool(true)) => Ok(Some(RustType::Value)),
+            Some(AdditionalProperties::Schema(additional)) => self
+                .type_of(additional, &format!("{}Value", hint))
+                .map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Generate the type of a schema, then the inline types it needs.
+    fn define(&mut self, name: String, schema: &Schema) -> Result<(), Error> {
+        let index = self.definitions.len();
+        self.definitions.push(TypeDef {
+            name: name.clone(),
+            doc: schema.description.clone().or_else(|| schema.title.clone()),
+            kind: Kind::Alias(RustType::Value),
+        });
+        let kind = self.kind(&name, schema)?;
+        self.definitions[index].kind = kind;
+        Ok(())
+    }
+
+    fn kind(&mut self, name: &str, schema: &Schema) -> Result<Kind, Error> {
+        let variants = schema
+            .one_of
+            .as_ref()
+            .or(schema.any_of.as_ref())
+            .filter(|variants| !variants.is_empty());
+        if let Some(variants) = variants {
+            if let Some(kind) = self.tagged(schema, variants)? {
+                return Ok(kind);
+            }
+            let mut untagged = Vec::new();
+            for (index, variant) in variants.iter().enumerate() {
+                let ty = self.type_of(variant, &format!("{}Variant{}", name, index + 1))?;
+                let ident = match &ty {
+                    RustType::Named(named) => named.clone(),
+                    ty => variant_ident(ty),
+                };
+                let ident = unique_in(&untagged, ident);
+                untagged.push((ident, ty));
+            }
+            return Ok(Kind::Untagged(untagged));
+        }
+        if let Some(values) = string_enum(schema) {
+            let mut variants: Vec<(String, String)> = Vec::new();
+            for value in values {
+                let ident = unique_in(&variants, type_ident(value));
+                variants.push((ident, value.to_string()));
+            }
+            return Ok(Kind::Enum(variants));
+        }
+        if schema.properties.is_none() && schema.all_of.is_none() {
+            return self.inline(schema, name).map(Kind::Alias);
+        }
+
+        let mut properties = Vec::new();
+        let mut required = BTreeSet::new();
+        let mut additional = None;
+        self.collect(
+            schema,
+            &mut properties,
+            &mut required,
+            &mut additional,
+            &mut Vec::new(),
+        )?;
+        let tag = self.tags.get(name).cloned();
+        let mut fields = Vec::new();
+        for (property, schema) in properties {
+            if tag.as_deref() == Some(property.as_str()) {
+                continue;
+            }
+            let resolved = self.openapi.resolve(&schema)?;
+            let directional = resolved.read_only == Some(true) || resolved.write_only == Some(true);
+            let optional = !required.contains(&property) || directional;
+            let mut ty = self.type_of(&schema, &format!("{}{}", name, pascal_case(&property)))?;
+            if optional {
+                ty = ty.optional();
+            }
+            let ident = unique_field(&fields, &field_ident(&property));
+            let doc = match &schema {
+                RefOrObject::Object(schema) => schema.description.clone(),
+                RefOrObject::Ref(_) => None,
+            };
+            fields.push(Field {
+                rename: (unraw(&ident) != property).then_some(property),
+                ident,
+                doc,
+                ty,
+                optional,
+                flatten: false,
+            });
+        }
+        if let Some(additional) = additional {
+            let values = self.type_of(&additional, &format!("{}Value", name))?;
+            fields.push(Field {
+                ident: unique_field(&fields, "additional_properties"),
+                rename: None,
+                doc: None,
+                ty: RustType::Map(Box::new(values)),
+                optional: false,
+                flatten: true,
+            });
+        }
+        Ok(Kind::Struct(fields))
+    }
+
+    /// The tagged enum of a `oneOf` with a discriminator, whose schemas must all be references to schemas which are
+    /// only used as variants.
+    fn tagged(
+        &mut self,
+        schema: &Schema,
+        variants: &[Box<RefOrObject<Schema>>],
+    ) -> Result<Option<Kind>, Error> {
+        let Some(discriminator) = &schema.discriminator else {
+            return Ok(None);
+        };
+        let mut tagged = Vec::new();
+        for variant in variants {
+            let RefOrObject::Ref(Reference { reference }) = variant.as_ref() else {
+                return Ok(None);
+            };
+            let Some(name) = self.component_type(reference) else {
+                return Ok(None);
+            };
+            // a variant which keeps its tag property is wrapped by an untagged enum
+            if self.tags.get(&name) != Some(&discriminator.property_name) {
+                return Ok(None);
+            }
+            let schema_name = split_reference(reference)
+                .1
+                .rsplit('/')
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            let value = discriminator
+                .mapping
+                .iter()
+                .flatten()
+                .find(|(_, target)| *target == reference || **target == schema_name)
+                .map_or(schema_name, |(value, _)| value.clone());
+            tagged.push((value, name.clone(), RustType::Named(name)));
+        }
+        Ok(Some(Kind::Tagged {
+            tag: discriminator.property_name.clone(),
+            variants: tagged,
+        }))
+    }
+
+    /// Gather the properties of a schema and of the schemas of its `allOf`, a property declared again replacing the
+    /// previous one.
+    fn collect(
+        &self,
+        schema: &Schema,
+        properties: &mut Vec<(String, RefOrObject<Schema>)>,
+        required: &mut BTreeSet<String>,
+        additional: &mut Option<RefOrObject<Schema>>,
+        stack: &mut Vec<String>,
+    ) -> Result<(), Error> {
+        for item in schema.all_of.iter().flatten() {
+            let reference = match item.as_ref() {
+                RefOrObject::Ref(Reference { reference }) if stack.contains(reference) => continue,
+                RefOrObject::Ref(Reference { reference }) => Some(reference.clone()),
+                RefOrObject::Object(_) => None,
+            };
+            stack.extend(reference.clone());
+            let resolved = self.openapi.resolve(item)?;
+            self.collect(resolved, properties, required, additional, stack)?;
+            if reference.is_some() {
+                stack.pop();
+            }
+        }
+        for (name, property) in schema.properties.iter().flatten() {
+            match properties.iter_mut().find(|(existing, _)| existing == name) {
+                Some((_, existing)) => *existing = property.clone(),
+                None => properties.push((name.clone(), property.clone())),
+            }
+        }
+        required.extend(schema.required.iter().flatten().cloned());
+        match &schema.additional_properties {
+            Some(AdditionalProperties::Bool(false)) | None => {}
+            Some(AdditionalProperties::Bool(true)) => {
+                *additional = Some(RefOrObject::Object(Schema::default()))
+            }
+            Some(AdditionalProperties::Schema(schema)) => *additional = Some((**schema).clone()),
+        }
+        Ok(())
+    }
+}
+
+/// The strongly connected component of each node of a graph, by Tarjan's algorithm.
+fn strongly_connected(edges: &[Vec<usize>]) -> Vec<usize> {
+    struct Tarjan<'a> {
+        edges: &'a [Vec<usize>],
+        index: Vec<Option<usize>>,
+        low: Vec<usize>,
+        stack: Vec<usize>,
+        on_stack: Vec<bool>,
+        component: Vec<usize>,
+        next: usize,
+        components: usize,
+    }
+
+    impl Tarjan<'_> {
+        fn visit(&mut self, node: usize) {
+            self.index[node] = Some(self.next);
+            self.low[node] = self.next;
+            self.next += 1;
+            self.stack.push(node);
+            self.on_stack[node] = true;
+            for &target in &self.edges[node] {
+                match self.index[target] {
+                    None => {
+                        self.visit(target);
+                        self.low[node] = self.low[node].min(self.low[target]);
+                    }
+                    Some(index) if self.on_stack[target] => {
+                        self.low[node] = self.low[node].min(index);
+                    }
+                    Some(_) => {}
+                }
+            }
+            if Some(self.low[node]) == self.index[node] {
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack[member] = false;
+                    self.component[member] = self.components;
+                    if member == node {
+                        break;
+                    }
+                }
+                self.components += 1;
+            }
+        }
+    }
+
+    let mut tarjan = Tarjan {
+        edges,
+        index: vec![None; edges.len()],
+        low: vec![0; edges.len()],
+        stack: Vec::new(),
+        on_stack: vec![false; edges.len()],
+        component: vec![0; edges.len()],
+        next: 0,
+        components: 0,
+    };
+    for node in 0..edges.len() {
+        if tarjan.index[node].is_none() {
+            tarjan.visit(node);
+        }
+    }
+    tarjan.component
+}
+
+/// Whether an inline schema needs a type of its own.
+fn needs_definition(schema: &Schema) -> bool {
+    let non_empty = |schemas: &Option<Vec<Box<RefOrObject<Schema>>>>| {
+        schemas.as_ref().is_some_and(|schemas| !schemas.is_empty())
+    };
+    non_empty(&schema.one_of)
+        || non_empty(&schema.any_of)
+        || non_empty(&schema.all_of)
+        || schema.properties.is_some()
+        || string_enum(schema).is_some()
+}
+
+/// The values of an enum of strings, `null` being left to the nullability of the schema.
+fn string_enum(schema: &Schema) -> Option<Vec<&str>> {
+    let values = schema.r#enum.as_ref()?;
+    if matches!(schema.r#type.as_deref(), Some(r#type) if r#type != "string") {
+        return None;
+    }
+    let strings: Vec<&str> = values.iter().filter_map(Value::as_str).collect();
+    let nulls = values.iter().filter(|value| value.is_null()).count();
+    (!strings.is_empty() && strings.len() + nulls == values.len()).then_some(strings)
+}
+
+/// The variant of an untagged enum holding a type which is not generated, such as `String`.
+fn variant_ident(ty: &RustType) -> String {
+    match ty {
+        RustType::Named(name) => name.clone(),
+        RustType::String => "String".to_string(),
+        RustType::I32 | RustType::I64 => "Integer".to_string(),
+        RustType::F32 | RustType::F64 => "Number".to_string(),
+        RustType::Bool => "Boolean".to_string(),
+        RustType::Value => "Value".to_string(),
+        RustType::Vec(_) => "Array".to_string(),
+        RustType::Map(_) => "Map".to_string(),
+        RustType::Bytes => "Bytes".to_string(),
+        RustType::Option(inner) | RustType::Box(inner) => variant_ident(inner),
+    }
+}
+
+/// An identifier which no variant has yet.
+fn unique_in<T>(variants: &[(String, T)], ident: String) -> String {
+    let mut unique = ident.clone();
+    let mut index = 1;
+    while variants.iter().any(|(existing, _)| *existing == unique) {
+        index += 1;
+        unique = format!("{}{}", ident, index);
+    }
+    unique
+}
+
+fn unique_field(fields: &[Field], ident: &str) -> String {
+    let mut unique = ident.to_string();
+    while fields.iter().any(|field| field.ident == unique) {
+        unique.push('_');
+    }
+    unique
+}
+
+/// Render the generated types as Rust source.
+pub(crate) fn render(definitions: &[TypeDef]) -> String {
+    definitions
+        .iter()
+        .map(render_definition)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn render_definition(definition: &TypeDef) -> String {
+    let mut out = doc_comment(definition.doc.as_deref(), "");
+    let name = &definition.name;
+    match &definition.kind {
+        Kind::Struct(fields) => {
+            out.push_str("#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]\n");
+            if fields.is_empty() {
+                out.push_str(&format!("pub struct {} {{}}\n", name));
+                return out;
+            }
+            out.push_str(&format!("pub struct {} {{\n", name));
+            for field in fields {
+                out.push_str(&doc_comment(field.doc.as_deref(), "    "));
+                let mut attributes = Vec::new();
+                if let Some(rename) = &field.rename {
+                    attributes.push(format!("rename = {:?}", rename));
+                }
+                if field.optional {
+                    attributes.push("default".to_string());
+                    attributes.push("skip_serializing_if = \"Option::is_none\"".to_string());
+                }
+                if field.flatten {
+                    attributes.push("flatten".to_string());
+                }
+                if !attributes.is_empty() {
+                    out.push_str(&format!("    #[serde({})]\n", attributes.join(", ")));
+                }
+                out.push_str(&format!("    pub {}: {},\n", field.ident, field.ty));
+            }
+            out.push_str("}\n");
+        }
+        Kind::Enum(variants) => {
+            out.push_str(
+                "#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]\n",
+            );
+            out.push_str(&format!("pub enum {} {{\n", name));
+            for (ident, value) in variants {
+                if ident != value {
+                    out.push_str(&format!("    #[serde(rename = {:?})]\n", value));
+                }
+                out.push_str(&format!("    {},\n", ident));
+            }
+            out.push_str("}\n");
+        }
+        Kind::Tagged { tag, variants } => {
+            out.push_str("#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]\n");
+            out.push_str(&format!("#[serde(tag = {:?})]\n", tag));
+            out.push_str(&format!("pub enum {} {{\n", name));
+            for (value, ident, ty) in variants {
+                if ident != value {
+                    out.push_str(&format!("    #[serde(rename = {:?})]\n", value));
+                }
+                out.push_str(&format!("    {}({}),\n", ident, ty));
+            }
+            out.push_str("}\n");
+        }
+        Kind::Untagged(variants) => {
+            out.push_str("#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]\n");
+            out.push_str("#[serde(untagged)]\n");
+            out.push_str(&format!("pub enum {} {{\n", name));
+            for (ident, ty) in variants {
+                out.push_str(&format!("    {}({}),\n", ident, ty));
+            }
+            out.push_str("}\n");
+        }
+        Kind::Alias(ty) => out.push_str(&format!("pub type {} = {};\n", name, ty)),
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn types(schemas: Value) -> String {
+        let openapi: OpenApi = serde_json::from_value(json!({
+            "openapi": "3.0.3",
+            "info": {"title": "Types", "version": "1.0.0"},
+            "paths": {},
+            "components": {"schemas": schemas}
+        }))
+        .unwrap();
+        render(Types::new(&openapi, &[]).unwrap().definitions())
+    }
+
+    #[test]
+    fn test_struct() {
+        let source = types(json!({
+            "Node": {
+                "description": "A node of a tree.",
+                "type": "object",
+                "required": ["id", "type"],
+                "properties": {
+                    "id": {"type": "integer", "format": "int32", "readOnly": true},
+                    "type": {"type": "string", "description": "The kind of node."},
+                    "parentNode": {"$ref": "#/components/schemas/Node"},
+                    "labels": {"type": "object", "additionalProperties": {"type": "string"}},
+                    "position": {"type": "object", "properties": {"x": {"type": "number"}}}
+                }
+            }
+        }));
+        assert_eq!(
+            source,
+            r#"/// A node of a tree.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Node {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub labels: Option<BTreeMap<String, String>>,
+    #[serde(rename = "parentNode", default, skip_serializing_if = "Option::is_none")]
+    pub parent_node: Option<Box<Node>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<NodePosition>,
+    /// The kind of node.
+    pub r#type: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NodePosition {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub x: Option<f64>,
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_enums() {
+        let source = types(json!({
+            "Status": {"type": "string", "enum": ["available", "on-hold", null], "nullable": true},
+            "Id": {"oneOf": [{"type": "string"}, {"type": "integer"}]},
+            "Pet": {
+                "oneOf": [{"$ref": "#/components/schemas/Cat"}, {"$ref": "#/components/schemas/Dog"}],
+                "discriminator": {"propertyName": "kind", "mapping": {"dog": "#/components/schemas/Dog"}}
+            },
+            "Cat": {"type": "object", "required": ["kind"], "properties": {"kind": {"type": "string"}, "indoor": {"type": "boolean"}}},
+            "Dog": {"type": "object", "properties": {"kind": {"type": "string"}}}
+        }));
+        assert!(source.contains(
+            "pub enum Status {\n    #[serde(rename = \"available\")]\n    Available,\n    #[serde(rename = \"on-hold\")]\n    OnHold,\n}"
+        ));
+        assert!(source.contains(
+            "#[serde(untagged)]\npub enum Id {\n    String(String),\n    Integer(i64),\n}"
+        ));
+        assert!(source.contains(
+            "#[serde(tag = \"kind\")]\npub enum Pet {\n    Cat(Cat),\n    #[serde(rename = \"dog\")]\n    Dog(Dog),\n}"
+        ));
+        // The tag is written by `Pet`, not by its variants.
+        assert!(source.contains("pub struct Cat {\n    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n    pub indoor: Option<bool>,\n}"));
+        assert!(source.contains("pub struct Dog {}"));
+    }
+
+    #[test]
+    fn test_variant_used_alone() {
+        let source = types(json!({
+            "Pet": {
+                "oneOf": [{"$ref": "#/components/schemas/Cat"}, {"$ref": "#/components/schemas/Dog"}],
+                "discriminator": {"propertyName": "kind"}
+            },
+            "Cat": {"type": "object", "required": ["kind"], "properties": {"kind": {"type": "string"}}},
+            "Dog": {"type": "object", "required": ["kind"], "properties": {"kind": {"type": "string"}}},
+            "Owner": {"type": "object", "properties": {"cat": {"$ref": "#/components/schemas/Cat"}}}
+        }));
+        // `Cat` needs its tag as the property of `Owner`, so `Pet` is untagged and its variants write their own
+        assert!(source.contains("pub struct Cat {\n    pub kind: String,\n}"));
+        assert!(source.contains("pub struct Dog {\n    pub kind: String,\n}"));
+        assert!(
+            source.contains("#[serde(untagged)]\npub enum Pet {\n    Cat(Cat),\n    Dog(Dog),\n}")
+        );
+    }
+
+    #[test]
+    fn test_cycles() {
+        let source = types(json!({
+            "Person": {"type": "object", "properties": {
+                "employer": {"$ref": "#/components/schemas/Company"},
+                "friends": {"type": "array", "items": {"$ref": "#/components/schemas/Person"}}
+            }},
+            "Company": {"type": "object", "required": ["ceo"], "properties": {
+                "ceo": {"$ref": "#/components/schemas/Person"},
+                "address": {"$ref": "#/components/schemas/Address"}
+            }},
+            "Address": {"type": "object", "properties": {"city": {"type": "string"}}},
+            "Shape": {"oneOf": [
+                {"$ref": "#/components/schemas/Group"},
+                {"type": "string"}
+            ]},
+            "Group": {"type": "object", "properties": {"first": {"$ref": "#/components/schemas/Shape"}}}
+        }));
+        assert!(source.contains("pub employer: Option<Box<Company>>,"));
+        assert!(source.contains("pub friends: Option<Vec<Person>>,"));
+        assert!(source.contains("pub ceo: Box<Person>,"));
+        assert!(source.contains("pub address: Option<Address>,"));
+        assert!(source.contains("    Group(Box<Group>),\n    String(String),"));
+        assert!(source.contains("pub first: Option<Box<Shape>>,"));
+    }
+
+    #[test]
+    fn test_all_of() {
+        let source = types(json!({
+            "Base": {"type": "object", "required": ["id"], "properties": {"id": {"type": "string"}}},
+            "Named": {
+                "allOf": [
+                    {"$ref": "#/components/schemas/Base"},
+                    {"type": "object", "required": ["name"], "properties": {"name": {"type": "string"}}}
+                ]
+            },
+            "Names": {"type": "array", "items": {"$ref": "#/components/schemas/Named"}},
+            "Alias": {"$ref": "#/components/schemas/Base"}
+        }));
+        assert!(
+            source.contains("pub struct Named {\n    pub id: String,\n    pub name: String,\n}")
+        );
+        assert!(source.contains("pub type Names = Vec<Named>;"));
+        assert!(source.contains("pub type Alias = Base;"));
+    }
+}
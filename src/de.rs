@@ -0,0 +1,86 @@
+//! Deserialization helpers shared by the versioned models.
+
+use serde::{de::Error, Deserialize, Deserializer};
+use serde_json::Value;
+
+/// The fields of a schema which a Swagger 2.0 parameter, items or header can also hold, as they describe a primitive value or an array.
+pub(crate) const SIMPLE_FIELDS: &[&str] = &[
+    "type",
+    "format",
+    "items",
+    "default",
+    "maximum",
+    "exclusiveMaximum",
+    "minimum",
+    "exclusiveMinimum",
+    "maxLength",
+    "minLength",
+    "pattern",
+    "maxItems",
+    "minItems",
+    "uniqueItems",
+    "enum",
+    "multipleOf",
+];
+
+/// Either a reference or an inline object, see [`deserialize_ref_or`].
+pub(crate) enum RefOr<R, T> {
+    Ref(R),
+    Object(T),
+}
+
+/// Deserialize a value which is either a `{"$ref": ...}` object or an inline object.
+///
+/// Unlike `#[serde(untagged)]`, the presence of `$ref` decides which one is expected, so the error of the inline object is reported rather than "data did not match any variant".
+/// The location of the error inside the inline object is kept as a JSON pointer in front of the message, see [`from_value`].
+pub(crate) fn deserialize_ref_or<'de, D, R, T>(deserializer: D) -> Result<RefOr<R, T>, D::Error>
+where
+    D: Deserializer<'de>,
+    R: Deserialize<'de>,
+    T: Deserialize<'de>,
+{
+    let value = Value::deserialize(deserializer)?;
+    if value.get("$ref").is_some() {
+        return R::deserialize(value)
+            .map(RefOr::Ref)
+            .map_err(D::Error::custom);
+    }
+    from_value(value).map(RefOr::Object)
+}
+
+/// Deserialize a buffered value, keeping the location of an error as a JSON pointer in front of the message.
+pub(crate) fn from_value<'de, T, E>(value: Value) -> Result<T, E>
+where
+    T: Deserialize<'de>,
+    E: Error,
+{
+    serde_path_to_error::deserialize(value).map_err(|e| {
+        let pointer = crate::pointer::from_path(e.path());
+        let message = e.into_inner().to_string();
+        match split_pointer(&message) {
+            Some((nested, message)) => E::custom(format!("{}{}: {}", pointer, nested, message)),
+            None if pointer.is_empty() => E::custom(message),
+            None => E::custom(format!("{}: {}", pointer, message)),
+        }
+    })
+}
+
+/// Deserialize a buffered value found at `location`, reporting the JSON pointer of an error in a crate error.
+pub(crate) fn deserialize_value<T>(value: Value, location: &str) -> Result<T, crate::Error>
+where
+    T: serde::de::DeserializeOwned,
+{
+    serde_path_to_error::deserialize(value).map_err(|e| {
+        let pointer = format!("{}{}", location, crate::pointer::from_path(e.path()));
+        crate::Error::from(e.into_inner()).at(pointer)
+    })
+}
+
+/// Split the JSON pointer written by [`from_value`] off the front of an error message.
+pub(crate) fn split_pointer(message: &str) -> Option<(&str, &str)> {
+    if message.starts_with('/') {
+        message.split_once(": ")
+    } else {
+        None
+    }
+}
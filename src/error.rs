@@ -0,0 +1,240 @@
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+use crate::de::split_pointer;
+
+/// # Error
+/// Errors raised while reading or writing an OpenApi document.
+///
+/// Every variant carries the path of the file involved when the document was loaded from or written to a path.
+#[derive(Debug)]
+pub enum Error {
+    /// The document could not be read or written.
+    Io {
+        path: Option<PathBuf>,
+        source: io::Error,
+    },
+    /// The document is not well-formed JSON or YAML.
+    Syntax {
+        path: Option<PathBuf>,
+        /// One-based line of the error, `0` when unknown.
+        line: usize,
+        /// One-based column of the error, `0` when unknown.
+        column: usize,
+        message: String,
+    },
+    /// The document declares a specification version which is not supported.
+    UnsupportedVersion {
+        path: Option<PathBuf>,
+        version: String,
+    },
+    /// The document is well-formed but does not match the structure of the specification.
+    Structure {
+        path: Option<PathBuf>,
+        /// JSON pointer to the value which could not be deserialized.
+        pointer: String,
+        /// One-based line of the error, `0` when unknown.
+        line: usize,
+        /// One-based column of the error, `0` when unknown.
+        column: usize,
+        message: String,
+    },
+    /// A `$ref` points to nothing, to an object of another type, or outside of the document.
+    UnresolvedReference { reference: String, message: String },
+    /// A chain of `$ref`s leads back to a reference which was already followed.
+    CyclicReference {
+        /// The references in the order they were followed, ending with the repeated one.
+        chain: Vec<String>,
+    },
+}
+
+impl Error {
+    /// The path of the file the error was raised for, if any.
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Error::Io { path, .. }
+            | Error::Syntax { path, .. }
+            | Error::UnsupportedVersion { path, .. }
+            | Error::Structure { path, .. } => path.as_deref(),
+            Error::UnresolvedReference { .. } | Error::CyclicReference { .. } => None,
+        }
+    }
+
+    /// The one-based line and column the error was raised at, if known.
+    pub fn location(&self) -> Option<(usize, usize)> {
+        match self {
+            Error::Syntax { line, column, .. } | Error::Structure { line, column, .. }
+                if *line > 0 =>
+            {
+                Some((*line, *column))
+            }
+            _ => None,
+        }
+    }
+
+    /// Attach the path of the file the error was raised for.
+    pub(crate) fn with_path<P>(mut self, file: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        match &mut self {
+            Error::Io { path, .. }
+            | Error::Syntax { path, .. }
+            | Error::UnsupportedVersion { path, .. }
+            | Error::Structure { path, .. } => *path = Some(file.as_ref().to_path_buf()),
+            Error::UnresolvedReference { .. } | Error::CyclicReference { .. } => {}
+        }
+        self
+    }
+
+    /// Attach the JSON pointer of the value a structural error was raised for.
+    pub(crate) fn at(mut self, location: String) -> Self {
+        if let Error::Structure {
+            pointer, message, ..
+        } = &mut self
+        {
+            let split = split_pointer(message)
+                .map(|(nested, rest)| (format!("{}{}", location, nested), rest.to_string()));
+            *pointer = match split {
+                Some((nested, rest)) => {
+                    *message = rest;
+                    nested
+                }
+                None => location,
+            };
+        }
+        self
+    }
+
+    #[cfg(feature = "yaml")]
+    pub(crate) fn yaml(e: serde_yaml::Error, syntax: bool) -> Self {
+        let (line, column) = e
+            .location()
+            .map_or((0, 0), |location| (location.line(), location.column()));
+        let message = e.to_string();
+        if syntax {
+            Error::Syntax {
+                path: None,
+                line,
+                column,
+                message,
+            }
+        } else {
+            Error::Structure {
+                path: None,
+                pointer: String::new(),
+                line,
+                column,
+                message,
+            }
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(path) = self.path() {
+            write!(f, "{}", path.display())?;
+            if let Some((line, column)) = self.location() {
+                write!(f, ":{}:{}", line, column)?;
+            }
+            write!(f, ": ")?;
+        }
+        match self {
+            Error::Io { source, .. } => write!(f, "{}", source),
+            Error::Syntax { message, .. } => write!(f, "syntax error: {}", message),
+            Error::UnsupportedVersion { version, .. } => {
+                write!(f, "unsupported specification version `{}`", version)
+            }
+            Error::Structure {
+                pointer, message, ..
+            } if !pointer.is_empty() => {
+                write!(f, "invalid document at `{}`: {}", pointer, message)
+            }
+            Error::Structure { message, .. } => write!(f, "invalid document: {}", message),
+            Error::UnresolvedReference { reference, message } => {
+                write!(f, "reference `{}` {}", reference, message)
+            }
+            Error::CyclicReference { chain } => {
+                write!(f, "cyclic reference `{}`", chain.join("` -> `"))
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// serde_json appends the position to its messages, which is already carried by the error.
+fn strip_position(message: String, line: usize, column: usize) -> String {
+    let suffix = format!(" at line {} column {}", line, column);
+    match message.strip_suffix(&suffix) {
+        Some(message) => message.to_string(),
+        None => message,
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(source: io::Error) -> Self {
+        Error::Io { path: None, source }
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        use serde_json::error::Category;
+
+        let (line, column) = (e.line(), e.column());
+        match e.classify() {
+            Category::Io => Error::Io {
+                path: None,
+                source: e.into(),
+            },
+            Category::Syntax | Category::Eof => Error::Syntax {
+                path: None,
+                line,
+                column,
+                message: strip_position(e.to_string(), line, column),
+            },
+            Category::Data => Error::Structure {
+                path: None,
+                pointer: String::new(),
+                line,
+                column,
+                message: strip_position(e.to_string(), line, column),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_error_category() {
+        let e: Error = serde_json::from_str::<Vec<u8>>("[1,").unwrap_err().into();
+        assert!(matches!(e, Error::Syntax { line: 1, .. }));
+
+        let e: Error = serde_json::from_str::<Vec<u8>>("[\"a\"]")
+            .unwrap_err()
+            .into();
+        assert!(matches!(e, Error::Structure { line: 1, .. }));
+    }
+
+    #[test]
+    fn test_display_with_path() {
+        let e = Error::from(serde_json::from_str::<Vec<u8>>("\n[1,").unwrap_err())
+            .with_path("api.json");
+        assert_eq!(e.path(), Some(Path::new("api.json")));
+        assert!(e.to_string().starts_with("api.json:2:3: syntax error"));
+    }
+}
@@ -7,7 +7,7 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// Contains openapi specification extensions
 /// see https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions
-#[derive(Debug, Eq, PartialEq, Clone)]
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
 pub struct Extensions(BTreeMap<String, serde_json::Value>);
 
 impl Extensions {
@@ -26,12 +26,6 @@ impl Extensions {
     }
 }
 
-impl Default for Extensions {
-    fn default() -> Self {
-        Self(BTreeMap::new())
-    }
-}
-
 impl<'de> Deserialize<'de> for Extensions {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
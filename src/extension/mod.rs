@@ -1,2 +1,3 @@
+#[allow(clippy::module_inception)]
 mod extension;
 pub use crate::extension::extension::*;
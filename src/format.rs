@@ -0,0 +1,120 @@
+use std::path::Path;
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{error::Error, pointer};
+
+/// # Format
+/// The textual formats an OpenApi document can be written in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// JavaScript Object Notation, see https://www.json.org
+    Json,
+    /// YAML Ain't Markup Language, see https://yaml.org
+    #[cfg(feature = "yaml")]
+    Yaml,
+}
+
+impl Format {
+    /// Guess the format from the extension of a file path.
+    ///
+    /// Returns `None` when the extension is missing or unknown.
+    pub fn from_path<P>(path: P) -> Option<Format>
+    where
+        P: AsRef<Path>,
+    {
+        let extension = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "json" => Some(Format::Json),
+            #[cfg(feature = "yaml")]
+            "yaml" | "yml" => Some(Format::Yaml),
+            _ => None,
+        }
+    }
+
+    /// Guess the format by sniffing the content of a document.
+    ///
+    /// A document whose first significant character opens a JSON object or array is JSON, anything else is YAML.
+    pub fn detect(content: &str) -> Format {
+        let first = content
+            .trim_start_matches('\u{feff}')
+            .trim_start()
+            .chars()
+            .next();
+        match first {
+            Some('{') | Some('[') => Format::Json,
+            #[cfg(feature = "yaml")]
+            _ => Format::Yaml,
+            #[cfg(not(feature = "yaml"))]
+            _ => Format::Json,
+        }
+    }
+
+    /// Deserialize a value from a string in this format.
+    ///
+    /// Structural errors carry the JSON pointer of the value which could not be deserialized.
+    pub fn from_str<T>(self, str: &str) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        match self {
+            Format::Json => {
+                let mut deserializer = serde_json::Deserializer::from_str(str);
+                let value = serde_path_to_error::deserialize(&mut deserializer).map_err(|e| {
+                    let pointer = pointer::from_path(e.path());
+                    Error::from(e.into_inner()).at(pointer)
+                })?;
+                deserializer.end()?;
+                Ok(value)
+            }
+            #[cfg(feature = "yaml")]
+            Format::Yaml => serde_path_to_error::deserialize(serde_yaml::Deserializer::from_str(
+                str,
+            ))
+            .map_err(|e| {
+                let pointer = pointer::from_path(e.path());
+                // serde_yaml does not tell malformed input apart from a mismatched structure
+                let syntax = serde_yaml::from_str::<serde::de::IgnoredAny>(str).is_err();
+                Error::yaml(e.into_inner(), syntax).at(pointer)
+            }),
+        }
+    }
+
+    /// Serialize a value into a string in this format.
+    pub fn to_string<T>(self, value: &T) -> Result<String, Error>
+    where
+        T: Serialize,
+    {
+        match self {
+            Format::Json => Ok(serde_json::to_string_pretty(value)?),
+            #[cfg(feature = "yaml")]
+            Format::Yaml => serde_yaml::to_string(value).map_err(|e| Error::yaml(e, false)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(Format::from_path("api.json"), Some(Format::Json));
+        assert_eq!(Format::from_path("api.JSON"), Some(Format::Json));
+        assert_eq!(Format::from_path("api"), None);
+        assert_eq!(Format::from_path("api.txt"), None);
+        #[cfg(feature = "yaml")]
+        {
+            assert_eq!(Format::from_path("dir/api.yaml"), Some(Format::Yaml));
+            assert_eq!(Format::from_path("api.yml"), Some(Format::Yaml));
+        }
+    }
+
+    #[test]
+    fn test_format_detect() {
+        assert_eq!(Format::detect("  \n{\"openapi\":\"3.0.0\"}"), Format::Json);
+        assert_eq!(Format::detect("\u{feff}[]"), Format::Json);
+        #[cfg(feature = "yaml")]
+        assert_eq!(Format::detect("openapi: 3.0.0\n"), Format::Yaml);
+    }
+}
@@ -0,0 +1,289 @@
+//! Validation of HTTP requests and responses against the operations of an OpenAPI 3.0 document.
+//!
+//! The types of this module do not depend on any HTTP library: a [`Request`] borrows the method, path, query string,
+//! headers and body of a request, which makes it easy to build from a middleware of any framework.
+//! [`OpenApi::validate_request`](crate::v3::OpenApi::validate_request) finds the operation it targets and reports
+//! every [`RequestError`], and [`OpenApi::extract_request`](crate::v3::OpenApi::extract_request) also returns the
+//! decoded values of the parameters and the body of a valid request. A [`Response`] is checked the same way against
+//! the responses of the operation its request targets by
+//! [`OpenApi::validate_response`](crate::v3::OpenApi::validate_response), which is handy to check recorded exchanges
+//! in contract tests. These methods compile the router of the document on each call, a [`Validator`] compiles it once
+//! for all the requests of a server.
+//!
+//! ```
+//! # use openapi_schema::http::{Request, Response};
+//! # use openapi_schema::v3::OpenApi;
+//! # fn example(openapi: &OpenApi) {
+//! let request = Request::new("POST", "/pets?dryRun=true")
+//!     .with_header("Content-Type", "application/json")
+//!     .with_body(br#"{"name": "Rex"}"#);
+//! for error in openapi.validate_request(&request) {
+//!     println!("{}", error);
+//! }
+//! let response = Response::new(201)
+//!     .with_header("Content-Type", "application/json")
+//!     .with_body(br#"{"id": 1, "name": "Rex"}"#);
+//! for error in openapi.validate_response(&request, &response) {
+//!     println!("{}", error);
+//! }
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+
+use percent_encoding::percent_decode_str;
+use serde_json::{Map, Number, Value};
+
+use crate::router::Router;
+use crate::v3::{
+    AdditionalProperties, Media, OpenApi, Operation, PathItem, RefOrObject, Reference, Schema,
+};
+
+mod request;
+mod response;
+
+pub use request::{Extracted, Request, RequestError};
+pub use response::{Response, ResponseError};
+
+/// # Validator
+/// Checks requests and responses like the methods of [`OpenApi`], with the router of the document compiled once
+/// rather than for each check, for a server which checks every request it receives.
+#[derive(Clone, Debug)]
+pub struct Validator<'a> {
+    openapi: &'a OpenApi,
+    router: Router<'a, PathItem, Operation>,
+}
+
+impl<'a> Validator<'a> {
+    /// Compile the router of a document.
+    pub fn new(openapi: &'a OpenApi) -> Self {
+        Validator {
+            openapi,
+            router: openapi.router(),
+        }
+    }
+
+    /// The document requests and responses are checked against.
+    pub fn openapi(&self) -> &'a OpenApi {
+        self.openapi
+    }
+
+    /// See [`OpenApi::validate_request`].
+    pub fn validate_request(&self, request: &Request) -> Vec<RequestError> {
+        self.extract_request(request).err().unwrap_or_default()
+    }
+}
+
+/// The headers which are described by the document outside of its parameters, so that header parameters of these
+/// names are ignored.
+pub(crate) const RESERVED_HEADERS: &[&str] = &["accept", "content-type", "authorization"];
+
+/// The media type of a `Content-Type`, lowercased and without its parameters.
+pub(crate) fn essence(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+/// The media type of a `content` map describing a `Content-Type`: the exact media type, else its range such as
+/// `image/*`, else `*/*`.
+pub(crate) fn select_media<'a>(
+    content: &'a BTreeMap<String, Media>,
+    content_type: &str,
+) -> Option<(&'a String, &'a Media)> {
+    let media_type = essence(content_type);
+    let range = format!("{}/*", media_type.split('/').next().unwrap_or_default());
+    let found = [media_type.as_str(), range.as_str(), "*/*"]
+        .into_iter()
+        .find_map(|wanted| content.iter().find(|(key, _)| essence(key) == wanted));
+    found
+}
+
+/// The value of a header, its repeated values joined by commas. Header names are case-insensitive.
+pub(crate) fn header(headers: &[(&str, &str)], name: &str) -> Option<String> {
+    let values: Vec<_> = headers
+        .iter()
+        .filter(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.trim())
+        .collect();
+    (!values.is_empty()).then(|| values.join(","))
+}
+
+pub(crate) fn percent_decode(str: &str) -> String {
+    percent_decode_str(str).decode_utf8_lossy().into_owned()
+}
+
+/// The pairs of a query string or a form, still percent-encoded, where `+` stands for a space and is written `%20`.
+///
+/// The pairs are left encoded so that the delimiters of a style are told apart from the escaped ones of its values.
+pub(crate) fn split_query(query: &str) -> Vec<(String, String)> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (key.replace('+', "%20"), value.replace('+', "%20"))
+        })
+        .collect()
+}
+
+/// The percent-decoded pairs of a query string or a form.
+pub(crate) fn parse_query(query: &str) -> Vec<(String, String)> {
+    split_query(query)
+        .into_iter()
+        .map(|(key, value)| (percent_decode(&key), percent_decode(&value)))
+        .collect()
+}
+
+/// The pairs of a `Cookie` header, such as `session=abc; theme=dark`, still percent-encoded.
+pub(crate) fn parse_cookies(cookie: &str) -> Vec<(String, String)> {
+    cookie
+        .split(';')
+        .filter_map(|pair| {
+            let (key, value) = pair.trim().split_once('=')?;
+            Some((key.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// The reference of an item which cannot be resolved, or the `pointer` of the item itself when it is inline.
+pub(crate) fn reference_of<T>(item: &RefOrObject<T>, pointer: String) -> String {
+    match item {
+        RefOrObject::Ref(Reference { reference }) => reference.clone(),
+        RefOrObject::Object(_) => pointer,
+    }
+}
+
+/// Parse a body according to its media type, `None` for a media type whose content is not checked, such as an image or
+/// `multipart/form-data`.
+///
+/// JSON bodies are parsed as is, forms become objects whose values are converted to the types of the schema, and text
+/// bodies become strings.
+pub(crate) fn parse_body(
+    openapi: &OpenApi,
+    media_type: &str,
+    body: &[u8],
+    schema: Option<&RefOrObject<Schema>>,
+) -> Result<Option<Value>, String> {
+    let media_type = essence(media_type);
+    if media_type == "application/json" || media_type.ends_with("+json") {
+        return serde_json::from_slice(body)
+            .map(Some)
+            .map_err(|e| format!("the body is not valid JSON: {}", e));
+    }
+    let text = || std::str::from_utf8(body).map_err(|_| "the body is not valid UTF-8".to_string());
+    if media_type == "application/x-www-form-urlencoded" {
+        let schema = schema.and_then(|schema| openapi.resolve(schema).ok());
+        let mut form = Map::new();
+        for (key, value) in parse_query(text()?) {
+            let array = schema
+                .and_then(|schema| schema.properties.as_ref()?.get(&key))
+                .and_then(|property| openapi.resolve(property).ok())
+                .is_some_and(|property| property.r#type.as_deref() == Some("array"));
+            match form.get_mut(&key) {
+                Some(Value::Array(items)) if array => items.push(Value::String(value)),
+                Some(_) => {}
+                None if array => {
+                    form.insert(key, Value::Array(vec![Value::String(value)]));
+                }
+                None => {
+                    form.insert(key, Value::String(value));
+                }
+            }
+        }
+        let form = Value::Object(form);
+        return Ok(Some(match schema {
+            Some(schema) => coerce_schema(openapi, schema, form),
+            None => form,
+        }));
+    }
+    if media_type.starts_with("text/") {
+        return text().map(|text| Some(Value::String(text.to_string())));
+    }
+    Ok(None)
+}
+
+/// Convert the strings of a decoded parameter or form to the types of its schema.
+///
+/// Strings which cannot be converted are kept, so that validating the value reports them.
+pub(crate) fn coerce(
+    openapi: &OpenApi,
+    schema: Option<&RefOrObject<Schema>>,
+    value: Value,
+) -> Value {
+    let Some(schema) = schema.and_then(|schema| openapi.resolve(schema).ok()) else {
+        return value;
+    };
+    coerce_schema(openapi, schema, value)
+}
+
+fn coerce_schema(openapi: &OpenApi, schema: &Schema, value: Value) -> Value {
+    match value {
+        Value::String(string) => {
+            let converted = match schema_type(openapi, schema) {
+                Some("integer") => string
+                    .parse::<i64>()
+                    .map(Number::from)
+                    .or_else(|_| string.parse::<u64>().map(Number::from))
+                    .ok()
+                    .map(Value::Number),
+                Some("number") => match string.parse::<i64>() {
+                    Ok(integer) => Some(Value::Number(integer.into())),
+                    Err(_) => string
+                        .parse::<f64>()
+                        .ok()
+                        .and_then(Number::from_f64)
+                        .map(Value::Number),
+                },
+                Some("boolean") => string.parse::<bool>().ok().map(Value::Bool),
+                _ => None,
+            };
+            converted.unwrap_or(Value::String(string))
+        }
+        Value::Array(items) => Value::Array(
+            items
+                .into_iter()
+                .map(|item| coerce(openapi, schema.items.as_deref(), item))
+                .collect(),
+        ),
+        Value::Object(map) => {
+            let additional = match &schema.additional_properties {
+                Some(AdditionalProperties::Schema(schema)) => Some(schema.as_ref()),
+                _ => None,
+            };
+            Value::Object(
+                map.into_iter()
+                    .map(|(key, value)| {
+                        let property = schema
+                            .properties
+                            .as_ref()
+                            .and_then(|properties| properties.get(&key))
+                            .or(additional);
+                        let value = coerce(openapi, property, value);
+                        (key, value)
+                    })
+                    .collect(),
+            )
+        }
+        value => value,
+    }
+}
+
+/// The type of a schema, looked up in its `allOf`, `oneOf` and `anyOf` when it declares none.
+pub(crate) fn schema_type<'a>(openapi: &'a OpenApi, schema: &'a Schema) -> Option<&'a str> {
+    if let Some(r#type) = &schema.r#type {
+        return Some(r#type);
+    }
+    if schema.properties.is_some() {
+        return Some("object");
+    }
+    [&schema.all_of, &schema.one_of, &schema.any_of]
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|item| openapi.resolve(item).ok())
+        .find_map(|schema| schema_type(openapi, schema))
+}
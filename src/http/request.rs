@@ -0,0 +1,565 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde_json::Value;
+
+use crate::instance::{Direction, InstanceError};
+use crate::pointer::{join, split_reference};
+use crate::router::RouteError;
+use crate::style::{Shape, Style};
+use crate::v3::instance::InstanceValidator;
+use crate::v3::{OpenApi, Operation, Parameter, PathItem, RefOrObject, Reference, Schema};
+
+use super::{
+    coerce, header, parse_body, parse_cookies, percent_decode, reference_of, schema_type,
+    select_media, split_query, Validator, RESERVED_HEADERS,
+};
+
+/// # Request
+/// An HTTP request to validate, borrowing its parts from the request of any HTTP library.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Request<'a> {
+    pub method: &'a str,
+    /// The path of the request, without its query string, still percent-encoded.
+    pub path: &'a str,
+    /// The query string, without the leading `?`, still percent-encoded.
+    pub query: &'a str,
+    pub headers: Vec<(&'a str, &'a str)>,
+    pub body: &'a [u8],
+}
+
+impl<'a> Request<'a> {
+    /// A request without headers nor body. A query string following the path, as in `/pets?limit=10`, is split from it.
+    pub fn new(method: &'a str, path: &'a str) -> Self {
+        let (path, query) = path.split_once('?').unwrap_or((path, ""));
+        Request {
+            method,
+            path,
+            query,
+            headers: Vec::new(),
+            body: &[],
+        }
+    }
+
+    /// Set the query string, without the leading `?`.
+    pub fn with_query(mut self, query: &'a str) -> Self {
+        self.query = query;
+        self
+    }
+
+    /// Add a header. A header sent several times is added once for each value.
+    pub fn with_header(mut self, name: &'a str, value: &'a str) -> Self {
+        self.headers.push((name, value));
+        self
+    }
+
+    /// Set the body.
+    pub fn with_body(mut self, body: &'a [u8]) -> Self {
+        self.body = body;
+        self
+    }
+}
+
+/// # RequestError
+/// A way a request does not follow the document.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RequestError {
+    /// No path of the document matches the path of the request.
+    NotFound { path: String },
+    /// The path matches a path of the document which has no operation for the method.
+    MethodNotAllowed { method: String, template: String },
+    /// A required parameter was not sent.
+    MissingParameter { name: String, location: String },
+    /// A parameter does not follow its `style`, or its content could not be parsed.
+    MalformedParameter {
+        name: String,
+        location: String,
+        message: String,
+    },
+    /// The value of a parameter does not match its schema.
+    InvalidParameter {
+        name: String,
+        location: String,
+        errors: Vec<InstanceError>,
+    },
+    /// The request body is required but was not sent.
+    MissingBody,
+    /// The `Content-Type` of the body is not one the operation accepts, `None` when it was not sent.
+    UnsupportedMediaType {
+        content_type: Option<String>,
+        expected: Vec<String>,
+    },
+    /// The body could not be parsed according to its `Content-Type`.
+    MalformedBody { message: String },
+    /// The body does not match the schema of its media type.
+    InvalidBody {
+        media_type: String,
+        errors: Vec<InstanceError>,
+    },
+    /// A parameter or the request body of the operation is a reference which points to nothing, so the request cannot
+    /// be checked.
+    UnresolvedReference { reference: String },
+}
+
+impl fmt::Display for RequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let errors = |f: &mut fmt::Formatter<'_>, errors: &[InstanceError]| {
+            errors
+                .iter()
+                .try_for_each(|error| write!(f, "\n  {}", error))
+        };
+        match self {
+            RequestError::NotFound { path } => write!(f, "no path matches `{}`", path),
+            RequestError::MethodNotAllowed { method, template } => {
+                write!(f, "`{}` has no `{}` operation", template, method)
+            }
+            RequestError::MissingParameter { name, location } => {
+                write!(f, "the {} parameter `{}` is required", location, name)
+            }
+            RequestError::MalformedParameter {
+                name,
+                location,
+                message,
+            } => write!(
+                f,
+                "the {} parameter `{}` is malformed: {}",
+                location, name, message
+            ),
+            RequestError::InvalidParameter {
+                name,
+                location,
+                errors: instance_errors,
+            } => {
+                write!(f, "the {} parameter `{}` is invalid:", location, name)?;
+                errors(f, instance_errors)
+            }
+            RequestError::MissingBody => f.write_str("the request body is required"),
+            RequestError::UnsupportedMediaType {
+                content_type,
+                expected,
+            } => write!(
+                f,
+                "the content type {} is not one of {}",
+                content_type
+                    .as_ref()
+                    .map_or("(none)".to_string(), |content_type| format!(
+                        "`{}`",
+                        content_type
+                    )),
+                expected.join(", ")
+            ),
+            RequestError::MalformedBody { message } => f.write_str(message),
+            RequestError::InvalidBody {
+                media_type,
+                errors: instance_errors,
+            } => {
+                write!(f, "the `{}` body is invalid:", media_type)?;
+                errors(f, instance_errors)
+            }
+            RequestError::UnresolvedReference { reference } => {
+                write!(f, "the reference `{}` points to nothing", reference)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RequestError {}
+
+impl RequestError {
+    /// The status of a response rejecting the request: `404` when no path matches, `405` when the path has no
+    /// operation for the method, `415` for an unsupported media type, `500` when the document cannot be followed and
+    /// `400` otherwise.
+    pub fn status(&self) -> u16 {
+        match self {
+            RequestError::NotFound { .. } => 404,
+            RequestError::MethodNotAllowed { .. } => 405,
+            RequestError::UnsupportedMediaType { .. } => 415,
+            RequestError::UnresolvedReference { .. } => 500,
+            _ => 400,
+        }
+    }
+}
+
+/// # Extracted
+/// The operation a valid request targets, with the decoded values of its parameters and body.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Extracted<'a> {
+    /// The template of the path item, such as `/pets/{petId}`.
+    pub template: &'a str,
+    /// The lowercase method of the operation.
+    pub method: &'static str,
+    pub operation: &'a Operation,
+    /// The values of the parameters which were sent, by location then name, converted to the types of their schemas.
+    pub parameters: BTreeMap<String, BTreeMap<String, Value>>,
+    /// The parsed body, `None` when none was sent or when its media type is not parsed, such as an image or
+    /// `multipart/form-data`.
+    pub body: Option<Value>,
+}
+
+impl Extracted<'_> {
+    /// The value of a parameter, such as `parameter("query", "limit")`.
+    pub fn parameter(&self, location: &str, name: &str) -> Option<&Value> {
+        self.parameters.get(location)?.get(name)
+    }
+}
+
+impl From<RouteError> for RequestError {
+    fn from(error: RouteError) -> Self {
+        match error {
+            RouteError::NotFound { path } => RequestError::NotFound { path },
+            RouteError::MethodNotAllowed {
+                method, template, ..
+            } => RequestError::MethodNotAllowed { method, template },
+        }
+    }
+}
+
+impl OpenApi {
+    /// Check a request against the operation of the document it targets.
+    ///
+    /// The operation is found from the method and the path, which may start with the path of one of the `servers`.
+    /// Its parameters are decoded according to their `style` and `explode` and checked against their schemas, and the
+    /// body is parsed according to its `Content-Type` and checked against the schema of its media type. JSON, form and
+    /// text bodies are checked, the content of other media types, such as `multipart/form-data`, is not. An empty list
+    /// means the request is valid.
+    pub fn validate_request(&self, request: &Request) -> Vec<RequestError> {
+        Validator::new(self).validate_request(request)
+    }
+
+    /// Check a request like [`validate_request`](OpenApi::validate_request), and return the operation it targets with
+    /// the decoded values of its parameters and body when it is valid.
+    pub fn extract_request(&self, request: &Request) -> Result<Extracted<'_>, Vec<RequestError>> {
+        Validator::new(self).extract_request(request)
+    }
+}
+
+impl<'a> Validator<'a> {
+    /// See [`OpenApi::extract_request`].
+    pub fn extract_request(&self, request: &Request) -> Result<Extracted<'a>, Vec<RequestError>> {
+        let found = self
+            .router
+            .route(request.method, request.path)
+            .map_err(|error| vec![error.into()])?;
+        let pointer = join(&join("#/paths", found.template), found.method);
+        let mut validator = RequestValidator {
+            openapi: self.openapi,
+            request,
+            path_parameters: found.parameters,
+            errors: Vec::new(),
+            values: BTreeMap::new(),
+            body: None,
+        };
+        let parameters = validator.parameters(found.path_item, found.operation, &pointer);
+        let query_names: Vec<&str> = parameters
+            .iter()
+            .filter(|(parameter, _)| parameter.r#in == "query")
+            .map(|(parameter, _)| parameter.name.as_str())
+            .collect();
+        for (parameter, pointer) in &parameters {
+            validator.parameter(parameter, pointer, &query_names);
+        }
+        validator.body(found.operation, &pointer);
+        if !validator.errors.is_empty() {
+            return Err(validator.errors);
+        }
+        Ok(Extracted {
+            template: found.template,
+            method: found.method,
+            operation: found.operation,
+            parameters: validator.values,
+            body: validator.body,
+        })
+    }
+}
+
+struct RequestValidator<'a> {
+    openapi: &'a OpenApi,
+    request: &'a Request<'a>,
+    /// The raw values of the path parameters, still percent-encoded.
+    path_parameters: BTreeMap<String, String>,
+    errors: Vec<RequestError>,
+    /// The decoded values of the parameters, by location then name.
+    values: BTreeMap<String, BTreeMap<String, Value>>,
+    body: Option<Value>,
+}
+
+impl<'a> RequestValidator<'a> {
+    /// The parameters of an operation with the pointers of their definitions, including those of its path item which it
+    /// does not override.
+    fn parameters(
+        &mut self,
+        item: &'a PathItem,
+        operation: &'a Operation,
+        pointer: &str,
+    ) -> Vec<(&'a Parameter, String)> {
+        let mut parameters =
+            self.resolve_parameters(operation.parameters.as_deref().unwrap_or_default(), pointer);
+        let (item_pointer, _) = pointer.rsplit_once('/').unwrap_or_default();
+        for (parameter, pointer) in
+            self.resolve_parameters(item.parameters.as_deref().unwrap_or_default(), item_pointer)
+        {
+            let overridden = parameters
+                .iter()
+                .any(|(other, _)| other.name == parameter.name && other.r#in == parameter.r#in);
+            if !overridden {
+                parameters.push((parameter, pointer));
+            }
+        }
+        parameters
+    }
+
+    /// The parameters of a list with the pointers of their definitions, reporting those which cannot be resolved.
+    fn resolve_parameters(
+        &mut self,
+        items: &'a [RefOrObject<Parameter>],
+        pointer: &str,
+    ) -> Vec<(&'a Parameter, String)> {
+        let mut parameters = Vec::new();
+        for (index, item) in items.iter().enumerate() {
+            let pointer = join(&join(pointer, "parameters"), &index.to_string());
+            let Ok(parameter) = self.openapi.resolve(item) else {
+                self.errors.push(RequestError::UnresolvedReference {
+                    reference: reference_of(item, pointer),
+                });
+                continue;
+            };
+            let pointer = match item {
+                RefOrObject::Ref(Reference { reference }) => {
+                    format!("#{}", split_reference(reference).1)
+                }
+                RefOrObject::Object(_) => pointer,
+            };
+            parameters.push((parameter, pointer));
+        }
+        parameters
+    }
+
+    fn parameter(&mut self, parameter: &Parameter, pointer: &str, query_names: &[&str]) {
+        let location = parameter.r#in.as_str();
+        if location == "header"
+            && RESERVED_HEADERS.contains(&parameter.name.to_ascii_lowercase().as_str())
+        {
+            return;
+        }
+        let malformed = |message: String| RequestError::MalformedParameter {
+            name: parameter.name.clone(),
+            location: location.to_string(),
+            message,
+        };
+
+        // A parameter described by `content` is sent as a single string in its media type.
+        if let Some((media_type, media)) = parameter.content.iter().flatten().next() {
+            let Some(raw) = self.raw(parameter) else {
+                self.missing(parameter);
+                return;
+            };
+            let value = match parse_body(
+                self.openapi,
+                media_type,
+                percent_decode(&raw).as_bytes(),
+                media.schema.as_ref(),
+            ) {
+                Ok(Some(value)) => value,
+                Ok(None) => return,
+                Err(message) => {
+                    self.errors.push(malformed(message));
+                    return;
+                }
+            };
+            if let Some(schema) = &media.schema {
+                let schema_path = join(&join(&join(pointer, "content"), media_type), "schema");
+                self.check(parameter, schema, &value, &schema_path);
+            }
+            self.insert(parameter, value);
+            return;
+        }
+
+        let schema = parameter.schema.as_ref();
+        let shape = schema
+            .and_then(|schema| self.openapi.resolve(schema).ok())
+            .map_or(Shape::Primitive, |schema| {
+                Shape::of(schema_type(self.openapi, schema))
+            });
+        let style = match parameter.style.as_deref() {
+            Some(name) => match Style::from_name(name) {
+                Some(style) => style,
+                None => {
+                    self.errors
+                        .push(malformed(format!("`{}` is not a style", name)));
+                    return;
+                }
+            },
+            None => Style::default_for(location),
+        };
+        let explode = parameter.explode.unwrap_or(style.explode_by_default());
+        let decoded = if style.is_paired() {
+            let pairs = match location {
+                "cookie" => self.cookies(),
+                _ => split_query(self.request.query)
+                    .into_iter()
+                    .filter(|(key, _)| {
+                        let key = percent_decode(key);
+                        key == parameter.name || !query_names.contains(&key.as_str())
+                    })
+                    .collect(),
+            };
+            style.decode_pairs(&parameter.name, explode, shape, &pairs)
+        } else {
+            match self.raw(parameter) {
+                Some(raw) => style
+                    .decode(&parameter.name, explode, shape, &raw)
+                    .map(Some),
+                None => Ok(None),
+            }
+        };
+        let value = match decoded {
+            Ok(Some(value)) => coerce(self.openapi, schema, value),
+            Ok(None) => {
+                self.missing(parameter);
+                return;
+            }
+            Err(e) => {
+                self.errors.push(malformed(e.message));
+                return;
+            }
+        };
+        if let Some(schema) = schema {
+            self.check(parameter, schema, &value, &join(pointer, "schema"));
+        }
+        self.insert(parameter, value);
+    }
+
+    fn insert(&mut self, parameter: &Parameter, value: Value) {
+        self.values
+            .entry(parameter.r#in.clone())
+            .or_default()
+            .insert(parameter.name.clone(), value);
+    }
+
+    /// The value of a parameter sent as a single string, still percent-encoded.
+    fn raw(&self, parameter: &Parameter) -> Option<String> {
+        match parameter.r#in.as_str() {
+            "path" => self.path_parameters.get(&parameter.name).cloned(),
+            "header" => header(&self.request.headers, &parameter.name),
+            "cookie" => self
+                .cookies()
+                .into_iter()
+                .find(|(name, _)| name == &parameter.name)
+                .map(|(_, value)| value),
+            _ => split_query(self.request.query)
+                .into_iter()
+                .find(|(name, _)| percent_decode(name) == parameter.name)
+                .map(|(_, value)| value),
+        }
+    }
+
+    /// The pairs of the `Cookie` headers. A header sent several times is joined with `; ` rather than with the comma
+    /// of other headers, as commas may delimit the items of a cookie value.
+    fn cookies(&self) -> Vec<(String, String)> {
+        let cookies: Vec<_> = self
+            .request
+            .headers
+            .iter()
+            .filter(|(key, _)| key.eq_ignore_ascii_case("cookie"))
+            .map(|(_, value)| value.trim())
+            .collect();
+        parse_cookies(&cookies.join("; "))
+    }
+
+    fn missing(&mut self, parameter: &Parameter) {
+        if parameter.required == Some(true) || parameter.r#in == "path" {
+            self.errors.push(RequestError::MissingParameter {
+                name: parameter.name.clone(),
+                location: parameter.r#in.clone(),
+            });
+        }
+    }
+
+    fn check(
+        &mut self,
+        parameter: &Parameter,
+        schema: &RefOrObject<Schema>,
+        value: &Value,
+        schema_path: &str,
+    ) {
+        let errors = InstanceValidator::new(self.openapi, Direction::Request).validate(
+            schema,
+            value,
+            "",
+            schema_path,
+        );
+        if !errors.is_empty() {
+            self.errors.push(RequestError::InvalidParameter {
+                name: parameter.name.clone(),
+                location: parameter.r#in.clone(),
+                errors,
+            });
+        }
+    }
+
+    fn body(&mut self, operation: &Operation, pointer: &str) {
+        let Some(item) = &operation.request_body else {
+            return;
+        };
+        let Ok(body) = self.openapi.resolve(item) else {
+            self.errors.push(RequestError::UnresolvedReference {
+                reference: reference_of(item, join(pointer, "requestBody")),
+            });
+            return;
+        };
+        let pointer = match item {
+            RefOrObject::Ref(Reference { reference }) => {
+                format!("#{}", split_reference(reference).1)
+            }
+            RefOrObject::Object(_) => join(pointer, "requestBody"),
+        };
+        if self.request.body.is_empty() {
+            if body.required == Some(true) {
+                self.errors.push(RequestError::MissingBody);
+            }
+            return;
+        }
+        let content_type = header(&self.request.headers, "content-type");
+        let Some((key, media)) = content_type
+            .as_deref()
+            .and_then(|content_type| select_media(&body.content, content_type))
+        else {
+            self.errors.push(RequestError::UnsupportedMediaType {
+                content_type,
+                expected: body.content.keys().cloned().collect(),
+            });
+            return;
+        };
+        // The media type sent decides how to parse the body, even when the document describes it by a range.
+        let media_type = content_type.as_deref().unwrap_or(key);
+        let value = match parse_body(
+            self.openapi,
+            media_type,
+            self.request.body,
+            media.schema.as_ref(),
+        ) {
+            Ok(Some(value)) => value,
+            Ok(None) => return,
+            Err(message) => {
+                self.errors.push(RequestError::MalformedBody { message });
+                return;
+            }
+        };
+        if let Some(schema) = &media.schema {
+            let schema_path = join(&join(&join(&pointer, "content"), key), "schema");
+            let errors = InstanceValidator::new(self.openapi, Direction::Request).validate(
+                schema,
+                &value,
+                "",
+                &schema_path,
+            );
+            if !errors.is_empty() {
+                self.errors.push(RequestError::InvalidBody {
+                    media_type: key.clone(),
+                    errors,
+                });
+            }
+        }
+        self.body = Some(value);
+    }
+}
@@ -0,0 +1,399 @@
+use std::fmt;
+
+use crate::instance::{Direction, InstanceError};
+use crate::pointer::{join, split_reference};
+use crate::router::{Match, RouteError};
+use crate::style::{Shape, Style};
+use crate::v3::instance::InstanceValidator;
+use crate::v3::{Header, OpenApi, Operation, PathItem, RefOrObject, Reference, Responses};
+
+use super::request::Request;
+use super::{coerce, header, parse_body, reference_of, schema_type, select_media, Validator};
+
+/// # Response
+/// An HTTP response to validate, borrowing its parts from the response of any HTTP library.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Response<'a> {
+    pub status: u16,
+    pub headers: Vec<(&'a str, &'a str)>,
+    pub body: &'a [u8],
+}
+
+impl<'a> Response<'a> {
+    /// A response without headers nor body.
+    pub fn new(status: u16) -> Self {
+        Response {
+            status,
+            headers: Vec::new(),
+            body: &[],
+        }
+    }
+
+    /// Add a header. A header sent several times is added once for each value.
+    pub fn with_header(mut self, name: &'a str, value: &'a str) -> Self {
+        self.headers.push((name, value));
+        self
+    }
+
+    /// Set the body.
+    pub fn with_body(mut self, body: &'a [u8]) -> Self {
+        self.body = body;
+        self
+    }
+}
+
+/// # ResponseError
+/// A way a response does not follow the document.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ResponseError {
+    /// No path of the document matches the path of the request.
+    NotFound { path: String },
+    /// The path matches a path of the document which has no operation for the method.
+    MethodNotAllowed { method: String, template: String },
+    /// The operation describes neither the status, nor its range, nor a `default` response.
+    UndocumentedStatus { status: u16 },
+    /// The response of the status is a reference which points to nothing, so the response cannot be checked.
+    UnresolvedResponse { reference: String },
+    /// A header of the response is a reference which points to nothing, so the header cannot be checked.
+    UnresolvedHeader { name: String, reference: String },
+    /// A required header was not sent.
+    MissingHeader { name: String },
+    /// A header does not follow its `style`, or its content could not be parsed.
+    MalformedHeader { name: String, message: String },
+    /// The value of a header does not match its schema.
+    InvalidHeader {
+        name: String,
+        errors: Vec<InstanceError>,
+    },
+    /// The response describes content but has no body.
+    MissingBody,
+    /// The response has a body but describes no content.
+    UnexpectedBody,
+    /// The `Content-Type` of the body is not one the response describes, `None` when it was not sent.
+    UnsupportedMediaType {
+        content_type: Option<String>,
+        expected: Vec<String>,
+    },
+    /// The body could not be parsed according to its `Content-Type`.
+    MalformedBody { message: String },
+    /// The body does not match the schema of its media type.
+    InvalidBody {
+        media_type: String,
+        errors: Vec<InstanceError>,
+    },
+}
+
+impl fmt::Display for ResponseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let errors = |f: &mut fmt::Formatter<'_>, errors: &[InstanceError]| {
+            errors
+                .iter()
+                .try_for_each(|error| write!(f, "\n  {}", error))
+        };
+        match self {
+            ResponseError::NotFound { path } => write!(f, "no path matches `{}`", path),
+            ResponseError::MethodNotAllowed { method, template } => {
+                write!(f, "`{}` has no `{}` operation", template, method)
+            }
+            ResponseError::UndocumentedStatus { status } => {
+                write!(f, "the status {} is not documented", status)
+            }
+            ResponseError::UnresolvedResponse { reference } => {
+                write!(f, "the response `{}` points to nothing", reference)
+            }
+            ResponseError::UnresolvedHeader { name, reference } => write!(
+                f,
+                "the header `{}` points to nothing: `{}`",
+                name, reference
+            ),
+            ResponseError::MissingHeader { name } => write!(f, "the header `{}` is required", name),
+            ResponseError::MalformedHeader { name, message } => {
+                write!(f, "the header `{}` is malformed: {}", name, message)
+            }
+            ResponseError::InvalidHeader {
+                name,
+                errors: instance_errors,
+            } => {
+                write!(f, "the header `{}` is invalid:", name)?;
+                errors(f, instance_errors)
+            }
+            ResponseError::MissingBody => f.write_str("the response body is missing"),
+            ResponseError::UnexpectedBody => f.write_str("the response has no documented content"),
+            ResponseError::UnsupportedMediaType {
+                content_type,
+                expected,
+            } => write!(
+                f,
+                "the content type {} is not one of {}",
+                content_type
+                    .as_ref()
+                    .map_or("(none)".to_string(), |content_type| format!(
+                        "`{}`",
+                        content_type
+                    )),
+                expected.join(", ")
+            ),
+            ResponseError::MalformedBody { message } => f.write_str(message),
+            ResponseError::InvalidBody {
+                media_type,
+                errors: instance_errors,
+            } => {
+                write!(f, "the `{}` body is invalid:", media_type)?;
+                errors(f, instance_errors)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ResponseError {}
+
+impl From<RouteError> for ResponseError {
+    fn from(error: RouteError) -> Self {
+        match error {
+            RouteError::NotFound { path } => ResponseError::NotFound { path },
+            RouteError::MethodNotAllowed {
+                method, template, ..
+            } => ResponseError::MethodNotAllowed { method, template },
+        }
+    }
+}
+
+impl OpenApi {
+    /// Check a response against the operation targeted by the request it answers.
+    ///
+    /// The response is described by its exact status, else by its range such as `2XX`, else by the `default` response
+    /// of the operation. Its declared headers are decoded and checked against their schemas, and the body is parsed
+    /// according to its `Content-Type` and checked against the schema of its media type. An empty list means the
+    /// response is valid.
+    pub fn validate_response(&self, request: &Request, response: &Response) -> Vec<ResponseError> {
+        Validator::new(self).validate_response(request, response)
+    }
+
+    /// Check a response against the operation a request was routed to.
+    fn response_errors(
+        &self,
+        found: Match<'_, PathItem, Operation>,
+        request: &Request,
+        response: &Response,
+    ) -> Vec<ResponseError> {
+        let pointer = join(
+            &join(&join("#/paths", found.template), found.method),
+            "responses",
+        );
+        let Some((status, item)) = select_status(&found.operation.responses, response.status)
+        else {
+            return vec![ResponseError::UndocumentedStatus {
+                status: response.status,
+            }];
+        };
+        let Ok(described) = self.resolve(item) else {
+            return vec![ResponseError::UnresolvedResponse {
+                reference: reference_of(item, join(&pointer, status)),
+            }];
+        };
+        let pointer = match item {
+            RefOrObject::Ref(Reference { reference }) => {
+                format!("#{}", split_reference(reference).1)
+            }
+            RefOrObject::Object(_) => join(&pointer, status),
+        };
+
+        let mut errors = Vec::new();
+        for (name, header_item) in described.headers.iter().flatten() {
+            // The content type of a response is described by its content, not by its headers.
+            if name.eq_ignore_ascii_case("content-type") {
+                continue;
+            }
+            let header_pointer = join(&join(&pointer, "headers"), name);
+            let Ok(definition) = self.resolve(header_item) else {
+                errors.push(ResponseError::UnresolvedHeader {
+                    name: name.clone(),
+                    reference: reference_of(header_item, header_pointer),
+                });
+                continue;
+            };
+            let pointer = match header_item {
+                RefOrObject::Ref(Reference { reference }) => {
+                    format!("#{}", split_reference(reference).1)
+                }
+                RefOrObject::Object(_) => header_pointer,
+            };
+            let value = header(&response.headers, name);
+            if let Some(error) = self.header(name, definition, value.as_deref(), &pointer) {
+                errors.push(error);
+            }
+        }
+
+        let content = described
+            .content
+            .as_ref()
+            .filter(|content| !content.is_empty());
+        match (content, response.body.is_empty()) {
+            (None, true) => {}
+            (None, false) => errors.push(ResponseError::UnexpectedBody),
+            (Some(_), true) => {
+                if request.method.eq_ignore_ascii_case("head") || response.status == 204 {
+                    return errors;
+                }
+                errors.push(ResponseError::MissingBody);
+            }
+            (Some(content), false) => {
+                let content_type = header(&response.headers, "content-type");
+                let Some((key, media)) = content_type
+                    .as_deref()
+                    .and_then(|content_type| select_media(content, content_type))
+                else {
+                    errors.push(ResponseError::UnsupportedMediaType {
+                        content_type,
+                        expected: content.keys().cloned().collect(),
+                    });
+                    return errors;
+                };
+                let media_type = content_type.as_deref().unwrap_or(key);
+                let value = match parse_body(self, media_type, response.body, media.schema.as_ref())
+                {
+                    Ok(Some(value)) => value,
+                    Ok(None) => return errors,
+                    Err(message) => {
+                        errors.push(ResponseError::MalformedBody { message });
+                        return errors;
+                    }
+                };
+                if let Some(schema) = &media.schema {
+                    let schema_path = join(&join(&join(&pointer, "content"), key), "schema");
+                    let instance_errors = InstanceValidator::new(self, Direction::Response)
+                        .validate(schema, &value, "", &schema_path);
+                    if !instance_errors.is_empty() {
+                        errors.push(ResponseError::InvalidBody {
+                            media_type: key.clone(),
+                            errors: instance_errors,
+                        });
+                    }
+                }
+            }
+        }
+        errors
+    }
+
+    /// Check the value of a response header, `None` when it was not sent.
+    fn header(
+        &self,
+        name: &str,
+        header: &Header,
+        value: Option<&str>,
+        pointer: &str,
+    ) -> Option<ResponseError> {
+        let malformed = |message: String| ResponseError::MalformedHeader {
+            name: name.to_string(),
+            message,
+        };
+        let Some(value) = value else {
+            return (header.required == Some(true)).then(|| ResponseError::MissingHeader {
+                name: name.to_string(),
+            });
+        };
+        let (value, schema, schema_path) = match header.content.iter().flatten().next() {
+            Some((media_type, media)) => {
+                let value =
+                    match parse_body(self, media_type, value.as_bytes(), media.schema.as_ref()) {
+                        Ok(Some(value)) => value,
+                        Ok(None) => return None,
+                        Err(message) => return Some(malformed(message)),
+                    };
+                let schema_path = join(&join(&join(pointer, "content"), media_type), "schema");
+                (value, media.schema.as_ref()?, schema_path)
+            }
+            None => {
+                let schema = header.schema.as_ref()?;
+                let shape = self.resolve(schema).map_or(Shape::Primitive, |schema| {
+                    Shape::of(schema_type(self, schema))
+                });
+                // Headers are always written in the `simple` style.
+                let explode = header.explode.unwrap_or(false);
+                let value = match Style::Simple.decode(name, explode, shape, value) {
+                    Ok(value) => coerce(self, Some(schema), value),
+                    Err(e) => return Some(malformed(e.message)),
+                };
+                (value, schema, join(pointer, "schema"))
+            }
+        };
+        let errors = InstanceValidator::new(self, Direction::Response).validate(
+            schema,
+            &value,
+            "",
+            &schema_path,
+        );
+        (!errors.is_empty()).then(|| ResponseError::InvalidHeader {
+            name: name.to_string(),
+            errors,
+        })
+    }
+}
+
+impl Validator<'_> {
+    /// See [`OpenApi::validate_response`].
+    pub fn validate_response(&self, request: &Request, response: &Response) -> Vec<ResponseError> {
+        match self.router.route(request.method, request.path) {
+            Ok(found) => self.openapi.response_errors(found, request, response),
+            Err(error) => vec![error.into()],
+        }
+    }
+}
+
+/// The response describing a status: the exact status code, else its range such as `2XX`, else `default`.
+fn select_status(
+    responses: &Responses,
+    status: u16,
+) -> Option<(&String, &RefOrObject<crate::v3::Response>)> {
+    let exact = status.to_string();
+    let range = format!("{}XX", status / 100);
+    responses
+        .get_key_value(&exact)
+        .or_else(|| {
+            responses
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(&range))
+        })
+        .or_else(|| responses.get_key_value("default"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_select_status() {
+        let responses: Responses = serde_json::from_value(json!({
+            "200": {"description": "Ok."},
+            "4xx": {"description": "Client error."},
+            "default": {"description": "Error."}
+        }))
+        .unwrap();
+        let status = |status| select_status(&responses, status).map(|(key, _)| key.as_str());
+        assert_eq!(status(200), Some("200"));
+        assert_eq!(status(404), Some("4xx"));
+        assert_eq!(status(503), Some("default"));
+    }
+
+    #[test]
+    fn test_unresolved_header() {
+        let openapi: OpenApi = serde_json::from_value(json!({
+            "openapi": "3.0.3",
+            "info": {"title": "Pets", "version": "1.0.0"},
+            "paths": {"/pets": {"get": {"responses": {"200": {
+                "description": "Ok.",
+                "headers": {"X-Rate-Limit": {"$ref": "#/components/headers/Missing"}}
+            }}}}}
+        }))
+        .unwrap();
+        let request = Request::new("GET", "/pets");
+        assert_eq!(
+            openapi.validate_response(&request, &Response::new(200)),
+            [ResponseError::UnresolvedHeader {
+                name: "X-Rate-Limit".to_string(),
+                reference: "#/components/headers/Missing".to_string()
+            }]
+        );
+    }
+}
@@ -0,0 +1,479 @@
+//! Validation of JSON instances, such as the payloads of requests and responses, against the schemas of a document.
+//!
+//! [`OpenApi::validate_instance`](crate::v3::OpenApi::validate_instance) and
+//! [`Swagger::validate_instance`](crate::v2::Swagger::validate_instance) check a [`Value`] against a schema, following
+//! its `$ref`s through the document, and report every keyword the value breaks as an [`InstanceError`]. The raw strings
+//! of Swagger 2.0 parameters sent outside of the body are checked by
+//! [`Parameter::validate_value`](crate::v2::Parameter::validate_value).
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::sync::{Mutex, OnceLock, PoisonError};
+
+use regex::Regex;
+use serde_json::{Number, Value};
+
+use crate::pointer::join;
+
+/// # Direction
+/// Whether an instance is sent to the API or returned by it.
+///
+/// Properties marked `readOnly` must not be sent in a request, and those marked `writeOnly` must not be returned in a
+/// response. In both cases they are not required.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Request,
+    Response,
+}
+
+/// # InstanceError
+/// A keyword of a schema broken by an instance.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InstanceError {
+    /// JSON pointer to the offending value inside the instance.
+    pub instance_path: String,
+    /// Location of the broken keyword: a JSON pointer from the schema the instance was checked against, or a reference
+    /// such as `#/components/schemas/Pet/required` once a `$ref` has been followed.
+    pub schema_path: String,
+    pub message: String,
+}
+
+impl fmt::Display for InstanceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "at `{}` (schema `{}`): {}",
+            self.instance_path, self.schema_path, self.message
+        )
+    }
+}
+
+/// Record that the keyword of the schema at `schema_path` is broken by the value at `instance_path`.
+pub(crate) fn error(
+    errors: &mut Vec<InstanceError>,
+    instance_path: &str,
+    schema_path: &str,
+    keyword: &str,
+    message: impl Into<String>,
+) {
+    errors.push(InstanceError {
+        instance_path: instance_path.to_string(),
+        schema_path: join(schema_path, keyword),
+        message: message.into(),
+    });
+}
+
+/// The keywords shared by the schemas and parameters of every version, which apply to a single value.
+#[derive(Default)]
+pub(crate) struct Keywords<'a> {
+    pub(crate) r#type: Option<&'a str>,
+    pub(crate) format: Option<&'a str>,
+    pub(crate) r#enum: Option<&'a [Value]>,
+    pub(crate) multiple_of: Option<&'a Number>,
+    pub(crate) maximum: Option<&'a Number>,
+    pub(crate) exclusive_maximum: bool,
+    pub(crate) minimum: Option<&'a Number>,
+    pub(crate) exclusive_minimum: bool,
+    pub(crate) max_length: Option<&'a Number>,
+    pub(crate) min_length: Option<&'a Number>,
+    pub(crate) pattern: Option<&'a str>,
+    pub(crate) max_items: Option<&'a Number>,
+    pub(crate) min_items: Option<&'a Number>,
+    pub(crate) unique_items: bool,
+    pub(crate) max_properties: Option<&'a Number>,
+    pub(crate) min_properties: Option<&'a Number>,
+}
+
+impl Keywords<'_> {
+    /// Check an instance, returning whether it is of the expected type.
+    ///
+    /// Nothing but the type is checked when it does not match, as every other keyword would fail for the same reason.
+    pub(crate) fn check(
+        &self,
+        instance: &Value,
+        instance_path: &str,
+        schema_path: &str,
+        errors: &mut Vec<InstanceError>,
+    ) -> bool {
+        let mut error = |keyword: &str, message: String| {
+            error(errors, instance_path, schema_path, keyword, message)
+        };
+        if let Some(r#type) = self.r#type {
+            if !is_type(instance, r#type) {
+                error(
+                    "type",
+                    format!("expected {}, found {}", r#type, type_of(instance)),
+                );
+                return false;
+            }
+        }
+        if let Some(format) = self.format {
+            if !is_format(instance, format) {
+                error("format", format!("{} is not a valid {}", instance, format));
+            }
+        }
+        if let Some(values) = self.r#enum {
+            if !values.iter().any(|value| equal(value, instance)) {
+                error(
+                    "enum",
+                    format!("{} is not one of the allowed values", instance),
+                );
+            }
+        }
+        match instance {
+            Value::Number(number) => {
+                let Some(value) = number.as_f64() else {
+                    return true;
+                };
+                if let Some(divisor) = self.multiple_of {
+                    if !is_multiple(number, divisor) {
+                        error(
+                            "multipleOf",
+                            format!("{} is not a multiple of {}", number, divisor),
+                        );
+                    }
+                }
+                if let Some(maximum) = self.maximum.and_then(Number::as_f64) {
+                    if value > maximum || self.exclusive_maximum && value == maximum {
+                        let relation = if self.exclusive_maximum {
+                            "less than"
+                        } else {
+                            "at most"
+                        };
+                        error(
+                            "maximum",
+                            format!("{} is not {} {}", number, relation, maximum),
+                        );
+                    }
+                }
+                if let Some(minimum) = self.minimum.and_then(Number::as_f64) {
+                    if value < minimum || self.exclusive_minimum && value == minimum {
+                        let relation = if self.exclusive_minimum {
+                            "greater than"
+                        } else {
+                            "at least"
+                        };
+                        error(
+                            "minimum",
+                            format!("{} is not {} {}", number, relation, minimum),
+                        );
+                    }
+                }
+            }
+            Value::String(string) => {
+                let length = string.chars().count();
+                if let Some(max) = self.max_length.and_then(Number::as_u64) {
+                    if length as u64 > max {
+                        error(
+                            "maxLength",
+                            format!("{} is longer than {} characters", instance, max),
+                        );
+                    }
+                }
+                if let Some(min) = self.min_length.and_then(Number::as_u64) {
+                    if (length as u64) < min {
+                        error(
+                            "minLength",
+                            format!("{} is shorter than {} characters", instance, min),
+                        );
+                    }
+                }
+                if let Some(pattern) = self.pattern {
+                    match pattern_regex(pattern) {
+                        Some(regex) if !regex.is_match(string) => error(
+                            "pattern",
+                            format!("{} does not match `{}`", instance, pattern),
+                        ),
+                        Some(_) => {}
+                        None => error(
+                            "pattern",
+                            format!("`{}` is not a valid regular expression", pattern),
+                        ),
+                    }
+                }
+            }
+            Value::Array(items) => {
+                if let Some(max) = self.max_items.and_then(Number::as_u64) {
+                    if items.len() as u64 > max {
+                        error("maxItems", format!("more than {} items", max));
+                    }
+                }
+                if let Some(min) = self.min_items.and_then(Number::as_u64) {
+                    if (items.len() as u64) < min {
+                        error("minItems", format!("fewer than {} items", min));
+                    }
+                }
+                if self.unique_items {
+                    let duplicated = items
+                        .iter()
+                        .enumerate()
+                        .any(|(i, item)| items[..i].iter().any(|other| equal(item, other)));
+                    if duplicated {
+                        error("uniqueItems", "the items are not unique".to_string());
+                    }
+                }
+            }
+            Value::Object(map) => {
+                if let Some(max) = self.max_properties.and_then(Number::as_u64) {
+                    if map.len() as u64 > max {
+                        error("maxProperties", format!("more than {} properties", max));
+                    }
+                }
+                if let Some(min) = self.min_properties.and_then(Number::as_u64) {
+                    if (map.len() as u64) < min {
+                        error("minProperties", format!("fewer than {} properties", min));
+                    }
+                }
+            }
+            Value::Null | Value::Bool(_) => {}
+        }
+        true
+    }
+}
+
+/// The name of the type of a value, as written in a schema.
+pub(crate) fn type_of(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(number) if is_integer(number) => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Whether a value is of a type of a schema. Unknown types match any value.
+pub(crate) fn is_type(value: &Value, r#type: &str) -> bool {
+    match (r#type, value) {
+        ("integer", Value::Number(number)) => is_integer(number),
+        ("number", Value::Number(_))
+        | ("string", Value::String(_))
+        | ("boolean", Value::Bool(_))
+        | ("array", Value::Array(_))
+        | ("object", Value::Object(_))
+        | ("null", Value::Null) => true,
+        ("integer" | "number" | "string" | "boolean" | "array" | "object" | "null", _) => false,
+        _ => true,
+    }
+}
+
+fn is_integer(number: &Number) -> bool {
+    number.is_i64() || number.is_u64() || number.as_f64().is_some_and(|n| n.fract() == 0.0)
+}
+
+/// The value of a number which is an `i64`, such as `42` or `42.0`.
+fn as_integer(number: &Number) -> Option<i64> {
+    number.as_i64().or_else(|| {
+        number
+            .as_f64()
+            .filter(|n| n.fract() == 0.0 && *n >= i64::MIN as f64 && *n < i64::MAX as f64)
+            .map(|n| n as i64)
+    })
+}
+
+/// Whether two values are equal, numbers being compared by value so that `1` equals `1.0`.
+pub(crate) fn equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a == b || a.as_f64() == b.as_f64(),
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| equal(a, b))
+        }
+        (Value::Object(a), Value::Object(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .all(|(key, a)| b.get(key).is_some_and(|b| equal(a, b)))
+        }
+        _ => a == b,
+    }
+}
+
+fn is_multiple(number: &Number, divisor: &Number) -> bool {
+    if let (Some(number), Some(divisor)) = (number.as_i64(), divisor.as_i64()) {
+        return divisor == 0 || number % divisor == 0;
+    }
+    match (number.as_f64(), divisor.as_f64()) {
+        (Some(number), Some(divisor)) if divisor != 0.0 => {
+            let quotient = number / divisor;
+            (quotient - quotient.round()).abs() < 1e-9
+        }
+        _ => true,
+    }
+}
+
+/// Whether a value has a format. Unknown formats, and formats of another type than the value's, always match.
+pub(crate) fn is_format(value: &Value, format: &str) -> bool {
+    match value {
+        Value::Number(number) => match format {
+            "int32" => as_integer(number).is_some_and(|n| i32::try_from(n).is_ok()),
+            "int64" => as_integer(number).is_some(),
+            _ => true,
+        },
+        Value::String(string) => {
+            let pattern = match format {
+                "date" => &DATE,
+                "date-time" => &DATE_TIME,
+                "time" => &TIME,
+                "email" => &EMAIL,
+                "uuid" => &UUID,
+                "byte" => &BYTE,
+                "uri" => &URI,
+                "ipv4" => return string.parse::<Ipv4Addr>().is_ok(),
+                "ipv6" => return string.parse::<Ipv6Addr>().is_ok(),
+                _ => return true,
+            };
+            regex(pattern).is_match(string) && (!format.starts_with("date") || is_date(string))
+        }
+        _ => true,
+    }
+}
+
+/// The regular expression of a `pattern` keyword, compiled once for each pattern, `None` when it is not valid.
+fn pattern_regex(pattern: &str) -> Option<Regex> {
+    static COMPILED: OnceLock<Mutex<BTreeMap<String, Option<Regex>>>> = OnceLock::new();
+    let mut compiled = COMPILED
+        .get_or_init(Mutex::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    compiled
+        .entry(pattern.to_string())
+        .or_insert_with(|| Regex::new(pattern).ok())
+        .clone()
+}
+
+/// A regular expression compiled once, on first use.
+struct Pattern(&'static str, OnceLock<Regex>);
+
+fn regex(pattern: &'static Pattern) -> &'static Regex {
+    pattern
+        .1
+        .get_or_init(|| Regex::new(pattern.0).expect("the formats are valid regular expressions"))
+}
+
+static DATE: Pattern = Pattern(r"^\d{4}-\d{2}-\d{2}$", OnceLock::new());
+static DATE_TIME: Pattern = Pattern(
+    r"^\d{4}-\d{2}-\d{2}[Tt]([01]\d|2[0-3]):[0-5]\d:([0-5]\d|60)(\.\d+)?([Zz]|[+-]([01]\d|2[0-3]):[0-5]\d)$",
+    OnceLock::new(),
+);
+static TIME: Pattern = Pattern(
+    r"^([01]\d|2[0-3]):[0-5]\d:([0-5]\d|60)(\.\d+)?([Zz]|[+-]([01]\d|2[0-3]):[0-5]\d)?$",
+    OnceLock::new(),
+);
+static EMAIL: Pattern = Pattern(r"^[^@\s]+@[^@\s]+\.[^@\s]+$", OnceLock::new());
+static UUID: Pattern = Pattern(
+    r"^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$",
+    OnceLock::new(),
+);
+static BYTE: Pattern = Pattern(
+    r"^(?:[A-Za-z0-9+/]{4})*(?:[A-Za-z0-9+/]{2}==|[A-Za-z0-9+/]{3}=)?$",
+    OnceLock::new(),
+);
+static URI: Pattern = Pattern(r"^[A-Za-z][A-Za-z0-9+.\-]*:[^\s]*$", OnceLock::new());
+
+/// Whether the date starting a `date` or `date-time` exists in the calendar.
+fn is_date(string: &str) -> bool {
+    let number = |range: std::ops::Range<usize>| string[range].parse::<u32>().unwrap_or(0);
+    let (year, month, day) = (number(0..4), number(5..7), number(8..10));
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return false,
+    };
+    (1..=days).contains(&day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_is_format() {
+        assert!(is_format(&json!("2024-02-29"), "date"));
+        assert!(!is_format(&json!("2023-02-29"), "date"));
+        assert!(is_format(
+            &json!("2024-01-31T10:00:00.5+02:00"),
+            "date-time"
+        ));
+        assert!(!is_format(&json!("2024-01-31 10:00:00"), "date-time"));
+        assert!(is_format(&json!("pets@example.com"), "email"));
+        assert!(!is_format(&json!("pets"), "email"));
+        assert!(is_format(
+            &json!("0f8fad5b-d9cb-469f-a165-70867728950e"),
+            "uuid"
+        ));
+        assert!(is_format(&json!("UGV0cw=="), "byte"));
+        assert!(!is_format(&json!("UGV0cw="), "byte"));
+        assert!(is_format(&json!("::1"), "ipv6"));
+        assert!(!is_format(&json!(3_000_000_000u64), "int32"));
+        assert!(is_format(&json!(42.0), "int32"));
+        assert!(is_format(&json!(3e9), "int64"));
+        assert!(!is_format(&json!(42.5), "int64"));
+        assert!(!is_format(&json!(1e19), "int64"));
+        assert!(is_format(&json!("anything"), "password"));
+    }
+
+    #[test]
+    fn test_keywords() {
+        let maximum = Number::from(10);
+        let keywords = Keywords {
+            r#type: Some("integer"),
+            maximum: Some(&maximum),
+            exclusive_maximum: true,
+            ..Keywords::default()
+        };
+        let mut errors = Vec::new();
+        assert!(keywords.check(&json!(9), "", "", &mut errors));
+        assert!(keywords.check(&json!(10), "/count", "/schema", &mut errors));
+        assert!(!keywords.check(&json!(1.5), "", "", &mut errors));
+        assert_eq!(
+            errors,
+            [
+                InstanceError {
+                    instance_path: "/count".to_string(),
+                    schema_path: "/schema/maximum".to_string(),
+                    message: "10 is not less than 10".to_string(),
+                },
+                InstanceError {
+                    instance_path: "".to_string(),
+                    schema_path: "/type".to_string(),
+                    message: "expected integer, found number".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pattern() {
+        // the compiled patterns are reused by later checks
+        for _ in 0..2 {
+            let mut errors = Vec::new();
+            for (pattern, instance) in [("^[A-Z]", "Rex"), ("^[A-Z]", "rex"), ("(", "rex")] {
+                let keywords = Keywords {
+                    pattern: Some(pattern),
+                    ..Keywords::default()
+                };
+                keywords.check(&json!(instance), "", "", &mut errors);
+            }
+            let messages: Vec<_> = errors.iter().map(|error| error.message.as_str()).collect();
+            assert_eq!(
+                messages,
+                [
+                    "\"rex\" does not match `^[A-Z]`",
+                    "`(` is not a valid regular expression"
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_equal() {
+        assert!(equal(&json!({"a": [1, 2.0]}), &json!({"a": [1.0, 2]})));
+        assert!(!equal(&json!([1]), &json!([1, 1])));
+    }
+}
@@ -1,54 +1,231 @@
-use serde::{Deserialize, Serialize};
-use std::{fs::File, io::Read, path::Path, result::Result};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use std::{
+    fs::File,
+    io::{Read, Write},
+    path::Path,
+    result::Result,
+};
 
+pub mod codegen;
+mod de;
+pub mod error;
 pub mod extension;
+pub mod format;
+pub mod http;
+pub mod instance;
+pub mod lint;
+pub mod loader;
+pub mod pointer;
+pub mod resolve;
+pub mod router;
+pub mod style;
 pub mod v2;
 pub mod v3;
+pub mod v3_1;
+pub mod validate;
 
+pub use error::Error;
 pub use extension::*;
+pub use format::Format;
 use v2::*;
 use v3::*;
 /// Supported versions of the OpenApi.
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+///
+/// The version is picked from the `swagger` or `openapi` field of the document, unknown versions are rejected.
+#[derive(Debug, Serialize, PartialEq)]
 #[serde(untagged)]
 pub enum Doc {
     V2(Swagger),
     V3(v3::OpenApi),
+    V31(v3_1::OpenApi),
+}
+
+impl Doc {
+    /// The specification version declared by the document.
+    pub fn version(&self) -> &str {
+        match self {
+            Doc::V2(swagger) => &swagger.swagger,
+            Doc::V3(openapi) => &openapi.openapi,
+            Doc::V31(openapi) => &openapi.openapi,
+        }
+    }
+
+    /// Deserialize a document which was already parsed into a JSON value.
+    pub(crate) fn from_value(value: Value) -> Result<Doc, Error> {
+        let fields = VersionFields {
+            swagger: value.get("swagger").cloned(),
+            openapi: value.get("openapi").cloned(),
+        };
+        match fields.version()? {
+            Version::V2 => de::deserialize_value(value, "").map(Doc::V2),
+            Version::V3 => de::deserialize_value(value, "").map(Doc::V3),
+            Version::V31 => de::deserialize_value(value, "").map(Doc::V31),
+        }
+    }
+
+    fn from_str_with(str: &str, format: Format) -> Result<Doc, Error> {
+        let fields: VersionFields = format.from_str(str)?;
+        match fields.version()? {
+            Version::V2 => format.from_str(str).map(Doc::V2),
+            Version::V3 => format.from_str(str).map(Doc::V3),
+            Version::V31 => format.from_str(str).map(Doc::V31),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Doc {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+        let fields = VersionFields {
+            swagger: value.get("swagger").cloned(),
+            openapi: value.get("openapi").cloned(),
+        };
+        match fields.version().map_err(D::Error::custom)? {
+            Version::V2 => de::from_value(value).map(Doc::V2),
+            Version::V3 => de::from_value(value).map(Doc::V3),
+            Version::V31 => de::from_value(value).map(Doc::V31),
+        }
+    }
+}
+
+/// The specification versions a document can be dispatched to.
+enum Version {
+    V2,
+    V3,
+    V31,
+}
+
+/// The fields declaring the specification version of a document.
+#[derive(Deserialize)]
+struct VersionFields {
+    swagger: Option<Value>,
+    openapi: Option<Value>,
+}
+
+impl VersionFields {
+    fn version(&self) -> Result<Version, Error> {
+        let (field, version) = match (&self.swagger, &self.openapi) {
+            (Some(version), _) => ("swagger", version),
+            (None, Some(version)) => ("openapi", version),
+            (None, None) => {
+                return Err(Error::Structure {
+                    path: None,
+                    pointer: String::new(),
+                    line: 0,
+                    column: 0,
+                    message: "missing field `swagger` or `openapi`".to_string(),
+                })
+            }
+        };
+        // a YAML document may declare the version as a number, which fails later with a precise error
+        let version = match version {
+            Value::String(version) => version.clone(),
+            version => version.to_string(),
+        };
+        match field {
+            "swagger" if version == "2.0" => Ok(Version::V2),
+            "openapi" if version == "3.0" || version.starts_with("3.0.") => Ok(Version::V3),
+            "openapi" if version == "3.1" || version.starts_with("3.1.") => Ok(Version::V31),
+            _ => Err(Error::UnsupportedVersion {
+                path: None,
+                version,
+            }),
+        }
+    }
 }
 
 /// deserialize an open api spec from a path
-pub fn from_path<P>(path: P) -> Result<Doc, serde_json::Error>
+///
+/// The format is chosen by the file extension (`.json`, `.yaml`, `.yml`) and falls back to sniffing the content.
+pub fn from_path<P>(path: P) -> Result<Doc, Error>
 where
     P: AsRef<Path>,
 {
-    from_reader(File::open(path).unwrap())
+    let path = path.as_ref();
+    File::open(path)
+        .map_err(Error::from)
+        .and_then(read_to_string)
+        .and_then(|content| {
+            let format = Format::from_path(path).unwrap_or_else(|| Format::detect(&content));
+            Doc::from_str_with(&content, format)
+        })
+        .map_err(|e| e.with_path(path))
 }
 
-/// deserialize from a string
-pub fn from_str(str: &str) -> Result<Doc, serde_json::Error> {
-    serde_json::from_str(str)
+/// deserialize from a string, detecting whether it is JSON or YAML
+pub fn from_str(str: &str) -> Result<Doc, Error> {
+    Doc::from_str_with(str, Format::detect(str))
 }
 
-/// deserialize an open api spec from type which implements Read
-pub fn from_reader<R>(read: R) -> Result<Doc, serde_json::Error>
+/// deserialize an open api spec from type which implements Read, detecting whether it is JSON or YAML
+pub fn from_reader<R>(read: R) -> Result<Doc, Error>
 where
     R: Read,
 {
-    serde_json::from_reader::<R, Doc>(read)
+    from_str(&read_to_string(read)?)
 }
 
 /// deserialize an swagger from type which implements Read
-pub fn swagger_from_reader<R>(read: R) -> Result<Swagger, serde_json::Error>
+pub fn swagger_from_reader<R>(read: R) -> Result<Swagger, Error>
 where
     R: Read,
 {
-    serde_json::from_reader::<R, Swagger>(read)
+    let content = read_to_string(read)?;
+    Format::detect(&content).from_str(&content)
 }
 
 /// deserialize an openapi from type which implements Read
-pub fn openapi_from_reader<R>(read: R) -> Result<OpenApi, serde_json::Error>
+pub fn openapi_from_reader<R>(read: R) -> Result<OpenApi, Error>
+where
+    R: Read,
+{
+    let content = read_to_string(read)?;
+    Format::detect(&content).from_str(&content)
+}
+
+/// serialize a `Doc`, `Swagger` or `OpenApi` into a string of the given format
+pub fn to_string<T>(doc: &T, format: Format) -> Result<String, Error>
+where
+    T: Serialize,
+{
+    format.to_string(doc)
+}
+
+/// serialize a `Doc`, `Swagger` or `OpenApi` into a type which implements Write
+pub fn to_writer<W, T>(mut write: W, doc: &T, format: Format) -> Result<(), Error>
+where
+    W: Write,
+    T: Serialize,
+{
+    write.write_all(format.to_string(doc)?.as_bytes())?;
+    Ok(())
+}
+
+/// serialize a `Doc`, `Swagger` or `OpenApi` to a path
+///
+/// The format is chosen by the file extension and defaults to JSON.
+pub fn to_path<P, T>(path: P, doc: &T) -> Result<(), Error>
+where
+    P: AsRef<Path>,
+    T: Serialize,
+{
+    let path = path.as_ref();
+    let format = Format::from_path(path).unwrap_or(Format::Json);
+    File::create(path)
+        .map_err(Error::from)
+        .and_then(|file| to_writer(file, doc, format))
+        .map_err(|e| e.with_path(path))
+}
+
+fn read_to_string<R>(mut read: R) -> Result<String, Error>
 where
     R: Read,
 {
-    serde_json::from_reader::<R, OpenApi>(read)
+    let mut content = String::new();
+    read.read_to_string(&mut content)?;
+    Ok(content)
 }
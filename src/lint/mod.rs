@@ -0,0 +1,285 @@
+//! Style checks for OpenAPI 3.0 documents.
+//!
+//! Where [`OpenApi::validate`](crate::v3::OpenApi::validate) checks the rules of the specification, a [`Linter`] checks
+//! the conventions of a style guide, each one a [`LintRule`]. The [built-in rules](rules) can be complemented by rules
+//! of your own, and a [`LintConfig`] turns rules off or changes their severity.
+//!
+//! ```
+//! # use openapi_schema::lint::{LintConfig, Linter};
+//! # use openapi_schema::v3::OpenApi;
+//! # fn example(openapi: &OpenApi) -> Result<(), openapi_schema::Error> {
+//! let config = LintConfig::from_str(r#"{"rules": {"paths-kebab-case": "off", "operation-tags": "error"}}"#)?;
+//! for diagnostic in Linter::new().with_config(config).lint(openapi) {
+//!     println!("{}", diagnostic);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+use crate::format::Format;
+use crate::v3::OpenApi;
+use crate::validate::{Diagnostic, Severity};
+
+pub mod rules;
+
+/// # Level
+/// Whether a rule is run, and the severity of what it reports.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Off,
+    Error,
+    Warning,
+    Info,
+}
+
+impl Level {
+    /// The severity of the diagnostics, `None` when the rule is off.
+    pub fn severity(self) -> Option<Severity> {
+        match self {
+            Level::Off => None,
+            Level::Error => Some(Severity::Error),
+            Level::Warning => Some(Severity::Warning),
+            Level::Info => Some(Severity::Info),
+        }
+    }
+}
+
+/// # Violation
+/// A place where a document breaks a lint rule.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Violation {
+    /// JSON pointer to the offending value.
+    pub pointer: String,
+    pub message: String,
+}
+
+/// # LintRule
+/// A convention checked over a document.
+pub trait LintRule {
+    /// The identifier of the rule, such as `operation-id-camel-case`, used in diagnostics and configurations.
+    fn id(&self) -> &str;
+
+    /// What the rule checks.
+    fn description(&self) -> &str;
+
+    /// The level of the rule when the configuration does not set one.
+    fn level(&self) -> Level {
+        Level::Warning
+    }
+
+    /// Every place where the document breaks the rule.
+    fn check(&self, openapi: &OpenApi) -> Vec<Violation>;
+}
+
+/// # LintConfig
+/// The level of each rule, by identifier. Rules which are not listed keep their own level.
+///
+/// In JSON or YAML, a configuration maps rule identifiers to `off`, `error`, `warning` or `info`:
+///
+/// ```yaml
+/// rules:
+///   operation-id-camel-case: error
+///   paths-kebab-case: off
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct LintConfig {
+    #[serde(default)]
+    pub rules: BTreeMap<String, Level>,
+}
+
+impl LintConfig {
+    /// Read a configuration from a JSON or YAML file.
+    pub fn from_path<P>(path: P) -> Result<LintConfig, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        fs::read_to_string(path)
+            .map_err(Error::from)
+            .and_then(|content| {
+                let format = Format::from_path(path).unwrap_or_else(|| Format::detect(&content));
+                format.from_str(&content)
+            })
+            .map_err(|e| e.with_path(path))
+    }
+
+    /// Read a configuration from a string, detecting whether it is JSON or YAML.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(str: &str) -> Result<LintConfig, Error> {
+        Format::detect(str).from_str(str)
+    }
+}
+
+/// # Linter
+/// Runs a set of rules over documents.
+pub struct Linter {
+    rules: Vec<Box<dyn LintRule>>,
+    config: LintConfig,
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Linter {
+    /// A linter running the [built-in rules](rules::builtin) at their own levels.
+    pub fn new() -> Self {
+        Linter {
+            rules: rules::builtin(),
+            config: LintConfig::default(),
+        }
+    }
+
+    /// A linter without any rule.
+    pub fn empty() -> Self {
+        Linter {
+            rules: Vec::new(),
+            config: LintConfig::default(),
+        }
+    }
+
+    /// Add a rule to run.
+    pub fn with_rule<R>(mut self, rule: R) -> Self
+    where
+        R: LintRule + 'static,
+    {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    /// Override the levels of the rules.
+    pub fn with_config(mut self, config: LintConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// The rules the linter knows of, whatever their level.
+    pub fn rules(&self) -> impl Iterator<Item = &dyn LintRule> {
+        self.rules.iter().map(|rule| rule.as_ref())
+    }
+
+    /// Run the rules which are not off over a document.
+    pub fn lint(&self, openapi: &OpenApi) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        for rule in &self.rules {
+            let level = self
+                .config
+                .rules
+                .get(rule.id())
+                .copied()
+                .unwrap_or_else(|| rule.level());
+            let Some(severity) = level.severity() else {
+                continue;
+            };
+            diagnostics.extend(rule.check(openapi).into_iter().map(|violation| Diagnostic {
+                severity,
+                pointer: violation.pointer,
+                rule: rule.id().to_string(),
+                message: violation.message,
+            }));
+        }
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    struct NoDescription;
+
+    impl LintRule for NoDescription {
+        fn id(&self) -> &str {
+            "info-description"
+        }
+
+        fn description(&self) -> &str {
+            "the API has a description"
+        }
+
+        fn level(&self) -> Level {
+            Level::Info
+        }
+
+        fn check(&self, openapi: &OpenApi) -> Vec<Violation> {
+            match openapi.info.description {
+                Some(_) => Vec::new(),
+                None => vec![Violation {
+                    pointer: "/info".to_string(),
+                    message: "the API has no description".to_string(),
+                }],
+            }
+        }
+    }
+
+    fn openapi() -> OpenApi {
+        serde_json::from_value(json!({
+            "openapi": "3.0.3",
+            "info": {"title": "Pets", "version": "1"},
+            "paths": {
+                "/pets": {"get": {"operationId": "list_pets", "responses": {"200": {"description": "Pets."}}}}
+            }
+        }))
+        .unwrap()
+    }
+
+    fn rules(diagnostics: &[Diagnostic]) -> Vec<(&str, Severity)> {
+        diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.rule.as_str(), diagnostic.severity))
+            .collect()
+    }
+
+    #[test]
+    fn test_lint() {
+        let diagnostics = Linter::new().with_rule(NoDescription).lint(&openapi());
+        assert_eq!(
+            rules(&diagnostics),
+            [
+                ("operation-id-camel-case", Severity::Warning),
+                ("operation-summary", Severity::Warning),
+                ("operation-tags", Severity::Warning),
+                ("info-description", Severity::Info),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_config() {
+        let config = LintConfig::from_str(
+            r#"{"rules": {"operation-summary": "off", "operation-tags": "error", "info-description": "warning"}}"#,
+        )
+        .unwrap();
+        let diagnostics = Linter::new()
+            .with_rule(NoDescription)
+            .with_config(config)
+            .lint(&openapi());
+        assert_eq!(
+            rules(&diagnostics),
+            [
+                ("operation-id-camel-case", Severity::Warning),
+                ("operation-tags", Severity::Error),
+                ("info-description", Severity::Warning),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_config_invalid_level() {
+        let error = LintConfig::from_str(r#"{"rules": {"operation-tags": "fatal"}}"#).unwrap_err();
+        assert!(
+            matches!(error, Error::Structure { pointer, .. } if pointer == "/rules/operation-tags")
+        );
+    }
+}
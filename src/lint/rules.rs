@@ -0,0 +1,288 @@
+//! The built-in lint rules.
+
+use crate::pointer::join;
+use crate::v3::{Media, OpenApi, Operation, RefOrObject, Response};
+
+use super::{LintRule, Violation};
+
+/// Every built-in rule.
+pub fn builtin() -> Vec<Box<dyn LintRule>> {
+    vec![
+        Box::new(OperationIdCamelCase),
+        Box::new(OperationSummary),
+        Box::new(OperationTags),
+        Box::new(NoInlineResponseSchemas),
+        Box::new(PathsKebabCase),
+    ]
+}
+
+/// The operations of a document with their pointers.
+fn operations(openapi: &OpenApi) -> impl Iterator<Item = (String, &Operation)> {
+    openapi.paths.iter().flat_map(|(path, item)| {
+        item.operations()
+            .map(move |(method, operation)| (join(&join("/paths", path), method), operation))
+    })
+}
+
+/// ### OperationIdCamelCase
+/// `operation-id-camel-case`: operationIds are written in camelCase, such as `listPets`.
+pub struct OperationIdCamelCase;
+
+impl LintRule for OperationIdCamelCase {
+    fn id(&self) -> &str {
+        "operation-id-camel-case"
+    }
+
+    fn description(&self) -> &str {
+        "operationIds are written in camelCase"
+    }
+
+    fn check(&self, openapi: &OpenApi) -> Vec<Violation> {
+        operations(openapi)
+            .filter_map(|(pointer, operation)| {
+                let id = operation.operation_id.as_ref()?;
+                let mut chars = id.chars();
+                let camel_case = chars.next().is_some_and(|c| c.is_ascii_lowercase())
+                    && chars.all(|c| c.is_ascii_alphanumeric());
+                (!camel_case).then(|| Violation {
+                    pointer: join(&pointer, "operationId"),
+                    message: format!("operationId `{}` is not in camelCase", id),
+                })
+            })
+            .collect()
+    }
+}
+
+/// ### OperationSummary
+/// `operation-summary`: every operation has a summary.
+pub struct OperationSummary;
+
+impl LintRule for OperationSummary {
+    fn id(&self) -> &str {
+        "operation-summary"
+    }
+
+    fn description(&self) -> &str {
+        "every operation has a summary"
+    }
+
+    fn check(&self, openapi: &OpenApi) -> Vec<Violation> {
+        operations(openapi)
+            .filter(|(_, operation)| {
+                operation
+                    .summary
+                    .as_ref()
+                    .is_none_or(|summary| summary.trim().is_empty())
+            })
+            .map(|(pointer, _)| Violation {
+                pointer,
+                message: "the operation has no summary".to_string(),
+            })
+            .collect()
+    }
+}
+
+/// ### OperationTags
+/// `operation-tags`: every operation has at least one tag.
+pub struct OperationTags;
+
+impl LintRule for OperationTags {
+    fn id(&self) -> &str {
+        "operation-tags"
+    }
+
+    fn description(&self) -> &str {
+        "every operation has at least one tag"
+    }
+
+    fn check(&self, openapi: &OpenApi) -> Vec<Violation> {
+        operations(openapi)
+            .filter(|(_, operation)| operation.tags.as_ref().is_none_or(Vec::is_empty))
+            .map(|(pointer, _)| Violation {
+                pointer,
+                message: "the operation has no tags".to_string(),
+            })
+            .collect()
+    }
+}
+
+/// ### NoInlineResponseSchemas
+/// `no-inline-response-schemas`: the schemas of responses are references to `components`.
+///
+/// An array whose items are a reference is allowed, as lists are rarely worth a component of their own.
+pub struct NoInlineResponseSchemas;
+
+impl NoInlineResponseSchemas {
+    fn response(&self, response: &Response, pointer: &str, violations: &mut Vec<Violation>) {
+        let content = response.content.iter().flatten();
+        for (media_type, Media { schema, .. }) in content {
+            let Some(RefOrObject::Object(schema)) = schema else {
+                continue;
+            };
+            if schema.r#type.as_deref() == Some("array")
+                && matches!(schema.items.as_deref(), Some(RefOrObject::Ref(_)))
+            {
+                continue;
+            }
+            violations.push(Violation {
+                pointer: join(&join(&join(pointer, "content"), media_type), "schema"),
+                message: format!(
+                    "the `{}` schema is inline, define it in `components/schemas`",
+                    media_type
+                ),
+            });
+        }
+    }
+}
+
+impl LintRule for NoInlineResponseSchemas {
+    fn id(&self) -> &str {
+        "no-inline-response-schemas"
+    }
+
+    fn description(&self) -> &str {
+        "the schemas of responses are references to components"
+    }
+
+    fn check(&self, openapi: &OpenApi) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        for (pointer, operation) in operations(openapi) {
+            for (status, response) in &operation.responses {
+                if let RefOrObject::Object(response) = response {
+                    let pointer = join(&join(&pointer, "responses"), status);
+                    self.response(response, &pointer, &mut violations);
+                }
+            }
+        }
+        let responses = openapi
+            .components
+            .iter()
+            .flat_map(|components| components.responses.iter().flatten());
+        for (name, response) in responses {
+            if let RefOrObject::Object(response) = response {
+                let pointer = join("/components/responses", name);
+                self.response(response, &pointer, &mut violations);
+            }
+        }
+        violations
+    }
+}
+
+/// ### PathsKebabCase
+/// `paths-kebab-case`: the segments of paths are written in kebab-case, such as `/pet-owners/{ownerId}`.
+///
+/// Templated segments are parameter names and are not checked.
+pub struct PathsKebabCase;
+
+impl LintRule for PathsKebabCase {
+    fn id(&self) -> &str {
+        "paths-kebab-case"
+    }
+
+    fn description(&self) -> &str {
+        "the segments of paths are written in kebab-case"
+    }
+
+    fn check(&self, openapi: &OpenApi) -> Vec<Violation> {
+        openapi
+            .paths
+            .keys()
+            .filter(|path| {
+                !path
+                    .split('/')
+                    .filter(|segment| !segment.is_empty() && !segment.starts_with('{'))
+                    .all(|segment| {
+                        segment.split('-').all(|word| {
+                            !word.is_empty()
+                                && word
+                                    .chars()
+                                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+                        })
+                    })
+            })
+            .map(|path| Violation {
+                pointer: join("/paths", path),
+                message: format!("path `{}` is not in kebab-case", path),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn pointers(rule: &dyn LintRule, openapi: &OpenApi) -> Vec<String> {
+        rule.check(openapi)
+            .into_iter()
+            .map(|violation| violation.pointer)
+            .collect()
+    }
+
+    #[test]
+    fn test_rules() {
+        let openapi: OpenApi = serde_json::from_value(json!({
+            "openapi": "3.0.3",
+            "info": {"title": "Pets", "version": "1"},
+            "paths": {
+                "/pet-owners/{owner_id}": {
+                    "get": {
+                        "operationId": "getOwner",
+                        "summary": "Find an owner.",
+                        "tags": ["owners"],
+                        "responses": {
+                            "200": {
+                                "description": "The owner.",
+                                "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Owner"}}}
+                            }
+                        }
+                    }
+                },
+                "/petOwners/{ownerId}/pets": {
+                    "get": {
+                        "operationId": "list-pets",
+                        "summary": " ",
+                        "tags": [],
+                        "responses": {
+                            "200": {
+                                "description": "The pets.",
+                                "content": {
+                                    "application/json": {"schema": {"type": "array", "items": {"$ref": "#/components/schemas/Pet"}}},
+                                    "application/xml": {"schema": {"type": "array", "items": {"type": "object"}}}
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "responses": {
+                    "Error": {"description": "An error.", "content": {"application/json": {"schema": {"type": "object"}}}}
+                }
+            }
+        }))
+        .unwrap();
+        let pets = "/paths/~1petOwners~1{ownerId}~1pets";
+        assert_eq!(
+            pointers(&OperationIdCamelCase, &openapi),
+            [format!("{}/get/operationId", pets)]
+        );
+        assert_eq!(
+            pointers(&OperationSummary, &openapi),
+            [format!("{}/get", pets)]
+        );
+        assert_eq!(
+            pointers(&OperationTags, &openapi),
+            [format!("{}/get", pets)]
+        );
+        assert_eq!(
+            pointers(&NoInlineResponseSchemas, &openapi),
+            [
+                format!("{}/get/responses/200/content/application~1xml/schema", pets),
+                "/components/responses/Error/content/application~1json/schema".to_string(),
+            ]
+        );
+        assert_eq!(pointers(&PathsKebabCase, &openapi), [pets]);
+    }
+}
@@ -0,0 +1,353 @@
+//! Loading of documents split across several files.
+//!
+//! A [`Loader`] starts from a root document and follows every `$ref` which points to another document,
+//! relative to the document holding it, such as `./schemas/user.yaml#/User`.
+//! Each document is loaded once into a [`DocumentSet`], which resolves references across all of them.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::fs;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::de::deserialize_value;
+use crate::resolve::{unresolved, Chain};
+use crate::validate::{is_literal, V2_LITERALS, V3_LITERALS};
+use crate::{pointer, Doc, Error, Format};
+
+/// Fetches remote documents, such as `https://example.com/schemas.yaml`.
+///
+/// The crate does no networking itself, an HTTP client is plugged in through this trait.
+pub trait Fetcher {
+    /// Fetch the content of the document at an absolute URL.
+    fn fetch(&self, url: &str) -> Result<String, Error>;
+}
+
+impl<F> Fetcher for F
+where
+    F: Fn(&str) -> Result<String, Error>,
+{
+    fn fetch(&self, url: &str) -> Result<String, Error> {
+        self(url)
+    }
+}
+
+/// # Loader
+/// Loads a root document and every document its `$ref`s point to.
+#[derive(Default)]
+pub struct Loader {
+    fetcher: Option<Box<dyn Fetcher>>,
+}
+
+impl Loader {
+    /// A loader for local files, which refuses remote URLs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use a fetcher to load the documents referenced by an `http://` or `https://` URL.
+    pub fn with_fetcher<F>(mut self, fetcher: F) -> Self
+    where
+        F: Fetcher + 'static,
+    {
+        self.fetcher = Some(Box::new(fetcher));
+        self
+    }
+
+    /// Load a root document from a path, followed by every document it references.
+    pub fn load<P>(&self, path: P) -> Result<DocumentSet, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let root = fs::canonicalize(path).map_err(|e| Error::from(e).with_path(path))?;
+        self.load_location(root.to_string_lossy().into_owned())
+    }
+
+    /// Load a root document from a URL through the fetcher, followed by every document it references.
+    pub fn load_url(&self, url: &str) -> Result<DocumentSet, Error> {
+        self.load_location(url.to_string())
+    }
+
+    fn load_location(&self, root: String) -> Result<DocumentSet, Error> {
+        let mut documents = BTreeMap::new();
+        let mut queue = VecDeque::from([root.clone()]);
+        // the keywords holding data are those of the version of the root
+        let mut literals = V3_LITERALS;
+        while let Some(location) = queue.pop_front() {
+            if documents.contains_key(&location) {
+                continue;
+            }
+            let value = self.read(&location)?;
+            if location == root && value.get("swagger").is_some() {
+                literals = V2_LITERALS;
+            }
+            let mut references = Vec::new();
+            collect_references(&value, "", literals, &mut references);
+            for reference in references {
+                let (document, _) = pointer::split_reference(reference);
+                if !document.is_empty() {
+                    queue.push_back(locate(&location, document)?);
+                }
+            }
+            documents.insert(location, value);
+        }
+        Ok(DocumentSet { root, documents })
+    }
+
+    fn read(&self, location: &str) -> Result<Value, Error> {
+        let content = if is_url(location) {
+            match &self.fetcher {
+                Some(fetcher) => fetcher.fetch(location)?,
+                None => {
+                    return Err(unresolved(
+                        location,
+                        "is a remote document and the loader has no fetcher",
+                    ))
+                }
+            }
+        } else {
+            fs::read_to_string(location).map_err(|e| Error::from(e).with_path(location))?
+        };
+        let format = Format::from_path(location).unwrap_or_else(|| Format::detect(&content));
+        format
+            .from_str(&content)
+            .map_err(|e| with_document(e, location))
+    }
+}
+
+/// # DocumentSet
+/// The documents loaded by a [`Loader`], keyed by their canonical path or URL.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DocumentSet {
+    root: String,
+    documents: BTreeMap<String, Value>,
+}
+
+/// The value a reference points to, see [`DocumentSet::lookup`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Target<'a> {
+    /// The canonical path or URL of the document holding the value.
+    pub document: &'a str,
+    /// The JSON pointer of the value inside its document.
+    pub pointer: String,
+    /// The value itself, whose own references are relative to `document`.
+    pub value: &'a Value,
+}
+
+impl DocumentSet {
+    /// The canonical path or URL of the root document.
+    pub fn root(&self) -> &str {
+        &self.root
+    }
+
+    /// Deserialize the root document.
+    pub fn doc(&self) -> Result<Doc, Error> {
+        Doc::from_value(self.documents[&self.root].clone())
+            .map_err(|e| with_document(e, &self.root))
+    }
+
+    /// Replace the content of the root document, such as with the OpenAPI 3.0 conversion of a Swagger 2.0 root, so that
+    /// its local references are looked up in the new content.
+    pub(crate) fn replace_root(&mut self, root: Value) {
+        self.documents.insert(self.root.clone(), root);
+    }
+
+    /// The raw content of a loaded document.
+    pub fn get(&self, document: &str) -> Option<&Value> {
+        self.documents.get(document)
+    }
+
+    /// Every loaded document, by canonical path or URL.
+    pub fn documents(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.documents.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    /// Find the value a reference found in the `base` document points to, without following further references.
+    pub fn lookup<'a>(&'a self, base: &str, reference: &str) -> Result<Target<'a>, Error> {
+        let (document, fragment) = pointer::split_reference(reference);
+        let location = if document.is_empty() {
+            base.to_string()
+        } else {
+            locate(base, document)?
+        };
+        let (document, root) = self
+            .documents
+            .get_key_value(&location)
+            .ok_or_else(|| unresolved(reference, "points to a document which is not loaded"))?;
+        let tokens = pointer::parse(&fragment)
+            .ok_or_else(|| unresolved(reference, "is not a JSON pointer"))?;
+        let value = tokens
+            .iter()
+            .try_fold(root, |value, token| match value {
+                Value::Object(map) => map.get(token),
+                Value::Array(items) => token.parse::<usize>().ok().and_then(|i| items.get(i)),
+                _ => None,
+            })
+            .ok_or_else(|| unresolved(reference, "points to nothing"))?;
+        Ok(Target {
+            document,
+            pointer: fragment,
+            value,
+        })
+    }
+
+    /// Follow a reference found in the `base` document, and the references it points to, to a value.
+    pub fn resolve_value<'a>(&'a self, base: &str, reference: &str) -> Result<Target<'a>, Error> {
+        let mut chain = Chain::default();
+        let mut target = self.lookup(base, reference)?;
+        chain.follow(&format!("{}#{}", target.document, target.pointer))?;
+        while let Some(Value::String(next)) = target.value.get("$ref") {
+            target = self.lookup(target.document, next)?;
+            chain.follow(&format!("{}#{}", target.document, target.pointer))?;
+        }
+        Ok(target)
+    }
+
+    /// Follow a reference found in the `base` document to the object it points to.
+    ///
+    /// References inside the returned object are still relative to the document it was found in.
+    pub fn resolve<T>(&self, base: &str, reference: &str) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        self.resolve_value(base, reference)?.deserialize()
+    }
+}
+
+impl Target<'_> {
+    /// Deserialize the value, reporting an error at its location in its document.
+    pub fn deserialize<T>(&self) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        deserialize_value(self.value.clone(), &self.pointer)
+            .map_err(|e| with_document(e, self.document))
+    }
+}
+
+/// Collect every `$ref` string of a value found under `key`, skipping the data of extensions and `literals` keywords
+/// such as examples.
+pub(crate) fn collect_references<'a>(
+    value: &'a Value,
+    key: &str,
+    literals: &[&str],
+    references: &mut Vec<&'a str>,
+) {
+    match value {
+        Value::Object(map) => {
+            if let Some(Value::String(reference)) = map.get("$ref") {
+                references.push(reference);
+            }
+            for (child, value) in map {
+                if !is_literal(key, child, literals) {
+                    collect_references(value, child, literals, references);
+                }
+            }
+        }
+        Value::Array(items) => items
+            .iter()
+            .for_each(|value| collect_references(value, "", literals, references)),
+        _ => {}
+    }
+}
+
+/// Attach the path of a local document to an error.
+fn with_document(e: Error, document: &str) -> Error {
+    if is_url(document) {
+        e
+    } else {
+        e.with_path(document)
+    }
+}
+
+pub(crate) fn is_url(location: &str) -> bool {
+    location.starts_with("http://") || location.starts_with("https://")
+}
+
+/// The canonical location of a document referenced from the `base` document.
+pub(crate) fn locate(base: &str, document: &str) -> Result<String, Error> {
+    if is_url(document) {
+        return Ok(document.to_string());
+    }
+    if is_url(base) {
+        return Ok(join_url(base, document));
+    }
+    let path = Path::new(base)
+        .parent()
+        .unwrap_or_else(|| Path::new(""))
+        .join(document);
+    fs::canonicalize(&path)
+        .map(|path| path.to_string_lossy().into_owned())
+        .map_err(|e| Error::from(e).with_path(path))
+}
+
+/// Resolve a relative URL reference against an absolute base URL.
+fn join_url(base: &str, reference: &str) -> String {
+    let (scheme, rest) = base.split_once("://").unwrap_or(("", base));
+    let authority_end = rest.find('/').unwrap_or(rest.len());
+    let (authority, path) = rest.split_at(authority_end);
+    let directory = if reference.starts_with('/') {
+        ""
+    } else {
+        &path[..path.rfind('/').map_or(0, |i| i + 1)]
+    };
+    let mut segments: Vec<&str> = Vec::new();
+    let joined = format!("{}{}", directory, reference);
+    for segment in joined.split('/') {
+        match segment {
+            "." => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+    if segments.first() != Some(&"") {
+        segments.insert(0, "");
+    }
+    format!("{}://{}{}", scheme, authority, segments.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_join_url() {
+        assert_eq!(
+            join_url("https://example.com/api/openapi.yaml", "./schemas/pet.yaml"),
+            "https://example.com/api/schemas/pet.yaml"
+        );
+        assert_eq!(
+            join_url("https://example.com/api/v1/openapi.yaml", "../common.yaml"),
+            "https://example.com/api/common.yaml"
+        );
+        assert_eq!(
+            join_url("https://example.com/api/openapi.yaml", "/root.yaml"),
+            "https://example.com/root.yaml"
+        );
+    }
+
+    #[test]
+    fn test_collect_references() {
+        let value = serde_json::json!({
+            "a": {"$ref": "#/b"},
+            "c": [{"$ref": "other.json#/d"}, {"e": 1}],
+            "f": {
+                "example": {"$ref": "example.json"},
+                "default": [{"$ref": "default.json"}],
+                "x-data": {"$ref": "extension.json"},
+                "properties": {"example": {"$ref": "property.json"}}
+            },
+            "responses": {"default": {"$ref": "response.json"}}
+        });
+        let mut references = Vec::new();
+        collect_references(&value, "", V3_LITERALS, &mut references);
+        assert_eq!(
+            references,
+            vec!["#/b", "other.json#/d", "property.json", "response.json"]
+        );
+    }
+}
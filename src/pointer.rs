@@ -0,0 +1,102 @@
+//! Helpers for [JSON pointers](https://tools.ietf.org/html/rfc6901), used to locate a value inside a document.
+
+use percent_encoding::percent_decode_str;
+
+/// Escape a reference token so it can be embedded in a JSON pointer.
+///
+/// `~` is written as `~0` and `/` as `~1`.
+pub fn escape(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+/// Unescape a reference token read from a JSON pointer.
+pub fn unescape(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
+
+/// Split a JSON pointer into its unescaped reference tokens.
+///
+/// Returns `None` when the pointer is neither empty nor starts with `/`.
+pub fn parse(pointer: &str) -> Option<Vec<String>> {
+    if pointer.is_empty() {
+        return Some(Vec::new());
+    }
+    pointer
+        .strip_prefix('/')
+        .map(|pointer| pointer.split('/').map(unescape).collect())
+}
+
+/// Split a `$ref` into the document it points to and the JSON pointer of the fragment.
+///
+/// The document is empty for a local reference such as `#/components/schemas/Pet`, the fragment is percent-decoded.
+pub fn split_reference(reference: &str) -> (&str, String) {
+    match reference.split_once('#') {
+        Some((document, fragment)) => (
+            document,
+            percent_decode_str(fragment)
+                .decode_utf8_lossy()
+                .into_owned(),
+        ),
+        None => (reference, String::new()),
+    }
+}
+
+/// Append a reference token to a JSON pointer.
+pub fn join(pointer: &str, token: &str) -> String {
+    format!("{}/{}", pointer, escape(token))
+}
+
+/// Convert the path of a failed deserialization into a JSON pointer.
+pub(crate) fn from_path(path: &serde_path_to_error::Path) -> String {
+    use serde_path_to_error::Segment;
+
+    path.iter()
+        .fold(String::new(), |pointer, segment| match segment {
+            Segment::Seq { index } => join(&pointer, &index.to_string()),
+            Segment::Map { key } => join(&pointer, key),
+            Segment::Enum { variant } => join(&pointer, variant),
+            Segment::Unknown => pointer,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("/pets/{id}"), "~1pets~1{id}");
+        assert_eq!(escape("a~b"), "a~0b");
+        assert_eq!(join("/paths", "/pets"), "/paths/~1pets");
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse(""), Some(vec![]));
+        assert_eq!(parse("components"), None);
+        assert_eq!(
+            parse("/paths/~1pets~1{id}/a~01"),
+            Some(vec![
+                "paths".to_string(),
+                "/pets/{id}".to_string(),
+                "a~1".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn test_split_reference() {
+        assert_eq!(
+            split_reference("#/components/schemas/Pet"),
+            ("", "/components/schemas/Pet".to_string())
+        );
+        assert_eq!(
+            split_reference("./user.yaml#/User%20Name"),
+            ("./user.yaml", "/User Name".to_string())
+        );
+        assert_eq!(
+            split_reference("./user.yaml"),
+            ("./user.yaml", String::new())
+        );
+    }
+}
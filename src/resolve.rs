@@ -0,0 +1,175 @@
+//! Resolution of local `$ref`s, the JSON pointers into the document which holds them.
+//!
+//! Each versioned model implements [`Navigate`] for its objects so a pointer such as `#/components/schemas/Pet/properties/name`
+//! can be followed through the typed document; see `v2::Swagger::resolve_schema`, `v3::OpenApi::resolve` and `v3_1::OpenApi::resolve`.
+
+use std::any::Any;
+use std::collections::BTreeMap;
+
+use crate::{error::Error, pointer};
+
+/// An object of a document which a JSON pointer can step into.
+pub trait Navigate: Any {
+    /// The value under the given reference token, if it exists.
+    fn child(&self, token: &str) -> Option<&dyn Navigate>;
+
+    /// The object as `Any`, to downcast the target of a pointer.
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<V: Navigate> Navigate for BTreeMap<String, V> {
+    fn child(&self, token: &str) -> Option<&dyn Navigate> {
+        self.get(token).map(|v| v as &dyn Navigate)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl<V: Navigate> Navigate for Vec<V> {
+    fn child(&self, token: &str) -> Option<&dyn Navigate> {
+        token
+            .parse::<usize>()
+            .ok()
+            .and_then(|index| self.get(index))
+            .map(|v| v as &dyn Navigate)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// A box is transparent, its content is what a pointer finds.
+impl<V: Navigate> Navigate for Box<V> {
+    fn child(&self, token: &str) -> Option<&dyn Navigate> {
+        (**self).child(token)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        (**self).as_any()
+    }
+}
+
+/// Implement [`Navigate`] for a struct, mapping the JSON name of each field to the field.
+///
+/// Optional fields are prefixed with `?`; a type listed without fields is a leaf.
+macro_rules! navigate {
+    ($ty:ty { $($name:literal => $($field:ident)? $(? $optional:ident)?),* $(,)? }) => {
+        impl $crate::resolve::Navigate for $ty {
+            #[allow(unused_variables)]
+            fn child(&self, token: &str) -> Option<&dyn $crate::resolve::Navigate> {
+                match token {
+                    $(
+                        $name => {
+                            $(return Some(&self.$field as &dyn $crate::resolve::Navigate);)?
+                            $(return self.$optional.as_ref().map(|v| v as &dyn $crate::resolve::Navigate);)?
+                        }
+                    )*
+                    _ => None,
+                }
+            }
+
+            fn as_any(&self) -> &dyn std::any::Any {
+                self
+            }
+        }
+    };
+}
+pub(crate) use navigate;
+
+/// Find the object a local reference such as `#/components/schemas/Pet` points to.
+pub(crate) fn find<'a>(root: &'a dyn Navigate, reference: &str) -> Result<&'a dyn Navigate, Error> {
+    let (document, fragment) = pointer::split_reference(reference);
+    if !document.is_empty() {
+        return Err(unresolved(
+            reference,
+            "points to another document, load it with a `Loader`",
+        ));
+    }
+    let tokens =
+        pointer::parse(&fragment).ok_or_else(|| unresolved(reference, "is not a JSON pointer"))?;
+    tokens.iter().try_fold(root, |node, token| {
+        node.child(token)
+            .ok_or_else(|| unresolved(reference, "points to nothing"))
+    })
+}
+
+/// Keeps track of the references followed from one object, to detect cycles.
+#[derive(Default)]
+pub(crate) struct Chain(Vec<String>);
+
+impl Chain {
+    /// Record the next reference of the chain, failing if it was already followed.
+    pub(crate) fn follow(&mut self, reference: &str) -> Result<(), Error> {
+        let cyclic = self.0.iter().any(|followed| followed == reference);
+        self.0.push(reference.to_string());
+        if cyclic {
+            return Err(Error::CyclicReference {
+                chain: self.0.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// What `dereference` does with a reference back into an object it is inlining, whose copy would never end.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CycleStrategy {
+    /// Leave the reference which closes the cycle in place.
+    #[default]
+    Keep,
+    /// Fail with [`Error::CyclicReference`].
+    Fail,
+}
+
+/// The references being inlined by `dereference`, from the outermost one.
+pub(crate) struct Inlining {
+    stack: Vec<String>,
+    cycles: CycleStrategy,
+}
+
+impl Inlining {
+    pub(crate) fn new(cycles: CycleStrategy) -> Self {
+        Inlining {
+            stack: Vec::new(),
+            cycles,
+        }
+    }
+
+    /// Start inlining a reference, returns `false` if it closes a cycle which is kept.
+    pub(crate) fn enter(&mut self, reference: &str) -> Result<bool, Error> {
+        if self.stack.iter().any(|inlining| inlining == reference) {
+            return match self.cycles {
+                CycleStrategy::Keep => Ok(false),
+                CycleStrategy::Fail => {
+                    let mut chain = self.stack.clone();
+                    chain.push(reference.to_string());
+                    Err(Error::CyclicReference { chain })
+                }
+            };
+        }
+        self.stack.push(reference.to_string());
+        Ok(true)
+    }
+
+    /// Done inlining the last entered reference.
+    pub(crate) fn leave(&mut self) {
+        self.stack.pop();
+    }
+}
+
+pub(crate) fn unresolved(reference: &str, message: &str) -> Error {
+    Error::UnresolvedReference {
+        reference: reference.to_string(),
+        message: message.to_string(),
+    }
+}
+
+/// The error for a reference which points to an object of another type than `T`.
+pub(crate) fn mismatched<T>(reference: &str) -> Error {
+    let name = std::any::type_name::<T>();
+    let name = name.rsplit("::").next().unwrap_or(name);
+    unresolved(reference, &format!("does not point to a {}", name))
+}
@@ -0,0 +1,366 @@
+//! Matching of concrete request paths, such as `/pets/42`, against the path templates of a document.
+//!
+//! [`OpenApi::router`](crate::v3::OpenApi::router) and [`Swagger::router`](crate::v2::Swagger::router) compile the
+//! templates of the `paths` once into a [`Router`], which finds the path item and the operation of a method and a path
+//! along with the values of the path parameters. As the specification requires, concrete segments are matched before
+//! templated ones, so `/pets/mine` wins over `/pets/{petId}`.
+//!
+//! ```
+//! # use openapi_schema::v3::OpenApi;
+//! # fn example(openapi: &OpenApi) {
+//! let router = openapi.router();
+//! if let Ok(found) = router.route("GET", "/pets/42") {
+//!     println!("{} {:?}", found.template, found.parameters);
+//! }
+//! # }
+//! ```
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// # Router
+/// The compiled path templates of a document, generic over the path item and operation types of its version.
+#[derive(Clone, Debug)]
+pub struct Router<'a, I, O> {
+    /// The prefixes a path may start with before its template: the `basePath` of a Swagger document, or the paths of
+    /// the servers of an OpenAPI document.
+    bases: Vec<String>,
+    /// The routes, the most concrete first.
+    routes: Vec<Route<'a, I, O>>,
+}
+
+#[derive(Clone, Debug)]
+struct Route<'a, I, O> {
+    template: &'a str,
+    segments: Vec<Segment>,
+    path_item: &'a I,
+    operations: Vec<(&'static str, &'a O)>,
+}
+
+/// A segment of a path template.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Segment {
+    /// A segment without parameters, such as `pets`.
+    Literal(String),
+    /// A segment with parameters, possibly mixed with literals as in `{name}.{extension}`.
+    Template(Vec<Part>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Part {
+    Literal(String),
+    Parameter(String),
+}
+
+impl Segment {
+    fn compile(segment: &str) -> Segment {
+        if !segment.contains('{') {
+            return Segment::Literal(segment.to_string());
+        }
+        let mut parts = Vec::new();
+        let mut rest = segment;
+        while let Some(start) = rest.find('{') {
+            let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+                break;
+            };
+            if start > 0 {
+                parts.push(Part::Literal(rest[..start].to_string()));
+            }
+            parts.push(Part::Parameter(rest[start + 1..end].to_string()));
+            rest = &rest[end + 1..];
+        }
+        if !rest.is_empty() {
+            parts.push(Part::Literal(rest.to_string()));
+        }
+        Segment::Template(parts)
+    }
+
+    /// How concrete the segment is: literals first, then segments mixing literals and parameters, then parameters.
+    fn rank(&self) -> u8 {
+        match self {
+            Segment::Literal(_) => 0,
+            Segment::Template(parts)
+                if parts.iter().any(|part| matches!(part, Part::Literal(_))) =>
+            {
+                1
+            }
+            Segment::Template(_) => 2,
+        }
+    }
+
+    fn matches(&self, segment: &str, parameters: &mut BTreeMap<String, String>) -> bool {
+        match self {
+            Segment::Literal(literal) => literal == segment,
+            Segment::Template(parts) => match_parts(parts, segment, parameters),
+        }
+    }
+}
+
+/// Match the parts of a templated segment, a parameter taking at least one character.
+fn match_parts(parts: &[Part], segment: &str, parameters: &mut BTreeMap<String, String>) -> bool {
+    match parts {
+        [] => segment.is_empty(),
+        [Part::Literal(literal), rest @ ..] => segment
+            .strip_prefix(literal.as_str())
+            .is_some_and(|segment| match_parts(rest, segment, parameters)),
+        [Part::Parameter(name)] => {
+            parameters.insert(name.clone(), segment.to_string());
+            !segment.is_empty()
+        }
+        [Part::Parameter(name), rest @ ..] => (1..=segment.len())
+            .filter(|&at| segment.is_char_boundary(at))
+            .any(|at| {
+                let mut attempt = parameters.clone();
+                attempt.insert(name.clone(), segment[..at].to_string());
+                let matched = match_parts(rest, &segment[at..], &mut attempt);
+                if matched {
+                    *parameters = attempt;
+                }
+                matched
+            }),
+    }
+}
+
+/// Compare templates segment by segment, a more concrete segment coming first.
+fn specificity(left: &[Segment], right: &[Segment]) -> Ordering {
+    left.iter()
+        .map(Segment::rank)
+        .cmp(right.iter().map(Segment::rank))
+}
+
+impl<'a, I, O> Router<'a, I, O> {
+    /// Compile the routes of the given path templates, path items and operations.
+    pub(crate) fn new(
+        bases: Vec<String>,
+        paths: impl IntoIterator<Item = (&'a str, &'a I, Vec<(&'static str, &'a O)>)>,
+    ) -> Self {
+        let mut routes: Vec<_> = paths
+            .into_iter()
+            .map(|(template, path_item, operations)| Route {
+                template,
+                segments: template.split('/').map(Segment::compile).collect(),
+                path_item,
+                operations,
+            })
+            .collect();
+        routes.sort_by(|left, right| {
+            specificity(&left.segments, &right.segments)
+                .then_with(|| left.template.cmp(right.template))
+        });
+        let bases = bases
+            .into_iter()
+            .map(|base| base.trim_end_matches('/').to_string())
+            .filter(|base| !base.is_empty())
+            .collect();
+        Router { bases, routes }
+    }
+
+    /// The path templates of the router, the most concrete first.
+    pub fn templates(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.routes.iter().map(|route| route.template)
+    }
+
+    /// Find the path item whose template matches a path, with the raw values of its path parameters.
+    ///
+    /// The path may start with a base path, and a query string is ignored. The values of the parameters are still
+    /// percent-encoded, as their decoding depends on their `style`.
+    pub fn find(&self, path: &str) -> Option<PathMatch<'a, I>> {
+        self.find_route(path).map(|(index, parameters)| PathMatch {
+            template: self.routes[index].template,
+            path_item: self.routes[index].path_item,
+            parameters,
+        })
+    }
+
+    /// Find the operation of a method, such as `GET` or `get`, on a path.
+    pub fn route(&self, method: &str, path: &str) -> Result<Match<'a, I, O>, RouteError> {
+        let Some((index, parameters)) = self.find_route(path) else {
+            return Err(RouteError::NotFound {
+                path: path.to_string(),
+            });
+        };
+        let route = &self.routes[index];
+        route
+            .operations
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(method))
+            .map(|&(method, operation)| Match {
+                template: route.template,
+                method,
+                path_item: route.path_item,
+                operation,
+                parameters,
+            })
+            .ok_or_else(|| RouteError::MethodNotAllowed {
+                method: method.to_ascii_uppercase(),
+                template: route.template.to_string(),
+                allowed: route.operations.iter().map(|(method, _)| *method).collect(),
+            })
+    }
+
+    /// The index of the first route matching a path, with the values of its parameters.
+    fn find_route(&self, path: &str) -> Option<(usize, BTreeMap<String, String>)> {
+        let path = path.split(['?', '#']).next().unwrap_or_default();
+        let mut candidates = vec![path];
+        candidates.extend(
+            self.bases
+                .iter()
+                .filter_map(|base| path.strip_prefix(base.as_str()))
+                .filter(|rest| rest.starts_with('/')),
+        );
+        let candidates: Vec<Vec<_>> = candidates
+            .into_iter()
+            .map(|path| path.split('/').collect())
+            .collect();
+        self.routes.iter().enumerate().find_map(|(index, route)| {
+            candidates.iter().find_map(|segments| {
+                if segments.len() != route.segments.len() {
+                    return None;
+                }
+                let mut parameters = BTreeMap::new();
+                route
+                    .segments
+                    .iter()
+                    .zip(segments)
+                    .all(|(template, segment)| template.matches(segment, &mut parameters))
+                    .then_some((index, parameters))
+            })
+        })
+    }
+}
+
+/// # PathMatch
+/// The path item a path matches.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PathMatch<'a, I> {
+    /// The template of the path item, such as `/pets/{petId}`.
+    pub template: &'a str,
+    pub path_item: &'a I,
+    /// The raw values of the path parameters, by name.
+    pub parameters: BTreeMap<String, String>,
+}
+
+/// # Match
+/// The operation a method and a path match.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Match<'a, I, O> {
+    /// The template of the path item, such as `/pets/{petId}`.
+    pub template: &'a str,
+    /// The lowercase method of the operation.
+    pub method: &'static str,
+    pub path_item: &'a I,
+    pub operation: &'a O,
+    /// The raw values of the path parameters, by name.
+    pub parameters: BTreeMap<String, String>,
+}
+
+/// # RouteError
+/// Why a method and a path match no operation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RouteError {
+    /// No path template matches the path.
+    NotFound { path: String },
+    /// A path template matches, but its path item has no operation for the method.
+    MethodNotAllowed {
+        method: String,
+        template: String,
+        /// The lowercase methods of the path item, such as for an `Allow` header.
+        allowed: Vec<&'static str>,
+    },
+}
+
+impl fmt::Display for RouteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RouteError::NotFound { path } => write!(f, "no path matches `{}`", path),
+            RouteError::MethodNotAllowed {
+                method, template, ..
+            } => write!(f, "`{}` has no `{}` operation", template, method),
+        }
+    }
+}
+
+impl std::error::Error for RouteError {}
+
+/// The path of a URL, such as `/v1` for `https://api.example.com/v1`, or the URL itself when it is relative.
+pub(crate) fn url_path(url: &str) -> &str {
+    match url.split_once("://") {
+        Some((_, rest)) => rest.find('/').map_or("", |start| &rest[start..]),
+        None => url,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn router<'a>(templates: &'a [&'a str], bases: &[&str]) -> Router<'a, &'a str, ()> {
+        Router::new(
+            bases.iter().map(|base| base.to_string()).collect(),
+            templates
+                .iter()
+                .map(|template| (*template, template, vec![("get", &())])),
+        )
+    }
+
+    #[test]
+    fn test_specificity() {
+        let router = router(
+            &[
+                "/{a}/{b}",
+                "/pets/{petId}",
+                "/pets/mine",
+                "/{a}/mine",
+                "/files/{name}.{ext}",
+            ],
+            &[],
+        );
+        assert_eq!(
+            router.templates().collect::<Vec<_>>(),
+            [
+                "/pets/mine",
+                "/files/{name}.{ext}",
+                "/pets/{petId}",
+                "/{a}/mine",
+                "/{a}/{b}"
+            ]
+        );
+        assert_eq!(router.find("/pets/mine").unwrap().template, "/pets/mine");
+        assert_eq!(router.find("/pets/42").unwrap().template, "/pets/{petId}");
+        assert_eq!(router.find("/cats/mine").unwrap().template, "/{a}/mine");
+        let found = router.find("/files/report.tar.gz").unwrap();
+        assert_eq!(found.template, "/files/{name}.{ext}");
+        assert_eq!(found.parameters["name"], "report");
+        assert_eq!(found.parameters["ext"], "tar.gz");
+        assert!(router.find("/pets").is_none());
+        assert!(router.find("/pets/").is_none());
+    }
+
+    #[test]
+    fn test_route() {
+        let router = router(&["/pets/{petId}"], &["/v1/"]);
+        let found = router.route("GET", "/v1/pets/42?full=true").unwrap();
+        assert_eq!((found.template, found.method), ("/pets/{petId}", "get"));
+        assert_eq!(found.parameters["petId"], "42");
+        assert_eq!(
+            router.route("delete", "/pets/42"),
+            Err(RouteError::MethodNotAllowed {
+                method: "DELETE".to_string(),
+                template: "/pets/{petId}".to_string(),
+                allowed: vec!["get"],
+            })
+        );
+        assert!(matches!(
+            router.route("GET", "/v2/pets/42"),
+            Err(RouteError::NotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_url_path() {
+        assert_eq!(url_path("https://api.example.com/v1"), "/v1");
+        assert_eq!(url_path("https://api.example.com"), "");
+        assert_eq!(url_path("/v1"), "/v1");
+    }
+}
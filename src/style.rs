@@ -0,0 +1,846 @@
+//! The serialization of parameter values on the wire, as described by their `style` and `explode`.
+//!
+//! A parameter holds a primitive, an array or an object, and its style tells how that value is written in a path
+//! segment, a header, a query string or a cookie. Decoding gives the values as strings, converting them to the types of
+//! a schema is left to the caller. Decoding splits the wire string on the delimiters of the style before percent-decoding
+//! each item, so that an escaped delimiter is part of a value. Encoding writes a JSON value in its style and percent-encodes it, keeping the
+//! delimiters of the style. Swagger 2.0 parameters are written according to their [`CollectionFormat`] instead.
+
+use std::fmt;
+
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use serde_json::{Map, Value};
+
+/// The characters which are percent-encoded in a value: all but the unreserved characters of RFC 3986.
+const UNRESERVED: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// The characters which are percent-encoded in a value which allows reserved characters, as with `allowReserved`.
+const RESERVED: &AsciiSet = &UNRESERVED
+    .remove(b':')
+    .remove(b'/')
+    .remove(b'?')
+    .remove(b'#')
+    .remove(b'[')
+    .remove(b']')
+    .remove(b'@')
+    .remove(b'!')
+    .remove(b'$')
+    .remove(b'&')
+    .remove(b'\'')
+    .remove(b'(')
+    .remove(b')')
+    .remove(b'*')
+    .remove(b'+')
+    .remove(b',')
+    .remove(b';')
+    .remove(b'=');
+
+/// # Style
+/// The `style` of a parameter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Style {
+    /// Path-style parameters, such as `;color=blue`.
+    Matrix,
+    /// Label-style parameters, such as `.blue`.
+    Label,
+    /// Form-style parameters, such as `color=blue` in a query string.
+    Form,
+    /// Comma-separated values, such as `blue,black`.
+    Simple,
+    /// Space-separated arrays, such as `color=blue%20black`.
+    SpaceDelimited,
+    /// Pipe-separated arrays, such as `color=blue|black`.
+    PipeDelimited,
+    /// Nested objects, such as `color[R]=100&color[G]=200`.
+    DeepObject,
+}
+
+impl Style {
+    /// The style named in a document, such as `deepObject`.
+    pub fn from_name(name: &str) -> Option<Style> {
+        match name {
+            "matrix" => Some(Style::Matrix),
+            "label" => Some(Style::Label),
+            "form" => Some(Style::Form),
+            "simple" => Some(Style::Simple),
+            "spaceDelimited" => Some(Style::SpaceDelimited),
+            "pipeDelimited" => Some(Style::PipeDelimited),
+            "deepObject" => Some(Style::DeepObject),
+            _ => None,
+        }
+    }
+
+    /// The name of the style in a document.
+    pub fn name(self) -> &'static str {
+        match self {
+            Style::Matrix => "matrix",
+            Style::Label => "label",
+            Style::Form => "form",
+            Style::Simple => "simple",
+            Style::SpaceDelimited => "spaceDelimited",
+            Style::PipeDelimited => "pipeDelimited",
+            Style::DeepObject => "deepObject",
+        }
+    }
+
+    /// The style of a parameter which sets none: `form` in a query or a cookie, `simple` in a path or a header.
+    pub fn default_for(location: &str) -> Style {
+        match location {
+            "query" | "cookie" => Style::Form,
+            _ => Style::Simple,
+        }
+    }
+
+    /// Whether a parameter of this style explodes when it does not set `explode`, which is only the case of `form`.
+    pub fn explode_by_default(self) -> bool {
+        self == Style::Form
+    }
+
+    /// Whether the value is written as pairs of a query string or a cookie rather than as a single string.
+    pub fn is_paired(self) -> bool {
+        matches!(
+            self,
+            Style::Form | Style::SpaceDelimited | Style::PipeDelimited | Style::DeepObject
+        )
+    }
+
+    /// Decode the value of a path or header parameter, written as a single string.
+    ///
+    /// `raw` is still percent-encoded, as it was sent. Only the `simple`, `label` and `matrix` styles are written this way.
+    pub fn decode(
+        self,
+        name: &str,
+        explode: bool,
+        shape: Shape,
+        raw: &str,
+    ) -> Result<Value, StyleError> {
+        match self {
+            Style::Simple => Ok(delimited(raw, ",", shape, explode)),
+            Style::Label => {
+                let rest = raw
+                    .strip_prefix('.')
+                    .ok_or_else(|| StyleError::new(format!("`{}` does not start with `.`", raw)))?;
+                let separator = if explode { "." } else { "," };
+                Ok(delimited(rest, separator, shape, explode))
+            }
+            Style::Matrix => {
+                let rest = raw
+                    .strip_prefix(';')
+                    .ok_or_else(|| StyleError::new(format!("`{}` does not start with `;`", raw)))?;
+                match (shape, explode) {
+                    (Shape::Object, true) => Ok(delimited(rest, ";", shape, true)),
+                    (Shape::Array, true) => rest
+                        .split(';')
+                        .map(|item| named(item, name).map(|value| string(&unescape(value))))
+                        .collect::<Result<_, _>>()
+                        .map(Value::Array),
+                    _ => named(rest, name).map(|value| delimited(value, ",", shape, false)),
+                }
+            }
+            _ => Err(StyleError::new(format!(
+                "`{}` values are written as pairs of a query string or a cookie",
+                self.name()
+            ))),
+        }
+    }
+
+    /// Decode the value of a query or cookie parameter from the pairs it was sent with, still percent-encoded.
+    ///
+    /// Returns `None` when the parameter was not sent. An exploded `form` object takes each pair as one of its
+    /// properties, so the pairs of the other parameters should be left out.
+    pub fn decode_pairs(
+        self,
+        name: &str,
+        explode: bool,
+        shape: Shape,
+        pairs: &[(String, String)],
+    ) -> Result<Option<Value>, StyleError> {
+        let mut values = pairs
+            .iter()
+            .filter(|(key, _)| unescape(key) == name)
+            .map(|(_, value)| value.as_str());
+        let separator = match self {
+            Style::Form => ",",
+            Style::SpaceDelimited => "%20",
+            Style::PipeDelimited => "|",
+            Style::DeepObject => {
+                let prefix = format!("{}[", name);
+                let properties: Map<String, Value> = pairs
+                    .iter()
+                    .filter_map(|(key, value)| {
+                        let key = unescape(key);
+                        let property = key.strip_prefix(&prefix)?.strip_suffix(']')?;
+                        Some((property.to_string(), string(&unescape(value))))
+                    })
+                    .collect();
+                return match shape {
+                    Shape::Object => {
+                        Ok((!properties.is_empty()).then_some(Value::Object(properties)))
+                    }
+                    _ => Err(StyleError::new("`deepObject` values must be objects")),
+                };
+            }
+            _ => {
+                return match values.next() {
+                    Some(value) => self.decode(name, explode, shape, value).map(Some),
+                    None => Ok(None),
+                };
+            }
+        };
+        Ok(match (shape, explode) {
+            (Shape::Array, true) => {
+                let items: Vec<_> = values.map(|value| string(&unescape(value))).collect();
+                (!items.is_empty()).then_some(Value::Array(items))
+            }
+            (Shape::Object, true) => {
+                let properties: Map<String, Value> = pairs
+                    .iter()
+                    .map(|(key, value)| (unescape(key), string(&unescape(value))))
+                    .collect();
+                (!properties.is_empty()).then_some(Value::Object(properties))
+            }
+            _ => values
+                .next()
+                .map(|value| delimited(value, separator, shape, false)),
+        })
+    }
+
+    /// Encode the value of a path or header parameter as a single string.
+    ///
+    /// Only the `simple`, `label` and `matrix` styles are written this way. Names, values and property names are
+    /// percent-encoded, so that they cannot be mistaken for the delimiters of the style.
+    pub fn encode(self, name: &str, explode: bool, value: &Value) -> Result<String, StyleError> {
+        let name = escape(name, false);
+        let (prefix, separator) = match self {
+            Style::Simple => ("", ','),
+            Style::Label => (".", if explode { '.' } else { ',' }),
+            Style::Matrix => (";", if explode { ';' } else { ',' }),
+            _ => {
+                return Err(StyleError::new(format!(
+                    "`{}` values are written as pairs of a query string or a cookie",
+                    self.name()
+                )))
+            }
+        };
+        let encoded = match (Encoded::of(value)?.escaped(false), self, explode) {
+            (Encoded::Primitive(value), Style::Matrix, _) => named_value(&name, &value),
+            (Encoded::Array(items), Style::Matrix, true) if !items.is_empty() => items
+                .iter()
+                .map(|item| named_value(&name, item))
+                .collect::<Vec<_>>()
+                .join(";"),
+            (Encoded::Array(items), Style::Matrix, _) => named_value(&name, &items.join(",")),
+            (Encoded::Object(properties), Style::Matrix, false) => {
+                named_value(&name, &flatten(&properties).join(","))
+            }
+            (Encoded::Primitive(value), ..) => value,
+            (Encoded::Array(items), ..) => items.join(&separator.to_string()),
+            (Encoded::Object(properties), _, true) => properties
+                .iter()
+                .map(|(key, value)| format!("{}={}", key, value))
+                .collect::<Vec<_>>()
+                .join(&separator.to_string()),
+            (Encoded::Object(properties), _, false) => flatten(&properties).join(","),
+        };
+        Ok(format!("{}{}", prefix, encoded))
+    }
+
+    /// Encode the value of a query or cookie parameter as the pairs it is sent with.
+    ///
+    /// The names and values of the pairs are percent-encoded, except for the reserved characters of RFC 3986 in the
+    /// values when `allow_reserved` is set. An exploded `form` object gives one pair for each of its properties.
+    pub fn encode_pairs(
+        self,
+        name: &str,
+        explode: bool,
+        allow_reserved: bool,
+        value: &Value,
+    ) -> Result<Vec<(String, String)>, StyleError> {
+        let value = Encoded::of(value)?.escaped(allow_reserved);
+        let name = escape(name, false);
+        let separator = match self {
+            Style::Form => ",",
+            Style::SpaceDelimited => "%20",
+            Style::PipeDelimited => "|",
+            Style::DeepObject => {
+                return match value {
+                    Encoded::Object(properties) => Ok(properties
+                        .into_iter()
+                        .map(|(key, value)| (format!("{}[{}]", name, key), value))
+                        .collect()),
+                    _ => Err(StyleError::new("`deepObject` values must be objects")),
+                };
+            }
+            _ => {
+                return Err(StyleError::new(format!(
+                    "`{}` values are written as a single string",
+                    self.name()
+                )))
+            }
+        };
+        Ok(match (value, explode) {
+            (Encoded::Primitive(value), _) => vec![(name, value)],
+            (Encoded::Array(items), true) => {
+                items.into_iter().map(|item| (name.clone(), item)).collect()
+            }
+            (Encoded::Array(items), false) => vec![(name, items.join(separator))],
+            (Encoded::Object(properties), true) => properties,
+            (Encoded::Object(properties), false) => {
+                vec![(name, flatten(&properties).join(separator))]
+            }
+        })
+    }
+}
+
+impl fmt::Display for Style {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// # Shape
+/// The kind of value a parameter holds, which decides how its wire form is split.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Shape {
+    Primitive,
+    Array,
+    Object,
+}
+
+impl Shape {
+    /// The shape of the values of a schema `type`, values of an unknown type being primitives.
+    pub fn of(r#type: Option<&str>) -> Shape {
+        match r#type {
+            Some("array") => Shape::Array,
+            Some("object") => Shape::Object,
+            _ => Shape::Primitive,
+        }
+    }
+}
+
+/// # CollectionFormat
+/// The `collectionFormat` of a Swagger 2.0 array parameter.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum CollectionFormat {
+    /// Comma-separated values, such as `blue,black`.
+    #[default]
+    Csv,
+    /// Space-separated values, such as `blue black`.
+    Ssv,
+    /// Tab-separated values.
+    Tsv,
+    /// Pipe-separated values, such as `blue|black`.
+    Pipes,
+    /// Repeated parameters, such as `color=blue&color=black`.
+    Multi,
+}
+
+impl CollectionFormat {
+    /// The format named in a document, such as `pipes`.
+    pub fn from_name(name: &str) -> Option<CollectionFormat> {
+        match name {
+            "csv" => Some(CollectionFormat::Csv),
+            "ssv" => Some(CollectionFormat::Ssv),
+            "tsv" => Some(CollectionFormat::Tsv),
+            "pipes" => Some(CollectionFormat::Pipes),
+            "multi" => Some(CollectionFormat::Multi),
+            _ => None,
+        }
+    }
+
+    /// The name of the format in a document.
+    pub fn name(self) -> &'static str {
+        match self {
+            CollectionFormat::Csv => "csv",
+            CollectionFormat::Ssv => "ssv",
+            CollectionFormat::Tsv => "tsv",
+            CollectionFormat::Pipes => "pipes",
+            CollectionFormat::Multi => "multi",
+        }
+    }
+
+    /// The separator of the items, `None` for `multi` whose items are sent as repeated parameters.
+    pub fn separator(self) -> Option<char> {
+        match self {
+            CollectionFormat::Csv => Some(','),
+            CollectionFormat::Ssv => Some(' '),
+            CollectionFormat::Tsv => Some('\t'),
+            CollectionFormat::Pipes => Some('|'),
+            CollectionFormat::Multi => None,
+        }
+    }
+
+    /// Split a percent-decoded value into its items, an empty value having none. A `multi` value is a single item.
+    pub fn split(self, raw: &str) -> Vec<&str> {
+        match self.separator() {
+            _ if raw.is_empty() => Vec::new(),
+            Some(separator) => raw.split(separator).collect(),
+            None => vec![raw],
+        }
+    }
+
+    /// Decode the percent-decoded values an array parameter was sent with into an array of strings.
+    ///
+    /// Only `multi` parameters may be sent several times.
+    pub fn decode(self, values: &[&str]) -> Result<Value, StyleError> {
+        let items = match (self, values) {
+            (CollectionFormat::Multi, values) => values.to_vec(),
+            (format, [value]) => format.split(value),
+            (format, values) => {
+                return Err(StyleError::new(format!(
+                    "expected a single `{}` value, found {}",
+                    format,
+                    values.len()
+                )))
+            }
+        };
+        Ok(Value::Array(items.into_iter().map(string).collect()))
+    }
+
+    /// Encode an array into the percent-encoded values it is sent with: one value for each item with `multi`, a single
+    /// value otherwise.
+    pub fn encode(self, value: &Value) -> Result<Vec<String>, StyleError> {
+        let items = match Encoded::of(value)?.escaped(false) {
+            Encoded::Primitive(value) => vec![value],
+            Encoded::Array(items) => items,
+            Encoded::Object(_) => {
+                return Err(StyleError::new(
+                    "objects cannot be written with a `collectionFormat`",
+                ))
+            }
+        };
+        let separator = match self {
+            CollectionFormat::Csv => ",",
+            CollectionFormat::Ssv => "%20",
+            CollectionFormat::Tsv => "%09",
+            CollectionFormat::Pipes => "|",
+            CollectionFormat::Multi => return Ok(items),
+        };
+        Ok(vec![items.join(separator)])
+    }
+}
+
+impl fmt::Display for CollectionFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// # StyleError
+/// A wire string which does not follow the style of its parameter.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StyleError {
+    pub message: String,
+}
+
+impl StyleError {
+    fn new(message: impl Into<String>) -> Self {
+        StyleError {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for StyleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for StyleError {}
+
+fn string(value: &str) -> Value {
+    Value::String(value.to_string())
+}
+
+/// The percent-decoded text of an item of a wire string.
+fn unescape(raw: &str) -> String {
+    percent_decode_str(raw).decode_utf8_lossy().into_owned()
+}
+
+/// The value of a `name=value` item, still percent-encoded, or an empty value for a bare `name`.
+fn named<'s>(item: &'s str, name: &str) -> Result<&'s str, StyleError> {
+    match item.split_once('=') {
+        Some((key, value)) if unescape(key) == name => Ok(value),
+        None if unescape(item) == name => Ok(""),
+        _ => Err(StyleError::new(format!(
+            "`{}` is not a value of `{}`",
+            item, name
+        ))),
+    }
+}
+
+/// Split a percent-encoded string on a separator into a value of the given shape, then percent-decode its items.
+///
+/// The properties of an object are written `key=value` when exploded, and as alternating keys and values otherwise.
+fn delimited(raw: &str, separator: &str, shape: Shape, explode: bool) -> Value {
+    let items = || {
+        raw.split(separator)
+            .filter(|_| !raw.is_empty())
+            .map(unescape)
+    };
+    match shape {
+        Shape::Primitive => string(&unescape(raw)),
+        Shape::Array => Value::Array(items().map(|item| string(&item)).collect()),
+        Shape::Object if explode => Value::Object(
+            raw.split(separator)
+                .filter(|_| !raw.is_empty())
+                .map(|item| {
+                    let (key, value) = item.split_once('=').unwrap_or((item, ""));
+                    (unescape(key), string(&unescape(value)))
+                })
+                .collect(),
+        ),
+        Shape::Object => {
+            let items: Vec<_> = items().collect();
+            Value::Object(
+                items
+                    .chunks(2)
+                    .map(|pair| {
+                        let value = pair.get(1).map_or("", String::as_str);
+                        (pair[0].clone(), string(value))
+                    })
+                    .collect(),
+            )
+        }
+    }
+}
+
+/// A value broken into the strings its style writes.
+enum Encoded {
+    Primitive(String),
+    Array(Vec<String>),
+    Object(Vec<(String, String)>),
+}
+
+impl Encoded {
+    /// Break a value into strings, `null` being written as an empty value. Values nested in arrays or objects cannot be
+    /// written by any style.
+    fn of(value: &Value) -> Result<Encoded, StyleError> {
+        Ok(match value {
+            Value::Array(items) => {
+                Encoded::Array(items.iter().map(primitive).collect::<Result<_, _>>()?)
+            }
+            Value::Object(properties) => Encoded::Object(
+                properties
+                    .iter()
+                    .map(|(key, value)| Ok((key.clone(), primitive(value)?)))
+                    .collect::<Result<_, StyleError>>()?,
+            ),
+            value => Encoded::Primitive(primitive(value)?),
+        })
+    }
+
+    /// Percent-encode the strings. Property names are always fully encoded.
+    fn escaped(self, allow_reserved: bool) -> Encoded {
+        match self {
+            Encoded::Primitive(value) => Encoded::Primitive(escape(&value, allow_reserved)),
+            Encoded::Array(items) => Encoded::Array(
+                items
+                    .iter()
+                    .map(|item| escape(item, allow_reserved))
+                    .collect(),
+            ),
+            Encoded::Object(properties) => Encoded::Object(
+                properties
+                    .iter()
+                    .map(|(key, value)| (escape(key, false), escape(value, allow_reserved)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// The string of a primitive value.
+fn primitive(value: &Value) -> Result<String, StyleError> {
+    match value {
+        Value::Null => Ok(String::new()),
+        Value::String(string) => Ok(string.clone()),
+        Value::Bool(_) | Value::Number(_) => Ok(value.to_string()),
+        Value::Array(_) | Value::Object(_) => Err(StyleError::new(
+            "nested arrays and objects cannot be written in a parameter",
+        )),
+    }
+}
+
+fn escape(value: &str, allow_reserved: bool) -> String {
+    let set = if allow_reserved { RESERVED } else { UNRESERVED };
+    utf8_percent_encode(value, set).to_string()
+}
+
+/// A `name=value` item of the `matrix` style, or a bare `name` for an empty value.
+fn named_value(name: &str, value: &str) -> String {
+    if value.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}={}", name, value)
+    }
+}
+
+/// The alternating keys and values of the properties of an object which is not exploded.
+fn flatten(properties: &[(String, String)]) -> Vec<&str> {
+    properties
+        .iter()
+        .flat_map(|(key, value)| [key.as_str(), value.as_str()])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn pairs(query: &[(&str, &str)]) -> Vec<(String, String)> {
+        query
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_decode() {
+        let decode =
+            |style: Style, explode, shape, raw| style.decode("id", explode, shape, raw).unwrap();
+        assert_eq!(
+            decode(Style::Simple, false, Shape::Primitive, "5"),
+            json!("5")
+        );
+        assert_eq!(
+            decode(Style::Simple, false, Shape::Array, "3,4,5"),
+            json!(["3", "4", "5"])
+        );
+        assert_eq!(
+            decode(
+                Style::Simple,
+                false,
+                Shape::Object,
+                "role,admin,firstName,Alex"
+            ),
+            json!({"role": "admin", "firstName": "Alex"})
+        );
+        assert_eq!(
+            decode(
+                Style::Simple,
+                true,
+                Shape::Object,
+                "role=admin,firstName=Alex"
+            ),
+            json!({"role": "admin", "firstName": "Alex"})
+        );
+        assert_eq!(
+            decode(Style::Label, true, Shape::Array, ".3.4.5"),
+            json!(["3", "4", "5"])
+        );
+        assert_eq!(
+            decode(Style::Label, false, Shape::Array, ".3,4,5"),
+            json!(["3", "4", "5"])
+        );
+        assert_eq!(
+            decode(Style::Matrix, false, Shape::Primitive, ";id=5"),
+            json!("5")
+        );
+        assert_eq!(
+            decode(Style::Matrix, true, Shape::Array, ";id=3;id=4;id=5"),
+            json!(["3", "4", "5"])
+        );
+        assert_eq!(
+            decode(
+                Style::Matrix,
+                true,
+                Shape::Object,
+                ";role=admin;firstName=Alex"
+            ),
+            json!({"role": "admin", "firstName": "Alex"})
+        );
+        assert_eq!(decode(Style::Simple, false, Shape::Array, ""), json!([]));
+        assert!(Style::Label
+            .decode("id", false, Shape::Primitive, "5")
+            .is_err());
+        assert!(Style::Matrix
+            .decode("id", false, Shape::Primitive, ";color=5")
+            .is_err());
+    }
+
+    #[test]
+    fn test_decode_pairs() {
+        let query = pairs(&[
+            ("id", "3"),
+            ("id", "4"),
+            ("list", "a|b"),
+            ("color[R]", "100"),
+            ("color[G]", "200"),
+        ]);
+        let decode = |style: Style, name, explode, shape| {
+            style.decode_pairs(name, explode, shape, &query).unwrap()
+        };
+        assert_eq!(
+            decode(Style::Form, "id", true, Shape::Array),
+            Some(json!(["3", "4"]))
+        );
+        assert_eq!(
+            decode(Style::Form, "id", false, Shape::Primitive),
+            Some(json!("3"))
+        );
+        assert_eq!(
+            decode(Style::PipeDelimited, "list", false, Shape::Array),
+            Some(json!(["a", "b"]))
+        );
+        assert_eq!(
+            decode(Style::DeepObject, "color", true, Shape::Object),
+            Some(json!({"R": "100", "G": "200"}))
+        );
+        assert_eq!(decode(Style::Form, "missing", true, Shape::Array), None);
+
+        let query = pairs(&[("role", "admin"), ("firstName", "Alex")]);
+        assert_eq!(
+            Style::Form
+                .decode_pairs("id", true, Shape::Object, &query)
+                .unwrap(),
+            Some(json!({"role": "admin", "firstName": "Alex"}))
+        );
+        let query = pairs(&[("id", "role,admin,firstName,Alex")]);
+        assert_eq!(
+            Style::Form
+                .decode_pairs("id", false, Shape::Object, &query)
+                .unwrap(),
+            Some(json!({"role": "admin", "firstName": "Alex"}))
+        );
+    }
+
+    #[test]
+    fn test_encode() {
+        let array = json!(["blue", "black", "brown"]);
+        let object = json!({"R": 100, "G": 200});
+        let encode =
+            |style: Style, explode, value: &Value| style.encode("color", explode, value).unwrap();
+        assert_eq!(encode(Style::Simple, false, &json!("blue")), "blue");
+        assert_eq!(encode(Style::Simple, false, &array), "blue,black,brown");
+        assert_eq!(encode(Style::Simple, false, &object), "G,200,R,100");
+        assert_eq!(encode(Style::Simple, true, &object), "G=200,R=100");
+        assert_eq!(encode(Style::Label, false, &array), ".blue,black,brown");
+        assert_eq!(encode(Style::Label, true, &array), ".blue.black.brown");
+        assert_eq!(encode(Style::Label, true, &object), ".G=200.R=100");
+        assert_eq!(encode(Style::Matrix, false, &json!("blue")), ";color=blue");
+        assert_eq!(encode(Style::Matrix, false, &Value::Null), ";color");
+        assert_eq!(
+            encode(Style::Matrix, false, &array),
+            ";color=blue,black,brown"
+        );
+        assert_eq!(
+            encode(Style::Matrix, true, &array),
+            ";color=blue;color=black;color=brown"
+        );
+        assert_eq!(encode(Style::Matrix, false, &object), ";color=G,200,R,100");
+        assert_eq!(encode(Style::Matrix, true, &object), ";G=200;R=100");
+        assert_eq!(
+            encode(Style::Simple, false, &json!(["a,b", "c d"])),
+            "a%2Cb,c%20d"
+        );
+        assert!(Style::Form.encode("color", false, &array).is_err());
+        assert!(Style::Simple.encode("color", false, &json!([[1]])).is_err());
+
+        // Decoding the encoded string gives the strings of the value back, escaped delimiters included.
+        let encoded = encode(Style::Matrix, true, &array);
+        assert_eq!(
+            Style::Matrix
+                .decode("color", true, Shape::Array, &encoded)
+                .unwrap(),
+            array
+        );
+        let escaped = json!(["a,b", "c d"]);
+        let encoded = encode(Style::Simple, false, &escaped);
+        assert_eq!(
+            Style::Simple
+                .decode("color", false, Shape::Array, &encoded)
+                .unwrap(),
+            escaped
+        );
+    }
+
+    #[test]
+    fn test_encode_pairs() {
+        let array = json!(["blue", "black"]);
+        let object = json!({"R": 100, "G": 200});
+        let encode = |style: Style, explode, value: &Value| {
+            let pairs = style.encode_pairs("color", explode, false, value).unwrap();
+            pairs
+                .iter()
+                .map(|(key, value)| format!("{}={}", key, value))
+                .collect::<Vec<_>>()
+                .join("&")
+        };
+        assert_eq!(encode(Style::Form, true, &json!("blue")), "color=blue");
+        assert_eq!(encode(Style::Form, true, &array), "color=blue&color=black");
+        assert_eq!(encode(Style::Form, false, &array), "color=blue,black");
+        assert_eq!(encode(Style::Form, true, &object), "G=200&R=100");
+        assert_eq!(encode(Style::Form, false, &object), "color=G,200,R,100");
+        assert_eq!(
+            encode(Style::SpaceDelimited, false, &array),
+            "color=blue%20black"
+        );
+        assert_eq!(
+            encode(Style::PipeDelimited, false, &array),
+            "color=blue|black"
+        );
+        assert_eq!(
+            encode(Style::DeepObject, true, &object),
+            "color[G]=200&color[R]=100"
+        );
+        assert!(Style::DeepObject
+            .encode_pairs("color", true, false, &array)
+            .is_err());
+
+        // `spaceDelimited` is left out, as its delimiter is the escaped space `%20`.
+        let escaped = json!(["a,b", "c d"]);
+        for style in [Style::Form, Style::PipeDelimited] {
+            let pairs = style.encode_pairs("color", false, false, &escaped).unwrap();
+            assert_eq!(
+                style
+                    .decode_pairs("color", false, Shape::Array, &pairs)
+                    .unwrap(),
+                Some(escaped.clone())
+            );
+        }
+
+        let url = json!("https://example.com/?a=1");
+        assert_eq!(
+            Style::Form.encode_pairs("next", true, false, &url).unwrap()[0].1,
+            "https%3A%2F%2Fexample.com%2F%3Fa%3D1"
+        );
+        assert_eq!(
+            Style::Form.encode_pairs("next", true, true, &url).unwrap()[0].1,
+            "https://example.com/?a=1"
+        );
+    }
+
+    #[test]
+    fn test_collection_format() {
+        let array = json!(["a b", "c"]);
+        let encode = |format: CollectionFormat| format.encode(&array).unwrap();
+        assert_eq!(encode(CollectionFormat::Csv), ["a%20b,c"]);
+        assert_eq!(encode(CollectionFormat::Ssv), ["a%20b%20c"]);
+        assert_eq!(encode(CollectionFormat::Tsv), ["a%20b%09c"]);
+        assert_eq!(encode(CollectionFormat::Pipes), ["a%20b|c"]);
+        assert_eq!(encode(CollectionFormat::Multi), ["a%20b", "c"]);
+
+        assert_eq!(
+            CollectionFormat::Pipes.decode(&["a|b"]),
+            Ok(json!(["a", "b"]))
+        );
+        assert_eq!(
+            CollectionFormat::Multi.decode(&["a", "b"]),
+            Ok(json!(["a", "b"]))
+        );
+        assert_eq!(CollectionFormat::Csv.decode(&[""]), Ok(json!([])));
+        assert!(CollectionFormat::Csv.decode(&["a", "b"]).is_err());
+        assert_eq!(
+            CollectionFormat::from_name("tsv"),
+            Some(CollectionFormat::Tsv)
+        );
+        assert_eq!(CollectionFormat::default().name(), "csv");
+    }
+}
@@ -0,0 +1,810 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::de::SIMPLE_FIELDS;
+use crate::pointer::join;
+use crate::v3::{self, Note};
+
+use super::schema::*;
+
+/// The media type assumed when neither the operation nor the document lists what it consumes or produces.
+const DEFAULT_MEDIA_TYPE: &str = "application/json";
+
+impl Swagger {
+    /// Convert the document to OpenAPI 3.0, with a note for each part which could not be converted.
+    ///
+    /// - `host`, `basePath` and `schemes` become `servers`;
+    /// - `definitions`, `parameters`, `responses` and `securityDefinitions` move into `components`, and the local references to them are rewritten;
+    /// - `body` and `formData` parameters become a `requestBody`, with a media type for each type the operation consumes;
+    /// - the schema of a response gets a media type for each type the operation produces.
+    ///
+    /// External references are kept as is, as the documents they point to are not converted.
+    pub fn to_v3(&self) -> (v3::OpenApi, Vec<Note>) {
+        let mut converter = Converter {
+            swagger: self,
+            notes: Vec::new(),
+        };
+        let consumes = media_types(self.consumes.as_ref());
+        let produces = media_types(self.produces.as_ref());
+
+        let mut components = v3::Components {
+            schemas: self.definitions.as_ref().map(|definitions| {
+                definitions
+                    .iter()
+                    .map(|(name, schema)| {
+                        let pointer = join("/definitions", name);
+                        (name.clone(), converter.schema(schema, &pointer))
+                    })
+                    .collect()
+            }),
+            responses: self.responses.as_ref().map(|responses| {
+                responses
+                    .iter()
+                    .map(|(name, response)| {
+                        let pointer = join("/responses", name);
+                        let response = converter.response(response, &produces, &pointer);
+                        (name.clone(), v3::RefOrObject::Object(response))
+                    })
+                    .collect()
+            }),
+            security_schemes: self.security_definitions.as_ref().map(|schemes| {
+                schemes
+                    .iter()
+                    .map(|(name, scheme)| {
+                        let scheme = security_scheme(scheme);
+                        (name.clone(), v3::RefOrObject::Object(scheme))
+                    })
+                    .collect()
+            }),
+            ..Default::default()
+        };
+        for (name, parameter) in self.parameters.iter().flatten() {
+            let pointer = join("/parameters", name);
+            match parameter.r#in {
+                ParamInEnum::Body => {
+                    let body = converter.body(parameter, &consumes, &pointer);
+                    components
+                        .request_bodies
+                        .get_or_insert_with(BTreeMap::new)
+                        .insert(name.clone(), v3::RefOrObject::Object(body));
+                }
+                // A form field is only meaningful inside the schema of a request body, which is built for each operation.
+                ParamInEnum::FormData => {}
+                _ => {
+                    if let Some(parameter) = converter.parameter(parameter, &pointer) {
+                        components
+                            .parameters
+                            .get_or_insert_with(BTreeMap::new)
+                            .insert(name.clone(), v3::RefOrObject::Object(parameter));
+                    }
+                }
+            }
+        }
+
+        let openapi = v3::OpenApi {
+            openapi: "3.0.3".to_string(),
+            info: info(&self.info),
+            servers: self.servers(),
+            paths: self
+                .paths
+                .iter()
+                .map(|(path, item)| {
+                    let pointer = join("/paths", path);
+                    (path.clone(), converter.path_item(item, &pointer))
+                })
+                .collect(),
+            components: Some(components).filter(|components| components != &Default::default()),
+            security: self.security.as_ref().map(|security| {
+                security
+                    .iter()
+                    .map(|requirement| requirement.0.clone())
+                    .collect()
+            }),
+            tags: self
+                .tags
+                .as_ref()
+                .map(|tags| tags.iter().map(tag).collect()),
+            external_docs: self.external_docs.as_ref().map(external_doc),
+            extensions: self.extensions.clone(),
+        };
+        (openapi, converter.notes)
+    }
+
+    /// A server for each scheme, serving the API at `host` and `basePath`.
+    fn servers(&self) -> Option<Vec<v3::Server>> {
+        if self.host.is_none() && self.base_path.is_none() {
+            return None;
+        }
+        let host = self.host.as_deref().unwrap_or_default();
+        let base_path = self.base_path.as_deref().unwrap_or_default();
+        let url = |scheme: &str| v3::Server {
+            url: format!("{}//{}{}", scheme, host, base_path),
+            description: None,
+            variables: None,
+            extensions: Default::default(),
+        };
+        Some(match (&self.schemes, &self.host) {
+            (Some(schemes), Some(_)) if !schemes.is_empty() => schemes
+                .iter()
+                .map(|scheme| url(&format!("{}:", scheme_name(scheme))))
+                .collect(),
+            (_, Some(_)) => vec![url("")],
+            (_, None) => vec![v3::Server {
+                url: base_path.to_string(),
+                ..url("")
+            }],
+        })
+    }
+}
+
+struct Converter<'a> {
+    swagger: &'a Swagger,
+    notes: Vec<Note>,
+}
+
+impl<'s> Converter<'s> {
+    fn note(&mut self, pointer: &str, message: impl Into<String>) {
+        self.notes.push(Note {
+            pointer: pointer.to_string(),
+            message: message.into(),
+        });
+    }
+
+    /// Rewrite a local reference to the definitions, parameters or responses of the document to its components.
+    ///
+    /// External references are kept as is, as the documents they point to are not converted.
+    fn reference(&mut self, reference: &str, pointer: &str) -> String {
+        let Some(fragment) = reference.strip_prefix('#') else {
+            self.note(
+                pointer,
+                format!(
+                    "the external reference `{}` was kept as is, the document it points to is not converted",
+                    reference
+                ),
+            );
+            return reference.to_string();
+        };
+        let fragment = if let Some(name) = fragment.strip_prefix("/definitions/") {
+            format!("/components/schemas/{}", name)
+        } else if let Some(name) = fragment.strip_prefix("/parameters/") {
+            let parameter = self
+                .swagger
+                .parameters
+                .as_ref()
+                .and_then(|parameters| parameters.get(&crate::pointer::unescape(name)));
+            match parameter {
+                Some(Parameter {
+                    r#in: ParamInEnum::Body,
+                    ..
+                }) => format!("/components/requestBodies/{}", name),
+                _ => format!("/components/parameters/{}", name),
+            }
+        } else if let Some(name) = fragment.strip_prefix("/responses/") {
+            format!("/components/responses/{}", name)
+        } else {
+            fragment.to_string()
+        };
+        format!("#{}", fragment)
+    }
+
+    fn reference_to<T>(&mut self, reference: &Reference, pointer: &str) -> v3::RefOrObject<T> {
+        v3::RefOrObject::Ref(v3::Reference {
+            reference: self.reference(&reference.reference, pointer),
+        })
+    }
+
+    /// The parameter a local reference points to, external ones are unknown.
+    fn resolve<'a>(&self, parameter: &'a ParameterOrRef) -> Option<&'a Parameter>
+    where
+        's: 'a,
+    {
+        let swagger: &'a Swagger = self.swagger;
+        swagger.resolve_parameter(parameter).ok()
+    }
+
+    fn path_item(&mut self, item: &PathItem, pointer: &str) -> v3::PathItem {
+        let shared = item.parameters.as_deref().unwrap_or_default();
+        let mut operation = |operation: &Option<Operation>, method: &str| {
+            operation
+                .as_ref()
+                .map(|operation| self.operation(operation, shared, &join(pointer, method)))
+        };
+        let get = operation(&item.get, "get");
+        let put = operation(&item.put, "put");
+        let post = operation(&item.post, "post");
+        let delete = operation(&item.delete, "delete");
+        let options = operation(&item.options, "options");
+        let head = operation(&item.head, "head");
+        let patch = operation(&item.patch, "patch");
+        let parameters_pointer = join(pointer, "parameters");
+        let mut parameters = Vec::new();
+        for (index, parameter) in shared.iter().enumerate() {
+            if !self.is_body(parameter) {
+                let pointer = join(&parameters_pointer, &index.to_string());
+                parameters.extend(self.parameter_or_ref(parameter, &pointer));
+            }
+        }
+        v3::PathItem {
+            reference: item
+                .reference
+                .as_deref()
+                .map(|reference| self.reference(reference, pointer)),
+            get,
+            put,
+            post,
+            delete,
+            options,
+            head,
+            patch,
+            parameters: Some(parameters).filter(|parameters| !parameters.is_empty()),
+            ..Default::default()
+        }
+    }
+
+    /// Whether a parameter is part of the request body, as a `body` or `formData` parameter.
+    fn is_body(&self, parameter: &ParameterOrRef) -> bool {
+        matches!(
+            self.resolve(parameter).map(|parameter| &parameter.r#in),
+            Some(ParamInEnum::Body | ParamInEnum::FormData)
+        )
+    }
+
+    fn parameter_or_ref(
+        &mut self,
+        parameter: &ParameterOrRef,
+        pointer: &str,
+    ) -> Option<v3::RefOrObject<v3::Parameter>> {
+        match parameter {
+            ParameterOrRef::Ref(reference) => Some(self.reference_to(reference, pointer)),
+            ParameterOrRef::Parameter(parameter) => self
+                .parameter(parameter, pointer)
+                .map(v3::RefOrObject::Object),
+        }
+    }
+
+    /// Convert an operation, which also takes the request body parameters of its path item that it does not override.
+    fn operation(
+        &mut self,
+        operation: &Operation,
+        shared: &[ParameterOrRef],
+        pointer: &str,
+    ) -> v3::Operation {
+        let own = operation.parameters.as_deref().unwrap_or_default();
+        let key = |parameter: &ParameterOrRef| {
+            self.resolve(parameter)
+                .map(|parameter| (parameter.name.clone(), parameter.r#in.clone()))
+        };
+        let overridden: Vec<_> = own.iter().filter_map(key).collect();
+        // The shared parameters are defined by the path item, whose pointer is the parent of the operation's.
+        let (item_pointer, _) = pointer.rsplit_once('/').unwrap_or_default();
+        let inherited = shared
+            .iter()
+            .enumerate()
+            .filter(|(_, parameter)| {
+                self.is_body(parameter)
+                    && key(parameter).is_none_or(|key| !overridden.contains(&key))
+            })
+            .map(|(index, parameter)| {
+                let pointer = join(&join(item_pointer, "parameters"), &index.to_string());
+                (parameter, pointer)
+            });
+        let own = own.iter().enumerate().map(|(index, parameter)| {
+            let pointer = join(&join(pointer, "parameters"), &index.to_string());
+            (parameter, pointer)
+        });
+        let all: Vec<_> = inherited.chain(own).collect();
+
+        let mut parameters = Vec::new();
+        let mut body = None;
+        let mut form = Vec::new();
+        for (parameter, pointer) in &all {
+            match self.resolve(parameter) {
+                Some(resolved) if resolved.r#in == ParamInEnum::Body => {
+                    body = Some((*parameter, pointer))
+                }
+                Some(resolved) if resolved.r#in == ParamInEnum::FormData => {
+                    form.push((resolved, pointer.as_str()))
+                }
+                _ => parameters.extend(self.parameter_or_ref(parameter, pointer)),
+            }
+        }
+
+        let consumes = media_types(
+            operation
+                .consumes
+                .as_ref()
+                .or(self.swagger.consumes.as_ref()),
+        );
+        let produces = media_types(
+            operation
+                .produces
+                .as_ref()
+                .or(self.swagger.produces.as_ref()),
+        );
+        let request_body = match body {
+            Some((ParameterOrRef::Ref(reference), pointer)) => {
+                Some(self.reference_to(reference, pointer))
+            }
+            Some((ParameterOrRef::Parameter(parameter), pointer)) => Some(v3::RefOrObject::Object(
+                self.body(parameter, &consumes, pointer),
+            )),
+            None if !form.is_empty() => Some(v3::RefOrObject::Object(self.form(&form, &consumes))),
+            None => None,
+        };
+
+        v3::Operation {
+            tags: operation.tags.clone(),
+            summary: operation.summary.clone(),
+            description: operation.description.clone(),
+            external_docs: operation.external_docs.as_ref().map(external_doc),
+            operation_id: operation.operation_id.clone(),
+            parameters: Some(parameters).filter(|parameters| !parameters.is_empty()),
+            request_body,
+            responses: operation
+                .responses
+                .iter()
+                .map(|(status, response)| {
+                    let pointer = join(&join(pointer, "responses"), status);
+                    let response = match response {
+                        ResponseOrRef::Ref(reference) => self.reference_to(reference, &pointer),
+                        ResponseOrRef::Response(response) => {
+                            v3::RefOrObject::Object(self.response(response, &produces, &pointer))
+                        }
+                    };
+                    (status.clone(), response)
+                })
+                .collect(),
+            callbacks: None,
+            deprecated: operation.deprecated,
+            security: operation.security.as_ref().map(|security| {
+                security
+                    .iter()
+                    .map(|requirement| requirement.0.clone())
+                    .collect()
+            }),
+            servers: None,
+            extensions: operation.extensions.clone(),
+        }
+    }
+
+    /// Convert a parameter outside of the request body, `body` and `formData` parameters having no location in
+    /// OpenAPI 3.0.
+    fn parameter(&mut self, parameter: &Parameter, pointer: &str) -> Option<v3::Parameter> {
+        let location = match parameter.r#in {
+            ParamInEnum::Query => "query",
+            ParamInEnum::Header => "header",
+            ParamInEnum::Path => "path",
+            ParamInEnum::FormData | ParamInEnum::Body => {
+                self.note(
+                    pointer,
+                    "a request body parameter outside of an operation has no equivalent in OpenAPI 3.0 and was dropped",
+                );
+                return None;
+            }
+        };
+        let array = parameter.r#type == Some(ParameterType::Array);
+        let query = parameter.r#in == ParamInEnum::Query;
+        let (style, explode) = match parameter.collection_format.as_deref() {
+            Some("multi") if query => (Some("form"), Some(true)),
+            Some("ssv") if query => (Some("spaceDelimited"), Some(false)),
+            Some("pipes") if query => (Some("pipeDelimited"), Some(false)),
+            // Comma separated values are the default of path and header parameters, but not of query ones.
+            Some("csv") | None if array && query => (Some("form"), Some(false)),
+            Some("tsv") => {
+                self.note(
+                    &join(pointer, "collectionFormat"),
+                    "tab separated values have no equivalent in OpenAPI 3.0, the default style is used",
+                );
+                (None, None)
+            }
+            Some(format @ ("multi" | "ssv" | "pipes")) => {
+                self.note(
+                    &join(pointer, "collectionFormat"),
+                    format!(
+                        "`{}` is only allowed on query parameters in OpenAPI 3.0, the default style is used",
+                        format
+                    ),
+                );
+                (None, None)
+            }
+            _ => (None, None),
+        };
+        Some(v3::Parameter {
+            name: parameter.name.clone(),
+            r#in: location.to_string(),
+            description: parameter.description.clone(),
+            required: parameter.required,
+            allow_empty_value: parameter.allow_empty_value,
+            style: style.map(str::to_string),
+            explode,
+            schema: self
+                .simple_schema(parameter, pointer)
+                .map(v3::RefOrObject::Object),
+            extensions: parameter.extensions.clone(),
+            ..Default::default()
+        })
+    }
+
+    fn body(
+        &mut self,
+        parameter: &Parameter,
+        consumes: &[String],
+        pointer: &str,
+    ) -> v3::RequestBody {
+        let schema = parameter
+            .schema
+            .as_ref()
+            .map(|schema| self.schema(schema, &join(pointer, "schema")));
+        v3::RequestBody {
+            description: parameter.description.clone(),
+            content: consumes
+                .iter()
+                .map(|media_type| {
+                    let media = v3::Media {
+                        schema: schema.clone(),
+                        ..Default::default()
+                    };
+                    (media_type.clone(), media)
+                })
+                .collect(),
+            required: parameter.required,
+            extensions: parameter.extensions.clone(),
+        }
+    }
+
+    /// A request body holding the form fields as the properties of an object.
+    fn form(&mut self, fields: &[(&Parameter, &str)], consumes: &[String]) -> v3::RequestBody {
+        let mut properties = BTreeMap::new();
+        for (field, pointer) in fields {
+            if let Some(mut schema) = self.simple_schema(*field, pointer) {
+                schema.description = field.description.clone();
+                properties.insert(field.name.clone(), v3::RefOrObject::Object(schema));
+            }
+        }
+        let schema = v3::Schema {
+            r#type: Some("object".to_string()),
+            properties: Some(properties),
+            required: Some(
+                fields
+                    .iter()
+                    .filter(|(field, _)| field.required == Some(true))
+                    .map(|(field, _)| field.name.clone())
+                    .collect::<Vec<_>>(),
+            )
+            .filter(|required| !required.is_empty()),
+            ..Default::default()
+        };
+        let mut media_types: Vec<&str> = consumes
+            .iter()
+            .map(String::as_str)
+            .filter(|media_type| {
+                media_type.starts_with("multipart/form-data")
+                    || media_type.starts_with("application/x-www-form-urlencoded")
+            })
+            .collect();
+        if media_types.is_empty() {
+            let file = fields
+                .iter()
+                .any(|(field, _)| field.r#type == Some(ParameterType::File));
+            media_types.push(if file {
+                "multipart/form-data"
+            } else {
+                "application/x-www-form-urlencoded"
+            });
+        }
+        v3::RequestBody {
+            description: None,
+            content: media_types
+                .into_iter()
+                .map(|media_type| {
+                    let media = v3::Media {
+                        schema: Some(v3::RefOrObject::Object(schema.clone())),
+                        ..Default::default()
+                    };
+                    (media_type.to_string(), media)
+                })
+                .collect(),
+            required: Some(fields.iter().any(|(field, _)| field.required == Some(true)))
+                .filter(|required| *required),
+            extensions: Default::default(),
+        }
+    }
+
+    fn response(
+        &mut self,
+        response: &Response,
+        produces: &[String],
+        pointer: &str,
+    ) -> v3::Response {
+        let schema = response
+            .schema
+            .as_ref()
+            .map(|schema| self.schema(schema, &join(pointer, "schema")));
+        let examples = response.examples.as_ref();
+        let mut media_types: Vec<String> = produces.to_vec();
+        // An example may be given for a type the operation does not list.
+        for media_type in examples.into_iter().flat_map(BTreeMap::keys) {
+            if !media_types.contains(media_type) {
+                media_types.push(media_type.clone());
+            }
+        }
+        let content: BTreeMap<String, v3::Media> = media_types
+            .into_iter()
+            .filter_map(|media_type| {
+                let example = examples
+                    .and_then(|examples| examples.get(&media_type))
+                    .cloned();
+                if schema.is_none() && example.is_none() {
+                    return None;
+                }
+                let media = v3::Media {
+                    schema: schema.clone(),
+                    example,
+                    ..Default::default()
+                };
+                Some((media_type, media))
+            })
+            .collect();
+        v3::Response {
+            description: response.description.clone(),
+            headers: response.headers.as_ref().map(|headers| {
+                headers
+                    .iter()
+                    .map(|(name, header)| {
+                        let pointer = join(&join(pointer, "headers"), name);
+                        let header = v3::Header {
+                            description: header.description.clone(),
+                            schema: self
+                                .simple_schema(header, &pointer)
+                                .map(v3::RefOrObject::Object),
+                            extensions: header.extensions.clone(),
+                            ..Default::default()
+                        };
+                        (name.clone(), v3::RefOrObject::Object(header))
+                    })
+                    .collect()
+            }),
+            content: Some(content).filter(|content| !content.is_empty()),
+            links: None,
+            extensions: response.extensions.clone(),
+        }
+    }
+
+    fn schema(&mut self, schema: &Schema, pointer: &str) -> v3::RefOrObject<v3::Schema> {
+        if let Some(reference) = &schema.reference {
+            return v3::RefOrObject::Ref(v3::Reference {
+                reference: self.reference(reference, pointer),
+            });
+        }
+        let (r#type, format) = match schema.r#type.as_deref() {
+            Some("file") => (Some("string".to_string()), Some("binary".to_string())),
+            _ => (schema.r#type.clone(), schema.format.clone()),
+        };
+        // The usual extension for what OpenAPI 3.0 calls `nullable`, the other extensions are carried over.
+        let nullable = schema.extensions.get("x-nullable").and_then(Value::as_bool);
+        let extensions = schema
+            .extensions
+            .iter()
+            .filter(|(key, _)| key.starts_with("x-") && key.as_str() != "x-nullable")
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect::<serde_json::Map<_, _>>();
+        v3::RefOrObject::Object(v3::Schema {
+            title: schema.title.clone(),
+            r#enum: schema.r#enum.clone(),
+            multiple_of: schema.multiple_of.clone(),
+            maximum: schema.maximum.clone(),
+            exclusive_maximum: schema.exclusive_maximum,
+            minimum: schema.minimum.clone(),
+            exclusive_minimum: schema.exclusive_minimum,
+            max_length: schema.max_length.clone(),
+            min_length: schema.min_length.clone(),
+            pattern: schema.pattern.clone(),
+            max_items: schema.max_items.clone(),
+            min_items: schema.min_items.clone(),
+            unique_items: schema.unique_items,
+            items: schema
+                .items
+                .as_deref()
+                .map(|items| Box::new(self.schema(items, &join(pointer, "items")))),
+            properties: schema.properties.as_ref().map(|properties| {
+                properties
+                    .iter()
+                    .map(|(name, property)| {
+                        let pointer = join(&join(pointer, "properties"), name);
+                        (name.clone(), self.schema(property, &pointer))
+                    })
+                    .collect()
+            }),
+            required: schema.required.clone(),
+            r#type,
+            all_of: schema.all_of.as_ref().map(|all_of| {
+                all_of
+                    .iter()
+                    .enumerate()
+                    .map(|(index, schema)| {
+                        let pointer = join(&join(pointer, "allOf"), &index.to_string());
+                        Box::new(self.schema(schema, &pointer))
+                    })
+                    .collect()
+            }),
+            description: schema.description.clone(),
+            format,
+            default: schema.default.clone(),
+            additional_properties: schema
+                .additional_properties
+                .as_ref()
+                .and_then(|additional| {
+                    self.additional_properties(additional, &join(pointer, "additionalProperties"))
+                }),
+            nullable,
+            discriminator: schema
+                .discriminator
+                .as_ref()
+                .map(|property_name| v3::Discriminator {
+                    property_name: property_name.clone(),
+                    mapping: None,
+                }),
+            read_only: schema.read_only,
+            xml: schema.xml.as_ref().map(|xml| v3::XML {
+                name: xml.name.clone(),
+                namespace: xml.namespace.clone(),
+                prefix: xml.prefix.clone(),
+                attribute: xml.attribute,
+                wrapped: xml.wrapped,
+            }),
+            external_docs: schema.external_docs.as_ref().map(external_doc),
+            example: schema.example.clone(),
+            extensions: serde_json::from_value(Value::Object(extensions)).unwrap_or_default(),
+            ..Default::default()
+        })
+    }
+
+    /// Convert the raw `additionalProperties` of a schema, `None` with a note when it is neither a boolean nor a schema.
+    fn additional_properties(
+        &mut self,
+        additional: &Value,
+        pointer: &str,
+    ) -> Option<v3::AdditionalProperties> {
+        if let Value::Bool(allowed) = additional {
+            return Some(v3::AdditionalProperties::Bool(*allowed));
+        }
+        match serde_json::from_value::<Schema>(additional.clone()) {
+            Ok(schema) => Some(v3::AdditionalProperties::Schema(Box::new(
+                self.schema(&schema, pointer),
+            ))),
+            Err(_) => {
+                self.note(pointer, "is neither a boolean nor a schema and is dropped");
+                None
+            }
+        }
+    }
+
+    /// The schema of a parameter, items or header, which describe a primitive value or an array of them, `None` with a
+    /// note when their fields do not make a schema.
+    fn simple_schema<T: Serialize>(&mut self, item: &T, pointer: &str) -> Option<v3::Schema> {
+        fn simple(value: Value) -> Value {
+            let Value::Object(mut map) = value else {
+                return value;
+            };
+            map.retain(|key, _| SIMPLE_FIELDS.contains(&key.as_str()));
+            if map.get("type") == Some(&Value::from("file")) {
+                map.insert("type".to_string(), Value::from("string"));
+                map.insert("format".to_string(), Value::from("binary"));
+            }
+            if let Some(items) = map.remove("items") {
+                map.insert("items".to_string(), simple(items));
+            }
+            Value::Object(map)
+        }
+        match serde_json::to_value(item)
+            .map(simple)
+            .and_then(serde_json::from_value)
+        {
+            Ok(schema) => Some(schema),
+            Err(e) => {
+                self.note(
+                    pointer,
+                    format!("the schema could not be converted and was dropped: {}", e),
+                );
+                None
+            }
+        }
+    }
+}
+
+fn media_types(types: Option<&Vec<String>>) -> Vec<String> {
+    match types {
+        Some(types) if !types.is_empty() => types.clone(),
+        _ => vec![DEFAULT_MEDIA_TYPE.to_string()],
+    }
+}
+
+fn scheme_name(scheme: &TransferProtocol) -> &'static str {
+    match scheme {
+        TransferProtocol::Http => "http",
+        TransferProtocol::Https => "https",
+        TransferProtocol::Ws => "ws",
+        TransferProtocol::Wss => "wss",
+    }
+}
+
+fn info(info: &Info) -> v3::Info {
+    v3::Info {
+        title: info.title.clone(),
+        description: info.description.clone(),
+        terms_of_service: info.terms_of_service.clone(),
+        contact: info.contact.as_ref().map(|contact| v3::Contact {
+            name: contact.name.clone(),
+            url: contact.url.clone(),
+            email: contact.email.clone(),
+            extensions: contact.extensions.clone(),
+        }),
+        license: info.license.as_ref().map(|license| v3::License {
+            name: license.name.clone(),
+            url: license.url.clone(),
+            extensions: license.extensions.clone(),
+        }),
+        version: info.version.clone(),
+        extensions: info.extensions.clone(),
+    }
+}
+
+fn tag(tag: &Tag) -> v3::Tag {
+    v3::Tag {
+        name: tag.name.clone(),
+        description: tag.description.clone(),
+        external_docs: tag.external_doc.as_ref().map(external_doc),
+        extensions: Default::default(),
+    }
+}
+
+fn external_doc(doc: &ExternalDoc) -> v3::ExternalDoc {
+    v3::ExternalDoc {
+        description: doc.description.clone(),
+        url: doc.url.clone(),
+        extensions: Default::default(),
+    }
+}
+
+fn security_scheme(scheme: &SecurityScheme) -> v3::SecurityScheme {
+    let flow = || v3::OAuthFlow {
+        authorization_url: scheme.authorization_url.clone(),
+        token_url: scheme.token_url.clone(),
+        refresh_url: None,
+        scopes: scheme.scopes.clone().unwrap_or_default(),
+    };
+    let (r#type, flows) = match scheme.r#type {
+        SecuritySchemeType::Basic => (v3::SecuritySchemeType::Http, None),
+        SecuritySchemeType::ApiKey => (v3::SecuritySchemeType::ApiKey, None),
+        SecuritySchemeType::Oauth2 => {
+            let mut flows = v3::OAuthFlows::default();
+            match scheme.flow {
+                Some(Flow::Implicit) => flows.implicit = Some(flow()),
+                Some(Flow::Password) => flows.password = Some(flow()),
+                Some(Flow::Application) => flows.client_credentials = Some(flow()),
+                Some(Flow::AccessCode) => flows.authorization_code = Some(flow()),
+                None => {}
+            }
+            (v3::SecuritySchemeType::Oauth2, Some(flows))
+        }
+    };
+    v3::SecurityScheme {
+        r#type,
+        description: scheme.description.clone(),
+        name: scheme.name.clone(),
+        r#in: scheme.r#in.as_ref().map(|location| {
+            match location {
+                InEnum::Query => "query",
+                InEnum::Header => "header",
+            }
+            .to_string()
+        }),
+        scheme: (scheme.r#type == SecuritySchemeType::Basic).then(|| "basic".to_string()),
+        bearer_format: None,
+        flows,
+        open_id_connect_url: None,
+        extensions: scheme.extensions.clone(),
+    }
+}
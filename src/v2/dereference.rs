@@ -0,0 +1,155 @@
+use crate::error::Error;
+use crate::resolve::{find, mismatched, CycleStrategy, Inlining};
+
+use super::schema::*;
+use super::visit::{walk_parameter, walk_path_item, walk_response, walk_schema, VisitMut, Walk};
+
+impl Swagger {
+    /// Replace every local reference with a copy of the object it points to.
+    ///
+    /// A reference back into an object being inlined, as in a recursive schema, is left in place or fails depending on `cycles`.
+    pub fn dereference(&mut self, cycles: CycleStrategy) -> Result<(), Error> {
+        let source = self.clone();
+        self.walk(&mut Dereferencer {
+            source: &source,
+            inlining: Inlining::new(cycles),
+        })
+    }
+}
+
+struct Dereferencer<'a> {
+    source: &'a Swagger,
+    inlining: Inlining,
+}
+
+impl VisitMut for Dereferencer<'_> {
+    fn visit_schema(&mut self, schema: &mut Schema) -> Result<(), Error> {
+        let Some(reference) = schema.reference.clone() else {
+            return walk_schema(self, schema);
+        };
+        if !self.inlining.enter(&reference)? {
+            return Ok(());
+        }
+        *schema = self.source.resolve_schema(schema)?.clone();
+        walk_schema(self, schema)?;
+        self.inlining.leave();
+        Ok(())
+    }
+
+    fn visit_parameter(&mut self, parameter: &mut ParameterOrRef) -> Result<(), Error> {
+        let reference = match parameter {
+            ParameterOrRef::Ref(Reference { reference }) => reference.clone(),
+            ParameterOrRef::Parameter(_) => return walk_parameter(self, parameter),
+        };
+        if !self.inlining.enter(&reference)? {
+            return Ok(());
+        }
+        *parameter = ParameterOrRef::Parameter(self.source.resolve_parameter(parameter)?.clone());
+        walk_parameter(self, parameter)?;
+        self.inlining.leave();
+        Ok(())
+    }
+
+    fn visit_response(&mut self, response: &mut ResponseOrRef) -> Result<(), Error> {
+        let reference = match response {
+            ResponseOrRef::Ref(Reference { reference }) => reference.clone(),
+            ResponseOrRef::Response(_) => return walk_response(self, response),
+        };
+        if !self.inlining.enter(&reference)? {
+            return Ok(());
+        }
+        *response = ResponseOrRef::Response(self.source.resolve_response(response)?.clone());
+        walk_response(self, response)?;
+        self.inlining.leave();
+        Ok(())
+    }
+
+    fn visit_path_item(&mut self, item: &mut PathItem) -> Result<(), Error> {
+        let Some(reference) = item.reference.clone() else {
+            return walk_path_item(self, item);
+        };
+        if !self.inlining.enter(&reference)? {
+            return Ok(());
+        }
+        *item = find(self.source, &reference)?
+            .as_any()
+            .downcast_ref::<PathItem>()
+            .ok_or_else(|| mismatched::<PathItem>(&reference))?
+            .clone();
+        walk_path_item(self, item)?;
+        self.inlining.leave();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn swagger() -> Swagger {
+        serde_json::from_value(json!({
+            "swagger": "2.0",
+            "info": {"title": "pets", "version": "1.0.0"},
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "parameters": [{"$ref": "#/parameters/limit"}],
+                        "responses": {
+                            "200": {
+                                "description": "pets",
+                                "schema": {"type": "array", "items": {"$ref": "#/definitions/Pet"}}
+                            },
+                            "default": {"$ref": "#/responses/Error"}
+                        }
+                    }
+                }
+            },
+            "definitions": {
+                "Pet": {
+                    "type": "object",
+                    "properties": {
+                        "name": {"type": "string"},
+                        "children": {"type": "array", "items": {"$ref": "#/definitions/Pet"}}
+                    }
+                },
+                "Error": {"type": "object", "properties": {"code": {"type": "integer"}}}
+            },
+            "parameters": {
+                "limit": {"name": "limit", "in": "query", "type": "integer"}
+            },
+            "responses": {
+                "Error": {"description": "error", "schema": {"$ref": "#/definitions/Error"}}
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_dereference() {
+        let mut swagger = swagger();
+        swagger.dereference(CycleStrategy::Keep).unwrap();
+        let get = serde_json::to_value(swagger.paths["/pets"].get.as_ref().unwrap()).unwrap();
+        assert_eq!(get["parameters"][0]["name"], "limit");
+        assert_eq!(
+            get["responses"]["default"]["schema"]["properties"]["code"],
+            json!({"type": "integer"})
+        );
+        let pet = &get["responses"]["200"]["schema"]["items"];
+        assert_eq!(pet["properties"]["name"], json!({"type": "string"}));
+        assert_eq!(
+            pet["properties"]["children"]["items"],
+            json!({"$ref": "#/definitions/Pet"})
+        );
+    }
+
+    #[test]
+    fn test_dereference_cycle_fails() {
+        match swagger().dereference(CycleStrategy::Fail) {
+            Err(Error::CyclicReference { chain }) => {
+                assert_eq!(chain, vec!["#/definitions/Pet", "#/definitions/Pet"])
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+}
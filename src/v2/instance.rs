@@ -0,0 +1,626 @@
+use serde_json::{Number, Value};
+
+use crate::instance::{error, Direction, InstanceError, Keywords};
+use crate::pointer::{join, split_reference};
+use crate::style::CollectionFormat;
+
+use super::schema::*;
+
+impl Swagger {
+    /// Check an instance, such as the payload of a request or a response, against a schema of the document.
+    ///
+    /// Every broken keyword is reported, an empty list means the instance is valid. `$ref`s are followed through the
+    /// document, and `readOnly` properties are forbidden in a [`Direction::Request`]. A schema with `x-nullable: true`
+    /// accepts `null`.
+    pub fn validate_instance(
+        &self,
+        schema: &Schema,
+        instance: &Value,
+        direction: Direction,
+    ) -> Vec<InstanceError> {
+        let mut validator = InstanceValidator {
+            swagger: self,
+            direction,
+            stack: Vec::new(),
+        };
+        let mut errors = Vec::new();
+        validator.schema(schema, instance, "", "", &mut errors);
+        errors
+    }
+}
+
+impl Parameter {
+    /// Check the raw values of a parameter sent outside of the body, as read from a query, a header, a path or a form.
+    ///
+    /// Each value is converted to the `type` of the parameter, arrays being split according to their
+    /// `collectionFormat`. A parameter sent several times, such as `?tag=a&tag=b`, has several values, which is only
+    /// allowed for the `multi` format. An empty slice means the parameter was not sent.
+    ///
+    /// ```
+    /// # use openapi_schema::v2::Parameter;
+    /// # fn example(limit: &Parameter) {
+    /// for error in limit.validate_value(&["25"]) {
+    ///     println!("{}", error);
+    /// }
+    /// # }
+    /// ```
+    pub fn validate_value(&self, values: &[&str]) -> Vec<InstanceError> {
+        let mut errors = Vec::new();
+        if self.r#in == ParamInEnum::Body {
+            error(
+                &mut errors,
+                "",
+                "",
+                "in",
+                format!(
+                    "`{}` is a body parameter, its payload is checked against its schema",
+                    self.name
+                ),
+            );
+            return errors;
+        }
+        if values.is_empty() {
+            if self.required == Some(true) {
+                error(
+                    &mut errors,
+                    "",
+                    "",
+                    "required",
+                    format!("the parameter `{}` is required", self.name),
+                );
+            }
+            return errors;
+        }
+        let empty_allowed = self.allow_empty_value == Some(true)
+            || !matches!(self.r#in, ParamInEnum::Query | ParamInEnum::FormData);
+        if !empty_allowed && values.iter().any(|value| value.is_empty()) {
+            error(
+                &mut errors,
+                "",
+                "",
+                "allowEmptyValue",
+                format!("the parameter `{}` must not be empty", self.name),
+            );
+            return errors;
+        }
+
+        let r#type = match &self.r#type {
+            Some(ParameterType::Array) => "array",
+            Some(ParameterType::String) => "string",
+            Some(ParameterType::Number) => "number",
+            Some(ParameterType::Integer) => "integer",
+            Some(ParameterType::Boolean) => "boolean",
+            Some(ParameterType::File) | None => return errors,
+        };
+        let value = if r#type == "array" {
+            let format = collection_format(self.collection_format.as_deref());
+            let raw: Vec<&str> = match (format, values) {
+                (CollectionFormat::Multi, values) => values.to_vec(),
+                (format, [value]) => format.split(value),
+                (format, values) => {
+                    error(
+                        &mut errors,
+                        "",
+                        "",
+                        "collectionFormat",
+                        format!(
+                            "expected a single `{}` value, found {}",
+                            format,
+                            values.len()
+                        ),
+                    );
+                    return errors;
+                }
+            };
+            match &self.items {
+                Some(items) => {
+                    let items_path = join("", "items");
+                    let mut decoded = Vec::new();
+                    for (index, raw) in raw.into_iter().enumerate() {
+                        let instance_path = join("", &index.to_string());
+                        decoded.push(decode_items(
+                            items,
+                            raw,
+                            &instance_path,
+                            &items_path,
+                            &mut errors,
+                        ));
+                    }
+                    Value::Array(decoded)
+                }
+                None => Value::Array(
+                    raw.into_iter()
+                        .map(|raw| Value::String(raw.to_string()))
+                        .collect(),
+                ),
+            }
+        } else {
+            let [value] = values else {
+                error(
+                    &mut errors,
+                    "",
+                    "",
+                    "type",
+                    format!("expected a single value, found {}", values.len()),
+                );
+                return errors;
+            };
+            match decode(value, r#type, "", "", &mut errors) {
+                Some(value) => value,
+                None => return errors,
+            }
+        };
+        parameter_keywords(self, r#type).check(&value, "", "", &mut errors);
+        errors
+    }
+}
+
+/// The `collectionFormat` of an array, `csv` when it is missing or unknown.
+fn collection_format(name: Option<&str>) -> CollectionFormat {
+    name.and_then(CollectionFormat::from_name)
+        .unwrap_or_default()
+}
+
+/// Convert a raw item, checking it against the keywords of its `Items`.
+///
+/// An item which cannot be converted is reported and kept as a string, so that it still counts in its array.
+fn decode_items(
+    items: &Items,
+    raw: &str,
+    instance_path: &str,
+    schema_path: &str,
+    errors: &mut Vec<InstanceError>,
+) -> Value {
+    let r#type = items_type(&items.r#type);
+    let value = if items.r#type == ItemsType::Array {
+        let format = collection_format(items.collection_format.as_deref());
+        let mut decoded = Vec::new();
+        for (index, raw) in format.split(raw).into_iter().enumerate() {
+            let instance_path = join(instance_path, &index.to_string());
+            let schema_path = join(schema_path, "items");
+            decoded.push(match items.items.as_deref() {
+                Some(nested) => decode_items(nested, raw, &instance_path, &schema_path, errors),
+                None => Value::String(raw.to_string()),
+            });
+        }
+        Value::Array(decoded)
+    } else {
+        match decode(raw, r#type, instance_path, schema_path, errors) {
+            Some(value) => value,
+            None => return Value::String(raw.to_string()),
+        }
+    };
+    let keywords = Keywords {
+        r#type: Some(r#type),
+        format: items.format.as_deref(),
+        r#enum: items.r#enum.as_deref(),
+        multiple_of: items.multiple_of.as_ref(),
+        maximum: items.maximum.as_ref(),
+        exclusive_maximum: items.exclusive_maximum == Some(true),
+        minimum: items.minimum.as_ref(),
+        exclusive_minimum: items.exclusive_minimum == Some(true),
+        max_length: items.max_length.as_ref(),
+        min_length: items.min_length.as_ref(),
+        pattern: items.pattern.as_deref(),
+        max_items: items.max_items.as_ref(),
+        min_items: items.min_items.as_ref(),
+        unique_items: items.unique_items == Some(true),
+        ..Keywords::default()
+    };
+    keywords.check(&value, instance_path, schema_path, errors);
+    value
+}
+
+/// Convert a raw scalar to its type, reporting it when it cannot be.
+fn decode(
+    raw: &str,
+    r#type: &str,
+    instance_path: &str,
+    schema_path: &str,
+    errors: &mut Vec<InstanceError>,
+) -> Option<Value> {
+    let value = match r#type {
+        "integer" => raw
+            .parse::<i64>()
+            .map(Number::from)
+            .or_else(|_| raw.parse::<u64>().map(Number::from))
+            .ok()
+            .map(Value::Number),
+        "number" => match raw.parse::<i64>() {
+            Ok(integer) => Some(Value::Number(integer.into())),
+            Err(_) => raw
+                .parse::<f64>()
+                .ok()
+                .and_then(Number::from_f64)
+                .map(Value::Number),
+        },
+        "boolean" => match raw {
+            "true" => Some(Value::Bool(true)),
+            "false" => Some(Value::Bool(false)),
+            _ => None,
+        },
+        _ => Some(Value::String(raw.to_string())),
+    };
+    if value.is_none() {
+        error(
+            errors,
+            instance_path,
+            schema_path,
+            "type",
+            format!("`{}` is not a valid {}", raw, r#type),
+        );
+    }
+    value
+}
+
+fn items_type(r#type: &ItemsType) -> &'static str {
+    match r#type {
+        ItemsType::String => "string",
+        ItemsType::Number => "number",
+        ItemsType::Integer => "integer",
+        ItemsType::Boolean => "boolean",
+        ItemsType::Array => "array",
+    }
+}
+
+fn parameter_keywords<'p>(parameter: &'p Parameter, r#type: &'p str) -> Keywords<'p> {
+    Keywords {
+        r#type: Some(r#type),
+        format: parameter.format.as_deref(),
+        r#enum: parameter.r#enum.as_deref(),
+        multiple_of: parameter.multiple_of.as_ref(),
+        maximum: parameter.maximum.as_ref(),
+        exclusive_maximum: parameter.exclusive_maximum == Some(true),
+        minimum: parameter.minimum.as_ref(),
+        exclusive_minimum: parameter.exclusive_minimum == Some(true),
+        max_length: parameter.max_length.as_ref(),
+        min_length: parameter.min_length.as_ref(),
+        pattern: parameter.pattern.as_deref(),
+        max_items: parameter.max_items.as_ref(),
+        min_items: parameter.min_items.as_ref(),
+        unique_items: parameter.unique_items == Some(true),
+        ..Keywords::default()
+    }
+}
+
+/// Checks instances against the schemas of a document.
+struct InstanceValidator<'a> {
+    swagger: &'a Swagger,
+    direction: Direction,
+    /// The references being followed, with the instance they are applied to, to stop at cycles.
+    stack: Vec<(String, String)>,
+}
+
+impl InstanceValidator<'_> {
+    fn schema(
+        &mut self,
+        schema: &Schema,
+        instance: &Value,
+        instance_path: &str,
+        schema_path: &str,
+        errors: &mut Vec<InstanceError>,
+    ) {
+        if let Some(reference) = &schema.reference {
+            self.reference(reference, instance, instance_path, schema_path, errors);
+            return;
+        }
+        let nullable = schema.extensions.get("x-nullable") == Some(&Value::Bool(true));
+        if instance.is_null() && nullable {
+            return;
+        }
+        let keywords = Keywords {
+            r#type: schema.r#type.as_deref(),
+            format: schema.format.as_deref(),
+            r#enum: schema.r#enum.as_deref(),
+            multiple_of: schema.multiple_of.as_ref(),
+            maximum: schema.maximum.as_ref(),
+            exclusive_maximum: schema.exclusive_maximum == Some(true),
+            minimum: schema.minimum.as_ref(),
+            exclusive_minimum: schema.exclusive_minimum == Some(true),
+            max_length: schema.max_length.as_ref(),
+            min_length: schema.min_length.as_ref(),
+            pattern: schema.pattern.as_deref(),
+            max_items: schema.max_items.as_ref(),
+            min_items: schema.min_items.as_ref(),
+            unique_items: schema.unique_items == Some(true),
+            ..Keywords::default()
+        };
+        if !keywords.check(instance, instance_path, schema_path, errors) {
+            return;
+        }
+        match instance {
+            Value::Object(_) => self.object(schema, instance, instance_path, schema_path, errors),
+            Value::Array(items) => {
+                if let Some(schema) = &schema.items {
+                    let schema_path = join(schema_path, "items");
+                    for (index, item) in items.iter().enumerate() {
+                        let instance_path = join(instance_path, &index.to_string());
+                        self.schema(schema, item, &instance_path, &schema_path, errors);
+                    }
+                }
+            }
+            _ => {}
+        }
+        for (index, item) in schema.all_of.iter().flatten().enumerate() {
+            let schema_path = join(&join(schema_path, "allOf"), &index.to_string());
+            self.schema(item, instance, instance_path, &schema_path, errors);
+        }
+        if let Some(name) = &schema.discriminator {
+            self.discriminator(name, instance, instance_path, schema_path, errors);
+        }
+    }
+
+    fn reference(
+        &mut self,
+        reference: &str,
+        instance: &Value,
+        instance_path: &str,
+        schema_path: &str,
+        errors: &mut Vec<InstanceError>,
+    ) {
+        let key = (reference.to_string(), instance_path.to_string());
+        // The same schema applied again to the same value cannot add anything, and would never end.
+        if self.stack.contains(&key) {
+            return;
+        }
+        let target = Schema {
+            reference: Some(reference.to_string()),
+            ..Schema::default()
+        };
+        match self.swagger.resolve_schema(&target) {
+            Ok(schema) => {
+                let target = format!("#{}", split_reference(reference).1);
+                self.stack.push(key);
+                self.schema(schema, instance, instance_path, &target, errors);
+                self.stack.pop();
+            }
+            Err(e) => error(errors, instance_path, schema_path, "$ref", e.to_string()),
+        }
+    }
+
+    /// Check the instance against the definition named by its discriminator property.
+    fn discriminator(
+        &mut self,
+        name: &str,
+        instance: &Value,
+        instance_path: &str,
+        schema_path: &str,
+        errors: &mut Vec<InstanceError>,
+    ) {
+        let Value::Object(map) = instance else {
+            return;
+        };
+        let Some(Value::String(value)) = map.get(name) else {
+            error(
+                errors,
+                instance_path,
+                schema_path,
+                "discriminator",
+                format!(
+                    "the discriminator property `{}` is missing or not a string",
+                    name
+                ),
+            );
+            return;
+        };
+        let defined = self
+            .swagger
+            .definitions
+            .as_ref()
+            .is_some_and(|definitions| definitions.contains_key(value));
+        if !defined {
+            error(
+                errors,
+                &join(instance_path, name),
+                schema_path,
+                "discriminator",
+                format!("`{}` does not name a definition", value),
+            );
+            return;
+        }
+        let reference = format!("#{}", join("/definitions", value));
+        self.reference(&reference, instance, instance_path, schema_path, errors);
+    }
+
+    fn object(
+        &mut self,
+        schema: &Schema,
+        instance: &Value,
+        instance_path: &str,
+        schema_path: &str,
+        errors: &mut Vec<InstanceError>,
+    ) {
+        let Value::Object(map) = instance else {
+            return;
+        };
+        let property = |name: &str| {
+            schema
+                .properties
+                .as_ref()
+                .and_then(|properties| properties.get(name))
+        };
+        let read_only = |this: &Self, name: &str| {
+            this.direction == Direction::Request
+                && property(name)
+                    .and_then(|schema| this.swagger.resolve_schema(schema).ok())
+                    .is_some_and(|schema| schema.read_only == Some(true))
+        };
+
+        for name in schema.required.iter().flatten() {
+            if !map.contains_key(name) && !read_only(self, name) {
+                error(
+                    errors,
+                    instance_path,
+                    schema_path,
+                    "required",
+                    format!("the property `{}` is required", name),
+                );
+            }
+        }
+        let additional = match &schema.additional_properties {
+            Some(Value::Bool(false)) => Some(None),
+            Some(value @ Value::Object(_)) => serde_json::from_value::<Schema>(value.clone())
+                .ok()
+                .map(Some),
+            _ => None,
+        };
+        for (name, value) in map {
+            let instance_path = join(instance_path, name);
+            if let Some(item) = property(name) {
+                let schema_path = join(&join(schema_path, "properties"), name);
+                if read_only(self, name) {
+                    let schema_path = match &item.reference {
+                        Some(reference) => format!("#{}", split_reference(reference).1),
+                        None => schema_path.clone(),
+                    };
+                    error(
+                        errors,
+                        &instance_path,
+                        &schema_path,
+                        "readOnly",
+                        format!("the property `{}` is read-only and must not be sent", name),
+                    );
+                }
+                self.schema(item, value, &instance_path, &schema_path, errors);
+                continue;
+            }
+            match &additional {
+                Some(None) => error(
+                    errors,
+                    &instance_path,
+                    schema_path,
+                    "additionalProperties",
+                    format!("the property `{}` is not allowed", name),
+                ),
+                Some(Some(item)) => {
+                    let schema_path = join(schema_path, "additionalProperties");
+                    self.schema(item, value, &instance_path, &schema_path, errors);
+                }
+                None => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn paths(errors: &[InstanceError]) -> Vec<(&str, &str)> {
+        errors
+            .iter()
+            .map(|error| (error.instance_path.as_str(), error.schema_path.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_validate_instance() {
+        let swagger: Swagger = serde_json::from_value(json!({
+            "swagger": "2.0",
+            "info": {"title": "Pets", "version": "1"},
+            "paths": {},
+            "definitions": {
+                "Pet": {
+                    "type": "object",
+                    "discriminator": "kind",
+                    "required": ["id", "name", "kind"],
+                    "properties": {
+                        "id": {"type": "integer", "readOnly": true},
+                        "name": {"type": "string", "maxLength": 3},
+                        "kind": {"type": "string"},
+                        "tag": {"type": "string", "x-nullable": true},
+                        "labels": {"type": "object", "additionalProperties": {"type": "string"}}
+                    }
+                },
+                "Dog": {
+                    "allOf": [
+                        {"$ref": "#/definitions/Pet"},
+                        {"type": "object", "required": ["bark"], "properties": {"bark": {"type": "boolean"}}}
+                    ]
+                }
+            }
+        }))
+        .unwrap();
+        let pet = Schema {
+            reference: Some("#/definitions/Pet".to_string()),
+            ..Schema::default()
+        };
+        let dog = json!({"id": 1, "name": "Rex", "kind": "Dog", "bark": true, "tag": null});
+        assert_eq!(
+            swagger.validate_instance(&pet, &dog, Direction::Response),
+            []
+        );
+
+        let request = json!({"id": 1, "name": "Rexy", "kind": "Dog", "labels": {"size": 3}});
+        let errors = swagger.validate_instance(&pet, &request, Direction::Request);
+        assert_eq!(
+            paths(&errors),
+            [
+                ("/id", "#/definitions/Pet/properties/id/readOnly"),
+                (
+                    "/labels/size",
+                    "#/definitions/Pet/properties/labels/additionalProperties/type"
+                ),
+                ("/name", "#/definitions/Pet/properties/name/maxLength"),
+                ("", "#/definitions/Dog/allOf/1/required"),
+            ]
+        );
+        let errors = swagger.validate_instance(
+            &pet,
+            &json!({"name": "Rex", "kind": "Cat"}),
+            Direction::Request,
+        );
+        assert_eq!(
+            paths(&errors),
+            [("/kind", "#/definitions/Pet/discriminator")]
+        );
+    }
+
+    fn parameter(parameter: Value) -> Parameter {
+        serde_json::from_value(parameter).unwrap()
+    }
+
+    #[test]
+    fn test_validate_value() {
+        let limit = parameter(json!({
+            "name": "limit", "in": "query", "type": "integer", "required": true, "minimum": 1, "maximum": 100
+        }));
+        assert_eq!(limit.validate_value(&["25"]), []);
+        assert_eq!(paths(&limit.validate_value(&["0"])), [("", "/minimum")]);
+        assert_eq!(paths(&limit.validate_value(&["ten"])), [("", "/type")]);
+        assert_eq!(paths(&limit.validate_value(&[])), [("", "/required")]);
+        assert_eq!(
+            paths(&limit.validate_value(&[""])),
+            [("", "/allowEmptyValue")]
+        );
+        assert_eq!(paths(&limit.validate_value(&["1", "2"])), [("", "/type")]);
+
+        let tags = parameter(json!({
+            "name": "tags", "in": "query", "type": "array", "collectionFormat": "pipes", "maxItems": 2,
+            "items": {"type": "string", "enum": ["cat", "dog", "fish"]}
+        }));
+        assert_eq!(tags.validate_value(&["cat|dog"]), []);
+        assert_eq!(
+            paths(&tags.validate_value(&["cat|bird|fish"])),
+            [("/1", "/items/enum"), ("", "/maxItems")]
+        );
+        assert_eq!(
+            paths(&tags.validate_value(&["cat", "dog"])),
+            [("", "/collectionFormat")]
+        );
+
+        let ids = parameter(json!({
+            "name": "ids", "in": "formData", "type": "array", "collectionFormat": "multi",
+            "items": {"type": "array", "collectionFormat": "csv", "items": {"type": "number", "multipleOf": 0.5}}
+        }));
+        assert_eq!(ids.validate_value(&["1,1.5", "2"]), []);
+        assert_eq!(
+            paths(&ids.validate_value(&["1,1.2", "x"])),
+            [
+                ("/0/1", "/items/items/multipleOf"),
+                ("/1/0", "/items/items/type")
+            ]
+        );
+    }
+}
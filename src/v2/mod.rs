@@ -1,2 +1,9 @@
+mod convert;
+mod dereference;
+mod instance;
+mod resolve;
+mod router;
 mod schema;
+mod validate;
+pub mod visit;
 pub use crate::v2::schema::*;
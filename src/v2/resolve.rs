@@ -0,0 +1,214 @@
+use crate::error::Error;
+use crate::resolve::{find, mismatched, navigate, Chain, Navigate};
+
+use super::schema::*;
+
+impl Swagger {
+    /// Resolve a schema which may be a `$ref`, following references to references.
+    ///
+    /// Only local references such as `#/definitions/Pet` are followed.
+    pub fn resolve_schema<'a>(&'a self, schema: &'a Schema) -> Result<&'a Schema, Error> {
+        let mut chain = Chain::default();
+        let mut schema = schema;
+        while let Some(reference) = &schema.reference {
+            chain.follow(reference)?;
+            schema = find(self, reference)?
+                .as_any()
+                .downcast_ref::<Schema>()
+                .ok_or_else(|| mismatched::<Schema>(reference))?;
+        }
+        Ok(schema)
+    }
+
+    /// Resolve a parameter which may be a `$ref`, following references to references.
+    ///
+    /// Only local references such as `#/parameters/limit` are followed.
+    pub fn resolve_parameter<'a>(
+        &'a self,
+        parameter: &'a ParameterOrRef,
+    ) -> Result<&'a Parameter, Error> {
+        let mut chain = Chain::default();
+        let mut parameter = parameter;
+        loop {
+            match parameter {
+                ParameterOrRef::Parameter(parameter) => return Ok(parameter),
+                ParameterOrRef::Ref(Reference { reference }) => {
+                    chain.follow(reference)?;
+                    let target = find(self, reference)?.as_any();
+                    if let Some(target) = target.downcast_ref::<Parameter>() {
+                        return Ok(target);
+                    }
+                    parameter = target
+                        .downcast_ref::<ParameterOrRef>()
+                        .ok_or_else(|| mismatched::<Parameter>(reference))?;
+                }
+            }
+        }
+    }
+
+    /// Resolve a response which may be a `$ref`, following references to references.
+    ///
+    /// Only local references such as `#/responses/NotFound` are followed.
+    pub fn resolve_response<'a>(
+        &'a self,
+        response: &'a ResponseOrRef,
+    ) -> Result<&'a Response, Error> {
+        let mut chain = Chain::default();
+        let mut response = response;
+        loop {
+            match response {
+                ResponseOrRef::Response(response) => return Ok(response),
+                ResponseOrRef::Ref(Reference { reference }) => {
+                    chain.follow(reference)?;
+                    let target = find(self, reference)?.as_any();
+                    if let Some(target) = target.downcast_ref::<Response>() {
+                        return Ok(target);
+                    }
+                    response = target
+                        .downcast_ref::<ResponseOrRef>()
+                        .ok_or_else(|| mismatched::<Response>(reference))?;
+                }
+            }
+        }
+    }
+}
+
+impl Navigate for ParameterOrRef {
+    fn child(&self, token: &str) -> Option<&dyn Navigate> {
+        match self {
+            ParameterOrRef::Ref(_) => None,
+            ParameterOrRef::Parameter(parameter) => parameter.child(token),
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+impl Navigate for ResponseOrRef {
+    fn child(&self, token: &str) -> Option<&dyn Navigate> {
+        match self {
+            ResponseOrRef::Ref(_) => None,
+            ResponseOrRef::Response(response) => response.child(token),
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+navigate!(Swagger {
+    "paths" => paths,
+    "definitions" => ?definitions,
+    "parameters" => ?parameters,
+    "responses" => ?responses,
+});
+
+navigate!(PathItem {
+    "get" => ?get,
+    "put" => ?put,
+    "post" => ?post,
+    "delete" => ?delete,
+    "options" => ?options,
+    "head" => ?head,
+    "patch" => ?patch,
+    "parameters" => ?parameters,
+});
+
+navigate!(Operation {
+    "parameters" => ?parameters,
+    "responses" => responses,
+});
+
+navigate!(Parameter {
+    "schema" => ?schema,
+});
+
+navigate!(Response {
+    "schema" => ?schema,
+});
+
+navigate!(Schema {
+    "items" => ?items,
+    "properties" => ?properties,
+    "allOf" => ?all_of,
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn swagger() -> Swagger {
+        serde_json::from_value(json!({
+            "swagger": "2.0",
+            "info": {"title": "pets"},
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "parameters": [{"$ref": "#/parameters/limit"}],
+                        "responses": {
+                            "200": {"description": "pets", "schema": {"$ref": "#/definitions/Pets"}},
+                            "default": {"$ref": "#/responses/Error"}
+                        }
+                    }
+                }
+            },
+            "definitions": {
+                "Pets": {"type": "array", "items": {"$ref": "#/definitions/Pet"}},
+                "Pet": {"$ref": "#/definitions/Animal"},
+                "Animal": {"type": "object", "properties": {"name": {"type": "string"}}},
+                "Self": {"$ref": "#/definitions/Self"}
+            },
+            "parameters": {
+                "limit": {"name": "limit", "in": "query", "type": "integer"}
+            },
+            "responses": {
+                "Error": {"description": "error", "schema": {"$ref": "#/definitions/Missing"}}
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_resolve() {
+        let swagger = swagger();
+        let operation = swagger.paths["/pets"].get.as_ref().unwrap();
+
+        let parameter = swagger
+            .resolve_parameter(&operation.parameters.as_ref().unwrap()[0])
+            .unwrap();
+        assert_eq!(parameter.name, "limit");
+
+        let response = swagger
+            .resolve_response(&operation.responses["default"])
+            .unwrap();
+        assert_eq!(response.description, "error");
+
+        let ResponseOrRef::Response(ok) = &operation.responses["200"] else {
+            panic!("expected an inline response")
+        };
+        let pets = swagger.resolve_schema(ok.schema.as_ref().unwrap()).unwrap();
+        let pet = swagger
+            .resolve_schema(pets.items.as_ref().unwrap())
+            .unwrap();
+        assert_eq!(pet.r#type.as_deref(), Some("object"));
+    }
+
+    #[test]
+    fn test_resolve_errors() {
+        let swagger = swagger();
+        let error = swagger.responses.as_ref().unwrap()["Error"].schema.as_ref();
+        assert!(matches!(
+            swagger.resolve_schema(error.unwrap()),
+            Err(Error::UnresolvedReference { .. })
+        ));
+        let cyclic = &swagger.definitions.as_ref().unwrap()["Self"];
+        assert!(matches!(
+            swagger.resolve_schema(cyclic),
+            Err(Error::CyclicReference { .. })
+        ));
+    }
+}
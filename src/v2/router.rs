@@ -0,0 +1,17 @@
+use crate::router::Router;
+
+use super::schema::*;
+
+impl Swagger {
+    /// Compile the templates of the `paths` into a router.
+    ///
+    /// Paths may be given with the `basePath` of the document, such as `/v1/pets` for a `basePath` of `/v1`.
+    pub fn router(&self) -> Router<'_, PathItem, Operation> {
+        Router::new(
+            self.base_path.iter().cloned().collect(),
+            self.paths
+                .iter()
+                .map(|(template, item)| (template.as_str(), item, item.operations().collect())),
+        )
+    }
+}
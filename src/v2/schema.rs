@@ -1,5 +1,6 @@
+use crate::de::{deserialize_ref_or, RefOr};
 use crate::extension::Extensions;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::{Number, Value};
 use std::collections::BTreeMap;
 
@@ -10,7 +11,7 @@ use std::collections::BTreeMap;
 /// Swagger document specification [here](https://swagger.io/specification/v2/)
 ///
 /// see https://swagger.io/specification/v2/#swagger-object
-#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone, Default)]
 pub struct Swagger {
     /// 2.0
     pub swagger: String,
@@ -24,13 +25,13 @@ pub struct Swagger {
     pub base_path: Option<String>,
     /// The transfer protocol of the API.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub schemas: Option<Vec<TransferProtocol>>,
+    pub schemes: Option<Vec<TransferProtocol>>,
     /// A list of MIME types the APIs can consume.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub consumes: Option<String>,
+    pub consumes: Option<Vec<String>>,
     /// A list of MIME types the APIs can produce.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub produces: Option<String>,
+    pub produces: Option<Vec<String>>,
 
     /// The available paths and operations for the API.
     pub paths: BTreeMap<String, PathItem>,
@@ -167,7 +168,7 @@ pub struct Tag {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     /// Additional external documentation for this tag.
-    #[serde(skip_serializing_if = "Option::is_none", rename = "externalDoc")]
+    #[serde(skip_serializing_if = "Option::is_none", rename = "externalDocs")]
     pub external_doc: Option<ExternalDoc>,
 }
 
@@ -179,7 +180,7 @@ pub enum InEnum {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
-#[serde(rename_all = "lowercase")]
+#[serde(rename_all = "camelCase")]
 pub enum Flow {
     Implicit,
     Password,
@@ -196,12 +197,6 @@ pub enum Scopes {
     AccessCode,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
-#[serde(rename_all = "lowercase")]
-pub enum AuthorizationUrl {
-    Implicit,
-    AccessCode,
-}
 /// ### SecuritySchemeType
 ///
 /// The type of the security scheme. Valid values are "basic", "apiKey" or "oauth2".
@@ -213,16 +208,6 @@ pub enum SecuritySchemeType {
     Oauth2,
 }
 
-/// ### TokenUrl
-///
-///  The token URL to be used for this flow.
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
-#[serde(rename_all = "camelCase")]
-pub enum TokenUrl {
-    Password,
-    Application,
-    AccessCode,
-}
 /// ## SecurityScheme
 ///
 /// A declaration of the security schemes available to be used in the specification.
@@ -235,18 +220,21 @@ pub struct SecurityScheme {
     /// A short description for security scheme.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
-    /// The name of the header or query parameter to be used.
-    pub name: String,
-    /// The location of the API key. Valid values are `"query"` or `"header"`.
-    pub r#in: InEnum,
+    /// The name of the header or query parameter to be used, for `"apiKey"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The location of the API key, for `"apiKey"`. Valid values are `"query"` or `"header"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r#in: Option<InEnum>,
     /// The flow used by the OAuth2 security scheme. Valid values are `"implicit"`,`"password"`,`"application"` or `"accessCode"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub flow: Option<Flow>,
     /// The authorization URL to be used for this flow. This SHOULD be in the form of a URL.
     #[serde(rename = "authorizationUrl", skip_serializing_if = "Option::is_none")]
-    pub authorization_url: Option<AuthorizationUrl>,
+    pub authorization_url: Option<String>,
     /// The token URL to be used for this flow. This SHOULD be in the form of a URL.
     #[serde(rename = "tokenUrl", skip_serializing_if = "Option::is_none")]
-    pub token_url: Option<TokenUrl>,
+    pub token_url: Option<String>,
     /// The available scopes for the OAuth2 security scheme.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub scopes: Option<BTreeMap<String, String>>,
@@ -263,13 +251,13 @@ pub struct SecurityScheme {
 /// The name used for each property MUST correspond to a security scheme declared in the Security Definitions.
 /// see https://swagger.io/specification/v2/#securityRequirementObject
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
-pub struct SecurityRequirementObject(BTreeMap<String, Vec<String>>);
+pub struct SecurityRequirementObject(pub BTreeMap<String, Vec<String>>);
 
 /// ### Path Item
 /// Describes the operations available on a single path.
 /// A Path Item may be empty, due to [ACL constraints](https://swagger.io/specification/v2/#securityFiltering).
 /// The path itself is still exposed to the documentation viewer but they will not know which operations and parameters are available.
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct PathItem {
     /// Allows for an external definition of this path item. The referenced structure MUST be in the format of a Path Item Object.
     /// If there are conflicts between the referenced definition and this Path Item's definition, the behavior is undefined.
@@ -301,9 +289,26 @@ pub struct PathItem {
     pub parameters: Option<Vec<ParameterOrRef>>,
 }
 
+impl PathItem {
+    /// The operations defined on the path, with their lowercase HTTP methods.
+    pub fn operations(&self) -> impl Iterator<Item = (&'static str, &Operation)> {
+        [
+            ("get", &self.get),
+            ("put", &self.put),
+            ("post", &self.post),
+            ("delete", &self.delete),
+            ("options", &self.options),
+            ("head", &self.head),
+            ("patch", &self.patch),
+        ]
+        .into_iter()
+        .filter_map(|(method, operation)| operation.as_ref().map(|operation| (method, operation)))
+    }
+}
+
 /// ### Operation
 /// Describes a single API operation on a path.
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct Operation {
     /// A list of tags for API documentation control.
     /// Tags can be used for logical grouping of operations by resources or any other qualifier.
@@ -334,10 +339,10 @@ pub struct Operation {
     pub responses: Responses,
     /// The transfer protocol for the operation.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub schemas: Option<TransferProtocol>,
+    pub schemes: Option<Vec<TransferProtocol>>,
     /// Declares this operation to be deprecated.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub deprecated: Option<String>,
+    pub deprecated: Option<bool>,
     /// A declaration of which security schemes are applied for this operation.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub security: Option<Vec<SecurityRequirementObject>>,
@@ -355,12 +360,25 @@ pub enum ParamInEnum {
     Body,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Debug, Serialize, PartialEq)]
 #[serde(untagged)]
+#[allow(clippy::large_enum_variant)]
 pub enum ParameterOrRef {
     Ref(Reference),
     Parameter(Parameter),
 }
+
+impl<'de> Deserialize<'de> for ParameterOrRef {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(match deserialize_ref_or(deserializer)? {
+            RefOr::Ref(reference) => ParameterOrRef::Ref(reference),
+            RefOr::Object(parameter) => ParameterOrRef::Parameter(parameter),
+        })
+    }
+}
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub struct Reference {
     #[serde(rename = "$ref")]
@@ -458,7 +476,7 @@ pub struct Schema {
     pub format: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "enum")]
-    pub r#enum: Option<Vec<String>>,
+    pub r#enum: Option<Vec<Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub required: Option<Vec<String>>,
 
@@ -498,9 +516,9 @@ pub struct Schema {
     )]
     pub additional_properties: Option<Value>,
 
-    /// Adds support for polymorphism.
+    /// Adds support for polymorphism, the name of the property which holds the name of the actual schema.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub discriminator: Option<Discriminator>,
+    pub discriminator: Option<String>,
 
     /// Relevant only for Schema "properties" definitions.
     #[serde(skip_serializing_if = "Option::is_none", rename = "readOnly")]
@@ -621,7 +639,7 @@ pub type Responses = BTreeMap<String, ResponseOrRef>;
 
 /// ### Response
 /// Describes a single response from an API Operation.
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct Response {
     ///  A short description of the response.
     pub description: String,
@@ -691,33 +709,24 @@ pub struct Header {
     pub extensions: Extensions,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Debug, Serialize, PartialEq)]
 #[serde(untagged)]
+#[allow(clippy::large_enum_variant)]
 pub enum ResponseOrRef {
-    Response {
-        description: String,
-        schema: Option<Schema>,
-        headers: Option<BTreeMap<String, Header>>,
-        examples: Option<BTreeMap<String, Value>>,
-        #[serde(flatten)]
-        extensions: Extensions,
-    },
-    Ref {
-        #[serde(rename = "$ref")]
-        reference: String,
-    },
+    Ref(Reference),
+    Response(Response),
 }
 
-/// ### Discriminator
-/// When request bodies or response payloads may be one of a number of different schemas, a discriminator object can be used to aid in serialization, deserialization, and validation.
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
-pub struct Discriminator {
-    /// The name of the property in the payload that will hold the discriminator value.
-    #[serde(rename = "propertyName")]
-    pub property_name: String,
-    /// An object to hold mappings between payload values and schema names or references.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub mapping: Option<BTreeMap<String, String>>,
+impl<'de> Deserialize<'de> for ResponseOrRef {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(match deserialize_ref_or(deserializer)? {
+            RefOr::Ref(reference) => ResponseOrRef::Ref(reference),
+            RefOr::Object(response) => ResponseOrRef::Response(response),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -731,7 +740,7 @@ mod test {
         }"#;
 
         assert_eq!(
-            serde_json::from_str::<ParameterOrRef>(&json_str).unwrap(),
+            serde_json::from_str::<ParameterOrRef>(json_str).unwrap(),
             ParameterOrRef::Ref(Reference {
                 reference: "/some/path".to_string()
             })
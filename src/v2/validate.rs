@@ -0,0 +1,507 @@
+use std::collections::BTreeMap;
+
+use crate::pointer::join;
+use crate::validate::{is_component_name, is_status, Diagnostic, Report, V2_LITERALS};
+
+use super::schema::*;
+
+/// The values of `collectionFormat`.
+const COLLECTION_FORMATS: &[&str] = &["csv", "ssv", "tsv", "pipes", "multi"];
+
+/// The media types a request carrying a file may be sent as.
+const FILE_MEDIA_TYPES: &[&str] = &["multipart/form-data", "application/x-www-form-urlencoded"];
+
+impl Swagger {
+    /// Check the document against the rules of the specification which its types cannot enforce.
+    ///
+    /// An empty list means the document is valid. References to other documents are not followed.
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut validator = Validator {
+            swagger: self,
+            report: Report::default(),
+        };
+        validator.document();
+        validator.report.diagnostics
+    }
+}
+
+struct Validator<'a> {
+    swagger: &'a Swagger,
+    report: Report,
+}
+
+impl<'a> Validator<'a> {
+    fn document(&mut self) {
+        let swagger = self.swagger;
+        if let Some(host) = &swagger.host {
+            if host.contains("://") || host.contains('/') {
+                self.report.error(
+                    "host",
+                    "/host",
+                    format!("host `{}` must not hold a scheme or a path", host),
+                );
+            }
+        }
+        if let Some(base_path) = &swagger.base_path {
+            if !base_path.starts_with('/') {
+                self.report.error(
+                    "base-path",
+                    "/basePath",
+                    format!("base path `{}` does not start with a slash", base_path),
+                );
+            }
+        }
+        self.report.duplicates(
+            "tags-unique",
+            "tag",
+            swagger
+                .tags
+                .iter()
+                .flatten()
+                .enumerate()
+                .map(|(index, tag)| {
+                    (
+                        tag.name.as_str(),
+                        join(&join("/tags", &index.to_string()), "name"),
+                    )
+                }),
+        );
+        self.report.paths(swagger.paths.keys());
+
+        let mut operation_ids = Vec::new();
+        for (path, item) in &swagger.paths {
+            let pointer = join("/paths", path);
+            self.path_item(path, item, &pointer);
+            for (method, operation) in item.operations() {
+                if let Some(id) = &operation.operation_id {
+                    let pointer = join(&join(&pointer, method), "operationId");
+                    operation_ids.push((id.as_str(), pointer));
+                }
+            }
+        }
+        self.report
+            .duplicates("operation-id-unique", "operationId", operation_ids);
+
+        if let Some(security) = &swagger.security {
+            self.security(security, "/security");
+        }
+        for (name, parameter) in swagger.parameters.iter().flatten() {
+            self.parameter(parameter, &join("/parameters", name), None);
+        }
+        for (name, response) in swagger.responses.iter().flatten() {
+            self.response(response, &join("/responses", name));
+        }
+        for (name, scheme) in swagger.security_definitions.iter().flatten() {
+            self.security_scheme(scheme, &join("/securityDefinitions", name));
+        }
+        for (section, names) in [
+            (
+                "definitions",
+                swagger.definitions.as_ref().map(|items| keys(items)),
+            ),
+            (
+                "parameters",
+                swagger.parameters.as_ref().map(|items| keys(items)),
+            ),
+            (
+                "responses",
+                swagger.responses.as_ref().map(|items| keys(items)),
+            ),
+        ] {
+            for name in names
+                .into_iter()
+                .flatten()
+                .filter(|name| !is_component_name(name))
+            {
+                self.report.error(
+                    "component-names",
+                    &join(&join("", section), name),
+                    format!("`{}` is not a valid definition name", name),
+                );
+            }
+        }
+        if let Ok(document) = serde_json::to_value(swagger) {
+            self.report.references(&document, V2_LITERALS);
+        }
+    }
+
+    fn path_item(&mut self, path: &str, item: &'a PathItem, pointer: &str) {
+        let (path_level, path_level_complete) =
+            self.parameters(item.parameters.as_deref(), pointer, None);
+        for (method, operation) in item.operations() {
+            let pointer = join(pointer, method);
+            let consumes = operation
+                .consumes
+                .as_ref()
+                .or(self.swagger.consumes.as_ref());
+            let (mut parameters, complete) =
+                self.parameters(operation.parameters.as_deref(), &pointer, consumes);
+            // The parameters of the operation override those of the path item with the same name and location.
+            for (parameter, pointer) in &path_level {
+                let overridden = parameters
+                    .iter()
+                    .any(|(other, _)| other.name == parameter.name && other.r#in == parameter.r#in);
+                if !overridden {
+                    parameters.push((parameter, pointer.clone()));
+                }
+            }
+            self.operation(operation, &pointer);
+            self.body(&parameters, &pointer);
+            let declared: Vec<_> = parameters
+                .iter()
+                .filter(|(parameter, _)| parameter.r#in == ParamInEnum::Path)
+                .map(|(parameter, pointer)| (parameter.name.as_str(), pointer.clone()))
+                .collect();
+            self.report
+                .path_parameters(path, &pointer, &declared, complete && path_level_complete);
+        }
+        if item.operations().next().is_none() {
+            let declared: Vec<_> = path_level
+                .iter()
+                .filter(|(parameter, _)| parameter.r#in == ParamInEnum::Path)
+                .map(|(parameter, pointer)| (parameter.name.as_str(), pointer.clone()))
+                .collect();
+            self.report
+                .path_parameters(path, pointer, &declared, path_level_complete);
+        }
+    }
+
+    fn operation(&mut self, operation: &'a Operation, pointer: &str) {
+        let pointer_responses = join(pointer, "responses");
+        if operation.responses.is_empty() {
+            self.report.error(
+                "responses-not-empty",
+                &pointer_responses,
+                "an operation must have at least one response",
+            );
+        }
+        for (status, response) in &operation.responses {
+            let pointer = join(&pointer_responses, status);
+            if !is_status(status, false) {
+                self.report.error(
+                    "response-status",
+                    &pointer,
+                    format!("`{}` is neither `default` nor a status code", status),
+                );
+            }
+            if let ResponseOrRef::Response(response) = response {
+                self.response(response, &pointer);
+            }
+        }
+        if let Some(security) = &operation.security {
+            self.security(security, &join(pointer, "security"));
+        }
+    }
+
+    /// Check a list of parameters, returning those which resolve with their pointers, and whether they all resolved.
+    ///
+    /// `consumes` are the media types of the operation, `None` for the parameters of a path item.
+    fn parameters(
+        &mut self,
+        parameters: Option<&'a [ParameterOrRef]>,
+        pointer: &str,
+        consumes: Option<&Vec<String>>,
+    ) -> (Vec<(&'a Parameter, String)>, bool) {
+        let pointer = join(pointer, "parameters");
+        let mut resolved = Vec::new();
+        let mut complete = true;
+        for (index, item) in parameters.unwrap_or_default().iter().enumerate() {
+            let pointer = join(&pointer, &index.to_string());
+            if let ParameterOrRef::Parameter(parameter) = item {
+                self.parameter(parameter, &pointer, consumes);
+            }
+            match self.swagger.resolve_parameter(item) {
+                Ok(parameter) => resolved.push((parameter, pointer)),
+                Err(_) => complete = false,
+            }
+        }
+        let keys: Vec<_> = resolved
+            .iter()
+            .map(|(parameter, pointer)| {
+                (
+                    format!("{} in {:?}", parameter.name, parameter.r#in),
+                    pointer,
+                )
+            })
+            .collect();
+        self.report.duplicates(
+            "parameters-unique",
+            "parameter",
+            keys.iter()
+                .map(|(key, pointer)| (key.as_str(), (*pointer).clone())),
+        );
+        (resolved, complete)
+    }
+
+    fn parameter(&mut self, parameter: &Parameter, pointer: &str, consumes: Option<&Vec<String>>) {
+        let body = parameter.r#in == ParamInEnum::Body;
+        if parameter.r#in == ParamInEnum::Path && parameter.required != Some(true) {
+            self.report.error(
+                "path-parameter-required",
+                pointer,
+                format!("path parameter `{}` must be required", parameter.name),
+            );
+        }
+        match (body, parameter.schema.is_some(), &parameter.r#type) {
+            (true, false, _) => self.report.error(
+                "parameter-type",
+                pointer,
+                "a body parameter requires a `schema`",
+            ),
+            (false, _, None) => self.report.error(
+                "parameter-type",
+                pointer,
+                "a parameter outside of the body requires a `type`",
+            ),
+            _ => {}
+        }
+        match &parameter.r#type {
+            Some(ParameterType::File) if parameter.r#in != ParamInEnum::FormData => {
+                self.report.error(
+                    "file-parameter",
+                    &join(pointer, "type"),
+                    "a file can only be sent as a `formData` parameter",
+                )
+            }
+            Some(ParameterType::File)
+                if consumes.is_some_and(|consumes| {
+                    !consumes
+                        .iter()
+                        .any(|media_type| FILE_MEDIA_TYPES.contains(&media_type.as_str()))
+                }) =>
+            {
+                self.report.error(
+                    "file-parameter",
+                    &join(pointer, "type"),
+                    format!(
+                        "a file can only be sent as {}",
+                        FILE_MEDIA_TYPES.join(" or ")
+                    ),
+                )
+            }
+            Some(ParameterType::Array) if parameter.items.is_none() => {
+                self.report
+                    .error("array-items", pointer, "an array requires `items`")
+            }
+            _ => {}
+        }
+        let multi = matches!(parameter.r#in, ParamInEnum::Query | ParamInEnum::FormData);
+        self.collection_format(parameter.collection_format.as_deref(), multi, pointer);
+        if let Some(items) = &parameter.items {
+            self.items(items, &join(pointer, "items"));
+        }
+    }
+
+    fn items(&mut self, items: &Items, pointer: &str) {
+        if items.r#type == ItemsType::Array && items.items.is_none() {
+            self.report
+                .error("array-items", pointer, "an array requires `items`");
+        }
+        self.collection_format(items.collection_format.as_deref(), false, pointer);
+        if let Some(items) = &items.items {
+            self.items(items, &join(pointer, "items"));
+        }
+    }
+
+    /// Check a `collectionFormat`, where `multi` is only allowed for query and form parameters.
+    fn collection_format(&mut self, format: Option<&str>, multi: bool, pointer: &str) {
+        let Some(format) = format else {
+            return;
+        };
+        let pointer = join(pointer, "collectionFormat");
+        if !COLLECTION_FORMATS.contains(&format) {
+            self.report.error(
+                "collection-format",
+                &pointer,
+                format!(
+                    "`{}` is not a collection format, expected one of {}",
+                    format,
+                    COLLECTION_FORMATS.join(", ")
+                ),
+            );
+        } else if format == "multi" && !multi {
+            self.report.error(
+                "collection-format",
+                &pointer,
+                "`multi` is only allowed for query and form parameters",
+            );
+        }
+    }
+
+    /// Check that an operation sends at most one body, and not along with form parameters.
+    fn body(&mut self, parameters: &[(&Parameter, String)], pointer: &str) {
+        let bodies: Vec<_> = parameters
+            .iter()
+            .filter(|(parameter, _)| parameter.r#in == ParamInEnum::Body)
+            .collect();
+        for (_, pointer) in bodies.iter().skip(1) {
+            self.report.error(
+                "body-parameter",
+                pointer,
+                "an operation can have only one body parameter",
+            );
+        }
+        let form = parameters
+            .iter()
+            .any(|(parameter, _)| parameter.r#in == ParamInEnum::FormData);
+        if form && !bodies.is_empty() {
+            self.report.error(
+                "body-parameter",
+                pointer,
+                "body and form parameters are mutually exclusive",
+            );
+        }
+    }
+
+    fn response(&mut self, response: &Response, pointer: &str) {
+        for (name, header) in response.headers.iter().flatten() {
+            let pointer = join(&join(pointer, "headers"), name);
+            if header.r#type == ItemsType::Array && header.items.is_none() {
+                self.report
+                    .error("array-items", &pointer, "an array requires `items`");
+            }
+            self.collection_format(header.collection_format.as_deref(), false, &pointer);
+            if let Some(items) = &header.items {
+                self.items(items, &join(&pointer, "items"));
+            }
+        }
+    }
+
+    /// Check that the requirements name security definitions, with scopes only for OAuth2.
+    fn security(&mut self, security: &[SecurityRequirementObject], pointer: &str) {
+        for (index, requirement) in security.iter().enumerate() {
+            for (name, scopes) in &requirement.0 {
+                let pointer = join(&join(pointer, &index.to_string()), name);
+                let scheme = self
+                    .swagger
+                    .security_definitions
+                    .as_ref()
+                    .and_then(|schemes| schemes.get(name));
+                match scheme {
+                    None => self.report.error(
+                        "security-schemes-defined",
+                        &pointer,
+                        format!("security definition `{}` is not defined", name),
+                    ),
+                    Some(scheme)
+                        if !scopes.is_empty() && scheme.r#type != SecuritySchemeType::Oauth2 =>
+                    {
+                        self.report.error(
+                            "security-schemes-defined",
+                            &pointer,
+                            format!("security definition `{}` has no scopes to require", name),
+                        )
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+    }
+
+    fn security_scheme(&mut self, scheme: &SecurityScheme, pointer: &str) {
+        let required: &[(&str, bool)] = match scheme.r#type {
+            SecuritySchemeType::Basic => &[],
+            SecuritySchemeType::ApiKey => &[
+                ("name", scheme.name.is_some()),
+                ("in", scheme.r#in.is_some()),
+            ],
+            SecuritySchemeType::Oauth2 => {
+                let (authorization, token) = match scheme.flow {
+                    Some(Flow::Implicit) => (true, false),
+                    Some(Flow::Password | Flow::Application) => (false, true),
+                    Some(Flow::AccessCode) => (true, true),
+                    None => (false, false),
+                };
+                &[
+                    ("flow", scheme.flow.is_some()),
+                    (
+                        "authorizationUrl",
+                        !authorization || scheme.authorization_url.is_some(),
+                    ),
+                    ("tokenUrl", !token || scheme.token_url.is_some()),
+                    ("scopes", scheme.scopes.is_some()),
+                ]
+            }
+        };
+        for (field, present) in required {
+            if !present {
+                self.report.error(
+                    "security-scheme-fields",
+                    pointer,
+                    format!("`{}` is required for this security definition", field),
+                );
+            }
+        }
+    }
+}
+
+fn keys<T>(items: &BTreeMap<String, T>) -> Vec<&String> {
+    items.keys().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_validate() {
+        let document = json!({
+            "swagger": "2.0",
+            "info": {"title": "Pets", "version": "1"},
+            "host": "https://example.com",
+            "basePath": "v1",
+            "consumes": ["application/json"],
+            "paths": {
+                "/pets/{petId}": {
+                    "parameters": [{"name": "petId", "in": "path", "type": "string"}],
+                    "post": {
+                        "operationId": "addPhoto",
+                        "parameters": [
+                            {"name": "photo", "in": "formData", "type": "file"},
+                            {"name": "pet", "in": "body", "schema": {"$ref": "#/definitions/Pet"}},
+                            {"name": "tags", "in": "header", "type": "array", "collectionFormat": "multi"}
+                        ],
+                        "responses": {"default": {"description": "Done."}}
+                    }
+                }
+            },
+            "definitions": {"Pet": {"type": "object"}}
+        });
+        let swagger: Swagger = serde_json::from_value(document).unwrap();
+        let mut diagnostics: Vec<_> = swagger
+            .validate()
+            .into_iter()
+            .map(|diagnostic| (diagnostic.rule, diagnostic.pointer))
+            .collect();
+        diagnostics.sort();
+        let post = "/paths/~1pets~1{petId}/post";
+        let expected: Vec<(String, String)> = [
+            ("array-items", format!("{}/parameters/2", post)),
+            ("base-path", "/basePath".to_string()),
+            ("body-parameter", post.to_string()),
+            (
+                "collection-format",
+                format!("{}/parameters/2/collectionFormat", post),
+            ),
+            ("file-parameter", format!("{}/parameters/0/type", post)),
+            ("host", "/host".to_string()),
+            (
+                "path-parameter-required",
+                "/paths/~1pets~1{petId}/parameters/0".to_string(),
+            ),
+        ]
+        .into_iter()
+        .map(|(rule, pointer)| (rule.to_string(), pointer))
+        .collect();
+        assert_eq!(diagnostics, expected);
+    }
+
+    #[test]
+    fn test_validate_petstore() {
+        let swagger: Swagger =
+            serde_json::from_str(&std::fs::read_to_string("./tests/petstore-v2.json").unwrap())
+                .unwrap();
+        assert_eq!(swagger.validate(), []);
+    }
+}
@@ -0,0 +1,180 @@
+//! Traversal of the references of a document.
+//!
+//! [`Walk`] steps through every object of a document which may hold a `$ref`, calling a [`VisitMut`] for each
+//! [`Schema`], [`ParameterOrRef`], [`ResponseOrRef`] and [`PathItem`]. The visitor decides what to do with a reference,
+//! and walks on into the object with the matching `walk_*` function.
+
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use crate::error::Error;
+
+use super::schema::*;
+
+/// A visitor which may rewrite the references of a document.
+pub trait VisitMut {
+    /// Visit a schema, which may be a `$ref`, then the schemas it holds.
+    fn visit_schema(&mut self, schema: &mut Schema) -> Result<(), Error> {
+        walk_schema(self, schema)
+    }
+
+    /// Visit a parameter which may be a reference, then its schema.
+    fn visit_parameter(&mut self, parameter: &mut ParameterOrRef) -> Result<(), Error> {
+        walk_parameter(self, parameter)
+    }
+
+    /// Visit a response which may be a reference, then its schema.
+    fn visit_response(&mut self, response: &mut ResponseOrRef) -> Result<(), Error> {
+        walk_response(self, response)
+    }
+
+    /// Visit a path item, which may be defined by a `$ref`, then the objects it holds.
+    fn visit_path_item(&mut self, item: &mut PathItem) -> Result<(), Error> {
+        walk_path_item(self, item)
+    }
+}
+
+/// An object which holds references, directly or through its children.
+pub trait Walk {
+    /// Call the visitor for every reference held by the object.
+    fn walk<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) -> Result<(), Error>;
+}
+
+/// Walk into the schemas held by a schema.
+///
+/// `additionalProperties` is kept as a raw value, its schema is walked through a typed copy.
+pub fn walk_schema<V>(visitor: &mut V, schema: &mut Schema) -> Result<(), Error>
+where
+    V: VisitMut + ?Sized,
+{
+    schema.items.walk(visitor)?;
+    schema.properties.walk(visitor)?;
+    schema.all_of.walk(visitor)?;
+    if let Some(additional @ Value::Object(_)) = &mut schema.additional_properties {
+        let mut schema: Schema = serde_json::from_value(additional.clone())?;
+        schema.walk(visitor)?;
+        *additional = serde_json::to_value(schema)?;
+    }
+    Ok(())
+}
+
+/// Walk into the schema of an inline parameter.
+pub fn walk_parameter<V>(visitor: &mut V, parameter: &mut ParameterOrRef) -> Result<(), Error>
+where
+    V: VisitMut + ?Sized,
+{
+    match parameter {
+        ParameterOrRef::Ref(_) => Ok(()),
+        ParameterOrRef::Parameter(parameter) => parameter.walk(visitor),
+    }
+}
+
+/// Walk into the schema of an inline response.
+pub fn walk_response<V>(visitor: &mut V, response: &mut ResponseOrRef) -> Result<(), Error>
+where
+    V: VisitMut + ?Sized,
+{
+    match response {
+        ResponseOrRef::Ref(_) => Ok(()),
+        ResponseOrRef::Response(response) => response.walk(visitor),
+    }
+}
+
+/// Walk into the operations and parameters of a path item.
+pub fn walk_path_item<V>(visitor: &mut V, item: &mut PathItem) -> Result<(), Error>
+where
+    V: VisitMut + ?Sized,
+{
+    for operation in [
+        &mut item.get,
+        &mut item.put,
+        &mut item.post,
+        &mut item.delete,
+        &mut item.options,
+        &mut item.head,
+        &mut item.patch,
+    ] {
+        operation.walk(visitor)?;
+    }
+    item.parameters.walk(visitor)
+}
+
+impl<T: Walk> Walk for Option<T> {
+    fn walk<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) -> Result<(), Error> {
+        match self {
+            Some(item) => item.walk(visitor),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: Walk> Walk for Box<T> {
+    fn walk<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) -> Result<(), Error> {
+        (**self).walk(visitor)
+    }
+}
+
+impl<T: Walk> Walk for Vec<T> {
+    fn walk<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) -> Result<(), Error> {
+        self.iter_mut().try_for_each(|item| item.walk(visitor))
+    }
+}
+
+impl<T: Walk> Walk for BTreeMap<String, T> {
+    fn walk<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) -> Result<(), Error> {
+        self.values_mut().try_for_each(|item| item.walk(visitor))
+    }
+}
+
+impl Walk for Schema {
+    fn walk<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) -> Result<(), Error> {
+        visitor.visit_schema(self)
+    }
+}
+
+impl Walk for ParameterOrRef {
+    fn walk<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) -> Result<(), Error> {
+        visitor.visit_parameter(self)
+    }
+}
+
+impl Walk for ResponseOrRef {
+    fn walk<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) -> Result<(), Error> {
+        visitor.visit_response(self)
+    }
+}
+
+impl Walk for PathItem {
+    fn walk<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) -> Result<(), Error> {
+        visitor.visit_path_item(self)
+    }
+}
+
+impl Walk for Swagger {
+    fn walk<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) -> Result<(), Error> {
+        self.paths.walk(visitor)?;
+        self.definitions.walk(visitor)?;
+        self.parameters.walk(visitor)?;
+        self.responses.walk(visitor)
+    }
+}
+
+impl Walk for Operation {
+    fn walk<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) -> Result<(), Error> {
+        self.parameters.walk(visitor)?;
+        self.responses.walk(visitor)
+    }
+}
+
+impl Walk for Parameter {
+    fn walk<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) -> Result<(), Error> {
+        self.schema.walk(visitor)
+    }
+}
+
+impl Walk for Response {
+    fn walk<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) -> Result<(), Error> {
+        self.schema.walk(visitor)
+    }
+}
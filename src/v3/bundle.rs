@@ -0,0 +1,159 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::mem;
+use std::path::Path;
+
+use crate::error::Error;
+use crate::loader::DocumentSet;
+use crate::pointer;
+
+use super::schema::*;
+use super::visit::{walk_path_item, walk_ref, Component, VisitMut, Walk};
+
+impl OpenApi {
+    /// Inline the objects of other documents into `components`, making the document self-contained.
+    ///
+    /// The document is the root of `set`, its references are relative to it. Each external object is added once under a name
+    /// taken from its pointer or file name, suffixed with a number if the name is already used, and every reference to it
+    /// is rewritten to the local `#/components/...` pointer. Path items defined in other documents are inlined in place.
+    ///
+    /// ```no_run
+    /// # use openapi_schema::{loader::Loader, Doc};
+    /// # fn example() -> Result<(), openapi_schema::Error> {
+    /// let set = Loader::new().load("openapi.yaml")?;
+    /// if let Doc::V3(mut openapi) = set.doc()? {
+    ///     openapi.bundle(&set)?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn bundle(&mut self, set: &DocumentSet) -> Result<(), Error> {
+        let mut bundler = Bundler {
+            set,
+            base: set.root().to_string(),
+            names: BTreeMap::new(),
+            taken: BTreeSet::new(),
+            added: Components::default(),
+        };
+        if let Some(components) = &self.components {
+            let components = serde_json::to_value(components)?;
+            for (section, items) in components.as_object().into_iter().flatten() {
+                for name in items.as_object().into_iter().flat_map(|items| items.keys()) {
+                    bundler.taken.insert((section.clone(), name.clone()));
+                }
+            }
+        }
+        self.walk(&mut bundler)?;
+
+        let added = &mut bundler.added;
+        let components = self.components.get_or_insert_with(Components::default);
+        merge::<Schema>(components, added);
+        merge::<Response>(components, added);
+        merge::<Parameter>(components, added);
+        merge::<Example>(components, added);
+        merge::<RequestBody>(components, added);
+        merge::<Header>(components, added);
+        merge::<SecurityScheme>(components, added);
+        merge::<Link>(components, added);
+        merge::<Callback>(components, added);
+        Ok(())
+    }
+}
+
+struct Bundler<'a> {
+    set: &'a DocumentSet,
+    /// The document the visited references are relative to.
+    base: String,
+    /// The local reference given to each external object, by its location.
+    names: BTreeMap<String, String>,
+    /// The section and name of every component.
+    taken: BTreeSet<(String, String)>,
+    added: Components,
+}
+
+impl Bundler<'_> {
+    /// A component name for the object at `pointer` in `document`, unused in the section of `T`.
+    fn name<T: Component>(&mut self, document: &str, pointer: &str) -> String {
+        let name = match pointer.rsplit('/').next() {
+            Some(token) if !token.is_empty() => pointer::unescape(token),
+            _ => Path::new(document).file_stem().map_or_else(
+                || document.to_string(),
+                |stem| stem.to_string_lossy().into_owned(),
+            ),
+        };
+        let name: String = name
+            .chars()
+            .map(|c| match c {
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '-' | '_' => c,
+                _ => '_',
+            })
+            .collect();
+        let mut candidate = name.clone();
+        let mut suffix = 1;
+        while !self
+            .taken
+            .insert((T::SECTION.to_string(), candidate.clone()))
+        {
+            suffix += 1;
+            candidate = format!("{}{}", name, suffix);
+        }
+        candidate
+    }
+}
+
+impl VisitMut for Bundler<'_> {
+    fn visit_ref<T: Component>(&mut self, item: &mut RefOrObject<T>) -> Result<(), Error> {
+        let reference = match item {
+            RefOrObject::Ref(Reference { reference }) => reference,
+            RefOrObject::Object(_) => return walk_ref(self, item),
+        };
+        let target = self.set.lookup(&self.base, reference)?;
+        if target.document == self.set.root() {
+            *reference = format!("#{}", target.pointer);
+            return Ok(());
+        }
+        let location = format!("{}#{}", target.document, target.pointer);
+        if let Some(local) = self.names.get(&location) {
+            *reference = local.clone();
+            return Ok(());
+        }
+
+        let name = self.name::<T>(target.document, &target.pointer);
+        let local = format!("#/components/{}/{}", T::SECTION, pointer::escape(&name));
+        self.names.insert(location, local.clone());
+        *reference = local;
+
+        let mut object: RefOrObject<T> = target.deserialize()?;
+        let base = mem::replace(&mut self.base, target.document.to_string());
+        object.walk(self)?;
+        self.base = base;
+        T::section(&mut self.added)
+            .get_or_insert_with(BTreeMap::new)
+            .insert(name, object);
+        Ok(())
+    }
+
+    fn visit_path_item(&mut self, item: &mut PathItem) -> Result<(), Error> {
+        let Some(reference) = &item.reference else {
+            return walk_path_item(self, item);
+        };
+        let target = self.set.lookup(&self.base, reference)?;
+        if target.document == self.set.root() {
+            item.reference = Some(format!("#{}", target.pointer));
+            return walk_path_item(self, item);
+        }
+        let target = self.set.resolve_value(&self.base, reference)?;
+        *item = target.deserialize()?;
+        let base = mem::replace(&mut self.base, target.document.to_string());
+        walk_path_item(self, item)?;
+        self.base = base;
+        Ok(())
+    }
+}
+
+fn merge<T: Component>(into: &mut Components, from: &mut Components) {
+    if let Some(items) = T::section(from).take() {
+        T::section(into)
+            .get_or_insert_with(BTreeMap::new)
+            .extend(items);
+    }
+}
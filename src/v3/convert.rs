@@ -0,0 +1,1029 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde_json::{Map, Value};
+
+use crate::de::SIMPLE_FIELDS;
+use crate::pointer::{escape, join, unescape};
+use crate::resolve::Navigate;
+use crate::v2;
+
+use super::schema::*;
+
+/// Media types whose request body is sent as form fields, which Swagger 2.0 describes as `formData` parameters.
+const FORM_MEDIA_TYPES: &[&str] = &["application/x-www-form-urlencoded", "multipart/form-data"];
+
+/// A part of a document which a conversion dropped or rewrote.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Note {
+    /// The JSON pointer of the part in the source document.
+    pub pointer: String,
+    /// What happened to it.
+    pub message: String,
+}
+
+impl OpenApi {
+    /// Convert the document to Swagger 2.0, with a note for each part which Swagger 2.0 cannot describe.
+    ///
+    /// - the first server gives `host`, `basePath` and `schemes`;
+    /// - `components` move back to `definitions`, `parameters`, `responses` and `securityDefinitions`;
+    /// - a request body becomes a `body` parameter, or `formData` parameters for form media types;
+    /// - the media types of the content become `consumes` and `produces`.
+    ///
+    /// `oneOf`, `anyOf`, `not`, callbacks, links, cookie parameters and the security schemes other than basic, API key and OAuth2
+    /// have no equivalent and are dropped, as are the security requirements naming such schemes. External references are
+    /// kept as is.
+    pub fn to_v2(&self) -> (v2::Swagger, Vec<Note>) {
+        let mut converter = Converter {
+            openapi: self,
+            body_names: body_names(self.components.as_ref()),
+            dropped_schemes: BTreeSet::new(),
+            notes: Vec::new(),
+        };
+        let swagger = converter.swagger();
+        (swagger, converter.notes)
+    }
+}
+
+struct Converter<'a> {
+    openapi: &'a OpenApi,
+    /// The name of the `body` parameter of each request body of the components, which share the `parameters` section
+    /// with the parameters.
+    body_names: BTreeMap<String, String>,
+    /// The security schemes which have no equivalent, whose requirements are dropped.
+    dropped_schemes: BTreeSet<String>,
+    notes: Vec<Note>,
+}
+
+impl<'a> Converter<'a> {
+    fn note(&mut self, pointer: &str, message: impl Into<String>) {
+        self.notes.push(Note {
+            pointer: pointer.to_string(),
+            message: message.into(),
+        });
+    }
+
+    fn dropped(&mut self, pointer: &str, what: &str) {
+        self.note(
+            pointer,
+            format!("{} has no equivalent in Swagger 2.0 and was dropped", what),
+        );
+    }
+
+    /// The object a local reference points to, external ones are unknown.
+    fn resolve<T: Navigate>(&self, item: &'a RefOrObject<T>) -> Option<&'a T> {
+        self.openapi.resolve(item).ok()
+    }
+
+    /// Rewrite a local reference to the components of the document to the matching Swagger 2.0 section.
+    ///
+    /// The request bodies and responses of the components which are references are dropped, so the references to them
+    /// point to their targets. External references are kept as is, as the documents they point to are not converted.
+    fn reference(&mut self, reference: &str, pointer: &str) -> String {
+        let reference = self.target(reference);
+        let Some(fragment) = reference.strip_prefix('#') else {
+            self.note(
+                pointer,
+                format!(
+                    "the external reference `{}` was kept as is, the document it points to is not converted",
+                    reference
+                ),
+            );
+            return reference;
+        };
+        if let Some(name) = fragment.strip_prefix("/components/requestBodies/") {
+            let name = unescape(name);
+            let name = self.body_names.get(&name).unwrap_or(&name);
+            return format!("#/parameters/{}", escape(name));
+        }
+        let sections = [
+            ("/components/schemas/", "/definitions/"),
+            ("/components/parameters/", "/parameters/"),
+            ("/components/responses/", "/responses/"),
+        ];
+        let fragment = sections
+            .iter()
+            .find_map(|(from, to)| {
+                fragment
+                    .strip_prefix(from)
+                    .map(|name| format!("{}{}", to, name))
+            })
+            .unwrap_or_else(|| fragment.to_string());
+        format!("#{}", fragment)
+    }
+
+    /// The reference to the request body or response a component which is a reference stands for, following the
+    /// references between components.
+    fn target(&self, reference: &str) -> String {
+        let mut reference = reference.to_string();
+        let mut seen = BTreeSet::new();
+        while seen.insert(reference.clone()) {
+            let Some(fragment) = reference.strip_prefix('#') else {
+                break;
+            };
+            let components = self.openapi.components.as_ref();
+            let next = if let Some(name) = fragment.strip_prefix("/components/requestBodies/") {
+                match components
+                    .and_then(|components| components.request_bodies.as_ref()?.get(&unescape(name)))
+                {
+                    Some(RefOrObject::Ref(reference)) => Some(&reference.reference),
+                    _ => None,
+                }
+            } else if let Some(name) = fragment.strip_prefix("/components/responses/") {
+                match components
+                    .and_then(|components| components.responses.as_ref()?.get(&unescape(name)))
+                {
+                    Some(RefOrObject::Ref(reference)) => Some(&reference.reference),
+                    _ => None,
+                }
+            } else {
+                None
+            };
+            match next {
+                Some(next) => reference = next.clone(),
+                None => break,
+            }
+        }
+        reference
+    }
+
+    fn swagger(&mut self) -> v2::Swagger {
+        let openapi = self.openapi;
+        let (host, base_path, schemes) =
+            self.servers(openapi.servers.as_deref().unwrap_or_default());
+        let mut swagger = v2::Swagger {
+            swagger: "2.0".to_string(),
+            info: info(&openapi.info),
+            host,
+            base_path,
+            schemes,
+            tags: openapi
+                .tags
+                .as_ref()
+                .map(|tags| tags.iter().map(tag).collect()),
+            external_docs: openapi.external_docs.as_ref().map(external_doc),
+            extensions: openapi.extensions.clone(),
+            ..Default::default()
+        };
+        // The components come first, so that the security schemes which are dropped are known to the requirements.
+        if let Some(components) = &openapi.components {
+            self.components(components, &mut swagger);
+        }
+        swagger.paths = openapi
+            .paths
+            .iter()
+            .map(|(path, item)| {
+                let pointer = join("/paths", path);
+                (path.clone(), self.path_item(item, &pointer))
+            })
+            .collect();
+        swagger.security = openapi
+            .security
+            .as_ref()
+            .map(|security| self.requirements(security, "/security"));
+        swagger
+    }
+
+    /// The host, base path and schemes of the first server, and the schemes of the other servers at the same location.
+    fn servers(
+        &mut self,
+        servers: &'a [Server],
+    ) -> (
+        Option<String>,
+        Option<String>,
+        Option<Vec<v2::TransferProtocol>>,
+    ) {
+        let mut location: Option<(Option<String>, String)> = None;
+        let mut schemes = Vec::new();
+        for (index, server) in servers.iter().enumerate() {
+            let pointer = join("/servers", &index.to_string());
+            let mut url = server.url.clone();
+            for (name, variable) in server.variables.iter().flatten() {
+                url = url.replace(&format!("{{{}}}", name), &variable.default);
+            }
+            let (scheme, rest) = match url.split_once("://") {
+                Some((scheme, rest)) => (Some(scheme.to_string()), rest.to_string()),
+                None => (None, url.strip_prefix("//").unwrap_or(&url).to_string()),
+            };
+            let (host, path) = if scheme.is_some() || url.starts_with("//") {
+                let end = rest.find('/').unwrap_or(rest.len());
+                (Some(rest[..end].to_string()), rest[end..].to_string())
+            } else {
+                (None, rest)
+            };
+            match &location {
+                None => location = Some((host, path)),
+                Some(first) if first == &(host.clone(), path.clone()) => {}
+                Some(_) => {
+                    self.note(
+                        &pointer,
+                        "Swagger 2.0 has a single host and base path, only the first server's were kept",
+                    );
+                    continue;
+                }
+            }
+            match scheme.as_deref() {
+                Some("http") => schemes.push(v2::TransferProtocol::Http),
+                Some("https") => schemes.push(v2::TransferProtocol::Https),
+                Some("ws") => schemes.push(v2::TransferProtocol::Ws),
+                Some("wss") => schemes.push(v2::TransferProtocol::Wss),
+                Some(scheme) => self.dropped(&pointer, &format!("the `{}` scheme", scheme)),
+                None => {}
+            }
+        }
+        let (host, path) = location.unwrap_or_default();
+        (
+            host,
+            Some(path).filter(|path| !path.is_empty()),
+            Some(schemes).filter(|schemes| !schemes.is_empty()),
+        )
+    }
+
+    fn components(&mut self, components: &'a Components, swagger: &mut v2::Swagger) {
+        let pointer = "/components";
+        swagger.definitions = components.schemas.as_ref().map(|schemas| {
+            schemas
+                .iter()
+                .map(|(name, schema)| {
+                    let pointer = join(&join(pointer, "schemas"), name);
+                    (name.clone(), self.schema(schema, &pointer))
+                })
+                .collect()
+        });
+
+        let mut parameters = BTreeMap::new();
+        for (name, parameter) in components.parameters.iter().flatten() {
+            let pointer = join(&join(pointer, "parameters"), name);
+            if let Some(v2::ParameterOrRef::Parameter(parameter)) =
+                self.parameter(parameter, &pointer)
+            {
+                parameters.insert(name.clone(), parameter);
+            }
+        }
+        for (name, body) in components.request_bodies.iter().flatten() {
+            let pointer = join(&join(pointer, "requestBodies"), name);
+            // Form bodies are inlined into the operations which use them as `formData` parameters.
+            if let Some(RequestBody { content, .. }) = self.resolve(body) {
+                if is_form(content) {
+                    continue;
+                }
+            }
+            let RefOrObject::Object(body) = body else {
+                self.note(
+                    &pointer,
+                    "a request body which is a reference was dropped, the references to it point to its target",
+                );
+                continue;
+            };
+            let renamed = self.body_names[name].clone();
+            if &renamed != name {
+                self.note(
+                    &pointer,
+                    format!(
+                        "a parameter has the same name, the body parameter was renamed `{}`",
+                        renamed
+                    ),
+                );
+            }
+            if let Some(parameter) = self.body(&renamed, body, &pointer) {
+                parameters.insert(renamed, parameter);
+            }
+        }
+        swagger.parameters = Some(parameters).filter(|parameters| !parameters.is_empty());
+
+        swagger.responses = components.responses.as_ref().map(|responses| {
+            responses
+                .iter()
+                .filter_map(|(name, response)| {
+                    let pointer = join(&join(pointer, "responses"), name);
+                    match self.response(response, &pointer, &mut BTreeSet::new()) {
+                        v2::ResponseOrRef::Response(response) => Some((name.clone(), response)),
+                        v2::ResponseOrRef::Ref(_) => {
+                            self.note(
+                                &pointer,
+                                "a response which is a reference was dropped, the references to it point to its target",
+                            );
+                            None
+                        }
+                    }
+                })
+                .collect()
+        });
+
+        let mut definitions = BTreeMap::new();
+        for (name, scheme) in components.security_schemes.iter().flatten() {
+            let pointer = join(&join(pointer, "securitySchemes"), name);
+            let converted = match self.resolve(scheme) {
+                Some(scheme) => self.security_scheme(scheme, &pointer),
+                None => {
+                    self.dropped(&pointer, "an external security scheme");
+                    None
+                }
+            };
+            match converted {
+                Some(scheme) => {
+                    definitions.insert(name.clone(), scheme);
+                }
+                None => {
+                    self.dropped_schemes.insert(name.clone());
+                }
+            }
+        }
+        swagger.security_definitions =
+            Some(definitions).filter(|definitions| !definitions.is_empty());
+
+        for (section, present) in [
+            ("examples", components.examples.is_some()),
+            ("headers", components.headers.is_some()),
+            ("links", components.links.is_some()),
+            ("callbacks", components.callbacks.is_some()),
+        ] {
+            if present {
+                self.dropped(
+                    &join(pointer, section),
+                    &format!("`components/{}`", section),
+                );
+            }
+        }
+    }
+
+    fn path_item(&mut self, item: &'a PathItem, pointer: &str) -> v2::PathItem {
+        if item.summary.is_some() || item.description.is_some() {
+            self.dropped(pointer, "the summary and description of a path item");
+        }
+        if item.servers.is_some() {
+            self.dropped(&join(pointer, "servers"), "the servers of a path item");
+        }
+        if item.trace.is_some() {
+            self.dropped(&join(pointer, "trace"), "a TRACE operation");
+        }
+        let reference = item
+            .reference
+            .as_deref()
+            .map(|reference| self.reference(reference, pointer));
+        let mut operation = |operation: &'a Option<Operation>, method: &str| {
+            operation
+                .as_ref()
+                .map(|operation| self.operation(operation, &join(pointer, method)))
+        };
+        v2::PathItem {
+            reference,
+            get: operation(&item.get, "get"),
+            put: operation(&item.put, "put"),
+            post: operation(&item.post, "post"),
+            delete: operation(&item.delete, "delete"),
+            options: operation(&item.options, "options"),
+            head: operation(&item.head, "head"),
+            patch: operation(&item.patch, "patch"),
+            parameters: self.parameters(item.parameters.as_deref(), pointer),
+        }
+    }
+
+    fn parameters(
+        &mut self,
+        parameters: Option<&'a [RefOrObject<Parameter>]>,
+        pointer: &str,
+    ) -> Option<Vec<v2::ParameterOrRef>> {
+        let pointer = join(pointer, "parameters");
+        let parameters: Vec<_> = parameters
+            .unwrap_or_default()
+            .iter()
+            .enumerate()
+            .filter_map(|(index, parameter)| {
+                self.parameter(parameter, &join(&pointer, &index.to_string()))
+            })
+            .collect();
+        Some(parameters).filter(|parameters| !parameters.is_empty())
+    }
+
+    fn operation(&mut self, operation: &'a Operation, pointer: &str) -> v2::Operation {
+        let mut parameters = self
+            .parameters(operation.parameters.as_deref(), pointer)
+            .unwrap_or_default();
+        let mut consumes = None;
+        if let Some(body) = &operation.request_body {
+            let pointer = join(pointer, "requestBody");
+            match self.resolve(body) {
+                Some(resolved) => {
+                    consumes = Some(resolved.content.keys().cloned().collect());
+                    match body {
+                        RefOrObject::Ref(reference) if !is_form(&resolved.content) => parameters
+                            .push(v2::ParameterOrRef::Ref(v2::Reference {
+                                reference: self.reference(&reference.reference, &pointer),
+                            })),
+                        _ if is_form(&resolved.content) => {
+                            parameters.extend(self.form(resolved, &pointer))
+                        }
+                        _ => parameters.extend(
+                            self.body("body", resolved, &pointer)
+                                .map(v2::ParameterOrRef::Parameter),
+                        ),
+                    }
+                }
+                None => self.dropped(&pointer, "an external request body"),
+            }
+        }
+
+        let mut produces = BTreeSet::new();
+        let responses = operation
+            .responses
+            .iter()
+            .map(|(status, response)| {
+                let pointer = join(&join(pointer, "responses"), status);
+                (
+                    status.clone(),
+                    self.response(response, &pointer, &mut produces),
+                )
+            })
+            .collect();
+        if operation.callbacks.is_some() {
+            self.dropped(&join(pointer, "callbacks"), "callbacks");
+        }
+        if operation.servers.is_some() {
+            self.dropped(&join(pointer, "servers"), "the servers of an operation");
+        }
+
+        v2::Operation {
+            tags: operation.tags.clone(),
+            summary: operation.summary.clone(),
+            description: operation.description.clone(),
+            external_docs: operation.external_docs.as_ref().map(external_doc),
+            operation_id: operation.operation_id.clone(),
+            consumes,
+            produces: Some(produces.into_iter().collect::<Vec<_>>())
+                .filter(|produces| !produces.is_empty()),
+            parameters: Some(parameters).filter(|parameters| !parameters.is_empty()),
+            responses,
+            schemes: None,
+            deprecated: operation.deprecated,
+            security: operation
+                .security
+                .as_ref()
+                .map(|security| self.requirements(security, &join(pointer, "security"))),
+            extensions: operation.extensions.clone(),
+        }
+    }
+
+    /// Convert a parameter, which is dropped if it is a cookie or cannot be described without a schema.
+    fn parameter(
+        &mut self,
+        parameter: &'a RefOrObject<Parameter>,
+        pointer: &str,
+    ) -> Option<v2::ParameterOrRef> {
+        let resolved = self.resolve(parameter);
+        if resolved.is_some_and(|parameter| parameter.r#in == "cookie") {
+            self.dropped(pointer, "a cookie parameter");
+            return None;
+        }
+        match parameter {
+            RefOrObject::Ref(reference) => Some(v2::ParameterOrRef::Ref(v2::Reference {
+                reference: self.reference(&reference.reference, pointer),
+            })),
+            RefOrObject::Object(parameter) => {
+                let mut fields = Map::new();
+                if let Some(schema) = &parameter.schema {
+                    match self.resolve(schema) {
+                        Some(schema) => fields = self.simple(schema, &join(pointer, "schema")),
+                        None => {
+                            self.dropped(&join(pointer, "schema"), "an external parameter schema")
+                        }
+                    }
+                } else if parameter.content.is_some() {
+                    self.dropped(&join(pointer, "content"), "the content of a parameter");
+                }
+                if fields.get("type") == Some(&Value::from("array")) {
+                    let format = match (parameter.style.as_deref(), parameter.explode) {
+                        (Some("form") | None, None | Some(true)) if parameter.r#in == "query" => {
+                            Some("multi")
+                        }
+                        (Some("spaceDelimited"), _) => Some("ssv"),
+                        (Some("pipeDelimited"), _) => Some("pipes"),
+                        (Some("form" | "simple") | None, _) => Some("csv"),
+                        (Some(style), _) => {
+                            self.dropped(pointer, &format!("the `{}` style", style));
+                            None
+                        }
+                    };
+                    if let Some(format) = format {
+                        fields.insert("collectionFormat".to_string(), Value::from(format));
+                    }
+                }
+                fields.insert("name".to_string(), Value::from(parameter.name.clone()));
+                fields.insert("in".to_string(), Value::from(parameter.r#in.clone()));
+                insert(&mut fields, "description", &parameter.description);
+                insert(&mut fields, "required", &parameter.required);
+                insert(&mut fields, "allowEmptyValue", &parameter.allow_empty_value);
+                extend(&mut fields, &parameter.extensions);
+                self.object(fields, pointer)
+                    .map(v2::ParameterOrRef::Parameter)
+            }
+        }
+    }
+
+    /// A `body` parameter holding the schema of the preferred media type.
+    fn body(&mut self, name: &str, body: &'a RequestBody, pointer: &str) -> Option<v2::Parameter> {
+        let mut fields = Map::new();
+        fields.insert("name".to_string(), Value::from(name));
+        fields.insert("in".to_string(), Value::from("body"));
+        insert(&mut fields, "description", &body.description);
+        insert(&mut fields, "required", &body.required);
+        extend(&mut fields, &body.extensions);
+        let pointer = join(pointer, "content");
+        if let Some((
+            media_type,
+            Media {
+                schema: Some(schema),
+                ..
+            },
+        )) = self.media(&body.content, &pointer)
+        {
+            let schema = self.schema(schema, &join(&join(&pointer, media_type), "schema"));
+            fields.insert(
+                "schema".to_string(),
+                serde_json::to_value(schema).unwrap_or_default(),
+            );
+        }
+        self.object(fields, &pointer)
+    }
+
+    /// A `formData` parameter for each property of the schema of a form body.
+    fn form(&mut self, body: &'a RequestBody, pointer: &str) -> Vec<v2::ParameterOrRef> {
+        let pointer = join(pointer, "content");
+        let Some((media_type, media)) = self.media(&body.content, &pointer) else {
+            return Vec::new();
+        };
+        let pointer = join(&join(&pointer, media_type), "schema");
+        let Some(schema) = media
+            .schema
+            .as_ref()
+            .and_then(|schema| self.resolve(schema))
+        else {
+            self.dropped(&pointer, "a form body without an object schema");
+            return Vec::new();
+        };
+        let required = schema.required.as_deref().unwrap_or_default();
+        let mut parameters = Vec::new();
+        for (name, property) in schema.properties.iter().flatten() {
+            let pointer = join(&join(&pointer, "properties"), name);
+            let Some(property) = self.resolve(property) else {
+                self.dropped(&pointer, "an external form field schema");
+                continue;
+            };
+            let mut fields = self.simple(property, &pointer);
+            if property.format.as_deref() == Some("binary") {
+                fields.insert("type".to_string(), Value::from("file"));
+                fields.remove("format");
+            }
+            fields.insert("name".to_string(), Value::from(name.clone()));
+            fields.insert("in".to_string(), Value::from("formData"));
+            insert(&mut fields, "description", &property.description);
+            if required.contains(name) {
+                fields.insert("required".to_string(), Value::from(true));
+            }
+            parameters.extend(
+                self.object(fields, &pointer)
+                    .map(v2::ParameterOrRef::Parameter),
+            );
+        }
+        parameters
+    }
+
+    /// The fields of a schema which describe a primitive value or an array, as held by parameters, items and headers.
+    fn simple(&mut self, schema: &'a Schema, pointer: &str) -> Map<String, Value> {
+        if schema.r#type.as_deref() == Some("object") || schema.properties.is_some() {
+            self.note(
+                pointer,
+                "Swagger 2.0 describes only primitive values and arrays outside of a body, the object schema was dropped",
+            );
+            return Map::new();
+        }
+        let mut fields = match serde_json::to_value(schema) {
+            Ok(Value::Object(fields)) => fields,
+            _ => Map::new(),
+        };
+        fields.retain(|key, _| key != "items" && SIMPLE_FIELDS.contains(&key.as_str()));
+        if let Some(items) = &schema.items {
+            match self.resolve(items) {
+                Some(items) => {
+                    let items = self.simple(items, &join(pointer, "items"));
+                    fields.insert("items".to_string(), Value::Object(items));
+                }
+                None => self.dropped(&join(pointer, "items"), "an external items schema"),
+            }
+        }
+        fields
+    }
+
+    /// Deserialize the fields gathered for a Swagger 2.0 object, noting why it was dropped if they do not describe one.
+    fn object<T: serde::de::DeserializeOwned>(
+        &mut self,
+        fields: Map<String, Value>,
+        pointer: &str,
+    ) -> Option<T> {
+        match serde_json::from_value(Value::Object(fields)) {
+            Ok(object) => Some(object),
+            Err(e) => {
+                self.note(
+                    pointer,
+                    format!("could not be converted and was dropped: {}", e),
+                );
+                None
+            }
+        }
+    }
+
+    /// The media type whose schema is kept, preferably JSON, noting the other schemas which are dropped.
+    fn media(
+        &mut self,
+        content: &'a BTreeMap<String, Media>,
+        pointer: &str,
+    ) -> Option<(&'a String, &'a Media)> {
+        let (media_type, media) = content
+            .iter()
+            .find(|(media_type, _)| media_type.starts_with("application/json"))
+            .or_else(|| content.iter().next())?;
+        for (other, other_media) in content {
+            if other_media.schema.is_some() && other_media.schema != media.schema {
+                self.note(
+                    &join(&join(pointer, other), "schema"),
+                    format!("Swagger 2.0 has one schema for all media types, the schema of `{}` was kept", media_type),
+                );
+            }
+        }
+        Some((media_type, media))
+    }
+
+    /// Convert a response, adding the media types of its content to `produces`.
+    fn response(
+        &mut self,
+        response: &'a RefOrObject<Response>,
+        pointer: &str,
+        produces: &mut BTreeSet<String>,
+    ) -> v2::ResponseOrRef {
+        if let Some(resolved) = self.resolve(response) {
+            produces.extend(resolved.content.iter().flat_map(BTreeMap::keys).cloned());
+        }
+        let response = match response {
+            RefOrObject::Ref(reference) => {
+                return v2::ResponseOrRef::Ref(v2::Reference {
+                    reference: self.reference(&reference.reference, pointer),
+                })
+            }
+            RefOrObject::Object(response) => response,
+        };
+        if response.links.is_some() {
+            self.dropped(&join(pointer, "links"), "links");
+        }
+
+        let mut schema = None;
+        let mut examples = BTreeMap::new();
+        if let Some(content) = &response.content {
+            let pointer = join(pointer, "content");
+            if let Some((
+                media_type,
+                Media {
+                    schema: Some(kept), ..
+                },
+            )) = self.media(content, &pointer)
+            {
+                schema = Some(self.schema(kept, &join(&join(&pointer, media_type), "schema")));
+            }
+            for (media_type, media) in content {
+                if let Some(example) = &media.example {
+                    examples.insert(media_type.clone(), example.clone());
+                }
+            }
+        }
+
+        let mut headers = BTreeMap::new();
+        for (name, header) in response.headers.iter().flatten() {
+            let pointer = join(&join(pointer, "headers"), name);
+            let Some(header) = self.resolve(header) else {
+                self.dropped(&pointer, "an external header");
+                continue;
+            };
+            let mut fields = match header
+                .schema
+                .as_ref()
+                .and_then(|schema| self.resolve(schema))
+            {
+                Some(schema) => self.simple(schema, &join(&pointer, "schema")),
+                None => Map::new(),
+            };
+            // Swagger 2.0 requires the type of a header.
+            fields
+                .entry("type")
+                .or_insert_with(|| Value::from("string"));
+            insert(&mut fields, "description", &header.description);
+            extend(&mut fields, &header.extensions);
+            if let Some(header) = self.object(fields, &pointer) {
+                headers.insert(name.clone(), header);
+            }
+        }
+
+        v2::ResponseOrRef::Response(v2::Response {
+            description: response.description.clone(),
+            schema,
+            headers: Some(headers).filter(|headers| !headers.is_empty()),
+            examples: Some(examples).filter(|examples| !examples.is_empty()),
+            extensions: response.extensions.clone(),
+        })
+    }
+
+    fn schema(&mut self, schema: &'a RefOrObject<Schema>, pointer: &str) -> v2::Schema {
+        let schema = match schema {
+            RefOrObject::Ref(reference) => {
+                return v2::Schema {
+                    reference: Some(self.reference(&reference.reference, pointer)),
+                    ..Default::default()
+                }
+            }
+            RefOrObject::Object(schema) => schema,
+        };
+        for (field, present) in [
+            ("oneOf", schema.one_of.is_some()),
+            ("anyOf", schema.any_of.is_some()),
+            ("not", schema.not.is_some()),
+            ("writeOnly", schema.write_only.is_some()),
+            ("deprecated", schema.deprecated.is_some()),
+        ] {
+            if present {
+                self.dropped(&join(pointer, field), &format!("`{}`", field));
+            }
+        }
+        if schema
+            .discriminator
+            .as_ref()
+            .is_some_and(|discriminator| discriminator.mapping.is_some())
+        {
+            self.dropped(
+                &join(pointer, "discriminator/mapping"),
+                "a discriminator mapping",
+            );
+        }
+
+        let mut extensions = schema.extensions.all().clone();
+        // The usual extension for what OpenAPI 3.0 calls `nullable`.
+        if let Some(nullable) = schema.nullable {
+            extensions.insert("x-nullable".to_string(), Value::from(nullable));
+        }
+        v2::Schema {
+            reference: None,
+            original_ref: None,
+            title: schema.title.clone(),
+            description: schema.description.clone(),
+            r#type: schema.r#type.clone(),
+            format: schema.format.clone(),
+            r#enum: schema.r#enum.clone(),
+            required: schema.required.clone(),
+            default: schema.default.clone(),
+            maximum: schema.maximum.clone(),
+            exclusive_maximum: schema.exclusive_maximum,
+            minimum: schema.minimum.clone(),
+            exclusive_minimum: schema.exclusive_minimum,
+            max_length: schema.max_length.clone(),
+            min_length: schema.min_length.clone(),
+            pattern: schema.pattern.clone(),
+            max_items: schema.max_items.clone(),
+            min_items: schema.min_items.clone(),
+            unique_items: schema.unique_items,
+            multiple_of: schema.multiple_of.clone(),
+            items: schema
+                .items
+                .as_deref()
+                .map(|items| Box::new(self.schema(items, &join(pointer, "items")))),
+            properties: schema.properties.as_ref().map(|properties| {
+                properties
+                    .iter()
+                    .map(|(name, property)| {
+                        let pointer = join(&join(pointer, "properties"), name);
+                        (name.clone(), self.schema(property, &pointer))
+                    })
+                    .collect()
+            }),
+            additional_properties: schema.additional_properties.as_ref().map(|additional| {
+                match additional {
+                    AdditionalProperties::Bool(allowed) => Value::Bool(*allowed),
+                    AdditionalProperties::Schema(additional) => {
+                        let additional =
+                            self.schema(additional, &join(pointer, "additionalProperties"));
+                        serde_json::to_value(additional).unwrap_or_default()
+                    }
+                }
+            }),
+            discriminator: schema
+                .discriminator
+                .as_ref()
+                .map(|discriminator| discriminator.property_name.clone()),
+            read_only: schema.read_only,
+            xml: schema.xml.as_ref().map(|xml| v2::XML {
+                name: xml.name.clone(),
+                namespace: xml.namespace.clone(),
+                prefix: xml.prefix.clone(),
+                attribute: xml.attribute,
+                wrapped: xml.wrapped,
+            }),
+            external_docs: schema.external_docs.as_ref().map(external_doc),
+            example: schema.example.clone(),
+            all_of: schema.all_of.as_ref().map(|all_of| {
+                all_of
+                    .iter()
+                    .enumerate()
+                    .map(|(index, schema)| {
+                        let pointer = join(&join(pointer, "allOf"), &index.to_string());
+                        Box::new(self.schema(schema, &pointer))
+                    })
+                    .collect()
+            }),
+            extensions,
+        }
+    }
+
+    /// Convert security requirements, dropping those which name a dropped security scheme, as they cannot be met.
+    fn requirements(
+        &mut self,
+        security: &[SecurityRequirement],
+        pointer: &str,
+    ) -> Vec<v2::SecurityRequirementObject> {
+        let mut requirements = Vec::new();
+        for (index, requirement) in security.iter().enumerate() {
+            match requirement
+                .keys()
+                .find(|name| self.dropped_schemes.contains(*name))
+            {
+                Some(name) => self.note(
+                    &join(pointer, &index.to_string()),
+                    format!(
+                        "the security scheme `{}` was dropped, so was the requirement naming it",
+                        name
+                    ),
+                ),
+                None => requirements.push(v2::SecurityRequirementObject(requirement.clone())),
+            }
+        }
+        requirements
+    }
+
+    fn security_scheme(
+        &mut self,
+        scheme: &'a SecurityScheme,
+        pointer: &str,
+    ) -> Option<v2::SecurityScheme> {
+        let mut converted = v2::SecurityScheme {
+            r#type: v2::SecuritySchemeType::Basic,
+            description: scheme.description.clone(),
+            name: None,
+            r#in: None,
+            flow: None,
+            authorization_url: None,
+            token_url: None,
+            scopes: None,
+            extensions: scheme.extensions.clone(),
+        };
+        match scheme.r#type {
+            SecuritySchemeType::Http => {
+                if !scheme
+                    .scheme
+                    .as_deref()
+                    .is_some_and(|scheme| scheme.eq_ignore_ascii_case("basic"))
+                {
+                    self.dropped(pointer, "HTTP authentication other than basic");
+                    return None;
+                }
+            }
+            SecuritySchemeType::ApiKey => {
+                converted.r#type = v2::SecuritySchemeType::ApiKey;
+                converted.name = scheme.name.clone();
+                converted.r#in = match scheme.r#in.as_deref() {
+                    Some("query") => Some(v2::InEnum::Query),
+                    Some("header") => Some(v2::InEnum::Header),
+                    _ => {
+                        self.dropped(pointer, "an API key outside of the query or headers");
+                        return None;
+                    }
+                };
+            }
+            SecuritySchemeType::Oauth2 => {
+                converted.r#type = v2::SecuritySchemeType::Oauth2;
+                let flows = scheme.flows.clone().unwrap_or_default();
+                let mut flows = [
+                    (v2::Flow::Implicit, flows.implicit),
+                    (v2::Flow::Password, flows.password),
+                    (v2::Flow::Application, flows.client_credentials),
+                    (v2::Flow::AccessCode, flows.authorization_code),
+                ]
+                .into_iter()
+                .filter_map(|(kind, flow)| flow.map(|flow| (kind, flow)));
+                if let Some((kind, flow)) = flows.next() {
+                    converted.flow = Some(kind);
+                    converted.authorization_url = flow.authorization_url;
+                    converted.token_url = flow.token_url;
+                    converted.scopes = Some(flow.scopes);
+                }
+                if flows.next().is_some() {
+                    self.note(
+                        &join(pointer, "flows"),
+                        "Swagger 2.0 has one flow for each security scheme, only the first was kept",
+                    );
+                }
+            }
+            SecuritySchemeType::OpenIdConnect => {
+                self.dropped(pointer, "OpenID Connect");
+                return None;
+            }
+        }
+        Some(converted)
+    }
+}
+
+/// The name of the `body` parameter of each request body of the components: its own, or with a `Body` suffix when a
+/// parameter has the same name.
+fn body_names(components: Option<&Components>) -> BTreeMap<String, String> {
+    let Some(components) = components else {
+        return BTreeMap::new();
+    };
+    let parameters = components.parameters.as_ref();
+    let bodies = components.request_bodies.iter().flatten();
+    let mut taken: BTreeSet<String> = parameters
+        .into_iter()
+        .flat_map(BTreeMap::keys)
+        .chain(bodies.clone().map(|(name, _)| name))
+        .cloned()
+        .collect();
+    bodies
+        .map(|(name, _)| {
+            if !parameters.is_some_and(|parameters| parameters.contains_key(name)) {
+                return (name.clone(), name.clone());
+            }
+            let mut renamed = format!("{}Body", name);
+            let mut index = 1;
+            while taken.contains(&renamed) {
+                index += 1;
+                renamed = format!("{}Body{}", name, index);
+            }
+            taken.insert(renamed.clone());
+            (name.clone(), renamed)
+        })
+        .collect()
+}
+
+fn is_form(content: &BTreeMap<String, Media>) -> bool {
+    content.keys().any(|media_type| {
+        FORM_MEDIA_TYPES
+            .iter()
+            .any(|form| media_type.starts_with(form))
+    })
+}
+
+fn insert<T: serde::Serialize>(fields: &mut Map<String, Value>, key: &str, value: &Option<T>) {
+    if let Some(value) = value
+        .as_ref()
+        .and_then(|value| serde_json::to_value(value).ok())
+    {
+        fields.insert(key.to_string(), value);
+    }
+}
+
+fn extend(fields: &mut Map<String, Value>, extensions: &crate::Extensions) {
+    fields.extend(
+        extensions
+            .all()
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone())),
+    );
+}
+
+fn info(info: &Info) -> v2::Info {
+    v2::Info {
+        title: info.title.clone(),
+        description: info.description.clone(),
+        terms_of_service: info.terms_of_service.clone(),
+        contact: info.contact.as_ref().map(|contact| v2::Contact {
+            name: contact.name.clone(),
+            url: contact.url.clone(),
+            email: contact.email.clone(),
+            extensions: contact.extensions.clone(),
+        }),
+        license: info.license.as_ref().map(|license| v2::License {
+            name: license.name.clone(),
+            url: license.url.clone(),
+            extensions: license.extensions.clone(),
+        }),
+        version: info.version.clone(),
+        extensions: info.extensions.clone(),
+    }
+}
+
+fn tag(tag: &Tag) -> v2::Tag {
+    v2::Tag {
+        name: tag.name.clone(),
+        description: tag.description.clone(),
+        external_doc: tag.external_docs.as_ref().map(external_doc),
+    }
+}
+
+fn external_doc(doc: &ExternalDoc) -> v2::ExternalDoc {
+    v2::ExternalDoc {
+        description: doc.description.clone(),
+        url: doc.url.clone(),
+    }
+}
@@ -0,0 +1,164 @@
+use crate::error::Error;
+use crate::resolve::{CycleStrategy, Inlining};
+
+use super::schema::*;
+use super::visit::{walk_path_item, walk_ref, Component, VisitMut, Walk};
+
+impl OpenApi {
+    /// Replace every local reference with a copy of the object it points to.
+    ///
+    /// A reference back into an object being inlined, as in a recursive schema, is left in place or fails depending on `cycles`.
+    pub fn dereference(&mut self, cycles: CycleStrategy) -> Result<(), Error> {
+        let source = self.clone();
+        self.walk(&mut Dereferencer {
+            source: &source,
+            inlining: Inlining::new(cycles),
+        })
+    }
+}
+
+struct Dereferencer<'a> {
+    source: &'a OpenApi,
+    inlining: Inlining,
+}
+
+impl VisitMut for Dereferencer<'_> {
+    fn visit_ref<T: Component>(&mut self, item: &mut RefOrObject<T>) -> Result<(), Error> {
+        let reference = match item {
+            RefOrObject::Ref(Reference { reference }) => reference.clone(),
+            RefOrObject::Object(_) => return walk_ref(self, item),
+        };
+        if !self.inlining.enter(&reference)? {
+            return Ok(());
+        }
+        *item = RefOrObject::Object(self.source.resolve_reference::<T>(&reference)?.clone());
+        walk_ref(self, item)?;
+        self.inlining.leave();
+        Ok(())
+    }
+
+    fn visit_path_item(&mut self, item: &mut PathItem) -> Result<(), Error> {
+        let Some(reference) = item.reference.clone() else {
+            return walk_path_item(self, item);
+        };
+        if !self.inlining.enter(&reference)? {
+            return Ok(());
+        }
+        let target = self.source.resolve_reference::<PathItem>(&reference)?;
+        *item = self.source.resolve_path_item(target)?.clone();
+        walk_path_item(self, item)?;
+        self.inlining.leave();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn openapi() -> OpenApi {
+        serde_json::from_value(json!({
+            "openapi": "3.0.3",
+            "info": {"title": "pets", "version": "1.0.0"},
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "parameters": [{"$ref": "#/components/parameters/Limit"}],
+                        "responses": {
+                            "200": {
+                                "description": "pets",
+                                "content": {
+                                    "application/json": {
+                                        "schema": {"type": "array", "items": {"$ref": "#/components/schemas/Pet"}}
+                                    }
+                                }
+                            }
+                        }
+                    }
+                },
+                "/animals": {"$ref": "#/paths/~1pets"}
+            },
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "properties": {
+                            "name": {"type": "string"},
+                            "parent": {"$ref": "#/components/schemas/Pet"}
+                        },
+                        "additionalProperties": {"$ref": "#/components/schemas/Tag"}
+                    },
+                    "Tag": {"type": "string"}
+                },
+                "parameters": {
+                    "Limit": {"name": "limit", "in": "query", "schema": {"type": "integer"}}
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_dereference() {
+        let mut openapi = openapi();
+        openapi.dereference(CycleStrategy::Keep).unwrap();
+        let paths = serde_json::to_value(&openapi.paths).unwrap();
+        let get = &paths["/pets"]["get"];
+        assert_eq!(get["parameters"][0]["name"], "limit");
+        let pet = &get["responses"]["200"]["content"]["application/json"]["schema"]["items"];
+        assert_eq!(pet["properties"]["name"], json!({"type": "string"}));
+        assert_eq!(pet["additionalProperties"], json!({"type": "string"}));
+        assert_eq!(
+            pet["properties"]["parent"],
+            json!({"$ref": "#/components/schemas/Pet"})
+        );
+        assert_eq!(paths["/animals"], paths["/pets"]);
+    }
+
+    #[test]
+    fn test_dereference_cycle_fails() {
+        let mut openapi = openapi();
+        match openapi.dereference(CycleStrategy::Fail) {
+            Err(Error::CyclicReference { chain }) => assert_eq!(
+                chain,
+                vec![
+                    "#/paths/~1pets",
+                    "#/components/schemas/Pet",
+                    "#/components/schemas/Pet"
+                ]
+            ),
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_dereference_without_cycles() {
+        let mut openapi = openapi();
+        let pet = openapi
+            .components
+            .as_mut()
+            .unwrap()
+            .schemas
+            .as_mut()
+            .unwrap();
+        pet.remove("Pet");
+        pet.insert(
+            "Pet".to_string(),
+            serde_json::from_value(
+                json!({"properties": {"tag": {"$ref": "#/components/schemas/Tag"}}}),
+            )
+            .unwrap(),
+        );
+        openapi.dereference(CycleStrategy::Fail).unwrap();
+        let value = serde_json::to_value(&openapi).unwrap();
+        fn has_ref(value: &Value) -> bool {
+            match value {
+                Value::Object(map) => map.contains_key("$ref") || map.values().any(has_ref),
+                Value::Array(items) => items.iter().any(has_ref),
+                _ => false,
+            }
+        }
+        assert!(!has_ref(&value));
+    }
+}
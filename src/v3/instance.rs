@@ -0,0 +1,586 @@
+use serde_json::Value;
+
+use crate::instance::{error, Direction, InstanceError, Keywords};
+use crate::pointer::{join, split_reference};
+
+use super::schema::*;
+
+impl OpenApi {
+    /// Check an instance, such as the payload of a request or a response, against a schema of the document.
+    ///
+    /// Every broken keyword is reported, an empty list means the instance is valid. `$ref`s are followed through the
+    /// document, and the `direction` decides whether `readOnly` or `writeOnly` properties are forbidden.
+    ///
+    /// ```
+    /// # use openapi_schema::instance::Direction;
+    /// # use openapi_schema::v3::{OpenApi, RefOrObject, Reference, Schema};
+    /// # fn example(openapi: &OpenApi) {
+    /// let pet = RefOrObject::<Schema>::Ref(Reference {
+    ///     reference: "#/components/schemas/Pet".to_string(),
+    /// });
+    /// let payload = serde_json::json!({"name": "Rex"});
+    /// for error in openapi.validate_instance(&pet, &payload, Direction::Request) {
+    ///     println!("{}", error);
+    /// }
+    /// # }
+    /// ```
+    pub fn validate_instance(
+        &self,
+        schema: &RefOrObject<Schema>,
+        instance: &Value,
+        direction: Direction,
+    ) -> Vec<InstanceError> {
+        InstanceValidator::new(self, direction).validate(schema, instance, "", "")
+    }
+}
+
+/// Checks instances against the schemas of a document.
+pub(crate) struct InstanceValidator<'a> {
+    openapi: &'a OpenApi,
+    direction: Direction,
+    /// The references being followed, with the instance they are applied to, to stop at cycles.
+    stack: Vec<(String, String)>,
+}
+
+impl<'a> InstanceValidator<'a> {
+    pub(crate) fn new(openapi: &'a OpenApi, direction: Direction) -> Self {
+        InstanceValidator {
+            openapi,
+            direction,
+            stack: Vec::new(),
+        }
+    }
+
+    /// Check the instance found at `instance_path` against the schema found at `schema_path`.
+    pub(crate) fn validate(
+        &mut self,
+        schema: &RefOrObject<Schema>,
+        instance: &Value,
+        instance_path: &str,
+        schema_path: &str,
+    ) -> Vec<InstanceError> {
+        let mut errors = Vec::new();
+        self.item(schema, instance, instance_path, schema_path, &mut errors);
+        errors
+    }
+
+    fn item(
+        &mut self,
+        item: &RefOrObject<Schema>,
+        instance: &Value,
+        instance_path: &str,
+        schema_path: &str,
+        errors: &mut Vec<InstanceError>,
+    ) {
+        match item {
+            RefOrObject::Object(schema) => {
+                self.schema(schema, instance, instance_path, schema_path, errors)
+            }
+            RefOrObject::Ref(Reference { reference }) => {
+                self.reference(reference, instance, instance_path, schema_path, errors)
+            }
+        }
+    }
+
+    fn reference(
+        &mut self,
+        reference: &str,
+        instance: &Value,
+        instance_path: &str,
+        schema_path: &str,
+        errors: &mut Vec<InstanceError>,
+    ) {
+        let key = (reference.to_string(), instance_path.to_string());
+        // The same schema applied again to the same value cannot add anything, and would never end.
+        if self.stack.contains(&key) {
+            return;
+        }
+        match self.openapi.resolve_reference::<Schema>(reference) {
+            Ok(schema) => {
+                let target = format!("#{}", split_reference(reference).1);
+                self.stack.push(key);
+                self.schema(schema, instance, instance_path, &target, errors);
+                self.stack.pop();
+            }
+            Err(e) => error(errors, instance_path, schema_path, "$ref", e.to_string()),
+        }
+    }
+
+    /// Resolve a schema, `None` when its reference points to nothing.
+    fn resolve<'s>(&'s self, item: &'s RefOrObject<Schema>) -> Option<&'s Schema> {
+        self.openapi.resolve(item).ok()
+    }
+
+    fn schema(
+        &mut self,
+        schema: &Schema,
+        instance: &Value,
+        instance_path: &str,
+        schema_path: &str,
+        errors: &mut Vec<InstanceError>,
+    ) {
+        if instance.is_null() && schema.nullable == Some(true) {
+            return;
+        }
+        if !keywords(schema).check(instance, instance_path, schema_path, errors) {
+            return;
+        }
+        match instance {
+            Value::Object(_) => self.object(schema, instance, instance_path, schema_path, errors),
+            Value::Array(items) => {
+                if let Some(schema) = &schema.items {
+                    let schema_path = join(schema_path, "items");
+                    for (index, item) in items.iter().enumerate() {
+                        let instance_path = join(instance_path, &index.to_string());
+                        self.item(schema, item, &instance_path, &schema_path, errors);
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        for (index, item) in schema.all_of.iter().flatten().enumerate() {
+            let schema_path = join(&join(schema_path, "allOf"), &index.to_string());
+            self.item(item, instance, instance_path, &schema_path, errors);
+        }
+        let discriminated = match &schema.discriminator {
+            Some(discriminator) => self.discriminator(
+                discriminator,
+                schema,
+                instance,
+                instance_path,
+                schema_path,
+                errors,
+            ),
+            None => false,
+        };
+        if !discriminated {
+            if let Some(items) = &schema.one_of {
+                let matches = self.matches(items, instance, instance_path, schema_path, "oneOf");
+                match matches.len() {
+                    1 => {}
+                    0 => error(
+                        errors,
+                        instance_path,
+                        schema_path,
+                        "oneOf",
+                        "the value matches none of the schemas of `oneOf`",
+                    ),
+                    _ => error(
+                        errors,
+                        instance_path,
+                        schema_path,
+                        "oneOf",
+                        format!(
+                            "the value matches the schemas {:?} of `oneOf` where only one is allowed",
+                            matches
+                        ),
+                    ),
+                }
+            }
+            if let Some(items) = &schema.any_of {
+                if self
+                    .matches(items, instance, instance_path, schema_path, "anyOf")
+                    .is_empty()
+                {
+                    error(
+                        errors,
+                        instance_path,
+                        schema_path,
+                        "anyOf",
+                        "the value matches none of the schemas of `anyOf`",
+                    );
+                }
+            }
+        }
+        if let Some(not) = &schema.not {
+            let not_path = join(schema_path, "not");
+            if self
+                .validate(not, instance, instance_path, &not_path)
+                .is_empty()
+            {
+                error(
+                    errors,
+                    instance_path,
+                    schema_path,
+                    "not",
+                    "the value must not match the schema of `not`",
+                );
+            }
+        }
+    }
+
+    /// The indexes of the schemas of a combinator the instance matches.
+    fn matches(
+        &mut self,
+        items: &[Box<RefOrObject<Schema>>],
+        instance: &Value,
+        instance_path: &str,
+        schema_path: &str,
+        keyword: &str,
+    ) -> Vec<usize> {
+        let schema_path = join(schema_path, keyword);
+        (0..items.len())
+            .filter(|&index| {
+                let schema_path = join(&schema_path, &index.to_string());
+                self.validate(&items[index], instance, instance_path, &schema_path)
+                    .is_empty()
+            })
+            .collect()
+    }
+
+    /// Check the instance against the schema its discriminator names, returning whether it replaces `oneOf` and `anyOf`.
+    ///
+    /// A value without the discriminator property is reported, as it cannot tell which schema it follows.
+    fn discriminator(
+        &mut self,
+        discriminator: &Discriminator,
+        schema: &Schema,
+        instance: &Value,
+        instance_path: &str,
+        schema_path: &str,
+        errors: &mut Vec<InstanceError>,
+    ) -> bool {
+        let Value::Object(map) = instance else {
+            return false;
+        };
+        let name = &discriminator.property_name;
+        let Some(Value::String(value)) = map.get(name) else {
+            error(
+                errors,
+                instance_path,
+                schema_path,
+                "discriminator",
+                format!(
+                    "the discriminator property `{}` is missing or not a string",
+                    name
+                ),
+            );
+            return true;
+        };
+        let reference = match discriminator
+            .mapping
+            .as_ref()
+            .and_then(|mapping| mapping.get(value))
+        {
+            Some(target) if target.contains('#') || target.contains('/') => target.clone(),
+            Some(target) => format!("#/components/schemas/{}", target),
+            None => format!("#/components/schemas/{}", value),
+        };
+        if self
+            .openapi
+            .resolve_reference::<Schema>(&reference)
+            .is_err()
+        {
+            error(
+                errors,
+                &join(instance_path, name),
+                schema_path,
+                "discriminator",
+                format!("`{}` does not name a schema", value),
+            );
+            return true;
+        }
+        self.reference(&reference, instance, instance_path, schema_path, errors);
+        schema.one_of.is_some() || schema.any_of.is_some()
+    }
+
+    fn object(
+        &mut self,
+        schema: &Schema,
+        instance: &Value,
+        instance_path: &str,
+        schema_path: &str,
+        errors: &mut Vec<InstanceError>,
+    ) {
+        let Value::Object(map) = instance else {
+            return;
+        };
+        let properties = schema.properties.as_ref();
+        let property = |name: &str| properties.and_then(|properties| properties.get(name));
+        let hidden = |this: &Self, name: &str| {
+            property(name)
+                .and_then(|item| this.resolve(item))
+                .and_then(|schema| match this.direction {
+                    Direction::Request => schema.read_only,
+                    Direction::Response => schema.write_only,
+                })
+                .unwrap_or(false)
+        };
+
+        for name in schema.required.iter().flatten() {
+            if !map.contains_key(name) && !hidden(self, name) {
+                error(
+                    errors,
+                    instance_path,
+                    schema_path,
+                    "required",
+                    format!("the property `{}` is required", name),
+                );
+            }
+        }
+        let additional = match &schema.additional_properties {
+            Some(AdditionalProperties::Bool(false)) => Some(None),
+            Some(AdditionalProperties::Schema(schema)) => Some(Some(schema.as_ref())),
+            _ => None,
+        };
+        for (name, value) in map {
+            let instance_path = join(instance_path, name);
+            if let Some(item) = property(name) {
+                let schema_path = join(&join(schema_path, "properties"), name);
+                if hidden(self, name) {
+                    let (keyword, message) = match self.direction {
+                        Direction::Request => ("readOnly", "is read-only and must not be sent"),
+                        Direction::Response => {
+                            ("writeOnly", "is write-only and must not be returned")
+                        }
+                    };
+                    let schema_path = match item {
+                        RefOrObject::Ref(Reference { reference }) => {
+                            format!("#{}", split_reference(reference).1)
+                        }
+                        RefOrObject::Object(_) => schema_path.clone(),
+                    };
+                    error(
+                        errors,
+                        &instance_path,
+                        &schema_path,
+                        keyword,
+                        format!("the property `{}` {}", name, message),
+                    );
+                }
+                self.item(item, value, &instance_path, &schema_path, errors);
+                continue;
+            }
+            match &additional {
+                Some(None) => error(
+                    errors,
+                    &instance_path,
+                    schema_path,
+                    "additionalProperties",
+                    format!("the property `{}` is not allowed", name),
+                ),
+                Some(Some(item)) => {
+                    let schema_path = join(schema_path, "additionalProperties");
+                    self.item(item, value, &instance_path, &schema_path, errors);
+                }
+                None => {}
+            }
+        }
+    }
+}
+
+fn keywords(schema: &Schema) -> Keywords<'_> {
+    Keywords {
+        r#type: schema.r#type.as_deref(),
+        format: schema.format.as_deref(),
+        r#enum: schema.r#enum.as_deref(),
+        multiple_of: schema.multiple_of.as_ref(),
+        maximum: schema.maximum.as_ref(),
+        exclusive_maximum: schema.exclusive_maximum == Some(true),
+        minimum: schema.minimum.as_ref(),
+        exclusive_minimum: schema.exclusive_minimum == Some(true),
+        max_length: schema.max_length.as_ref(),
+        min_length: schema.min_length.as_ref(),
+        pattern: schema.pattern.as_deref(),
+        max_items: schema.max_items.as_ref(),
+        min_items: schema.min_items.as_ref(),
+        unique_items: schema.unique_items == Some(true),
+        max_properties: schema.max_properties.as_ref(),
+        min_properties: schema.min_properties.as_ref(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn openapi() -> OpenApi {
+        serde_json::from_value(json!({
+            "openapi": "3.0.3",
+            "info": {"title": "Pets", "version": "1"},
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "required": ["id", "name", "kind"],
+                        "discriminator": {"propertyName": "kind", "mapping": {"dog": "Dog"}},
+                        "properties": {
+                            "id": {"type": "integer", "format": "int64", "readOnly": true},
+                            "name": {"type": "string", "minLength": 1, "pattern": "^[A-Z]"},
+                            "kind": {"type": "string"},
+                            "tag": {"type": "string", "nullable": true},
+                            "secret": {"type": "string", "writeOnly": true},
+                            "owner": {"$ref": "#/components/schemas/Owner"}
+                        }
+                    },
+                    "Dog": {
+                        "allOf": [
+                            {"$ref": "#/components/schemas/Pet"},
+                            {"type": "object", "properties": {"bark": {"type": "boolean"}}, "required": ["bark"]}
+                        ]
+                    },
+                    "Owner": {
+                        "type": "object",
+                        "additionalProperties": false,
+                        "properties": {
+                            "email": {"type": "string", "format": "email"},
+                            "pets": {"type": "array", "maxItems": 1, "items": {"$ref": "#/components/schemas/Pet"}}
+                        }
+                    },
+                    "Id": {"oneOf": [{"type": "integer"}, {"type": "number", "minimum": 0}]},
+                    "Name": {"anyOf": [{"type": "string"}, {"type": "integer"}], "not": {"enum": ["root"]}}
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    fn schema(name: &str) -> RefOrObject<Schema> {
+        RefOrObject::Ref(Reference {
+            reference: format!("#/components/schemas/{}", name),
+        })
+    }
+
+    fn paths(errors: &[InstanceError]) -> Vec<(&str, &str)> {
+        errors
+            .iter()
+            .map(|error| (error.instance_path.as_str(), error.schema_path.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_validate_instance() {
+        let openapi = openapi();
+        let dog = json!({"id": 1, "name": "Rex", "kind": "dog", "bark": true, "tag": null});
+        assert_eq!(
+            openapi.validate_instance(&schema("Pet"), &dog, Direction::Response),
+            []
+        );
+        assert_eq!(
+            openapi.validate_instance(&schema("Dog"), &dog, Direction::Response),
+            []
+        );
+
+        let pet = json!({
+            "id": "1",
+            "name": "rex",
+            "kind": "dog",
+            "owner": {"email": "nobody", "phone": "555", "pets": [{}, {}]}
+        });
+        let errors = openapi.validate_instance(&schema("Pet"), &pet, Direction::Response);
+        assert_eq!(
+            paths(&errors),
+            [
+                ("/id", "#/components/schemas/Pet/properties/id/type"),
+                ("/name", "#/components/schemas/Pet/properties/name/pattern"),
+                (
+                    "/owner/email",
+                    "#/components/schemas/Owner/properties/email/format"
+                ),
+                (
+                    "/owner/pets",
+                    "#/components/schemas/Owner/properties/pets/maxItems"
+                ),
+                ("/owner/pets/0", "#/components/schemas/Pet/required"),
+                ("/owner/pets/0", "#/components/schemas/Pet/required"),
+                ("/owner/pets/0", "#/components/schemas/Pet/required"),
+                ("/owner/pets/0", "#/components/schemas/Pet/discriminator"),
+                ("/owner/pets/1", "#/components/schemas/Pet/required"),
+                ("/owner/pets/1", "#/components/schemas/Pet/required"),
+                ("/owner/pets/1", "#/components/schemas/Pet/required"),
+                ("/owner/pets/1", "#/components/schemas/Pet/discriminator"),
+                (
+                    "/owner/phone",
+                    "#/components/schemas/Owner/additionalProperties"
+                ),
+                ("", "#/components/schemas/Dog/allOf/1/required"),
+            ]
+        );
+        assert_eq!(errors[0].message, "expected integer, found string");
+    }
+
+    #[test]
+    fn test_direction() {
+        let openapi = openapi();
+        let request = json!({"id": 1, "name": "Rex", "kind": "cat", "secret": "s"});
+        let errors = openapi.validate_instance(&schema("Pet"), &request, Direction::Request);
+        assert_eq!(
+            paths(&errors),
+            [
+                ("/id", "#/components/schemas/Pet/properties/id/readOnly"),
+                ("/kind", "#/components/schemas/Pet/discriminator"),
+            ]
+        );
+        assert_eq!(errors[1].message, "`cat` does not name a schema");
+
+        let request = json!({"name": "Rex", "kind": "dog", "bark": false, "secret": "s"});
+        assert_eq!(
+            openapi.validate_instance(&schema("Pet"), &request, Direction::Request),
+            []
+        );
+        let errors = openapi.validate_instance(&schema("Pet"), &request, Direction::Response);
+        assert_eq!(
+            paths(&errors),
+            [
+                ("", "#/components/schemas/Pet/required"),
+                (
+                    "/secret",
+                    "#/components/schemas/Pet/properties/secret/writeOnly"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_combinators() {
+        let openapi = openapi();
+        let validate = |name, instance| {
+            openapi
+                .validate_instance(&schema(name), &instance, Direction::Request)
+                .into_iter()
+                .map(|error| error.schema_path)
+                .collect::<Vec<_>>()
+        };
+        assert!(validate("Id", json!(1.5)).is_empty());
+        assert_eq!(validate("Id", json!(1)), ["#/components/schemas/Id/oneOf"]);
+        assert_eq!(
+            validate("Id", json!(-1.5)),
+            ["#/components/schemas/Id/oneOf"]
+        );
+        assert!(validate("Name", json!("rex")).is_empty());
+        assert_eq!(
+            validate("Name", json!(true)),
+            ["#/components/schemas/Name/anyOf"]
+        );
+        assert_eq!(
+            validate("Name", json!("root")),
+            ["#/components/schemas/Name/not"]
+        );
+    }
+
+    #[test]
+    fn test_inline_schema() {
+        let openapi = openapi();
+        let array: RefOrObject<Schema> = serde_json::from_value(json!({
+            "type": "array",
+            "uniqueItems": true,
+            "items": {"type": "number", "multipleOf": 0.5, "maximum": 10, "exclusiveMaximum": true}
+        }))
+        .unwrap();
+        let errors =
+            openapi.validate_instance(&array, &json!([1.5, 10, 0.3, 1.5]), Direction::Request);
+        assert_eq!(
+            paths(&errors),
+            [
+                ("", "/uniqueItems"),
+                ("/1", "/items/maximum"),
+                ("/2", "/items/multipleOf"),
+            ]
+        );
+        let errors = openapi.validate_instance(&schema("Nothing"), &json!(1), Direction::Request);
+        assert_eq!(paths(&errors), [("", "/$ref")]);
+    }
+}
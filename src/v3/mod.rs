@@ -1,2 +1,21 @@
+mod bundle;
+mod convert;
+mod dereference;
+pub(crate) mod instance;
+mod resolve;
+mod router;
 mod schema;
+mod server;
+mod to_schema;
+mod upgrade;
+mod validate;
+pub mod visit;
+pub use crate::v3::convert::Note;
 pub use crate::v3::schema::*;
+pub use crate::v3::server::{ServerError, ServerMatch};
+#[doc(hidden)]
+pub use crate::v3::to_schema::__private;
+pub use crate::v3::to_schema::ToSchema;
+/// Derive [`ToSchema`] for a struct or an enum, following its serde attributes.
+#[cfg(feature = "derive")]
+pub use openapi_schema_derive::ToSchema;
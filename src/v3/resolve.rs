@@ -0,0 +1,303 @@
+use crate::error::Error;
+use crate::resolve::{find, mismatched, navigate, Chain, Navigate};
+
+use super::schema::*;
+
+impl OpenApi {
+    /// Resolve an object which may be a reference, following references to references.
+    ///
+    /// Only local references such as `#/components/schemas/Pet` are followed, and the target must be of the same type.
+    ///
+    /// ```
+    /// # use openapi_schema::v3::{OpenApi, RefOrObject, Reference, Schema};
+    /// # fn example(openapi: &OpenApi) -> Result<(), openapi_schema::Error> {
+    /// let pet = RefOrObject::<Schema>::Ref(Reference {
+    ///     reference: "#/components/schemas/Pet".to_string(),
+    /// });
+    /// let schema: &Schema = openapi.resolve(&pet)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn resolve<'a, T>(&'a self, item: &'a RefOrObject<T>) -> Result<&'a T, Error>
+    where
+        T: Navigate,
+    {
+        let mut chain = Chain::default();
+        let mut item = item;
+        loop {
+            match item {
+                RefOrObject::Object(object) => return Ok(object),
+                RefOrObject::Ref(Reference { reference }) => {
+                    chain.follow(reference)?;
+                    item = find(self, reference)?
+                        .as_any()
+                        .downcast_ref::<RefOrObject<T>>()
+                        .ok_or_else(|| mismatched::<T>(reference))?;
+                }
+            }
+        }
+    }
+
+    /// Resolve a local reference such as `#/components/schemas/Pet` to the object it points to.
+    pub fn resolve_reference<T>(&self, reference: &str) -> Result<&T, Error>
+    where
+        T: Navigate,
+    {
+        let item = find(self, reference)?;
+        match item.as_any().downcast_ref::<RefOrObject<T>>() {
+            Some(item) => self.resolve(item),
+            None => item
+                .as_any()
+                .downcast_ref::<T>()
+                .ok_or_else(|| mismatched::<T>(reference)),
+        }
+    }
+
+    /// Resolve a path item which may be defined by a `$ref` to another path item of the document.
+    pub fn resolve_path_item<'a>(&'a self, item: &'a PathItem) -> Result<&'a PathItem, Error> {
+        let mut chain = Chain::default();
+        let mut item = item;
+        while let Some(reference) = &item.reference {
+            chain.follow(reference)?;
+            item = self.resolve_reference(reference)?;
+        }
+        Ok(item)
+    }
+}
+
+impl<T: Navigate> Navigate for RefOrObject<T> {
+    fn child(&self, token: &str) -> Option<&dyn Navigate> {
+        match self {
+            RefOrObject::Ref(_) => None,
+            RefOrObject::Object(object) => object.child(token),
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+navigate!(OpenApi {
+    "paths" => paths,
+    "components" => ?components,
+});
+
+navigate!(Components {
+    "schemas" => ?schemas,
+    "responses" => ?responses,
+    "parameters" => ?parameters,
+    "examples" => ?examples,
+    "requestBodies" => ?request_bodies,
+    "headers" => ?headers,
+    "securitySchemes" => ?security_schemes,
+    "links" => ?links,
+    "callbacks" => ?callbacks,
+});
+
+navigate!(PathItem {
+    "get" => ?get,
+    "put" => ?put,
+    "post" => ?post,
+    "delete" => ?delete,
+    "options" => ?options,
+    "head" => ?head,
+    "patch" => ?patch,
+    "trace" => ?trace,
+    "parameters" => ?parameters,
+});
+
+navigate!(Operation {
+    "parameters" => ?parameters,
+    "requestBody" => ?request_body,
+    "responses" => responses,
+    "callbacks" => ?callbacks,
+});
+
+navigate!(Parameter {
+    "schema" => ?schema,
+    "examples" => ?examples,
+    "content" => ?content,
+});
+
+navigate!(RequestBody {
+    "content" => content,
+});
+
+navigate!(Response {
+    "headers" => ?headers,
+    "content" => ?content,
+    "links" => ?links,
+});
+
+navigate!(Media {
+    "schema" => ?schema,
+    "examples" => ?examples,
+    "encoding" => ?encoding,
+});
+
+navigate!(Encoding {
+    "headers" => ?headers,
+});
+
+navigate!(Header {
+    "schema" => ?schema,
+    "examples" => ?examples,
+    "content" => ?content,
+});
+
+navigate!(Schema {
+    "items" => ?items,
+    "properties" => ?properties,
+    "additionalProperties" => ?additional_properties,
+    "allOf" => ?all_of,
+    "oneOf" => ?one_of,
+    "anyOf" => ?any_of,
+    "not" => ?not,
+});
+
+/// The schema of `additionalProperties` is what a pointer finds, a boolean is a leaf.
+impl Navigate for AdditionalProperties {
+    fn child(&self, token: &str) -> Option<&dyn Navigate> {
+        match self {
+            AdditionalProperties::Bool(_) => None,
+            AdditionalProperties::Schema(schema) => schema.child(token),
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        match self {
+            AdditionalProperties::Bool(_) => self,
+            AdditionalProperties::Schema(schema) => schema.as_any(),
+        }
+    }
+}
+
+navigate!(Example {});
+navigate!(Link {});
+navigate!(SecurityScheme {});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn openapi() -> OpenApi {
+        serde_json::from_value(json!({
+            "openapi": "3.0.3",
+            "info": {"title": "pets"},
+            "paths": {
+                "/pets/{id}": {
+                    "get": {
+                        "parameters": [{"$ref": "#/components/parameters/Id"}],
+                        "responses": {
+                            "200": {"$ref": "#/components/responses/Pet"},
+                            "404": {"$ref": "#/components/responses/Missing"}
+                        }
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "properties": {
+                            "name": {"type": "string"},
+                            "tags": {"type": "array", "items": {"$ref": "#/components/schemas/Tag"}},
+                            "labels": {"type": "object", "additionalProperties": {"$ref": "#/components/schemas/Tag"}}
+                        }
+                    },
+                    "Tag": {"type": "string"},
+                    "Alias": {"$ref": "#/components/schemas/Pet"},
+                    "a/b~c": {"$ref": "#/components/schemas/Pet/properties/name"},
+                    "Loop": {"$ref": "#/components/schemas/Loop2"},
+                    "Loop2": {"$ref": "#/components/schemas/Loop"}
+                },
+                "parameters": {
+                    "Id": {"name": "id", "in": "path", "required": true}
+                },
+                "responses": {
+                    "Pet": {
+                        "description": "a pet",
+                        "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Alias"}}}
+                    }
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    fn reference<T>(reference: &str) -> RefOrObject<T> {
+        RefOrObject::Ref(Reference {
+            reference: reference.to_string(),
+        })
+    }
+
+    #[test]
+    fn test_resolve_nested_refs() {
+        let openapi = openapi();
+        let schema: &Schema = openapi
+            .resolve_reference("#/components/schemas/Alias")
+            .unwrap();
+        assert_eq!(schema.r#type.as_deref(), Some("object"));
+
+        let schema: &Schema = openapi
+            .resolve_reference("#/components/schemas/Pet/properties/tags/items")
+            .unwrap();
+        assert_eq!(schema.r#type.as_deref(), Some("string"));
+        let schema: &Schema = openapi
+            .resolve_reference("#/components/schemas/Pet/properties/labels/additionalProperties")
+            .unwrap();
+        assert_eq!(schema.r#type.as_deref(), Some("string"));
+
+        let operation = openapi.paths["/pets/{id}"].get.as_ref().unwrap();
+        let response = openapi.resolve(&operation.responses["200"]).unwrap();
+        assert_eq!(response.description, "a pet");
+        let parameter = openapi
+            .resolve(&operation.parameters.as_ref().unwrap()[0])
+            .unwrap();
+        assert_eq!(parameter.name, "id");
+    }
+
+    #[test]
+    fn test_resolve_escaped_pointer() {
+        let openapi = openapi();
+        let alias = reference::<Schema>("#/components/schemas/a~1b~0c");
+        let schema = openapi.resolve(&alias).unwrap();
+        assert_eq!(schema.r#type.as_deref(), Some("string"));
+
+        let parameter: &Parameter = openapi
+            .resolve_reference("#/paths/~1pets~1%7Bid%7D/get/parameters/0")
+            .unwrap();
+        assert_eq!(parameter.name, "id");
+    }
+
+    #[test]
+    fn test_resolve_errors() {
+        let openapi = openapi();
+        let operation = openapi.paths["/pets/{id}"].get.as_ref().unwrap();
+        assert!(matches!(
+            openapi.resolve(&operation.responses["404"]),
+            Err(Error::UnresolvedReference { .. })
+        ));
+        assert!(matches!(
+            openapi.resolve(&reference::<Response>("#/components/schemas/Pet")),
+            Err(Error::UnresolvedReference { .. })
+        ));
+        assert!(matches!(
+            openapi.resolve(&reference::<Schema>("pet.json#/Pet")),
+            Err(Error::UnresolvedReference { .. })
+        ));
+        match openapi.resolve(&reference::<Schema>("#/components/schemas/Loop")) {
+            Err(Error::CyclicReference { chain }) => assert_eq!(
+                chain,
+                vec![
+                    "#/components/schemas/Loop",
+                    "#/components/schemas/Loop2",
+                    "#/components/schemas/Loop"
+                ]
+            ),
+            other => panic!("expected a cycle, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use crate::router::{url_path, Router};
+
+use super::schema::*;
+
+impl OpenApi {
+    /// Compile the templates of the `paths` into a router.
+    ///
+    /// Paths may be given with the path of a server, such as `/v1/pets` for a server at `https://api.example.com/v1`,
+    /// its variables taking any of their `enum` values. Path items which are `$ref`s are followed.
+    pub fn router(&self) -> Router<'_, PathItem, Operation> {
+        let bases = self
+            .servers
+            .iter()
+            .flatten()
+            .flat_map(|server| server.urls().unwrap_or_else(|_| vec![server.default_url()]))
+            .map(|url| url_path(&url).to_string())
+            .collect();
+        Router::new(
+            bases,
+            self.paths.iter().map(|(template, item)| {
+                let item = self.resolve_path_item(item).unwrap_or(item);
+                (template.as_str(), item, item.operations().collect())
+            }),
+        )
+    }
+}
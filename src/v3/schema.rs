@@ -1,7 +1,8 @@
 use std::collections::BTreeMap;
 
+use crate::de::{deserialize_ref_or, from_value, RefOr};
 use crate::extension::Extensions;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::{Number, Value};
 
 /// # OpenApi
@@ -23,7 +24,7 @@ pub struct OpenApi {
     pub components: Option<Components>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub security: Option<SecurityRequirement>,
+    pub security: Option<Vec<SecurityRequirement>>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<Tag>>,
@@ -31,6 +32,9 @@ pub struct OpenApi {
     /// Additional external documentation.
     #[serde(skip_serializing_if = "Option::is_none", rename = "externalDocs")]
     pub external_docs: Option<ExternalDoc>,
+
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 /// ## Info
 /// The object provides metadata about the API.
@@ -125,7 +129,7 @@ pub struct ServerVariable {
 }
 /// # Components
 /// Holds a set of reusable objects for different aspects of the OAS. All objects defined within the components object will have no effect on the API unless they are explicitly referenced from properties outside the components object.
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct Components {
     /// An object to hold reusable Schema Objects.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -169,7 +173,7 @@ pub type Paths = BTreeMap<String, PathItem>;
 
 /// ### Path Item
 /// Describes the operations available on a single path.
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct PathItem {
     /// Allows for an external definition of this path item. The referenced structure MUST be in the format of a Path Item Object.
     /// If there are conflicts between the referenced definition and this Path Item's definition, the behavior is undefined.
@@ -217,9 +221,27 @@ pub struct PathItem {
     pub extensions: Extensions,
 }
 
+impl PathItem {
+    /// The operations defined on the path, with their lowercase HTTP methods.
+    pub fn operations(&self) -> impl Iterator<Item = (&'static str, &Operation)> {
+        [
+            ("get", &self.get),
+            ("put", &self.put),
+            ("post", &self.post),
+            ("delete", &self.delete),
+            ("options", &self.options),
+            ("head", &self.head),
+            ("patch", &self.patch),
+            ("trace", &self.trace),
+        ]
+        .into_iter()
+        .filter_map(|(method, operation)| operation.as_ref().map(|operation| (method, operation)))
+    }
+}
+
 /// ### Operation
 /// Describes a single API operation on a path.
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct Operation {
     /// A list of tags for API documentation control.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -279,7 +301,7 @@ pub struct ExternalDoc {
 ///
 /// Describes a single operation parameter.
 /// A unique parameter is defined by a combination of a name and location.
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct Parameter {
     /// The name of the parameter.
     pub name: String,
@@ -328,7 +350,7 @@ pub struct Parameter {
 
 /// ### Request Body
 /// Describes a single request body.
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct RequestBody {
     /// A brief description of the request body.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -350,7 +372,7 @@ pub type Responses = BTreeMap<String, RefOrObject<Response>>;
 
 /// ### Reponse
 /// Describes a single response from an API Operation, including design-time, static links to operations based on the response.
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct Response {
     /// A short description of the response.
     pub description: String,
@@ -370,7 +392,7 @@ pub struct Response {
 
 /// ### Media
 /// Each Media Type Object provides schema and examples for the media type identified by its key.
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct Media {
     /// The schema defining the content of the request, response, or parameter.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -388,7 +410,7 @@ pub struct Media {
 
 /// ### Encoding
 /// A single encoding definition applied to a single schema property.
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct Encoding {
     /// The Content-Type for encoding a specific property.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -527,13 +549,13 @@ pub struct Reference {
 /// ### Schema
 /// The Schema Object allows the definition of input and output data types.
 /// ser https://swagger.io/specification/v3/#schema-object
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct Schema {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "enum")]
-    pub r#enum: Option<Vec<String>>,
+    pub r#enum: Option<Vec<Value>>,
     #[serde(skip_serializing_if = "Option::is_none", rename = "multipleOf")]
     pub multiple_of: Option<Number>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -582,12 +604,12 @@ pub struct Schema {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub format: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub default: Option<String>,
+    pub default: Option<Value>,
     #[serde(
         skip_serializing_if = "Option::is_none",
         rename = "additionalProperties"
     )]
-    pub additional_properties: Option<Value>,
+    pub additional_properties: Option<AdditionalProperties>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub nullable: Option<bool>,
@@ -612,6 +634,9 @@ pub struct Schema {
     /// Specifies that a schema is deprecated and SHOULD be transitioned out of usage.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deprecated: Option<bool>,
+
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// ### Discriminator
@@ -656,20 +681,27 @@ pub struct SecurityScheme {
     /// A short description for security scheme.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
-    /// The name of the header, query or cookie parameter to be used.
-    pub name: String,
-    /// The location of the API key. Valid values are "query", "header" or "cookie".
-    pub r#in: String,
-    /// The name of the HTTP Authorization scheme to be used in the [Authorization header as defined in RFC7235](https://tools.ietf.org/html/rfc7235#section-5.1).
-    pub scheme: String,
+    /// The name of the header, query or cookie parameter to be used, for `apiKey`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The location of the API key, for `apiKey`. Valid values are "query", "header" or "cookie".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r#in: Option<String>,
+    /// The name of the HTTP Authorization scheme to be used in the [Authorization header as defined in RFC7235](https://tools.ietf.org/html/rfc7235#section-5.1), for `http`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scheme: Option<String>,
     /// A hint to the client to identify how the bearer token is formatted.
-    #[serde(skip_serializing_if = "Option::is_none", rename = "beareFormat")]
-    pub beare_format: Option<String>,
-    ///  An object containing configuration information for the flow types supported.
-    pub flows: OAuthFlows,
-    ///  OpenId Connect URL to discover OAuth2 configuration values.
-    #[serde(rename = "openIdConnectUrl")]
-    pub open_id_connect_url: String,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "bearerFormat")]
+    pub bearer_format: Option<String>,
+    ///  An object containing configuration information for the flow types supported, for `oauth2`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flows: Option<OAuthFlows>,
+    ///  OpenId Connect URL to discover OAuth2 configuration values, for `openIdConnect`.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "openIdConnectUrl")]
+    pub open_id_connect_url: Option<String>,
+
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 /// #### SecuritySchemeType
@@ -686,7 +718,7 @@ pub enum SecuritySchemeType {
 /// ### OAuthFlows
 ///
 /// Allows configuration of the supported OAuth Flows.
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct OAuthFlows {
     /// Configuration for the OAuth Implicit flow
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -704,14 +736,14 @@ pub struct OAuthFlows {
 
 /// ### OAuthFlow
 /// Configuration details for a supported OAuth Flow
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct OAuthFlow {
-    /// The authorization URL to be used for this flow.
-    #[serde(rename = "authorizationUrl")]
-    pub authorization_url: String,
-    /// The token URL to be used for this flow.
-    #[serde(rename = "tokenUrl")]
-    pub token_url: String,
+    /// The authorization URL to be used for the `implicit` and `authorizationCode` flows.
+    #[serde(rename = "authorizationUrl", skip_serializing_if = "Option::is_none")]
+    pub authorization_url: Option<String>,
+    /// The token URL to be used for the `password`, `clientCredentials` and `authorizationCode` flows.
+    #[serde(rename = "tokenUrl", skip_serializing_if = "Option::is_none")]
+    pub token_url: Option<String>,
     /// The URL to be used for obtaining refresh tokens.
     #[serde(rename = "refreshUrl", skip_serializing_if = "Option::is_none")]
     pub refresh_url: Option<String>,
@@ -723,9 +755,47 @@ pub struct OAuthFlow {
 /// Lists the required security schemes to execute this operation.
 pub type SecurityRequirement = BTreeMap<String, Vec<String>>;
 
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+/// ### AdditionalProperties
+/// The `additionalProperties` of an object: whether properties it does not list are allowed, or the schema they follow.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum AdditionalProperties {
+    Bool(bool),
+    Schema(Box<RefOrObject<Schema>>),
+}
+
+impl<'de> Deserialize<'de> for AdditionalProperties {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match Value::deserialize(deserializer)? {
+            Value::Bool(b) => Ok(AdditionalProperties::Bool(b)),
+            value => from_value(value).map(|schema| AdditionalProperties::Schema(Box::new(schema))),
+        }
+    }
+}
+
+/// ### RefOrObject
+/// Either a Reference Object or the object itself.
+#[derive(Clone, Debug, Serialize, PartialEq)]
 #[serde(untagged)]
 pub enum RefOrObject<T> {
     Ref(Reference),
     Object(T),
 }
+
+impl<'de, T> Deserialize<'de> for RefOrObject<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(match deserialize_ref_or(deserializer)? {
+            RefOr::Ref(reference) => RefOrObject::Ref(reference),
+            RefOr::Object(object) => RefOrObject::Object(object),
+        })
+    }
+}
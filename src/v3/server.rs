@@ -0,0 +1,345 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use super::schema::*;
+
+/// # ServerError
+/// Why the variables of a server URL could not be substituted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ServerError {
+    /// The URL uses a variable which the server does not declare.
+    UndeclaredVariable { name: String },
+    /// A value was given for a variable which the server does not declare.
+    UnknownVariable { name: String },
+    /// The value of a variable is not one of its `enum`.
+    NotInEnum {
+        name: String,
+        value: String,
+        allowed: Vec<String>,
+    },
+}
+
+impl fmt::Display for ServerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServerError::UndeclaredVariable { name } => {
+                write!(f, "the variable `{}` is not declared", name)
+            }
+            ServerError::UnknownVariable { name } => {
+                write!(f, "the server has no variable `{}`", name)
+            }
+            ServerError::NotInEnum {
+                name,
+                value,
+                allowed,
+            } => write!(
+                f,
+                "`{}` is not a value of `{}`, expected one of {}",
+                value,
+                name,
+                allowed.join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ServerError {}
+
+/// # ServerMatch
+/// The server a URL starts with.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ServerMatch<'a, 'u> {
+    pub server: &'a Server,
+    /// The values of the variables of the server, by name.
+    pub variables: BTreeMap<String, String>,
+    /// The rest of the URL after the server, such as `/pets?limit=10`.
+    pub path: &'u str,
+}
+
+/// A part of a server URL template.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Part<'a> {
+    Literal(&'a str),
+    Variable(&'a str),
+}
+
+/// Split a URL template such as `https://{region}.example.com` into literals and variables.
+fn parts(url: &str) -> Vec<Part<'_>> {
+    let mut parts = Vec::new();
+    let mut rest = url;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+            break;
+        };
+        if start > 0 {
+            parts.push(Part::Literal(&rest[..start]));
+        }
+        parts.push(Part::Variable(&rest[start + 1..end]));
+        rest = &rest[end + 1..];
+    }
+    if !rest.is_empty() {
+        parts.push(Part::Literal(rest));
+    }
+    parts
+}
+
+impl Server {
+    /// The URL with each variable substituted by its default.
+    ///
+    /// Variables which the server does not declare are left as they are.
+    pub fn default_url(&self) -> String {
+        parts(&self.url)
+            .into_iter()
+            .map(|part| match part {
+                Part::Literal(literal) => literal.to_string(),
+                Part::Variable(name) => self.variable(name).map_or_else(
+                    || format!("{{{}}}", name),
+                    |variable| variable.default.clone(),
+                ),
+            })
+            .collect()
+    }
+
+    /// The URL with each variable substituted by the given value, else by its default.
+    ///
+    /// Values must be one of the `enum` of their variable when it has one.
+    pub fn expand(&self, values: &BTreeMap<String, String>) -> Result<String, ServerError> {
+        if let Some(name) = values.keys().find(|name| self.variable(name).is_none()) {
+            return Err(ServerError::UnknownVariable { name: name.clone() });
+        }
+        parts(&self.url)
+            .into_iter()
+            .map(|part| match part {
+                Part::Literal(literal) => Ok(literal.to_string()),
+                Part::Variable(name) => {
+                    let variable =
+                        self.variable(name)
+                            .ok_or_else(|| ServerError::UndeclaredVariable {
+                                name: name.to_string(),
+                            })?;
+                    let value = values.get(name).unwrap_or(&variable.default);
+                    match &variable.r#enum {
+                        Some(allowed) if !allowed.contains(value) => Err(ServerError::NotInEnum {
+                            name: name.to_string(),
+                            value: value.clone(),
+                            allowed: allowed.clone(),
+                        }),
+                        _ => Ok(value.clone()),
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Every concrete URL of the server: each combination of the `enum` values of its variables, a variable without an
+    /// `enum` taking its default.
+    ///
+    /// The URLs are ordered by the values of the variables in the order of their `enum`, the variables being taken by
+    /// name.
+    pub fn urls(&self) -> Result<Vec<String>, ServerError> {
+        let mut combinations = vec![BTreeMap::new()];
+        for (name, variable) in self.variables.iter().flatten() {
+            let values = match &variable.r#enum {
+                Some(values) if !values.is_empty() => values.clone(),
+                _ => vec![variable.default.clone()],
+            };
+            combinations = combinations
+                .into_iter()
+                .flat_map(|combination: BTreeMap<String, String>| {
+                    values.iter().map(move |value| {
+                        let mut combination = combination.clone();
+                        combination.insert(name.clone(), value.clone());
+                        combination
+                    })
+                })
+                .collect();
+        }
+        combinations
+            .iter()
+            .map(|combination| self.expand(combination))
+            .collect()
+    }
+
+    /// Match a URL which starts with the server, returning the values of its variables and the rest of the URL.
+    ///
+    /// A variable with an `enum` only matches one of its values, other variables match any text up to the next `/`. A
+    /// relative server URL such as `/v1` is matched against the path of the URL.
+    pub fn match_url<'u>(&self, url: &'u str) -> Option<(BTreeMap<String, String>, &'u str)> {
+        let template = self.url.trim_end_matches('/');
+        let url = match (template.contains("://"), url.split_once("://")) {
+            (false, Some((_, rest))) => &rest[rest.find('/').unwrap_or(rest.len())..],
+            _ => url,
+        };
+        let mut values = BTreeMap::new();
+        let rest = self.match_parts(&parts(template), url, &mut values)?;
+        Some((values, rest))
+    }
+
+    fn match_parts<'u>(
+        &self,
+        parts: &[Part],
+        url: &'u str,
+        values: &mut BTreeMap<String, String>,
+    ) -> Option<&'u str> {
+        let (part, rest) = match parts.split_first() {
+            None => {
+                // The server must end at a boundary of the URL, `/v1` does not match `/v10/pets`.
+                let boundary = url.is_empty() || url.starts_with(['/', '?', '#']);
+                return boundary.then_some(url);
+            }
+            Some(split) => split,
+        };
+        let name = match part {
+            Part::Literal(literal) => {
+                return self.match_parts(rest, url.strip_prefix(literal)?, values);
+            }
+            Part::Variable(name) => *name,
+        };
+        let candidates: Vec<&str> = match self
+            .variable(name)
+            .and_then(|variable| variable.r#enum.as_ref())
+        {
+            Some(allowed) => {
+                let mut allowed: Vec<_> = allowed
+                    .iter()
+                    .map(String::as_str)
+                    .filter(|value| url.starts_with(value))
+                    .collect();
+                allowed.sort_by_key(|value| std::cmp::Reverse(value.len()));
+                allowed
+            }
+            None => {
+                let end = url.find(['/', '?', '#']).unwrap_or(url.len());
+                (1..=end)
+                    .filter(|&at| url.is_char_boundary(at))
+                    .map(|at| &url[..at])
+                    .collect()
+            }
+        };
+        candidates.into_iter().find_map(|value| {
+            let mut attempt = values.clone();
+            attempt.insert(name.to_string(), value.to_string());
+            let matched = self.match_parts(rest, &url[value.len()..], &mut attempt)?;
+            *values = attempt;
+            Some(matched)
+        })
+    }
+
+    fn variable(&self, name: &str) -> Option<&ServerVariable> {
+        self.variables.as_ref()?.get(name)
+    }
+}
+
+impl OpenApi {
+    /// Find the server a URL starts with, such as `https://eu.petstore.example.com/v1` for
+    /// `https://eu.petstore.example.com/v1/pets`.
+    ///
+    /// When several servers match, the one leaving the shortest rest wins, then the first one of the document.
+    pub fn match_server<'u>(&self, url: &'u str) -> Option<ServerMatch<'_, 'u>> {
+        let mut best: Option<ServerMatch> = None;
+        for server in self.servers.iter().flatten() {
+            let Some((variables, path)) = server.match_url(url) else {
+                continue;
+            };
+            if best
+                .as_ref()
+                .is_none_or(|best| path.len() < best.path.len())
+            {
+                best = Some(ServerMatch {
+                    server,
+                    variables,
+                    path,
+                });
+            }
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn server() -> Server {
+        serde_json::from_value(json!({
+            "url": "https://{region}.api.example.com:{port}/{version}",
+            "variables": {
+                "region": {"default": "eu", "enum": ["eu", "us", "us-east"]},
+                "port": {"default": "443", "enum": ["443", "8443"]},
+                "version": {"default": "v1"}
+            }
+        }))
+        .unwrap()
+    }
+
+    fn values(values: &[(&str, &str)]) -> BTreeMap<String, String> {
+        values
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_expand() {
+        let server = server();
+        assert_eq!(server.default_url(), "https://eu.api.example.com:443/v1");
+        assert_eq!(
+            server.expand(&values(&[("region", "us"), ("version", "v2")])),
+            Ok("https://us.api.example.com:443/v2".to_string())
+        );
+        assert_eq!(
+            server.expand(&values(&[("region", "asia")])),
+            Err(ServerError::NotInEnum {
+                name: "region".to_string(),
+                value: "asia".to_string(),
+                allowed: vec!["eu".to_string(), "us".to_string(), "us-east".to_string()],
+            })
+        );
+        assert_eq!(
+            server.expand(&values(&[("tenant", "acme")])),
+            Err(ServerError::UnknownVariable {
+                name: "tenant".to_string()
+            })
+        );
+        let undeclared = Server {
+            url: "https://{tenant}.example.com".to_string(),
+            description: None,
+            variables: None,
+            extensions: Default::default(),
+        };
+        assert_eq!(undeclared.default_url(), "https://{tenant}.example.com");
+        assert!(undeclared.expand(&BTreeMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_urls() {
+        let urls = server().urls().unwrap();
+        assert_eq!(urls.len(), 6);
+        assert_eq!(urls[0], "https://eu.api.example.com:443/v1");
+        assert_eq!(urls[1], "https://us.api.example.com:443/v1");
+        assert_eq!(urls[3], "https://eu.api.example.com:8443/v1");
+    }
+
+    #[test]
+    fn test_match_url() {
+        let server = server();
+        assert_eq!(
+            server.match_url("https://us-east.api.example.com:8443/v2/pets?limit=1"),
+            Some((
+                values(&[("region", "us-east"), ("port", "8443"), ("version", "v2")]),
+                "/pets?limit=1"
+            ))
+        );
+        assert_eq!(
+            server.match_url("https://asia.api.example.com:443/v1"),
+            None
+        );
+        let relative: Server = serde_json::from_value(json!({"url": "/v1/"})).unwrap();
+        assert_eq!(
+            relative.match_url("http://localhost:8080/v1/pets"),
+            Some((BTreeMap::new(), "/pets"))
+        );
+        assert_eq!(relative.match_url("/v10/pets"), None);
+    }
+}
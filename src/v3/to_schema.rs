@@ -0,0 +1,365 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use serde_json::Value;
+
+use super::schema::*;
+
+/// # ToSchema
+/// A Rust type described by a schema, usually derived with `#[derive(ToSchema)]` and the `derive` feature.
+///
+/// A named type is added once to the schemas of the components and referenced from the schemas which contain it,
+/// while other types, such as `String` or `Vec<T>`, are inlined.
+///
+/// ```
+/// # use openapi_schema::v3::{Components, ToSchema};
+/// let mut components = Components::default();
+/// <Vec<String>>::register(&mut components);
+/// assert_eq!(<Vec<String>>::schema().r#type.as_deref(), Some("array"));
+/// ```
+pub trait ToSchema {
+    /// The name of the schema in the components, `None` for a type which is always inlined.
+    fn name() -> Option<String> {
+        None
+    }
+
+    /// The schema of the type, in which the named types it contains are referenced.
+    fn schema() -> Schema;
+
+    /// Add the named types the schema references to the components.
+    fn register_dependencies(_components: &mut Components) {}
+
+    /// Add the schema of the type to the components if it is named, along with the named types it references.
+    ///
+    /// A schema already in the components is kept, which ends the registration of recursive types.
+    fn register(components: &mut Components) {
+        let Some(name) = Self::name() else {
+            return Self::register_dependencies(components);
+        };
+        let schemas = components.schemas.get_or_insert_with(BTreeMap::new);
+        if schemas.contains_key(&name) {
+            return;
+        }
+        schemas.insert(name, RefOrObject::Object(Self::schema()));
+        Self::register_dependencies(components);
+    }
+
+    /// A reference to the schema of the type in the components if it is named, the schema itself otherwise.
+    fn reference() -> RefOrObject<Schema> {
+        match Self::name() {
+            Some(name) => RefOrObject::Ref(Reference {
+                reference: format!("#/components/schemas/{}", crate::pointer::escape(&name)),
+            }),
+            None => RefOrObject::Object(Self::schema()),
+        }
+    }
+}
+
+fn typed(r#type: &str, format: Option<&str>) -> Schema {
+    Schema {
+        r#type: Some(r#type.to_string()),
+        format: format.map(str::to_string),
+        ..Schema::default()
+    }
+}
+
+macro_rules! impl_to_schema {
+    ($($ty:ty => $type:literal, $format:expr;)*) => {
+        $(
+            impl ToSchema for $ty {
+                fn schema() -> Schema {
+                    typed($type, $format)
+                }
+            }
+        )*
+    };
+}
+
+impl_to_schema! {
+    bool => "boolean", None;
+    i8 => "integer", Some("int32");
+    i16 => "integer", Some("int32");
+    i32 => "integer", Some("int32");
+    i64 => "integer", Some("int64");
+    isize => "integer", Some("int64");
+    u8 => "integer", Some("int32");
+    u16 => "integer", Some("int32");
+    u32 => "integer", Some("int64");
+    u64 => "integer", Some("int64");
+    usize => "integer", Some("int64");
+    f32 => "number", Some("float");
+    f64 => "number", Some("double");
+    char => "string", None;
+    str => "string", None;
+    String => "string", None;
+}
+
+impl ToSchema for Value {
+    fn schema() -> Schema {
+        Schema::default()
+    }
+}
+
+impl ToSchema for () {
+    fn schema() -> Schema {
+        __private::unit()
+    }
+}
+
+impl<T: ToSchema + ?Sized> ToSchema for &T {
+    fn name() -> Option<String> {
+        T::name()
+    }
+
+    fn schema() -> Schema {
+        T::schema()
+    }
+
+    fn register_dependencies(components: &mut Components) {
+        T::register_dependencies(components)
+    }
+}
+
+impl<T: ToSchema + ?Sized> ToSchema for Box<T> {
+    fn name() -> Option<String> {
+        T::name()
+    }
+
+    fn schema() -> Schema {
+        T::schema()
+    }
+
+    fn register_dependencies(components: &mut Components) {
+        T::register_dependencies(components)
+    }
+}
+
+/// An `Option` has the schema of its value made `nullable`, a reference being wrapped in an `allOf` to be so.
+impl<T: ToSchema> ToSchema for Option<T> {
+    fn schema() -> Schema {
+        Schema {
+            nullable: Some(true),
+            ..__private::inline::<T>()
+        }
+    }
+
+    fn register_dependencies(components: &mut Components) {
+        T::register(components)
+    }
+}
+
+fn array<T: ToSchema>(unique: bool) -> Schema {
+    Schema {
+        items: Some(Box::new(T::reference())),
+        unique_items: unique.then_some(true),
+        ..typed("array", None)
+    }
+}
+
+fn map<T: ToSchema>() -> Schema {
+    Schema {
+        additional_properties: Some(AdditionalProperties::Schema(Box::new(T::reference()))),
+        ..typed("object", None)
+    }
+}
+
+macro_rules! impl_to_schema_collection {
+    ($($ty:ident<$($param:ident),*> => $schema:expr;)*) => {
+        $(
+            impl<$($param),*> ToSchema for $ty<$($param),*>
+            where
+                T: ToSchema,
+            {
+                fn schema() -> Schema {
+                    $schema
+                }
+
+                fn register_dependencies(components: &mut Components) {
+                    T::register(components)
+                }
+            }
+        )*
+    };
+}
+
+impl_to_schema_collection! {
+    Vec<T> => array::<T>(false);
+    BTreeSet<T> => array::<T>(true);
+    HashSet<T, S> => array::<T>(true);
+    BTreeMap<K, T> => map::<T>();
+    HashMap<K, T, S> => map::<T>();
+}
+
+impl<T: ToSchema> ToSchema for [T] {
+    fn schema() -> Schema {
+        array::<T>(false)
+    }
+
+    fn register_dependencies(components: &mut Components) {
+        T::register(components)
+    }
+}
+
+/// Helpers of the code generated by `#[derive(ToSchema)]`, which are not part of the API.
+#[doc(hidden)]
+pub mod __private {
+    use super::*;
+
+    /// An object with `(name, schema, required)` properties.
+    pub fn object(properties: Vec<(&str, RefOrObject<Schema>, bool)>) -> Schema {
+        let required: Vec<String> = properties
+            .iter()
+            .filter(|(_, _, required)| *required)
+            .map(|(name, _, _)| name.to_string())
+            .collect();
+        Schema {
+            properties: Some(
+                properties
+                    .into_iter()
+                    .map(|(name, schema, _)| (name.to_string(), schema))
+                    .collect(),
+            ),
+            required: (!required.is_empty()).then_some(required),
+            ..typed("object", None)
+        }
+    }
+
+    /// The properties of the type flattened into an object.
+    pub fn flatten<T: ToSchema>(schema: &mut Schema) {
+        let flattened = T::schema();
+        match flattened.properties {
+            Some(properties) => {
+                schema
+                    .properties
+                    .get_or_insert_with(BTreeMap::new)
+                    .extend(properties);
+                if let Some(required) = flattened.required {
+                    schema
+                        .required
+                        .get_or_insert_with(Vec::new)
+                        .extend(required);
+                }
+                if flattened.additional_properties.is_some() {
+                    schema.additional_properties = flattened.additional_properties;
+                }
+            }
+            None if flattened.additional_properties.is_some() => {
+                schema.additional_properties = flattened.additional_properties;
+            }
+            None => {
+                let all_of = schema.all_of.get_or_insert_with(Vec::new);
+                all_of.push(Box::new(T::reference()));
+            }
+        }
+    }
+
+    /// The schema of the type, wrapped in an `allOf` if it is a reference.
+    pub fn inline<T: ToSchema>() -> Schema {
+        match T::reference() {
+            RefOrObject::Object(schema) => schema,
+            reference => all_of(vec![reference]),
+        }
+    }
+
+    /// A schema with a description, unless it is a reference, which cannot have one.
+    pub fn describe(schema: RefOrObject<Schema>, description: Option<&str>) -> RefOrObject<Schema> {
+        match schema {
+            RefOrObject::Object(schema) => {
+                RefOrObject::Object(with_description(schema, description))
+            }
+            reference => reference,
+        }
+    }
+
+    /// A schema which is only returned, `readOnly`, or only sent, `writeOnly`, a reference being wrapped in an `allOf`.
+    pub fn access(
+        schema: RefOrObject<Schema>,
+        read_only: bool,
+        write_only: bool,
+    ) -> RefOrObject<Schema> {
+        if !read_only && !write_only {
+            return schema;
+        }
+        let schema = match schema {
+            RefOrObject::Object(schema) => schema,
+            reference => all_of(vec![reference]),
+        };
+        RefOrObject::Object(Schema {
+            read_only: read_only.then_some(true),
+            write_only: write_only.then_some(true),
+            ..schema
+        })
+    }
+
+    pub fn with_description(mut schema: Schema, description: Option<&str>) -> Schema {
+        if let Some(description) = description {
+            schema.description = Some(description.to_string());
+        }
+        schema
+    }
+
+    /// A string which is one of the values.
+    pub fn string_enum(values: &[&str]) -> Schema {
+        Schema {
+            r#enum: Some(values.iter().map(|value| Value::from(*value)).collect()),
+            ..typed("string", None)
+        }
+    }
+
+    /// A fixed-length array, whose items are one of the schemas of its elements.
+    pub fn tuple(items: Vec<RefOrObject<Schema>>) -> Schema {
+        let length = items.len();
+        let mut unique: Vec<RefOrObject<Schema>> = Vec::new();
+        for item in items {
+            if !unique.contains(&item) {
+                unique.push(item);
+            }
+        }
+        let items = if unique.len() == 1 {
+            unique.remove(0)
+        } else {
+            RefOrObject::Object(Schema {
+                any_of: Some(unique.into_iter().map(Box::new).collect()),
+                ..Schema::default()
+            })
+        };
+        Schema {
+            items: Some(Box::new(items)),
+            min_items: Some(length.into()),
+            max_items: Some(length.into()),
+            ..typed("array", None)
+        }
+    }
+
+    /// The `null` of a unit struct or variant.
+    pub fn unit() -> Schema {
+        Schema {
+            r#enum: Some(vec![Value::Null]),
+            nullable: Some(true),
+            ..Schema::default()
+        }
+    }
+
+    pub fn all_of(schemas: Vec<RefOrObject<Schema>>) -> Schema {
+        Schema {
+            all_of: Some(schemas.into_iter().map(Box::new).collect()),
+            ..Schema::default()
+        }
+    }
+
+    /// One of the schemas, told apart by the `tag` property when there is one.
+    pub fn one_of(schemas: Vec<Schema>, tag: Option<&str>) -> Schema {
+        Schema {
+            one_of: Some(
+                schemas
+                    .into_iter()
+                    .map(|schema| Box::new(RefOrObject::Object(schema)))
+                    .collect(),
+            ),
+            discriminator: tag.map(|tag| Discriminator {
+                property_name: tag.to_string(),
+                mapping: None,
+            }),
+            ..Schema::default()
+        }
+    }
+}
@@ -0,0 +1,298 @@
+use serde_json::{Map, Value};
+
+use crate::de::deserialize_value;
+use crate::error::Error;
+use crate::pointer::join;
+use crate::v3_1;
+
+use super::schema::*;
+use super::Note;
+
+/// The version of the upgraded documents.
+const VERSION: &str = "3.1.0";
+
+/// The methods of the operations of a path item.
+const METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+impl OpenApi {
+    /// Upgrade the document to OpenAPI 3.1, with a note for each rewrite.
+    ///
+    /// The schemas are rewritten to JSON Schema 2020-12:
+    /// - `nullable: true` adds `"null"` to `type`, and to `enum` if there is one;
+    /// - a boolean `exclusiveMaximum` or `exclusiveMinimum` takes the value of `maximum` or `minimum`;
+    /// - `example` becomes the single item of `examples`.
+    ///
+    /// ```
+    /// # use openapi_schema::v3::OpenApi;
+    /// # fn example(openapi: &OpenApi) -> Result<(), openapi_schema::Error> {
+    /// let (upgraded, notes) = openapi.to_v3_1()?;
+    /// assert_eq!(upgraded.openapi, "3.1.0");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_v3_1(&self) -> Result<(v3_1::OpenApi, Vec<Note>), Error> {
+        let mut value = serde_json::to_value(self)?;
+        let mut upgrader = Upgrader { notes: Vec::new() };
+        upgrader.document(&mut value);
+        let openapi = deserialize_value(value, "")?;
+        Ok((openapi, upgrader.notes))
+    }
+}
+
+/// Rewrites a serialized document, visiting each object by its place in the document as JSON values hold no types.
+struct Upgrader {
+    notes: Vec<Note>,
+}
+
+impl Upgrader {
+    fn note(&mut self, pointer: &str, message: impl Into<String>) {
+        self.notes.push(Note {
+            pointer: pointer.to_string(),
+            message: message.into(),
+        });
+    }
+
+    fn document(&mut self, document: &mut Value) {
+        if let Some(version) = document.get_mut("openapi") {
+            self.note(
+                "/openapi",
+                format!(
+                    "`openapi` was bumped from `{}` to `{}`",
+                    version.as_str().unwrap_or_default(),
+                    VERSION
+                ),
+            );
+            *version = Value::from(VERSION);
+        }
+        for (path, item) in objects(document, "paths") {
+            self.path_item(item, &join("/paths", path));
+        }
+        let Some(components) = document.get_mut("components") else {
+            return;
+        };
+        let pointer = "/components";
+        for (name, schema) in objects(components, "schemas") {
+            self.schema(schema, &join(&join(pointer, "schemas"), name));
+        }
+        for (name, response) in objects(components, "responses") {
+            self.response(response, &join(&join(pointer, "responses"), name));
+        }
+        for (name, parameter) in objects(components, "parameters") {
+            self.parameter(parameter, &join(&join(pointer, "parameters"), name));
+        }
+        for (name, body) in objects(components, "requestBodies") {
+            self.content(body, &join(&join(pointer, "requestBodies"), name));
+        }
+        for (name, header) in objects(components, "headers") {
+            self.parameter(header, &join(&join(pointer, "headers"), name));
+        }
+        for (name, callback) in objects(components, "callbacks") {
+            self.callback(callback, &join(&join(pointer, "callbacks"), name));
+        }
+    }
+
+    fn path_item(&mut self, item: &mut Value, pointer: &str) {
+        for method in METHODS {
+            if let Some(operation) = item.get_mut(*method) {
+                self.operation(operation, &join(pointer, method));
+            }
+        }
+        self.parameters(item, pointer);
+    }
+
+    fn parameters(&mut self, object: &mut Value, pointer: &str) {
+        if let Some(Value::Array(parameters)) = object.get_mut("parameters") {
+            for (index, parameter) in parameters.iter_mut().enumerate() {
+                let pointer = join(&join(pointer, "parameters"), &index.to_string());
+                self.parameter(parameter, &pointer);
+            }
+        }
+    }
+
+    fn operation(&mut self, operation: &mut Value, pointer: &str) {
+        self.parameters(operation, pointer);
+        if let Some(body) = operation.get_mut("requestBody") {
+            self.content(body, &join(pointer, "requestBody"));
+        }
+        for (status, response) in objects(operation, "responses") {
+            self.response(response, &join(&join(pointer, "responses"), status));
+        }
+        for (name, callback) in objects(operation, "callbacks") {
+            self.callback(callback, &join(&join(pointer, "callbacks"), name));
+        }
+    }
+
+    fn callback(&mut self, callback: &mut Value, pointer: &str) {
+        if let Value::Object(items) = callback {
+            for (expression, item) in items {
+                self.path_item(item, &join(pointer, expression));
+            }
+        }
+    }
+
+    fn response(&mut self, response: &mut Value, pointer: &str) {
+        for (name, header) in objects(response, "headers") {
+            self.parameter(header, &join(&join(pointer, "headers"), name));
+        }
+        self.content(response, pointer);
+    }
+
+    /// Upgrade a parameter or a header, which hold either a schema or content.
+    fn parameter(&mut self, parameter: &mut Value, pointer: &str) {
+        if let Some(schema) = parameter.get_mut("schema") {
+            self.schema(schema, &join(pointer, "schema"));
+        }
+        self.content(parameter, pointer);
+    }
+
+    /// Upgrade the media types of the `content` of an object.
+    fn content(&mut self, object: &mut Value, pointer: &str) {
+        let pointer = join(pointer, "content");
+        for (media_type, media) in objects(object, "content") {
+            let pointer = join(&pointer, media_type);
+            if let Some(schema) = media.get_mut("schema") {
+                self.schema(schema, &join(&pointer, "schema"));
+            }
+            for (property, encoding) in objects(media, "encoding") {
+                let pointer = join(&join(&pointer, "encoding"), property);
+                for (name, header) in objects(encoding, "headers") {
+                    self.parameter(header, &join(&join(&pointer, "headers"), name));
+                }
+            }
+        }
+    }
+
+    fn schema(&mut self, schema: &mut Value, pointer: &str) {
+        let Value::Object(schema) = schema else {
+            return;
+        };
+        // A reference is not a schema of its own in OpenAPI 3.0, whatever is beside it is ignored.
+        if schema.contains_key("$ref") {
+            return;
+        }
+        self.nullable(schema, pointer);
+        self.exclusive(schema, pointer, "exclusiveMaximum", "maximum");
+        self.exclusive(schema, pointer, "exclusiveMinimum", "minimum");
+        if let Some(example) = schema.remove("example") {
+            self.note(
+                &join(pointer, "example"),
+                "`example` became the single item of `examples`",
+            );
+            match schema.get_mut("examples") {
+                Some(Value::Array(examples)) => examples.insert(0, example),
+                _ => {
+                    schema.insert("examples".to_string(), Value::Array(vec![example]));
+                }
+            }
+        }
+
+        for (name, property) in schema
+            .get_mut("properties")
+            .and_then(Value::as_object_mut)
+            .into_iter()
+            .flatten()
+        {
+            self.schema(property, &join(&join(pointer, "properties"), name));
+        }
+        for keyword in ["items", "not", "additionalProperties"] {
+            if let Some(subschema) = schema.get_mut(keyword) {
+                self.schema(subschema, &join(pointer, keyword));
+            }
+        }
+        for keyword in ["allOf", "oneOf", "anyOf"] {
+            if let Some(Value::Array(subschemas)) = schema.get_mut(keyword) {
+                for (index, subschema) in subschemas.iter_mut().enumerate() {
+                    self.schema(
+                        subschema,
+                        &join(&join(pointer, keyword), &index.to_string()),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Replace `nullable` by a `"null"` type.
+    fn nullable(&mut self, schema: &mut Map<String, Value>, pointer: &str) {
+        let pointer = join(pointer, "nullable");
+        match schema.remove("nullable") {
+            Some(Value::Bool(true)) => {}
+            Some(_) => {
+                self.note(&pointer, "`nullable: false` was removed");
+                return;
+            }
+            None => return,
+        }
+        let Some(Value::String(name)) = schema.get("type") else {
+            self.note(
+                &pointer,
+                "`nullable: true` was removed, the schema has no `type` to add `\"null\"` to",
+            );
+            return;
+        };
+        let types = Value::from(vec![name.clone(), "null".to_string()]);
+        self.note(
+            &pointer,
+            format!("`nullable: true` became `type: {}`", types),
+        );
+        schema.insert("type".to_string(), types);
+        if let Some(Value::Array(values)) = schema.get_mut("enum") {
+            if !values.contains(&Value::Null) {
+                values.push(Value::Null);
+                self.note(&pointer, "`null` was added to `enum`");
+            }
+        }
+    }
+
+    /// Replace a boolean `exclusiveMaximum` or `exclusiveMinimum` by the bound it makes exclusive.
+    fn exclusive(
+        &mut self,
+        schema: &mut Map<String, Value>,
+        pointer: &str,
+        keyword: &str,
+        bound: &str,
+    ) {
+        let pointer = join(pointer, keyword);
+        match schema.remove(keyword) {
+            Some(Value::Bool(true)) => match schema.remove(bound) {
+                Some(value) => {
+                    self.note(
+                        &pointer,
+                        format!(
+                            "`{}: true` became `{}: {}` in place of `{}`",
+                            keyword, keyword, value, bound
+                        ),
+                    );
+                    schema.insert(keyword.to_string(), value);
+                }
+                None => self.note(
+                    &pointer,
+                    format!(
+                        "`{}: true` was removed, the schema has no `{}`",
+                        keyword, bound
+                    ),
+                ),
+            },
+            Some(Value::Bool(false)) => {
+                self.note(&pointer, format!("`{}: false` was removed", keyword))
+            }
+            Some(value) => {
+                schema.insert(keyword.to_string(), value);
+            }
+            None => {}
+        }
+    }
+}
+
+/// The entries of the object under `key`.
+fn objects<'a>(
+    value: &'a mut Value,
+    key: &str,
+) -> impl Iterator<Item = (&'a String, &'a mut Value)> {
+    value
+        .get_mut(key)
+        .and_then(Value::as_object_mut)
+        .into_iter()
+        .flatten()
+}
@@ -0,0 +1,682 @@
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use crate::pointer::join;
+use crate::validate::{
+    is_component_name, is_status, template_names, Diagnostic, Report, V3_LITERALS,
+};
+
+use super::schema::*;
+
+/// The locations a parameter may be in.
+const LOCATIONS: &[&str] = &["query", "header", "path", "cookie"];
+
+impl OpenApi {
+    /// Check the document against the rules of the specification which its types cannot enforce.
+    ///
+    /// An empty list means the document is valid. References to other documents are not followed.
+    ///
+    /// ```
+    /// # use openapi_schema::v3::OpenApi;
+    /// # use openapi_schema::validate::Severity;
+    /// # fn example(openapi: &OpenApi) {
+    /// for diagnostic in openapi.validate() {
+    ///     if diagnostic.severity == Severity::Error {
+    ///         eprintln!("{}", diagnostic);
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut validator = Validator {
+            openapi: self,
+            report: Report::default(),
+        };
+        validator.document();
+        validator.report.diagnostics
+    }
+}
+
+struct Validator<'a> {
+    openapi: &'a OpenApi,
+    report: Report,
+}
+
+impl<'a> Validator<'a> {
+    fn document(&mut self) {
+        let openapi = self.openapi;
+        for (index, server) in openapi.servers.iter().flatten().enumerate() {
+            self.server(server, &join("/servers", &index.to_string()));
+        }
+        self.report.duplicates(
+            "tags-unique",
+            "tag",
+            openapi
+                .tags
+                .iter()
+                .flatten()
+                .enumerate()
+                .map(|(index, tag)| {
+                    (
+                        tag.name.as_str(),
+                        join(&join("/tags", &index.to_string()), "name"),
+                    )
+                }),
+        );
+        self.report.paths(openapi.paths.keys());
+
+        let mut operation_ids = Vec::new();
+        for (path, item) in &openapi.paths {
+            let pointer = join("/paths", path);
+            self.path_item(item, &pointer, Some(path));
+            for (method, operation) in item.operations() {
+                if let Some(id) = &operation.operation_id {
+                    let pointer = join(&join(&pointer, method), "operationId");
+                    operation_ids.push((id.as_str(), pointer));
+                }
+            }
+        }
+        self.report
+            .duplicates("operation-id-unique", "operationId", operation_ids);
+
+        if let Some(security) = &openapi.security {
+            self.security(security, "/security");
+        }
+        if let Some(components) = &openapi.components {
+            self.components(components);
+        }
+        if let Ok(document) = serde_json::to_value(openapi) {
+            self.report.references(&document, V3_LITERALS);
+        }
+    }
+
+    fn server(&mut self, server: &Server, pointer: &str) {
+        let variables = server.variables.as_ref();
+        for name in template_names(&server.url) {
+            if !variables.is_some_and(|variables| variables.contains_key(name)) {
+                self.report.error(
+                    "server-variables-defined",
+                    &join(pointer, "url"),
+                    format!("variable `{}` of `{}` is not defined", name, server.url),
+                );
+            }
+        }
+        for (name, variable) in variables.into_iter().flatten() {
+            let pointer = join(&join(pointer, "variables"), name);
+            match &variable.r#enum {
+                Some(values) if values.is_empty() => self.report.warning(
+                    "server-variable-enum",
+                    &join(&pointer, "enum"),
+                    format!("variable `{}` has no values", name),
+                ),
+                Some(values) if !values.contains(&variable.default) => self.report.warning(
+                    "server-variable-enum",
+                    &join(&pointer, "default"),
+                    format!(
+                        "the default `{}` of variable `{}` is not one of its values",
+                        variable.default, name
+                    ),
+                ),
+                _ => {}
+            }
+        }
+    }
+
+    /// Check a path item, whose path parameters are checked against its template unless it is a callback.
+    fn path_item(&mut self, item: &'a PathItem, pointer: &str, path: Option<&str>) {
+        self.parameters(item.parameters.as_deref(), pointer);
+        for (index, server) in item.servers.iter().flatten().enumerate() {
+            self.server(server, &join(&join(pointer, "servers"), &index.to_string()));
+        }
+        let (path_level, path_level_complete) =
+            self.path_parameters(item.parameters.as_deref(), pointer);
+        for (method, operation) in item.operations() {
+            let pointer = join(pointer, method);
+            self.operation(operation, &pointer);
+            let Some(path) = path else {
+                continue;
+            };
+            // The parameters of the operation override those of the path item with the same name.
+            let (mut declared, complete) =
+                self.path_parameters(operation.parameters.as_deref(), &pointer);
+            for (name, pointer) in &path_level {
+                if !declared.iter().any(|(declared, _)| declared == name) {
+                    declared.push((name, pointer.clone()));
+                }
+            }
+            self.report
+                .path_parameters(path, &pointer, &declared, complete && path_level_complete);
+        }
+        if item.operations().next().is_none() {
+            if let Some(path) = path {
+                self.report
+                    .path_parameters(path, pointer, &path_level, path_level_complete);
+            }
+        }
+    }
+
+    /// The names and pointers of the path parameters of a list, and whether every reference of the list resolved.
+    fn path_parameters(
+        &self,
+        parameters: Option<&'a [RefOrObject<Parameter>]>,
+        pointer: &str,
+    ) -> (Vec<(&'a str, String)>, bool) {
+        let mut declared = Vec::new();
+        let mut complete = true;
+        for (index, parameter) in parameters.unwrap_or_default().iter().enumerate() {
+            let Ok(parameter) = self.openapi.resolve(parameter) else {
+                complete = false;
+                continue;
+            };
+            if parameter.r#in == "path" {
+                let pointer = join(&join(pointer, "parameters"), &index.to_string());
+                declared.push((parameter.name.as_str(), pointer));
+            }
+        }
+        (declared, complete)
+    }
+
+    fn operation(&mut self, operation: &'a Operation, pointer: &str) {
+        self.parameters(operation.parameters.as_deref(), pointer);
+        if let Some(RefOrObject::Object(body)) = &operation.request_body {
+            self.content(&body.content, &join(pointer, "requestBody"));
+        }
+        self.responses(&operation.responses, &join(pointer, "responses"));
+        for (name, callback) in operation.callbacks.iter().flatten() {
+            if let RefOrObject::Object(callback) = callback {
+                self.callback(callback, &join(&join(pointer, "callbacks"), name));
+            }
+        }
+        if let Some(security) = &operation.security {
+            self.security(security, &join(pointer, "security"));
+        }
+        for (index, server) in operation.servers.iter().flatten().enumerate() {
+            self.server(server, &join(&join(pointer, "servers"), &index.to_string()));
+        }
+    }
+
+    fn callback(&mut self, callback: &'a Callback, pointer: &str) {
+        for (expression, item) in callback {
+            self.path_item(item, &join(pointer, expression), None);
+        }
+    }
+
+    fn parameters(&mut self, parameters: Option<&'a [RefOrObject<Parameter>]>, pointer: &str) {
+        let pointer = join(pointer, "parameters");
+        let mut keys = Vec::new();
+        for (index, item) in parameters.unwrap_or_default().iter().enumerate() {
+            let pointer = join(&pointer, &index.to_string());
+            if let RefOrObject::Object(parameter) = item {
+                self.parameter(parameter, &pointer);
+            }
+            if let Ok(parameter) = self.openapi.resolve(item) {
+                keys.push((format!("{} in {}", parameter.name, parameter.r#in), pointer));
+            }
+        }
+        self.report.duplicates(
+            "parameters-unique",
+            "parameter",
+            keys.iter()
+                .map(|(key, pointer)| (key.as_str(), pointer.clone())),
+        );
+    }
+
+    fn parameter(&mut self, parameter: &'a Parameter, pointer: &str) {
+        if !LOCATIONS.contains(&parameter.r#in.as_str()) {
+            self.report.error(
+                "parameter-in",
+                &join(pointer, "in"),
+                format!(
+                    "`{}` is not a parameter location, expected one of {}",
+                    parameter.r#in,
+                    LOCATIONS.join(", ")
+                ),
+            );
+        }
+        if parameter.r#in == "path" && parameter.required != Some(true) {
+            self.report.error(
+                "path-parameter-required",
+                pointer,
+                format!("path parameter `{}` must be required", parameter.name),
+            );
+        }
+        self.schema_or_content(
+            parameter.schema.is_some(),
+            parameter.content.as_ref(),
+            pointer,
+        );
+        self.example_or_examples(
+            parameter.example.is_some(),
+            parameter.examples.is_some(),
+            pointer,
+        );
+        if let Some(content) = &parameter.content {
+            self.content(content, pointer);
+        }
+    }
+
+    fn header(&mut self, header: &'a Header, pointer: &str) {
+        self.schema_or_content(header.schema.is_some(), header.content.as_ref(), pointer);
+        self.example_or_examples(header.example.is_some(), header.examples.is_some(), pointer);
+        if let Some(content) = &header.content {
+            self.content(content, pointer);
+        }
+    }
+
+    /// A parameter or header is described either by a schema or by the single media type of its content.
+    fn schema_or_content(
+        &mut self,
+        schema: bool,
+        content: Option<&BTreeMap<String, Media>>,
+        pointer: &str,
+    ) {
+        match (schema, content) {
+            (true, Some(_)) => self.report.error(
+                "parameter-schema-or-content",
+                pointer,
+                "`schema` and `content` are mutually exclusive",
+            ),
+            (false, None) => self.report.error(
+                "parameter-schema-or-content",
+                pointer,
+                "either `schema` or `content` is required",
+            ),
+            (false, Some(content)) if content.len() != 1 => self.report.error(
+                "parameter-schema-or-content",
+                &join(pointer, "content"),
+                "`content` must hold a single media type",
+            ),
+            _ => {}
+        }
+    }
+
+    fn example_or_examples(&mut self, example: bool, examples: bool, pointer: &str) {
+        if example && examples {
+            self.report.error(
+                "example-or-examples",
+                pointer,
+                "`example` and `examples` are mutually exclusive",
+            );
+        }
+    }
+
+    fn content(&mut self, content: &'a BTreeMap<String, Media>, pointer: &str) {
+        let pointer = join(pointer, "content");
+        for (media_type, media) in content {
+            let pointer = join(&pointer, media_type);
+            self.example_or_examples(media.example.is_some(), media.examples.is_some(), &pointer);
+            for (property, encoding) in media.encoding.iter().flatten() {
+                self.headers(
+                    encoding.headers.as_ref(),
+                    &join(&join(&pointer, "encoding"), property),
+                );
+            }
+        }
+    }
+
+    fn headers(
+        &mut self,
+        headers: Option<&'a BTreeMap<String, RefOrObject<Header>>>,
+        pointer: &str,
+    ) {
+        for (name, header) in headers.into_iter().flatten() {
+            if let RefOrObject::Object(header) = header {
+                self.header(header, &join(&join(pointer, "headers"), name));
+            }
+        }
+    }
+
+    fn responses(&mut self, responses: &'a Responses, pointer: &str) {
+        if responses.is_empty() {
+            self.report.error(
+                "responses-not-empty",
+                pointer,
+                "an operation must have at least one response",
+            );
+        }
+        for (status, response) in responses {
+            let pointer = join(pointer, status);
+            if !is_status(status, true) {
+                self.report.error(
+                    "response-status",
+                    &pointer,
+                    format!(
+                        "`{}` is neither `default`, a status code nor a range such as `2XX`",
+                        status
+                    ),
+                );
+            }
+            if let RefOrObject::Object(response) = response {
+                self.response(response, &pointer);
+            }
+        }
+    }
+
+    fn response(&mut self, response: &'a Response, pointer: &str) {
+        self.headers(response.headers.as_ref(), pointer);
+        if let Some(content) = &response.content {
+            self.content(content, pointer);
+        }
+        for (name, link) in response.links.iter().flatten() {
+            if let RefOrObject::Object(link) = link {
+                self.link(link, &join(&join(pointer, "links"), name));
+            }
+        }
+    }
+
+    fn link(&mut self, link: &Link, pointer: &str) {
+        match (&link.operation_ref, &link.operation_id) {
+            (Some(_), Some(_)) => self.report.error(
+                "link-operation",
+                pointer,
+                "`operationRef` and `operationId` are mutually exclusive",
+            ),
+            (None, None) => self.report.error(
+                "link-operation",
+                pointer,
+                "either `operationRef` or `operationId` is required",
+            ),
+            (None, Some(id)) => {
+                let exists = self.openapi.paths.values().any(|item| {
+                    item.operations()
+                        .any(|(_, operation)| operation.operation_id.as_ref() == Some(id))
+                });
+                if !exists {
+                    self.report.error(
+                        "link-operation",
+                        &join(pointer, "operationId"),
+                        format!("no operation has the operationId `{}`", id),
+                    );
+                }
+            }
+            (Some(_), None) => {}
+        }
+    }
+
+    /// Check that the requirements name security schemes, with scopes only for those which have them.
+    fn security(&mut self, security: &[SecurityRequirement], pointer: &str) {
+        let schemes = self
+            .openapi
+            .components
+            .as_ref()
+            .and_then(|components| components.security_schemes.as_ref());
+        for (index, requirement) in security.iter().enumerate() {
+            for (name, scopes) in requirement {
+                let pointer = join(&join(pointer, &index.to_string()), name);
+                let scheme = schemes
+                    .and_then(|schemes| schemes.get(name))
+                    .and_then(|scheme| self.openapi.resolve(scheme).ok());
+                match scheme {
+                    None => self.report.error(
+                        "security-schemes-defined",
+                        &pointer,
+                        format!("security scheme `{}` is not defined", name),
+                    ),
+                    Some(scheme)
+                        if !scopes.is_empty()
+                            && !matches!(
+                                scheme.r#type,
+                                SecuritySchemeType::Oauth2 | SecuritySchemeType::OpenIdConnect
+                            ) =>
+                    {
+                        self.report.error(
+                            "security-schemes-defined",
+                            &pointer,
+                            format!("security scheme `{}` has no scopes to require", name),
+                        )
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+    }
+
+    fn components(&mut self, components: &'a Components) {
+        let pointer = "/components";
+        if let Ok(Value::Object(sections)) = serde_json::to_value(components) {
+            for (section, items) in &sections {
+                let Some(items) = items.as_object().filter(|_| !section.starts_with("x-")) else {
+                    continue;
+                };
+                for name in items.keys().filter(|name| !is_component_name(name)) {
+                    self.report.error(
+                        "component-names",
+                        &join(&join(pointer, section), name),
+                        format!("`{}` is not a valid component name", name),
+                    );
+                }
+            }
+        }
+
+        for (name, parameter) in components.parameters.iter().flatten() {
+            if let RefOrObject::Object(parameter) = parameter {
+                self.parameter(parameter, &join(&join(pointer, "parameters"), name));
+            }
+        }
+        for (name, body) in components.request_bodies.iter().flatten() {
+            if let RefOrObject::Object(body) = body {
+                self.content(&body.content, &join(&join(pointer, "requestBodies"), name));
+            }
+        }
+        for (name, response) in components.responses.iter().flatten() {
+            if let RefOrObject::Object(response) = response {
+                self.response(response, &join(&join(pointer, "responses"), name));
+            }
+        }
+        for (name, header) in components.headers.iter().flatten() {
+            if let RefOrObject::Object(header) = header {
+                self.header(header, &join(&join(pointer, "headers"), name));
+            }
+        }
+        for (name, link) in components.links.iter().flatten() {
+            if let RefOrObject::Object(link) = link {
+                self.link(link, &join(&join(pointer, "links"), name));
+            }
+        }
+        for (name, callback) in components.callbacks.iter().flatten() {
+            if let RefOrObject::Object(callback) = callback {
+                self.callback(callback, &join(&join(pointer, "callbacks"), name));
+            }
+        }
+        for (name, scheme) in components.security_schemes.iter().flatten() {
+            if let RefOrObject::Object(scheme) = scheme {
+                self.security_scheme(scheme, &join(&join(pointer, "securitySchemes"), name));
+            }
+        }
+    }
+
+    fn security_scheme(&mut self, scheme: &SecurityScheme, pointer: &str) {
+        let required: &[(&str, bool)] = match scheme.r#type {
+            SecuritySchemeType::ApiKey => &[
+                ("name", scheme.name.is_some()),
+                ("in", scheme.r#in.is_some()),
+            ],
+            SecuritySchemeType::Http => &[("scheme", scheme.scheme.is_some())],
+            SecuritySchemeType::Oauth2 => &[("flows", scheme.flows.is_some())],
+            SecuritySchemeType::OpenIdConnect => {
+                &[("openIdConnectUrl", scheme.open_id_connect_url.is_some())]
+            }
+        };
+        for (field, present) in required {
+            if !present {
+                self.report.error(
+                    "security-scheme-fields",
+                    pointer,
+                    format!("`{}` is required for this type of security scheme", field),
+                );
+            }
+        }
+        if let Some(location) = scheme.r#in.as_deref() {
+            if !["query", "header", "cookie"].contains(&location) {
+                self.report.error(
+                    "security-scheme-fields",
+                    &join(pointer, "in"),
+                    format!("`{}` is not a location for an API key", location),
+                );
+            }
+        }
+        let Some(flows) = &scheme.flows else {
+            return;
+        };
+        let pointer = join(pointer, "flows");
+        for (name, flow, authorization, token) in [
+            ("implicit", &flows.implicit, true, false),
+            ("password", &flows.password, false, true),
+            ("clientCredentials", &flows.client_credentials, false, true),
+            ("authorizationCode", &flows.authorization_code, true, true),
+        ] {
+            let Some(flow) = flow else {
+                continue;
+            };
+            let pointer = join(&pointer, name);
+            for (field, required, present) in [
+                (
+                    "authorizationUrl",
+                    authorization,
+                    flow.authorization_url.is_some(),
+                ),
+                ("tokenUrl", token, flow.token_url.is_some()),
+            ] {
+                if required && !present {
+                    self.report.error(
+                        "oauth-flow-urls",
+                        &pointer,
+                        format!("`{}` is required for the `{}` flow", field, name),
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::BTreeSet;
+
+    fn rules(document: Value) -> Vec<(String, String)> {
+        let openapi: OpenApi = serde_json::from_value(document).unwrap();
+        openapi
+            .validate()
+            .into_iter()
+            .map(|diagnostic| (diagnostic.rule, diagnostic.pointer))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn test_validate() {
+        let document = json!({
+            "openapi": "3.0.3",
+            "info": {"title": "Pets", "version": "1"},
+            "servers": [{"url": "https://{region}.example.com"}],
+            "tags": [{"name": "pets"}, {"name": "pets"}],
+            "paths": {
+                "/pets/{petId}": {
+                    "get": {
+                        "operationId": "getPet",
+                        "parameters": [
+                            {"name": "id", "in": "path", "schema": {"type": "string"}},
+                            {"name": "q", "in": "body", "schema": {"type": "string"}}
+                        ],
+                        "responses": {"20": {"$ref": "#/components/responses/Missing"}}
+                    },
+                    "put": {"operationId": "getPet", "responses": {}, "security": [{"key": []}]}
+                }
+            }
+        });
+        let pet = "/paths/~1pets~1{petId}";
+        let expected: Vec<(String, String)> = [
+            ("operation-id-unique", format!("{}/put/operationId", pet)),
+            ("parameter-in", format!("{}/get/parameters/1/in", pet)),
+            (
+                "path-parameter-required",
+                format!("{}/get/parameters/0", pet),
+            ),
+            ("path-parameters-defined", format!("{}/get", pet)),
+            ("path-parameters-defined", format!("{}/put", pet)),
+            (
+                "path-parameters-in-template",
+                format!("{}/get/parameters/0", pet),
+            ),
+            (
+                "references-resolve",
+                format!("{}/get/responses/20/$ref", pet),
+            ),
+            ("response-status", format!("{}/get/responses/20", pet)),
+            ("responses-not-empty", format!("{}/put/responses", pet)),
+            (
+                "security-schemes-defined",
+                format!("{}/put/security/0/key", pet),
+            ),
+            ("server-variables-defined", "/servers/0/url".to_string()),
+            ("tags-unique", "/tags/1/name".to_string()),
+        ]
+        .into_iter()
+        .map(|(rule, pointer)| (rule.to_string(), pointer))
+        .collect();
+        assert_eq!(rules(document), expected);
+    }
+
+    #[test]
+    fn test_validate_valid() {
+        let document = json!({
+            "openapi": "3.0.3",
+            "info": {"title": "Pets", "version": "1"},
+            "paths": {
+                "/pets/{petId}": {
+                    "parameters": [{"name": "petId", "in": "path", "required": true, "schema": {"type": "string"}}],
+                    "get": {
+                        "operationId": "getPet",
+                        "security": [{"oauth": ["read"]}],
+                        "responses": {
+                            "2XX": {
+                                "description": "The pet.",
+                                "links": {"self": {"operationId": "getPet"}}
+                            }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "securitySchemes": {
+                    "oauth": {
+                        "type": "oauth2",
+                        "flows": {"implicit": {"authorizationUrl": "https://example.com", "scopes": {"read": "Read."}}}
+                    }
+                }
+            }
+        });
+        assert_eq!(rules(document), []);
+    }
+
+    #[test]
+    fn test_validate_unresolved_parameter() {
+        // the external parameter may declare `petId`, the path is not reported
+        let document = json!({
+            "openapi": "3.0.3",
+            "info": {"title": "Pets", "version": "1"},
+            "paths": {
+                "/pets/{petId}": {
+                    "get": {
+                        "operationId": "getPet",
+                        "parameters": [{"$ref": "parameters.yaml#/petId"}],
+                        "responses": {"200": {"description": "The pet."}}
+                    }
+                }
+            }
+        });
+        assert_eq!(rules(document), []);
+    }
+
+    #[test]
+    fn test_validate_petstore() {
+        let openapi: OpenApi =
+            serde_json::from_str(&std::fs::read_to_string("./tests/petstore-v3.json").unwrap())
+                .unwrap();
+        assert_eq!(openapi.validate(), []);
+    }
+}
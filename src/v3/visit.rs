@@ -0,0 +1,213 @@
+//! Traversal of the references of a document.
+//!
+//! [`Walk`] steps through every object of a document which may hold a `$ref`, calling a [`VisitMut`] for each
+//! [`RefOrObject`] and [`PathItem`]. The visitor decides what to do with a reference, and walks on into the object
+//! with [`walk_ref`] or [`walk_path_item`].
+
+use std::collections::BTreeMap;
+
+use serde::de::DeserializeOwned;
+
+use crate::error::Error;
+use crate::resolve::Navigate;
+
+use super::schema::*;
+
+/// A visitor which may rewrite the references of a document.
+pub trait VisitMut {
+    /// Visit an object which may be a reference, then the objects it holds.
+    fn visit_ref<T: Component>(&mut self, item: &mut RefOrObject<T>) -> Result<(), Error> {
+        walk_ref(self, item)
+    }
+
+    /// Visit a path item, which may be defined by a `$ref`, then the objects it holds.
+    fn visit_path_item(&mut self, item: &mut PathItem) -> Result<(), Error> {
+        walk_path_item(self, item)
+    }
+}
+
+/// An object which holds references, directly or through its children.
+pub trait Walk {
+    /// Call the visitor for every reference held by the object.
+    fn walk<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) -> Result<(), Error>;
+}
+
+/// An object which can be defined under `components`.
+pub trait Component: Walk + Navigate + Clone + DeserializeOwned {
+    /// The name of the `components` field holding objects of this type, such as `schemas`.
+    const SECTION: &'static str;
+
+    /// The `components` field holding objects of this type.
+    fn section(components: &mut Components) -> &mut Option<BTreeMap<String, RefOrObject<Self>>>;
+}
+
+/// Walk into an inline object, a reference holds nothing to walk.
+pub fn walk_ref<V, T>(visitor: &mut V, item: &mut RefOrObject<T>) -> Result<(), Error>
+where
+    V: VisitMut + ?Sized,
+    T: Component,
+{
+    match item {
+        RefOrObject::Ref(_) => Ok(()),
+        RefOrObject::Object(object) => object.walk(visitor),
+    }
+}
+
+/// Walk into the operations and parameters of a path item.
+pub fn walk_path_item<V>(visitor: &mut V, item: &mut PathItem) -> Result<(), Error>
+where
+    V: VisitMut + ?Sized,
+{
+    for operation in [
+        &mut item.get,
+        &mut item.put,
+        &mut item.post,
+        &mut item.delete,
+        &mut item.options,
+        &mut item.head,
+        &mut item.patch,
+        &mut item.trace,
+    ] {
+        operation.walk(visitor)?;
+    }
+    item.parameters.walk(visitor)
+}
+
+impl<T: Walk> Walk for Option<T> {
+    fn walk<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) -> Result<(), Error> {
+        match self {
+            Some(item) => item.walk(visitor),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: Walk> Walk for Box<T> {
+    fn walk<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) -> Result<(), Error> {
+        (**self).walk(visitor)
+    }
+}
+
+impl<T: Walk> Walk for Vec<T> {
+    fn walk<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) -> Result<(), Error> {
+        self.iter_mut().try_for_each(|item| item.walk(visitor))
+    }
+}
+
+impl<T: Walk> Walk for BTreeMap<String, T> {
+    fn walk<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) -> Result<(), Error> {
+        self.values_mut().try_for_each(|item| item.walk(visitor))
+    }
+}
+
+impl<T: Component> Walk for RefOrObject<T> {
+    fn walk<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) -> Result<(), Error> {
+        visitor.visit_ref(self)
+    }
+}
+
+impl Walk for PathItem {
+    fn walk<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) -> Result<(), Error> {
+        visitor.visit_path_item(self)
+    }
+}
+
+impl Walk for AdditionalProperties {
+    fn walk<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) -> Result<(), Error> {
+        match self {
+            AdditionalProperties::Bool(_) => Ok(()),
+            AdditionalProperties::Schema(schema) => schema.walk(visitor),
+        }
+    }
+}
+
+/// Implement [`Walk`] for a struct by walking the listed fields.
+macro_rules! walk {
+    ($ty:ty { $($field:ident),* $(,)? }) => {
+        impl Walk for $ty {
+            #[allow(unused_variables)]
+            fn walk<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) -> Result<(), Error> {
+                $(self.$field.walk(visitor)?;)*
+                Ok(())
+            }
+        }
+    };
+}
+
+walk!(OpenApi { paths, components });
+walk!(Components {
+    schemas,
+    responses,
+    parameters,
+    examples,
+    request_bodies,
+    headers,
+    security_schemes,
+    links,
+    callbacks,
+});
+walk!(Operation {
+    parameters,
+    request_body,
+    responses,
+    callbacks,
+});
+walk!(Parameter {
+    schema,
+    examples,
+    content,
+});
+walk!(RequestBody { content });
+walk!(Response {
+    headers,
+    content,
+    links,
+});
+walk!(Media {
+    schema,
+    examples,
+    encoding,
+});
+walk!(Encoding { headers });
+walk!(Header {
+    schema,
+    examples,
+    content,
+});
+walk!(Schema {
+    items,
+    properties,
+    all_of,
+    one_of,
+    any_of,
+    not,
+    additional_properties,
+});
+walk!(Example {});
+walk!(Link {});
+walk!(SecurityScheme {});
+
+/// Implement [`Component`] for a type stored in the given `components` field.
+macro_rules! component {
+    ($ty:ty, $section:literal, $field:ident) => {
+        impl Component for $ty {
+            const SECTION: &'static str = $section;
+
+            fn section(
+                components: &mut Components,
+            ) -> &mut Option<BTreeMap<String, RefOrObject<Self>>> {
+                &mut components.$field
+            }
+        }
+    };
+}
+
+component!(Schema, "schemas", schemas);
+component!(Response, "responses", responses);
+component!(Parameter, "parameters", parameters);
+component!(Example, "examples", examples);
+component!(RequestBody, "requestBodies", request_bodies);
+component!(Header, "headers", headers);
+component!(SecurityScheme, "securitySchemes", security_schemes);
+component!(Link, "links", links);
+component!(Callback, "callbacks", callbacks);
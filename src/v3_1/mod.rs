@@ -0,0 +1,3 @@
+mod resolve;
+mod schema;
+pub use crate::v3_1::schema::*;
@@ -0,0 +1,224 @@
+use crate::error::Error;
+use crate::resolve::{find, mismatched, navigate, Chain, Navigate};
+
+use super::schema::*;
+
+impl OpenApi {
+    /// Resolve an object which may be a reference, following references to references.
+    ///
+    /// Only local references such as `#/components/responses/NotFound` are followed, and the target must be of the same type.
+    /// The `summary` and `description` of a reference are not applied to the target.
+    pub fn resolve<'a, T>(&'a self, item: &'a RefOrObject<T>) -> Result<&'a T, Error>
+    where
+        T: Navigate,
+    {
+        let mut chain = Chain::default();
+        let mut item = item;
+        loop {
+            match item {
+                RefOrObject::Object(object) => return Ok(object),
+                RefOrObject::Ref(Reference { reference, .. }) => {
+                    chain.follow(reference)?;
+                    item = find(self, reference)?
+                        .as_any()
+                        .downcast_ref::<RefOrObject<T>>()
+                        .ok_or_else(|| mismatched::<T>(reference))?;
+                }
+            }
+        }
+    }
+
+    /// Resolve a schema with a `$ref`, following references to references.
+    ///
+    /// Only local JSON pointers such as `#/components/schemas/Pet` are followed, `$id` and `$anchor` are not looked up.
+    /// The keywords next to a `$ref` are not merged into the target.
+    pub fn resolve_schema<'a>(&'a self, schema: &'a Schema) -> Result<&'a Schema, Error> {
+        let mut chain = Chain::default();
+        let mut schema = schema;
+        while let Some(reference) = match schema {
+            Schema::Object(object) => object.reference.as_ref(),
+            Schema::Bool(_) => None,
+        } {
+            chain.follow(reference)?;
+            schema = find(self, reference)?
+                .as_any()
+                .downcast_ref::<Schema>()
+                .ok_or_else(|| mismatched::<Schema>(reference))?;
+        }
+        Ok(schema)
+    }
+
+    /// Resolve a path item which is defined by a `$ref` to another path item of the document.
+    pub fn resolve_path_item<'a>(&'a self, item: &'a PathItem) -> Result<&'a PathItem, Error> {
+        let mut chain = Chain::default();
+        let mut item = item;
+        while let Some(reference) = &item.reference {
+            chain.follow(reference)?;
+            item = find(self, reference)?
+                .as_any()
+                .downcast_ref::<PathItem>()
+                .ok_or_else(|| mismatched::<PathItem>(reference))?;
+        }
+        Ok(item)
+    }
+}
+
+impl<T: Navigate> Navigate for RefOrObject<T> {
+    fn child(&self, token: &str) -> Option<&dyn Navigate> {
+        match self {
+            RefOrObject::Ref(_) => None,
+            RefOrObject::Object(object) => object.child(token),
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+impl Navigate for Schema {
+    fn child(&self, token: &str) -> Option<&dyn Navigate> {
+        match self {
+            Schema::Bool(_) => None,
+            Schema::Object(object) => object.child(token),
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+navigate!(OpenApi {
+    "paths" => ?paths,
+    "webhooks" => ?webhooks,
+    "components" => ?components,
+});
+
+navigate!(Components {
+    "schemas" => ?schemas,
+    "responses" => ?responses,
+    "parameters" => ?parameters,
+    "examples" => ?examples,
+    "requestBodies" => ?request_bodies,
+    "headers" => ?headers,
+    "securitySchemes" => ?security_schemes,
+    "links" => ?links,
+    "callbacks" => ?callbacks,
+    "pathItems" => ?path_items,
+});
+
+navigate!(PathItem {
+    "get" => ?get,
+    "put" => ?put,
+    "post" => ?post,
+    "delete" => ?delete,
+    "options" => ?options,
+    "head" => ?head,
+    "patch" => ?patch,
+    "trace" => ?trace,
+    "parameters" => ?parameters,
+});
+
+navigate!(Operation {
+    "parameters" => ?parameters,
+    "requestBody" => ?request_body,
+    "responses" => ?responses,
+    "callbacks" => ?callbacks,
+});
+
+navigate!(Parameter {
+    "schema" => ?schema,
+    "examples" => ?examples,
+    "content" => ?content,
+});
+
+navigate!(RequestBody {
+    "content" => content,
+});
+
+navigate!(Response {
+    "headers" => ?headers,
+    "content" => ?content,
+    "links" => ?links,
+});
+
+navigate!(Media {
+    "schema" => ?schema,
+    "examples" => ?examples,
+    "encoding" => ?encoding,
+});
+
+navigate!(Encoding {
+    "headers" => ?headers,
+});
+
+navigate!(Header {
+    "schema" => ?schema,
+    "examples" => ?examples,
+    "content" => ?content,
+});
+
+navigate!(SchemaObject {
+    "$defs" => ?defs,
+    "contentSchema" => ?content_schema,
+    "allOf" => ?all_of,
+    "anyOf" => ?any_of,
+    "oneOf" => ?one_of,
+    "not" => ?not,
+    "if" => ?r#if,
+    "then" => ?then,
+    "else" => ?r#else,
+    "dependentSchemas" => ?dependent_schemas,
+    "prefixItems" => ?prefix_items,
+    "items" => ?items,
+    "contains" => ?contains,
+    "properties" => ?properties,
+    "patternProperties" => ?pattern_properties,
+    "additionalProperties" => ?additional_properties,
+    "propertyNames" => ?property_names,
+    "unevaluatedItems" => ?unevaluated_items,
+    "unevaluatedProperties" => ?unevaluated_properties,
+});
+
+navigate!(Link {});
+navigate!(SecurityScheme {});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        let content = std::fs::read_to_string("./tests/openapi31.json").unwrap();
+        let openapi: OpenApi = serde_json::from_str(&content).unwrap();
+
+        let item = openapi.resolve_path_item(&openapi.paths.as_ref().unwrap()["/pets/{petId}"]);
+        let operation = item.unwrap().get.as_ref().unwrap();
+        assert_eq!(operation.operation_id.as_deref(), Some("getPet"));
+
+        let responses = operation.responses.as_ref().unwrap();
+        assert_eq!(
+            openapi.resolve(&responses["default"]).unwrap().description,
+            "An error"
+        );
+
+        let media = &openapi
+            .resolve(&responses["200"])
+            .unwrap()
+            .content
+            .as_ref()
+            .unwrap()["application/json"];
+        let Schema::Object(pet) = openapi
+            .resolve_schema(media.schema.as_ref().unwrap())
+            .unwrap()
+        else {
+            panic!("expected a schema object")
+        };
+        let owner = &pet.properties.as_ref().unwrap()["owner"];
+        let Schema::Object(owner) = openapi.resolve_schema(owner).unwrap() else {
+            panic!("expected a schema object")
+        };
+        assert!(owner.properties.as_ref().unwrap().contains_key("name"));
+    }
+}
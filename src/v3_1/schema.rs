@@ -0,0 +1,791 @@
+use std::collections::BTreeMap;
+
+use crate::de::{deserialize_ref_or, from_value, RefOr};
+use crate::extension::Extensions;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Number, Value};
+
+pub use crate::v3::{
+    Contact, Discriminator, Example, ExternalDoc, Server, ServerVariable, Tag, XML,
+};
+
+/// # OpenApi
+/// This is the root object of the OpenAPI 3.1 document.
+/// see https://spec.openapis.org/oas/v3.1.0#openapi-object
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct OpenApi {
+    /// This string MUST be the version number of the OpenAPI Specification that the OpenAPI document uses.
+    pub openapi: String,
+    /// Provides metadata about the API.
+    pub info: Info,
+    /// The default value for the `$schema` keyword within Schema Objects contained within this OAS document.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "jsonSchemaDialect")]
+    pub json_schema_dialect: Option<String>,
+    /// An array of Server Objects, which provide connectivity information to a target server.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub servers: Option<Vec<Server>>,
+    /// The available paths and operations for the API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paths: Option<Paths>,
+    /// The incoming webhooks that MAY be received as part of this API and that the API consumer MAY choose to implement.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhooks: Option<BTreeMap<String, PathItem>>,
+    /// An element to hold various schemas for the document.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components: Option<Components>,
+    /// A declaration of which security mechanisms can be used across the API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security: Option<Vec<SecurityRequirement>>,
+    /// A list of tags used by the document with additional metadata.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<Tag>>,
+    /// Additional external documentation.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "externalDocs")]
+    pub external_docs: Option<ExternalDoc>,
+
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// ## Info
+/// The object provides metadata about the API.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Info {
+    ///  The title of the API.
+    pub title: String,
+    /// A short summary of the API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// A description of the API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// A URL to the Terms of Service for the API.
+    #[serde(rename = "termsOfService", skip_serializing_if = "Option::is_none")]
+    pub terms_of_service: Option<String>,
+    /// The contact information for the exposed API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact: Option<Contact>,
+    /// The license information for the exposed API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<License>,
+    /// The version of the OpenAPI document (which is distinct from the OpenAPI Specification version or the API implementation version).
+    pub version: String,
+
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// ### License
+/// License information for the exposed API.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct License {
+    /// The license name used for the API.
+    pub name: String,
+    /// An [SPDX](https://spdx.org/licenses/) license expression for the API. The `identifier` field is mutually exclusive of the `url` field.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<String>,
+    /// A URL to the license used for the API. MUST be in the form of a URL.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// # Components
+/// Holds a set of reusable objects for different aspects of the OAS. All objects defined within the components object will have no effect on the API unless they are explicitly referenced from properties outside the components object.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Components {
+    /// An object to hold reusable Schema Objects.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schemas: Option<BTreeMap<String, Schema>>,
+    /// An object to hold reusable Response Objects.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub responses: Option<BTreeMap<String, RefOrObject<Response>>>,
+    /// An object to hold reusable Parameter Objects.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<BTreeMap<String, RefOrObject<Parameter>>>,
+    /// An object to hold reusable Example Objects.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<BTreeMap<String, RefOrObject<Example>>>,
+    /// An object to hold reusable Request Body Objects.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "requestBodies")]
+    pub request_bodies: Option<BTreeMap<String, RefOrObject<RequestBody>>>,
+    /// An object to hold reusable Header Objects.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<BTreeMap<String, RefOrObject<Header>>>,
+    /// An object to hold reusable Security Scheme Objects.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "securitySchemes")]
+    pub security_schemes: Option<BTreeMap<String, RefOrObject<SecurityScheme>>>,
+    /// An object to hold reusable Link Objects.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub links: Option<BTreeMap<String, RefOrObject<Link>>>,
+    /// An object to hold reusable Callback Objects.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub callbacks: Option<BTreeMap<String, RefOrObject<Callback>>>,
+    /// An object to hold reusable Path Item Objects.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "pathItems")]
+    pub path_items: Option<BTreeMap<String, PathItem>>,
+
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// ### Paths
+/// Holds the relative paths to the individual endpoints and their operations.
+///
+/// The path is appended to the URL from the Server Object in order to construct the full URL.
+pub type Paths = BTreeMap<String, PathItem>;
+
+/// ### Path Item
+/// Describes the operations available on a single path.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct PathItem {
+    /// Allows for a referenced definition of this path item.
+    /// In case a Path Item Object field appears both in the defined object and the referenced object, the behavior is undefined.
+    #[serde(rename = "$ref", skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+    /// An optional, string summary, intended to apply to all operations in this path.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// An optional, string description, intended to apply to all operations in this path.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// A definition of a GET operation on this path.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub get: Option<Operation>,
+    /// A definition of a PUT operation on this path.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub put: Option<Operation>,
+    /// A definition of a POST operation on this path.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post: Option<Operation>,
+    /// A definition of a DELETE operation on this path.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delete: Option<Operation>,
+    /// A definition of a OPTIONS operation on this path.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options: Option<Operation>,
+    /// A definition of a HEAD operation on this path.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub head: Option<Operation>,
+    /// A definition of a PATCH operation on this path.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub patch: Option<Operation>,
+    /// A definition of a TRACE operation on this path.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace: Option<Operation>,
+    /// An alternative server array to service all operations in this path.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub servers: Option<Vec<Server>>,
+    /// A list of parameters that are applicable for all the operations described under this path.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<Vec<RefOrObject<Parameter>>>,
+
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// ### Operation
+/// Describes a single API operation on a path.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Operation {
+    /// A list of tags for API documentation control.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    /// A short summary of what the operation does.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// A verbose explanation of the operation behavior.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Additional external documentation for this operation.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "externalDocs")]
+    pub external_docs: Option<ExternalDoc>,
+    /// Unique string used to identify the operation.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "operationId")]
+    pub operation_id: Option<String>,
+    /// A list of parameters that are applicable for this operation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<Vec<RefOrObject<Parameter>>>,
+    /// The request body applicable for this operation.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "requestBody")]
+    pub request_body: Option<RefOrObject<RequestBody>>,
+    /// The list of possible responses as they are returned from executing this operation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub responses: Option<Responses>,
+    /// A map of possible out-of band callbacks related to the parent operation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub callbacks: Option<BTreeMap<String, RefOrObject<Callback>>>,
+    /// Declares this operation to be deprecated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+    /// A declaration of which security mechanisms can be used for this operation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security: Option<Vec<SecurityRequirement>>,
+    /// An alternative server array to service this operation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub servers: Option<Vec<Server>>,
+
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// ## Parameter
+///
+/// Describes a single operation parameter.
+/// A unique parameter is defined by a combination of a name and location.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct Parameter {
+    /// The name of the parameter.
+    pub name: String,
+    /// The location of the parameter. Possible values are "query", "header", "path" or "cookie".
+    pub r#in: String,
+    /// A brief description of the parameter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Determines whether this parameter is mandatory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+    /// Specifies that a parameter is deprecated and SHOULD be transitioned out of usage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+    /// Sets the ability to pass empty-valued parameters.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "allowEmptyValue")]
+    pub allow_empty_value: Option<bool>,
+    ///Describes how the parameter value will be serialized depending on the type of the parameter value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<String>,
+    /// When this is true, parameter values of type array or object generate separate parameters for each value of the array or key-value pair of the map.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explode: Option<bool>,
+    /// Determines whether the parameter value SHOULD allow reserved characters
+    #[serde(skip_serializing_if = "Option::is_none", rename = "allowReserved")]
+    pub allow_reserved: Option<bool>,
+    /// The schema defining the type used for the parameter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<Schema>,
+    /// Example of the parameter's potential value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example: Option<Value>,
+    /// Examples of the parameter's potential value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<BTreeMap<String, RefOrObject<Example>>>,
+    /// A map containing the representations for the parameter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<BTreeMap<String, Media>>,
+
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// ### Request Body
+/// Describes a single request body.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct RequestBody {
+    /// A brief description of the request body.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The content of the request body.
+    /// The key is a media type or media type range and the value describes it.
+    pub content: BTreeMap<String, Media>,
+    /// Determines if the request body is required in the request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// ## Responses
+/// A container for the expected responses of an operation. The container maps a HTTP response code to the expected response.
+pub type Responses = BTreeMap<String, RefOrObject<Response>>;
+
+/// ### Reponse
+/// Describes a single response from an API Operation, including design-time, static links to operations based on the response.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct Response {
+    /// A description of the response.
+    pub description: String,
+    /// Maps a header name to its definition.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<BTreeMap<String, RefOrObject<Header>>>,
+    /// A map containing descriptions of potential response payloads.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<BTreeMap<String, Media>>,
+    /// A map of operations links that can be followed from the response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub links: Option<BTreeMap<String, RefOrObject<Link>>>,
+
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// ### Media
+/// Each Media Type Object provides schema and examples for the media type identified by its key.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Media {
+    /// The schema defining the content of the request, response, or parameter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<Schema>,
+    /// Example of the media type.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example: Option<Value>,
+    /// Examples of the media type.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<BTreeMap<String, RefOrObject<Example>>>,
+    /// A map between a property name and its encoding information.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<BTreeMap<String, Encoding>>,
+
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// ### Encoding
+/// A single encoding definition applied to a single schema property.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct Encoding {
+    /// The Content-Type for encoding a specific property.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "contentType")]
+    pub content_type: Option<String>,
+    /// A map allowing additional information to be provided as headers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<BTreeMap<String, RefOrObject<Header>>>,
+    /// Describes how a specific property value will be serialized depending on its type.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<String>,
+    /// When this is true, property values of type array or object generate separate parameters for each value of the array, or key-value-pair of the map.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explode: Option<bool>,
+    /// Determines whether the parameter value SHOULD allow reserved characters, as defined by RFC3986 :/?#[]@!$&'()*+,;= to be included without percent-encoding.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "allowReserved")]
+    pub allow_reserved: Option<bool>,
+
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// ### Callback
+/// A map of possible out-of band callbacks related to the parent operation.
+pub type Callback = BTreeMap<String, PathItem>;
+
+/// ### Link
+/// The Link object represents a possible design-time link for a response.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct Link {
+    /// A relative or absolute URI reference to an OAS operation.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "operationRef")]
+    pub operation_ref: Option<String>,
+    /// The name of an existing, resolvable OAS operation, as defined with a unique operationId. This field is mutually exclusive of the operationRef field.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "operationId")]
+    pub operation_id: Option<String>,
+    /// A map representing parameters to pass to an operation as specified with operationId or identified via operationRef.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<BTreeMap<String, Value>>,
+    /// A literal value or {expression} to use as a request body when calling the target operation.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "requestBody")]
+    pub request_body: Option<Value>,
+    /// A description of the link.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// A server object to be used by the target operation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server: Option<Server>,
+
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// ### Header
+/// The Header Object follows the structure of the Parameter Object without `name` and `in`.
+/// see https://spec.openapis.org/oas/v3.1.0#header-object
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Header {
+    /// A brief description of the header.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Determines whether this header is mandatory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+    /// Specifies that a header is deprecated and SHOULD be transitioned out of usage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+    /// Sets the ability to pass empty-valued parameters.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "allowEmptyValue")]
+    pub allow_empty_value: Option<bool>,
+    ///Describes how the header value will be serialized.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<String>,
+    /// When this is true, header values of type array or object generate separate parameters for each value of the array or key-value pair of the map.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explode: Option<bool>,
+    /// Determines whether the header value SHOULD allow reserved characters
+    #[serde(skip_serializing_if = "Option::is_none", rename = "allowReserved")]
+    pub allow_reserved: Option<bool>,
+    /// The schema defining the type used for the header.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<Schema>,
+    /// Example of the header's potential value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example: Option<Value>,
+    /// Examples of the header's potential value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<BTreeMap<String, RefOrObject<Example>>>,
+    /// A map containing the representations for the header.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<BTreeMap<String, Media>>,
+
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// ### Reference
+/// A simple object to allow referencing other components in the OpenAPI document, internally and externally.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct Reference {
+    /// The reference identifier. This MUST be in the form of a URI.
+    #[serde(rename = "$ref")]
+    pub reference: String,
+    /// A short summary which by default SHOULD override that of the referenced component.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// A description which by default SHOULD override that of the referenced component.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// ### Schema
+/// The Schema Object is a superset of the [JSON Schema Specification Draft 2020-12](https://json-schema.org/specification-links.html#2020-12).
+///
+/// A schema is either a boolean, `true` accepting and `false` rejecting every instance, or a schema object.
+/// see https://spec.openapis.org/oas/v3.1.0#schema-object
+#[derive(Clone, Debug, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum Schema {
+    Bool(bool),
+    Object(Box<SchemaObject>),
+}
+
+impl<'de> Deserialize<'de> for Schema {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match Value::deserialize(deserializer)? {
+            Value::Bool(b) => Ok(Schema::Bool(b)),
+            value => from_value(value).map(|object| Schema::Object(Box::new(object))),
+        }
+    }
+}
+
+/// #### SchemaType
+/// The `type` keyword, either a single type name or an array of type names.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum SchemaType {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+/// #### SchemaObject
+/// The keywords of a schema which is not a boolean.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct SchemaObject {
+    /// The dialect of this schema.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "$schema")]
+    pub schema: Option<String>,
+    /// The canonical URI of this schema.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "$id")]
+    pub id: Option<String>,
+    /// A reference to another schema.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "$ref")]
+    pub reference: Option<String>,
+    /// A plain name fragment identifying this schema.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "$anchor")]
+    pub anchor: Option<String>,
+    /// A reference resolved against the dynamic scope.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "$dynamicRef")]
+    pub dynamic_ref: Option<String>,
+    /// A plain name fragment identifying this schema in the dynamic scope.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "$dynamicAnchor")]
+    pub dynamic_anchor: Option<String>,
+    /// A comment for schema maintainers.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "$comment")]
+    pub comment: Option<String>,
+    /// Re-usable schemas defined inline.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "$defs")]
+    pub defs: Option<BTreeMap<String, Schema>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "readOnly")]
+    pub read_only: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "writeOnly")]
+    pub write_only: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<Vec<Value>>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "type")]
+    pub r#type: Option<SchemaType>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "enum")]
+    pub r#enum: Option<Vec<Value>>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "const")]
+    pub r#const: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "multipleOf")]
+    pub multiple_of: Option<Number>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<Number>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "exclusiveMaximum")]
+    pub exclusive_maximum: Option<Number>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<Number>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "exclusiveMinimum")]
+    pub exclusive_minimum: Option<Number>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "maxLength")]
+    pub max_length: Option<Number>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "minLength")]
+    pub min_length: Option<Number>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "maxItems")]
+    pub max_items: Option<Number>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "minItems")]
+    pub min_items: Option<Number>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "uniqueItems")]
+    pub unique_items: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "maxContains")]
+    pub max_contains: Option<Number>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "minContains")]
+    pub min_contains: Option<Number>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "maxProperties")]
+    pub max_properties: Option<Number>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "minProperties")]
+    pub min_properties: Option<Number>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "dependentRequired")]
+    pub dependent_required: Option<BTreeMap<String, Vec<String>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "contentEncoding")]
+    pub content_encoding: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "contentMediaType")]
+    pub content_media_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "contentSchema")]
+    pub content_schema: Option<Schema>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "allOf")]
+    pub all_of: Option<Vec<Schema>>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "anyOf")]
+    pub any_of: Option<Vec<Schema>>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "oneOf")]
+    pub one_of: Option<Vec<Schema>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub not: Option<Schema>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "if")]
+    pub r#if: Option<Schema>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub then: Option<Schema>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "else")]
+    pub r#else: Option<Schema>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "dependentSchemas")]
+    pub dependent_schemas: Option<BTreeMap<String, Schema>>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "prefixItems")]
+    pub prefix_items: Option<Vec<Schema>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub items: Option<Schema>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contains: Option<Schema>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub properties: Option<BTreeMap<String, Schema>>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "patternProperties")]
+    pub pattern_properties: Option<BTreeMap<String, Schema>>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        rename = "additionalProperties"
+    )]
+    pub additional_properties: Option<Schema>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "propertyNames")]
+    pub property_names: Option<Schema>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "unevaluatedItems")]
+    pub unevaluated_items: Option<Schema>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        rename = "unevaluatedProperties"
+    )]
+    pub unevaluated_properties: Option<Schema>,
+
+    /// Adds support for polymorphism.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub discriminator: Option<Discriminator>,
+    /// This MAY be used only on properties schemas.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xml: Option<XML>,
+    /// Additional external documentation for this schema.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "externalDocs")]
+    pub external_docs: Option<ExternalDoc>,
+    /// A free-form property to include an example of an instance for this schema. Deprecated in favor of `examples`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example: Option<Value>,
+
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// ### SecurityScheme
+/// Defines a security scheme that can be used by the operations.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct SecurityScheme {
+    ///  The type of the security scheme.
+    pub r#type: SecuritySchemeType,
+    /// A description for security scheme.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The name of the header, query or cookie parameter to be used. Required for `apiKey`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The location of the API key. Valid values are "query", "header" or "cookie". Required for `apiKey`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r#in: Option<String>,
+    /// The name of the HTTP Authorization scheme to be used in the Authorization header. Required for `http`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scheme: Option<String>,
+    /// A hint to the client to identify how the bearer token is formatted.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "bearerFormat")]
+    pub bearer_format: Option<String>,
+    ///  An object containing configuration information for the flow types supported. Required for `oauth2`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flows: Option<OAuthFlows>,
+    ///  OpenId Connect URL to discover OAuth2 configuration values. Required for `openIdConnect`.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "openIdConnectUrl")]
+    pub open_id_connect_url: Option<String>,
+
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// #### SecuritySchemeType
+/// for SecurityScheme.type
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum SecuritySchemeType {
+    ApiKey,
+    Http,
+    #[serde(rename = "mutualTLS")]
+    MutualTls,
+    Oauth2,
+    OpenIdConnect,
+}
+
+/// ### OAuthFlows
+///
+/// Allows configuration of the supported OAuth Flows.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct OAuthFlows {
+    /// Configuration for the OAuth Implicit flow
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub implicit: Option<OAuthFlow>,
+    /// Configuration for the OAuth Resource Owner Password flow
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<OAuthFlow>,
+    /// Configuration for the OAuth Client Credentials flow. Previously called application in OpenAPI 2.0.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "clientCredentials")]
+    pub client_credentials: Option<OAuthFlow>,
+    /// Configuration for the OAuth Authorization Code flow. Previously called accessCode in OpenAPI 2.0.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "authorizationCode")]
+    pub authorization_code: Option<OAuthFlow>,
+
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// ### OAuthFlow
+/// Configuration details for a supported OAuth Flow
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct OAuthFlow {
+    /// The authorization URL to be used for this flow. Required for `implicit` and `authorizationCode`.
+    #[serde(rename = "authorizationUrl", skip_serializing_if = "Option::is_none")]
+    pub authorization_url: Option<String>,
+    /// The token URL to be used for this flow. Required for `password`, `clientCredentials` and `authorizationCode`.
+    #[serde(rename = "tokenUrl", skip_serializing_if = "Option::is_none")]
+    pub token_url: Option<String>,
+    /// The URL to be used for obtaining refresh tokens.
+    #[serde(rename = "refreshUrl", skip_serializing_if = "Option::is_none")]
+    pub refresh_url: Option<String>,
+    /// The available scopes for the OAuth2 security scheme.
+    pub scopes: BTreeMap<String, String>,
+
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// ### SecurityRequirement
+/// Lists the required security schemes to execute this operation.
+pub type SecurityRequirement = BTreeMap<String, Vec<String>>;
+
+/// ### RefOrObject
+/// Either a Reference Object or the object itself.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum RefOrObject<T> {
+    Ref(Reference),
+    Object(T),
+}
+
+impl<'de, T> Deserialize<'de> for RefOrObject<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(match deserialize_ref_or(deserializer)? {
+            RefOr::Ref(reference) => RefOrObject::Ref(reference),
+            RefOr::Object(object) => RefOrObject::Object(object),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_boolean_schema() {
+        let schema: Schema = serde_json::from_value(json!(false)).unwrap();
+        assert_eq!(schema, Schema::Bool(false));
+        assert_eq!(serde_json::to_value(&schema).unwrap(), json!(false));
+    }
+
+    #[test]
+    fn test_type_array() {
+        let value = json!({
+            "type": ["string", "null"],
+            "exclusiveMaximum": 10,
+            "additionalProperties": false,
+            "prefixItems": [{"const": 1}, true]
+        });
+        let schema: Schema = serde_json::from_value(value.clone()).unwrap();
+        let Schema::Object(object) = &schema else {
+            panic!("expected a schema object")
+        };
+        assert_eq!(
+            object.r#type,
+            Some(SchemaType::Multiple(vec![
+                "string".to_string(),
+                "null".to_string()
+            ]))
+        );
+        assert_eq!(object.additional_properties, Some(Schema::Bool(false)));
+        assert_eq!(serde_json::to_value(&schema).unwrap(), value);
+    }
+}
@@ -0,0 +1,318 @@
+//! Diagnostics raised when checking a document against the rules of its specification.
+//!
+//! The types of this crate accept documents which the specification forbids, such as duplicated `operationId`s or path
+//! parameters which are not required. [`OpenApi::validate`](crate::v3::OpenApi::validate) and
+//! [`Swagger::validate`](crate::v2::Swagger::validate) report each of these as a [`Diagnostic`].
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use serde_json::Value;
+
+use crate::pointer::{join, split_reference};
+
+/// The keywords of a Swagger 2.0 document whose values are data, so that their `$ref`s are not references.
+pub(crate) const V2_LITERALS: &[&str] = &["example", "examples", "default", "enum"];
+
+/// The keywords of an OpenAPI 3 document whose values are data, so that their `$ref`s are not references.
+pub(crate) const V3_LITERALS: &[&str] = &["example", "value", "default", "enum"];
+
+/// # Severity
+/// How serious a diagnostic is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The document breaks a rule the specification says it MUST follow.
+    Error,
+    /// The document breaks a rule the specification says it SHOULD follow.
+    Warning,
+    /// The document could be improved.
+    Info,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+        })
+    }
+}
+
+/// # Diagnostic
+/// A rule broken by a document.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// JSON pointer to the value breaking the rule.
+    pub pointer: String,
+    /// The identifier of the rule, such as `operation-id-unique`.
+    pub rule: String,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} [{}] at `{}`: {}",
+            self.severity, self.rule, self.pointer, self.message
+        )
+    }
+}
+
+/// The diagnostics collected while validating a document.
+#[derive(Default)]
+pub(crate) struct Report {
+    pub(crate) diagnostics: Vec<Diagnostic>,
+}
+
+impl Report {
+    pub(crate) fn push(
+        &mut self,
+        severity: Severity,
+        rule: &str,
+        pointer: &str,
+        message: impl Into<String>,
+    ) {
+        self.diagnostics.push(Diagnostic {
+            severity,
+            pointer: pointer.to_string(),
+            rule: rule.to_string(),
+            message: message.into(),
+        });
+    }
+
+    pub(crate) fn error(&mut self, rule: &str, pointer: &str, message: impl Into<String>) {
+        self.push(Severity::Error, rule, pointer, message);
+    }
+
+    pub(crate) fn warning(&mut self, rule: &str, pointer: &str, message: impl Into<String>) {
+        self.push(Severity::Warning, rule, pointer, message);
+    }
+
+    /// Report each name after its first occurrence, found at the pointer paired with it.
+    pub(crate) fn duplicates<'a>(
+        &mut self,
+        rule: &str,
+        what: &str,
+        names: impl IntoIterator<Item = (&'a str, String)>,
+    ) {
+        let mut seen = BTreeSet::new();
+        for (name, pointer) in names {
+            if !seen.insert(name) {
+                self.error(rule, &pointer, format!("{} `{}` is not unique", what, name));
+            }
+        }
+    }
+
+    /// Check the path templates: each must start with a slash, and no two may differ only by the names of their parameters.
+    pub(crate) fn paths<'a>(&mut self, paths: impl IntoIterator<Item = &'a String>) {
+        let mut templates = BTreeMap::new();
+        for path in paths {
+            let pointer = join("/paths", path);
+            if !path.starts_with('/') {
+                self.error(
+                    "path-leading-slash",
+                    &pointer,
+                    format!("path `{}` does not start with a slash", path),
+                );
+            }
+            let mut template = String::new();
+            let mut rest = path.as_str();
+            while let Some(start) = rest.find('{') {
+                template.push_str(&rest[..=start]);
+                rest = rest[start..]
+                    .find('}')
+                    .map_or("", |end| &rest[start + end..]);
+            }
+            template.push_str(rest);
+            if let Some(other) = templates.insert(template, path) {
+                self.error(
+                    "paths-equivalent",
+                    &pointer,
+                    format!("path `{}` is equivalent to `{}`", path, other),
+                );
+            }
+        }
+    }
+
+    /// Check that the path parameters match the template: each templated name is declared and each declaration is used.
+    ///
+    /// `declared` pairs the name of every path parameter of an operation with its pointer. `complete` is false when some
+    /// parameters are references which do not resolve: as they may declare any name, templated names are not reported.
+    pub(crate) fn path_parameters(
+        &mut self,
+        path: &str,
+        pointer: &str,
+        declared: &[(&str, String)],
+        complete: bool,
+    ) {
+        let templated = template_names(path);
+        for name in templated.iter().filter(|_| complete) {
+            if !declared.iter().any(|(declared, _)| declared == name) {
+                self.error(
+                    "path-parameters-defined",
+                    pointer,
+                    format!("path parameter `{}` of `{}` is not declared", name, path),
+                );
+            }
+        }
+        for (name, pointer) in declared {
+            if !templated.contains(name) {
+                self.error(
+                    "path-parameters-in-template",
+                    pointer,
+                    format!("path parameter `{}` does not appear in `{}`", name, path),
+                );
+            }
+        }
+    }
+
+    /// Check that every local `$ref` of a serialized document points to a value.
+    ///
+    /// Literal values, such as examples and defaults, are skipped as their `$ref`s are data.
+    pub(crate) fn references(&mut self, document: &Value, literals: &[&str]) {
+        let mut stack = vec![(document, String::new(), "")];
+        while let Some((value, pointer, key)) = stack.pop() {
+            match value {
+                Value::Object(map) => {
+                    if let Some(Value::String(reference)) = map.get("$ref") {
+                        let (file, fragment) = split_reference(reference);
+                        if file.is_empty() && document.pointer(&fragment).is_none() {
+                            self.error(
+                                "references-resolve",
+                                &join(&pointer, "$ref"),
+                                format!("`{}` points to nothing", reference),
+                            );
+                        }
+                    }
+                    for (child, value) in map {
+                        if !is_literal(key, child, literals) {
+                            stack.push((value, join(&pointer, child), child));
+                        }
+                    }
+                }
+                Value::Array(items) => {
+                    for (index, value) in items.iter().enumerate() {
+                        stack.push((value, join(&pointer, &index.to_string()), ""));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// The keywords of maps whose members are named by the document, such as the `default` response of `responses`.
+const NAMED: &[&str] = &[
+    "properties",
+    "definitions",
+    "schemas",
+    "responses",
+    "parameters",
+    "headers",
+    "examples",
+    "requestBodies",
+    "securitySchemes",
+    "links",
+    "callbacks",
+    "content",
+];
+
+/// Whether the `child` member of an object under `key` holds data, an extension or one of the `literals` keywords.
+pub(crate) fn is_literal(key: &str, child: &str, literals: &[&str]) -> bool {
+    // Whatever their name, the members of named maps hold objects of the document.
+    !NAMED.contains(&key) && (child.starts_with("x-") || literals.contains(&child))
+}
+
+/// The names of the parameters of a path template, such as `petId` for `/pets/{petId}`.
+pub(crate) fn template_names(path: &str) -> Vec<&str> {
+    path.split('{')
+        .skip(1)
+        .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+        .collect()
+}
+
+/// Whether a component name matches `^[a-zA-Z0-9.\-_]+$`.
+pub(crate) fn is_component_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+}
+
+/// Whether a key of a responses map is `default` or an HTTP status code, or a range such as `2XX` when `ranges` are allowed.
+pub(crate) fn is_status(status: &str, ranges: bool) -> bool {
+    let bytes = status.as_bytes();
+    status == "default"
+        || bytes.len() == 3
+            && (b'1'..=b'5').contains(&bytes[0])
+            && (bytes[1..].iter().all(u8::is_ascii_digit)
+                || ranges && bytes[1..].eq_ignore_ascii_case(b"XX"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_template_names() {
+        assert_eq!(template_names("/pets/{petId}/photos/{id}"), ["petId", "id"]);
+        assert!(template_names("/pets").is_empty());
+    }
+
+    #[test]
+    fn test_is_status() {
+        assert!(is_status("200", false));
+        assert!(is_status("default", false));
+        assert!(is_status("4XX", true));
+        assert!(!is_status("4XX", false));
+        assert!(!is_status("600", true));
+        assert!(!is_status("20", true));
+    }
+
+    #[test]
+    fn test_paths() {
+        let paths = [
+            "/pets/{id}".to_string(),
+            "/pets/{name}".to_string(),
+            "pets".to_string(),
+        ];
+        let mut report = Report::default();
+        report.paths(&paths);
+        let rules: Vec<_> = report.diagnostics.iter().map(|d| d.rule.as_str()).collect();
+        assert_eq!(rules, ["paths-equivalent", "path-leading-slash"]);
+    }
+
+    #[test]
+    fn test_references() {
+        let document = json!({
+            "definitions": {"Pet": {"properties": {"example": {"$ref": "#/definitions/Tag"}}}},
+            "example": {"$ref": "#/nothing"},
+            "x-ref": {"$ref": "#/nothing"},
+            "items": [{"$ref": "#/definitions/Pet"}],
+            "responses": {"default": {"$ref": "#/responses/Error"}}
+        });
+        let mut report = Report::default();
+        report.references(&document, &["example", "default"]);
+        assert_eq!(
+            report.diagnostics,
+            [
+                Diagnostic {
+                    severity: Severity::Error,
+                    pointer: "/responses/default/$ref".to_string(),
+                    rule: "references-resolve".to_string(),
+                    message: "`#/responses/Error` points to nothing".to_string(),
+                },
+                Diagnostic {
+                    severity: Severity::Error,
+                    pointer: "/definitions/Pet/properties/example/$ref".to_string(),
+                    rule: "references-resolve".to_string(),
+                    message: "`#/definitions/Tag` points to nothing".to_string(),
+                }
+            ]
+        );
+    }
+}
@@ -0,0 +1,381 @@
+// Generated from an OpenAPI document, do not edit by hand.
+
+use openapi_schema::style::Style;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::future::Future;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Cat {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub indoor: Option<bool>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Dog {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub breed: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Error {
+    pub code: i32,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Pet {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<PetKind>,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PetKind {
+    Cat(Cat),
+    Dog(Dog),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct UpdatePetBody {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+}
+
+/// The parameters of `GET /pets`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ListPetsParams {
+    pub limit: Option<i64>,
+    pub tags: Option<Vec<String>>,
+    pub session: Option<String>,
+}
+
+/// The responses of `GET /pets`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ListPetsResponse {
+    /// A page of pets.
+    Ok(Vec<Pet>),
+    /// An error.
+    Default { status: u16, body: Error },
+}
+
+/// The responses of `POST /pets`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CreatePetResponse {
+    /// The created pet.
+    Created(Pet),
+}
+
+/// The parameters of `GET /pets/{petId}`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GetPetParams {
+    pub pet_id: i64,
+}
+
+/// The responses of `GET /pets/{petId}`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum GetPetResponse {
+    /// The pet.
+    Ok(Pet),
+}
+
+/// The parameters of `PUT /pets/{petId}`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UpdatePetParams {
+    pub pet_id: i64,
+}
+
+/// The responses of `PUT /pets/{petId}`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum UpdatePetResponse {
+    /// Renamed.
+    NoContent,
+}
+
+/// A request sent by the [`Client`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct HttpRequest {
+    /// The uppercase method, such as `GET`.
+    pub method: &'static str,
+    /// The URL, with its query string.
+    pub url: String,
+    /// The headers, with lowercase names.
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Vec<u8>>,
+}
+
+/// A response received by the [`Client`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+/// Sends the requests of the [`Client`], such as over HTTP, or to an in-memory server in tests.
+pub trait Transport {
+    type Error;
+
+    fn send(&self, request: HttpRequest) -> impl Future<Output = Result<HttpResponse, Self::Error>>;
+}
+
+/// Why an operation failed.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ClientError<E> {
+    /// The transport could not send the request.
+    Transport(E),
+    /// A parameter or the body could not be encoded.
+    Encode(String),
+    /// The body of a response does not match its type.
+    Decode { status: u16, message: String },
+    /// The status of a response is not one the operation describes.
+    UnexpectedStatus(HttpResponse),
+}
+
+impl<E: fmt::Display> fmt::Display for ClientError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Transport(error) => write!(f, "the request could not be sent: {}", error),
+            ClientError::Encode(message) => write!(f, "the request could not be encoded: {}", message),
+            ClientError::Decode { status, message } => {
+                write!(f, "the {} response could not be decoded: {}", status, message)
+            }
+            ClientError::UnexpectedStatus(response) => {
+                write!(f, "the {} status is not described", response.status)
+            }
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> std::error::Error for ClientError<E> {}
+
+/// A client of the operations of the document.
+#[derive(Clone, Debug)]
+pub struct Client<T> {
+    transport: T,
+    base_url: String,
+}
+
+impl<T: Transport> Client<T> {
+    /// A client sending its requests to the first server of the document, `https://eu.petstore.example.com/v1`.
+    pub fn new(transport: T) -> Self {
+        Client {
+            transport,
+            base_url: "https://eu.petstore.example.com/v1".to_string(),
+        }
+    }
+
+    /// Send the requests to another server, such as a staging one.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    /// List the pets.
+    ///
+    /// `GET /pets`
+    pub async fn list_pets(
+        &self,
+        params: &ListPetsParams,
+    ) -> Result<ListPetsResponse, ClientError<T::Error>> {
+        let mut url = format!("{}/pets", self.base_url);
+        let mut query = Vec::new();
+        if let Some(value) = &params.limit {
+            query.extend(encode_pairs("limit", Style::Form, true, false, value).map_err(ClientError::Encode)?);
+        }
+        if let Some(value) = &params.tags {
+            query.extend(encode_pairs("tags", Style::Form, true, false, value).map_err(ClientError::Encode)?);
+        }
+        if !query.is_empty() {
+            url.push('?');
+            url.push_str(&join_pairs(&query, "&"));
+        }
+        let mut headers = vec![
+            ("accept".to_string(), "application/json".to_string()),
+        ];
+        let mut cookies = Vec::new();
+        if let Some(value) = &params.session {
+            cookies.extend(encode_pairs("session", Style::Form, true, false, value).map_err(ClientError::Encode)?);
+        }
+        if !cookies.is_empty() {
+            headers.push(("cookie".to_string(), join_pairs(&cookies, "; ")));
+        }
+        let response = self
+            .transport
+            .send(HttpRequest {
+                method: "GET",
+                url,
+                headers,
+                body: None,
+            })
+            .await
+            .map_err(ClientError::Transport)?;
+        let status = response.status;
+        let decoded = match status {
+            200 => decode_json(&response.body).map(ListPetsResponse::Ok),
+            _ => decode_json(&response.body).map(|body| ListPetsResponse::Default { status, body }),
+        };
+        decoded.map_err(|message| ClientError::Decode { status, message })
+    }
+
+    /// Add a pet.
+    ///
+    /// `POST /pets`
+    pub async fn create_pet(
+        &self,
+        body: &Pet,
+    ) -> Result<CreatePetResponse, ClientError<T::Error>> {
+        let url = format!("{}/pets", self.base_url);
+        let headers = vec![
+            ("accept".to_string(), "application/json".to_string()),
+            ("content-type".to_string(), "application/json".to_string()),
+        ];
+        let body = Some(encode_json(body).map_err(ClientError::Encode)?);
+        let response = self
+            .transport
+            .send(HttpRequest {
+                method: "POST",
+                url,
+                headers,
+                body,
+            })
+            .await
+            .map_err(ClientError::Transport)?;
+        let status = response.status;
+        let decoded = match status {
+            201 => decode_json(&response.body).map(CreatePetResponse::Created),
+            _ => return Err(ClientError::UnexpectedStatus(response)),
+        };
+        decoded.map_err(|message| ClientError::Decode { status, message })
+    }
+
+    /// Find a pet.
+    ///
+    /// `GET /pets/{petId}`
+    pub async fn get_pet(
+        &self,
+        params: &GetPetParams,
+    ) -> Result<GetPetResponse, ClientError<T::Error>> {
+        let url = format!("{}/pets/{}", self.base_url, encode_value("petId", Style::Simple, false, &params.pet_id).map_err(ClientError::Encode)?);
+        let headers = vec![
+            ("accept".to_string(), "application/json".to_string()),
+        ];
+        let response = self
+            .transport
+            .send(HttpRequest {
+                method: "GET",
+                url,
+                headers,
+                body: None,
+            })
+            .await
+            .map_err(ClientError::Transport)?;
+        let status = response.status;
+        let decoded = match status {
+            200 => decode_json(&response.body).map(GetPetResponse::Ok),
+            _ => return Err(ClientError::UnexpectedStatus(response)),
+        };
+        decoded.map_err(|message| ClientError::Decode { status, message })
+    }
+
+    /// Rename a pet.
+    ///
+    /// `PUT /pets/{petId}`
+    pub async fn update_pet(
+        &self,
+        params: &UpdatePetParams,
+        body: &UpdatePetBody,
+    ) -> Result<UpdatePetResponse, ClientError<T::Error>> {
+        let url = format!("{}/pets/{}", self.base_url, encode_value("petId", Style::Simple, false, &params.pet_id).map_err(ClientError::Encode)?);
+        let headers = vec![
+            ("content-type".to_string(), "application/x-www-form-urlencoded".to_string()),
+        ];
+        let body = Some(encode_form(body).map_err(ClientError::Encode)?);
+        let response = self
+            .transport
+            .send(HttpRequest {
+                method: "PUT",
+                url,
+                headers,
+                body,
+            })
+            .await
+            .map_err(ClientError::Transport)?;
+        let status = response.status;
+        let decoded = match status {
+            204 => Ok(UpdatePetResponse::NoContent),
+            _ => return Err(ClientError::UnexpectedStatus(response)),
+        };
+        decoded.map_err(|message| ClientError::Decode { status, message })
+    }
+}
+
+fn encode_value<V: serde::Serialize + ?Sized>(
+    name: &str,
+    style: Style,
+    explode: bool,
+    value: &V,
+) -> Result<String, String> {
+    let value = serde_json::to_value(value).map_err(|error| error.to_string())?;
+    style.encode(name, explode, &value).map_err(|error| error.to_string())
+}
+
+fn encode_pairs<V: serde::Serialize + ?Sized>(
+    name: &str,
+    style: Style,
+    explode: bool,
+    allow_reserved: bool,
+    value: &V,
+) -> Result<Vec<(String, String)>, String> {
+    let value = serde_json::to_value(value).map_err(|error| error.to_string())?;
+    style
+        .encode_pairs(name, explode, allow_reserved, &value)
+        .map_err(|error| error.to_string())
+}
+
+fn join_pairs(pairs: &[(String, String)], separator: &str) -> String {
+    pairs
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+fn encode_json<V: serde::Serialize + ?Sized>(value: &V) -> Result<Vec<u8>, String> {
+    serde_json::to_vec(value).map_err(|error| error.to_string())
+}
+
+fn encode_form<V: serde::Serialize + ?Sized>(value: &V) -> Result<Vec<u8>, String> {
+    let serde_json::Value::Object(properties) =
+        serde_json::to_value(value).map_err(|error| error.to_string())?
+    else {
+        return Err("a form must be an object".to_string());
+    };
+    let mut pairs = Vec::new();
+    for (name, value) in properties.iter().filter(|(_, value)| !value.is_null()) {
+        pairs.extend(
+            Style::Form
+                .encode_pairs(name, true, false, value)
+                .map_err(|error| error.to_string())?,
+        );
+    }
+    Ok(join_pairs(&pairs, "&").into_bytes())
+}
+
+fn decode_json<V: serde::de::DeserializeOwned>(body: &[u8]) -> Result<V, String> {
+    serde_json::from_slice(body).map_err(|error| error.to_string())
+}
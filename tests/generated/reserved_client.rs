@@ -0,0 +1,311 @@
+// Generated from an OpenAPI document, do not edit by hand.
+
+use openapi_schema::style::Style;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::future::Future;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Api {
+    #[serde(rename = "v1")]
+    V1,
+}
+
+pub type BTreeMap2 = BTreeMap<String, i64>;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Box2 {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub counts: Option<BTreeMap2>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub items: Option<Vec2>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub labels: Option<BTreeMap<String, String>>,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<Box<Box2>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub option: Option<Option2>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Client2 {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transport: Option<Transport2>,
+}
+
+pub type ClientError2 = String;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Deserialize2 {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub serialize: Option<Serialize2>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Extracted {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api: Option<Api>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Future2 {
+    #[serde(rename = "later")]
+    Later,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HttpRequest2 {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style: Option<Style2>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HttpResponse2 {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub future: Option<Future2>,
+}
+
+pub type OnceLock = bool;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OpenApi {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub once: Option<OnceLock>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Option2 {
+    #[serde(rename = "some")]
+    Some,
+    #[serde(rename = "none")]
+    None,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Request {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validator: Option<Validator>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Result2 {
+    Box2(Box2),
+    String2(String2),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Serialize2 {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+}
+
+pub type Server = Vec<Request>;
+
+pub type String2 = String;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Style2 {
+    #[serde(rename = "form")]
+    Form,
+    #[serde(rename = "simple")]
+    Simple,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Transport2 {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Validator {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+pub type Vec2 = Vec<String2>;
+
+/// The responses of `POST /boxes`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CreateBoxResponse {
+    /// The result.
+    Created(Result2),
+}
+
+/// The parameters of `GET /boxes/{id}`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GetBoxParams {
+    pub id: String2,
+}
+
+/// The responses of `GET /boxes/{id}`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum GetBoxResponse {
+    /// The box.
+    Ok(Box2),
+}
+
+/// A request sent by the [`Client`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct HttpRequest {
+    /// The uppercase method, such as `GET`.
+    pub method: &'static str,
+    /// The URL, with its query string.
+    pub url: String,
+    /// The headers, with lowercase names.
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Vec<u8>>,
+}
+
+/// A response received by the [`Client`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+/// Sends the requests of the [`Client`], such as over HTTP, or to an in-memory server in tests.
+pub trait Transport {
+    type Error;
+
+    fn send(&self, request: HttpRequest) -> impl Future<Output = Result<HttpResponse, Self::Error>>;
+}
+
+/// Why an operation failed.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ClientError<E> {
+    /// The transport could not send the request.
+    Transport(E),
+    /// A parameter or the body could not be encoded.
+    Encode(String),
+    /// The body of a response does not match its type.
+    Decode { status: u16, message: String },
+    /// The status of a response is not one the operation describes.
+    UnexpectedStatus(HttpResponse),
+}
+
+impl<E: fmt::Display> fmt::Display for ClientError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Transport(error) => write!(f, "the request could not be sent: {}", error),
+            ClientError::Encode(message) => write!(f, "the request could not be encoded: {}", message),
+            ClientError::Decode { status, message } => {
+                write!(f, "the {} response could not be decoded: {}", status, message)
+            }
+            ClientError::UnexpectedStatus(response) => {
+                write!(f, "the {} status is not described", response.status)
+            }
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> std::error::Error for ClientError<E> {}
+
+/// A client of the operations of the document.
+#[derive(Clone, Debug)]
+pub struct Client<T> {
+    transport: T,
+    base_url: String,
+}
+
+impl<T: Transport> Client<T> {
+    /// A client sending its requests to the first server of the document, ``.
+    pub fn new(transport: T) -> Self {
+        Client {
+            transport,
+            base_url: "".to_string(),
+        }
+    }
+
+    /// Send the requests to another server, such as a staging one.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    /// `POST /boxes`
+    pub async fn create_box(
+        &self,
+        body: &Box2,
+    ) -> Result<CreateBoxResponse, ClientError<T::Error>> {
+        let url = format!("{}/boxes", self.base_url);
+        let headers = vec![
+            ("accept".to_string(), "application/json".to_string()),
+            ("content-type".to_string(), "application/json".to_string()),
+        ];
+        let body = Some(encode_json(body).map_err(ClientError::Encode)?);
+        let response = self
+            .transport
+            .send(HttpRequest {
+                method: "POST",
+                url,
+                headers,
+                body,
+            })
+            .await
+            .map_err(ClientError::Transport)?;
+        let status = response.status;
+        let decoded = match status {
+            201 => decode_json(&response.body).map(CreateBoxResponse::Created),
+            _ => return Err(ClientError::UnexpectedStatus(response)),
+        };
+        decoded.map_err(|message| ClientError::Decode { status, message })
+    }
+
+    /// `GET /boxes/{id}`
+    pub async fn get_box(
+        &self,
+        params: &GetBoxParams,
+    ) -> Result<GetBoxResponse, ClientError<T::Error>> {
+        let url = format!("{}/boxes/{}", self.base_url, encode_value("id", Style::Simple, false, &params.id).map_err(ClientError::Encode)?);
+        let headers = vec![
+            ("accept".to_string(), "application/json".to_string()),
+        ];
+        let response = self
+            .transport
+            .send(HttpRequest {
+                method: "GET",
+                url,
+                headers,
+                body: None,
+            })
+            .await
+            .map_err(ClientError::Transport)?;
+        let status = response.status;
+        let decoded = match status {
+            200 => decode_json(&response.body).map(GetBoxResponse::Ok),
+            _ => return Err(ClientError::UnexpectedStatus(response)),
+        };
+        decoded.map_err(|message| ClientError::Decode { status, message })
+    }
+}
+
+fn encode_value<V: serde::Serialize + ?Sized>(
+    name: &str,
+    style: Style,
+    explode: bool,
+    value: &V,
+) -> Result<String, String> {
+    let value = serde_json::to_value(value).map_err(|error| error.to_string())?;
+    style.encode(name, explode, &value).map_err(|error| error.to_string())
+}
+
+fn encode_json<V: serde::Serialize + ?Sized>(value: &V) -> Result<Vec<u8>, String> {
+    serde_json::to_vec(value).map_err(|error| error.to_string())
+}
+
+fn decode_json<V: serde::de::DeserializeOwned>(body: &[u8]) -> Result<V, String> {
+    serde_json::from_slice(body).map_err(|error| error.to_string())
+}
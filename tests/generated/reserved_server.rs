@@ -0,0 +1,345 @@
+// Generated from an OpenAPI document, do not edit by hand.
+
+use openapi_schema::http::{Extracted, Request, Validator};
+use openapi_schema::v3::OpenApi;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::OnceLock;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Api2 {
+    #[serde(rename = "v1")]
+    V1,
+}
+
+pub type BTreeMap2 = BTreeMap<String, i64>;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Box2 {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub counts: Option<BTreeMap2>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub items: Option<Vec2>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub labels: Option<BTreeMap<String, String>>,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<Box<Box2>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub option: Option<Option2>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Client {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transport: Option<Transport>,
+}
+
+pub type ClientError = String;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Deserialize2 {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub serialize: Option<Serialize2>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Extracted2 {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api: Option<Api2>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Future2 {
+    #[serde(rename = "later")]
+    Later,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HttpRequest2 {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style: Option<Style>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HttpResponse2 {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub future: Option<Future2>,
+}
+
+pub type OnceLock2 = bool;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OpenApi2 {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub once: Option<OnceLock2>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Option2 {
+    #[serde(rename = "some")]
+    Some,
+    #[serde(rename = "none")]
+    None,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Request2 {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validator: Option<Validator2>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Result2 {
+    Box2(Box2),
+    String2(String2),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Serialize2 {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+}
+
+pub type Server2 = Vec<Request2>;
+
+pub type String2 = String;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Style {
+    #[serde(rename = "form")]
+    Form,
+    #[serde(rename = "simple")]
+    Simple,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Transport {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Validator2 {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+pub type Vec2 = Vec<String2>;
+
+/// The responses of `POST /boxes`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CreateBoxResponse {
+    /// The result.
+    Created(Result2),
+}
+
+/// The parameters of `GET /boxes/{id}`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GetBoxParams {
+    pub id: String2,
+}
+
+/// The responses of `GET /boxes/{id}`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum GetBoxResponse {
+    /// The box.
+    Ok(Box2),
+}
+
+impl CreateBoxResponse {
+    /// The HTTP response of the variant.
+    pub fn into_response(self) -> HttpResponse {
+        match self {
+            CreateBoxResponse::Created(body) => json_response(201, "application/json", &body),
+        }
+    }
+}
+
+impl GetBoxResponse {
+    /// The HTTP response of the variant.
+    pub fn into_response(self) -> HttpResponse {
+        match self {
+            GetBoxResponse::Ok(body) => json_response(200, "application/json", &body),
+        }
+    }
+}
+
+/// A request received by the [`Server`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HttpRequest {
+    /// The method, such as `GET`.
+    pub method: String,
+    /// The path, with its query string, such as `/v1/pets?limit=10`.
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+/// A response sent by the [`Server`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct HttpResponse {
+    pub status: u16,
+    /// The headers, with lowercase names.
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+/// The operations of the document, which the [`Server`] calls once their requests are checked.
+pub trait Api {
+    /// `POST /boxes`
+    fn create_box(
+        &self,
+        body: Box2,
+    ) -> impl Future<Output = CreateBoxResponse> + Send;
+
+    /// `GET /boxes/{id}`
+    fn get_box(
+        &self,
+        params: GetBoxParams,
+    ) -> impl Future<Output = GetBoxResponse> + Send;
+}
+
+/// The document the requests are checked against.
+const DOCUMENT: &str = r##"{"openapi":"3.0.3","info":{"title":"Reserved names","version":"1.0.0"},"paths":{"/boxes":{"post":{"operationId":"createBox","requestBody":{"content":{"application/json":{"schema":{"$ref":"#/components/schemas/Box"}}},"required":true},"responses":{"201":{"description":"The result.","content":{"application/json":{"schema":{"$ref":"#/components/schemas/Result"}}}}}}},"/boxes/{id}":{"get":{"operationId":"getBox","parameters":[{"name":"id","in":"path","required":true,"schema":{"$ref":"#/components/schemas/String"}}],"responses":{"200":{"description":"The box.","content":{"application/json":{"schema":{"$ref":"#/components/schemas/Box"}}}}}}}},"components":{"schemas":{"Api":{"enum":["v1"],"type":"string"},"BTreeMap":{"type":"object","additionalProperties":{"type":"integer"}},"Box":{"properties":{"counts":{"$ref":"#/components/schemas/BTreeMap"},"items":{"$ref":"#/components/schemas/Vec"},"labels":{"type":"object","additionalProperties":{"type":"string"}},"name":{"type":"string"},"next":{"$ref":"#/components/schemas/Box"},"option":{"$ref":"#/components/schemas/Option"}},"required":["name"],"type":"object"},"Client":{"properties":{"transport":{"$ref":"#/components/schemas/Transport"}},"type":"object"},"ClientError":{"type":"string"},"Deserialize":{"properties":{"serialize":{"$ref":"#/components/schemas/Serialize"}},"type":"object"},"Extracted":{"properties":{"api":{"$ref":"#/components/schemas/Api"}},"type":"object"},"Future":{"enum":["later"],"type":"string"},"HttpRequest":{"properties":{"style":{"$ref":"#/components/schemas/Style"}},"type":"object"},"HttpResponse":{"properties":{"future":{"$ref":"#/components/schemas/Future"}},"type":"object"},"OnceLock":{"type":"boolean"},"OpenApi":{"properties":{"once":{"$ref":"#/components/schemas/OnceLock"}},"type":"object"},"Option":{"enum":["some","none"],"type":"string"},"Request":{"properties":{"validator":{"$ref":"#/components/schemas/Validator"}},"type":"object"},"Result":{"oneOf":[{"$ref":"#/components/schemas/Box"},{"$ref":"#/components/schemas/String"}]},"Serialize":{"properties":{"tags":{"items":{"type":"string"},"type":"array"}},"type":"object"},"Server":{"items":{"$ref":"#/components/schemas/Request"},"type":"array"},"String":{"type":"string"},"Style":{"enum":["form","simple"],"type":"string"},"Transport":{"properties":{"name":{"type":"string"}},"type":"object"},"Validator":{"properties":{"name":{"type":"string"}},"type":"object"},"Vec":{"items":{"$ref":"#/components/schemas/String"},"type":"array"}}}}"##;
+
+/// Serves the operations of an [`Api`]: routes each request to its operation, checks it against the document, decodes
+/// its parameters and body, then encodes the response of the operation.
+#[derive(Clone, Debug)]
+pub struct Server<A> {
+    api: A,
+    validator: Validator<'static>,
+}
+
+/// The document, parsed once for every server.
+fn document() -> &'static OpenApi {
+    static OPENAPI: OnceLock<OpenApi> = OnceLock::new();
+    OPENAPI.get_or_init(|| serde_json::from_str(DOCUMENT).expect("the document is valid"))
+}
+
+impl<A: Api> Server<A> {
+    pub fn new(api: A) -> Self {
+        Server {
+            api,
+            validator: Validator::new(document()),
+        }
+    }
+
+    pub fn api(&self) -> &A {
+        &self.api
+    }
+
+    /// The document the requests are checked against.
+    pub fn openapi(&self) -> &OpenApi {
+        self.validator.openapi()
+    }
+
+    /// Answer a request. A request which does not follow the document is answered with its errors, as a `404` when no
+    /// path matches, a `405` when the path has no operation for the method, a `415` for an unsupported media type and
+    /// a `400` otherwise.
+    pub async fn handle(&self, request: &HttpRequest) -> HttpResponse {
+        let mut checked = Request::new(&request.method, &request.path).with_body(&request.body);
+        for (name, value) in &request.headers {
+            checked = checked.with_header(name, value);
+        }
+        let extracted = match self.validator.extract_request(&checked) {
+            Ok(extracted) => extracted,
+            Err(errors) => {
+                let message = errors
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                return text_response(errors[0].status(), message);
+            }
+        };
+        self.dispatch(request, &extracted)
+            .await
+            .unwrap_or_else(|response| response)
+    }
+
+    async fn dispatch(
+        &self,
+        _request: &HttpRequest,
+        extracted: &Extracted<'_>,
+    ) -> Result<HttpResponse, HttpResponse> {
+        match (extracted.method, extracted.template) {
+            ("post", "/boxes") => {
+                let body = required_body(extracted)?;
+                let response = self.api.create_box(body).await;
+                Ok(response.into_response())
+            }
+            ("get", "/boxes/{id}") => {
+                let params = GetBoxParams {
+                    id: required_parameter(extracted, "path", "id")?,
+                };
+                let response = self.api.get_box(params).await;
+                Ok(response.into_response())
+            }
+            (method, template) => {
+                let message = format!("`{}` has no `{}` operation", template, method);
+                Err(text_response(405, message))
+            }
+        }
+    }
+}
+
+fn parameter<V: serde::de::DeserializeOwned>(
+    extracted: &Extracted<'_>,
+    location: &str,
+    name: &str,
+) -> Result<Option<V>, HttpResponse> {
+    extracted
+        .parameter(location, name)
+        .map(|value| serde_json::from_value(value.clone()))
+        .transpose()
+        .map_err(|error| {
+            let message = format!("the {} parameter `{}` is invalid: {}", location, name, error);
+            text_response(400, message)
+        })
+}
+
+fn required_parameter<V: serde::de::DeserializeOwned>(
+    extracted: &Extracted<'_>,
+    location: &str,
+    name: &str,
+) -> Result<V, HttpResponse> {
+    parameter(extracted, location, name)?.ok_or_else(|| {
+        let message = format!("the {} parameter `{}` is required", location, name);
+        text_response(400, message)
+    })
+}
+
+fn body<V: serde::de::DeserializeOwned>(extracted: &Extracted<'_>) -> Result<Option<V>, HttpResponse> {
+    extracted
+        .body
+        .clone()
+        .map(serde_json::from_value)
+        .transpose()
+        .map_err(|error| text_response(400, format!("the request body is invalid: {}", error)))
+}
+
+fn required_body<V: serde::de::DeserializeOwned>(extracted: &Extracted<'_>) -> Result<V, HttpResponse> {
+    body(extracted)?.ok_or_else(|| text_response(400, "the request body is required".to_string()))
+}
+
+fn json_response<V: serde::Serialize>(status: u16, media_type: &str, body: &V) -> HttpResponse {
+    match serde_json::to_vec(body) {
+        Ok(body) => bytes_response(status, media_type, body),
+        Err(error) => text_response(500, error.to_string()),
+    }
+}
+
+fn bytes_response(status: u16, media_type: &str, body: Vec<u8>) -> HttpResponse {
+    HttpResponse {
+        status,
+        headers: vec![("content-type".to_string(), media_type.to_string())],
+        body,
+    }
+}
+
+fn text_response(status: u16, message: String) -> HttpResponse {
+    bytes_response(status, "text/plain", message.into_bytes())
+}
@@ -0,0 +1,360 @@
+// Generated from an OpenAPI document, do not edit by hand.
+
+use openapi_schema::http::{Extracted, Request, Validator};
+use openapi_schema::v3::OpenApi;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::OnceLock;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Cat {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub indoor: Option<bool>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Dog {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub breed: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Error {
+    pub code: i32,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Pet {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<PetKind>,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PetKind {
+    Cat(Cat),
+    Dog(Dog),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct UpdatePetBody {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+}
+
+/// The parameters of `GET /pets`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ListPetsParams {
+    pub limit: Option<i64>,
+    pub tags: Option<Vec<String>>,
+    pub session: Option<String>,
+}
+
+/// The responses of `GET /pets`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ListPetsResponse {
+    /// A page of pets.
+    Ok(Vec<Pet>),
+    /// An error.
+    Default { status: u16, body: Error },
+}
+
+/// The responses of `POST /pets`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CreatePetResponse {
+    /// The created pet.
+    Created(Pet),
+}
+
+/// The parameters of `GET /pets/{petId}`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GetPetParams {
+    pub pet_id: i64,
+}
+
+/// The responses of `GET /pets/{petId}`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum GetPetResponse {
+    /// The pet.
+    Ok(Pet),
+}
+
+/// The parameters of `PUT /pets/{petId}`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UpdatePetParams {
+    pub pet_id: i64,
+}
+
+/// The responses of `PUT /pets/{petId}`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum UpdatePetResponse {
+    /// Renamed.
+    NoContent,
+}
+
+impl ListPetsResponse {
+    /// The HTTP response of the variant.
+    pub fn into_response(self) -> HttpResponse {
+        match self {
+            ListPetsResponse::Ok(body) => json_response(200, "application/json", &body),
+            ListPetsResponse::Default { status, body } => json_response(status, "application/json", &body),
+        }
+    }
+}
+
+impl CreatePetResponse {
+    /// The HTTP response of the variant.
+    pub fn into_response(self) -> HttpResponse {
+        match self {
+            CreatePetResponse::Created(body) => json_response(201, "application/json", &body),
+        }
+    }
+}
+
+impl GetPetResponse {
+    /// The HTTP response of the variant.
+    pub fn into_response(self) -> HttpResponse {
+        match self {
+            GetPetResponse::Ok(body) => json_response(200, "application/json", &body),
+        }
+    }
+}
+
+impl UpdatePetResponse {
+    /// The HTTP response of the variant.
+    pub fn into_response(self) -> HttpResponse {
+        match self {
+            UpdatePetResponse::NoContent => empty_response(204),
+        }
+    }
+}
+
+/// A request received by the [`Server`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HttpRequest {
+    /// The method, such as `GET`.
+    pub method: String,
+    /// The path, with its query string, such as `/v1/pets?limit=10`.
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+/// A response sent by the [`Server`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct HttpResponse {
+    pub status: u16,
+    /// The headers, with lowercase names.
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+/// The operations of the document, which the [`Server`] calls once their requests are checked.
+pub trait Api {
+    /// List the pets.
+    ///
+    /// `GET /pets`
+    fn list_pets(
+        &self,
+        params: ListPetsParams,
+    ) -> impl Future<Output = ListPetsResponse> + Send;
+
+    /// Add a pet.
+    ///
+    /// `POST /pets`
+    fn create_pet(
+        &self,
+        body: Pet,
+    ) -> impl Future<Output = CreatePetResponse> + Send;
+
+    /// Find a pet.
+    ///
+    /// `GET /pets/{petId}`
+    fn get_pet(
+        &self,
+        params: GetPetParams,
+    ) -> impl Future<Output = GetPetResponse> + Send;
+
+    /// Rename a pet.
+    ///
+    /// `PUT /pets/{petId}`
+    fn update_pet(
+        &self,
+        params: UpdatePetParams,
+        body: UpdatePetBody,
+    ) -> impl Future<Output = UpdatePetResponse> + Send;
+}
+
+/// The document the requests are checked against.
+const DOCUMENT: &str = r##"{"openapi":"3.0.3","info":{"title":"Petstore","version":"1.0.0"},"servers":[{"url":"https://{region}.petstore.example.com/v1","variables":{"region":{"enum":["eu","us"],"default":"eu"}}},{"url":"http://eu.petstore.example.com/v1"}],"paths":{"/pets":{"get":{"tags":["pets"],"summary":"List the pets.","operationId":"listPets","parameters":[{"$ref":"#/components/parameters/limit"},{"name":"tags","in":"query","schema":{"items":{"type":"string"},"type":"array"}},{"name":"session","in":"cookie","schema":{"type":"string"}}],"responses":{"200":{"description":"A page of pets.","headers":{"x-next":{"description":"A link to the next page.","schema":{"type":"string"}},"x-rate-limit":{"description":"The number of requests left.","required":true,"schema":{"minimum":0,"type":"integer"}}},"content":{"application/json":{"schema":{"items":{"$ref":"#/components/schemas/Pet"},"type":"array"},"example":[{"id":1,"name":"Rex"}]}}},"default":{"$ref":"#/components/responses/Error"}}},"post":{"tags":["pets"],"summary":"Add a pet.","operationId":"createPet","requestBody":{"$ref":"#/components/requestBodies/Pet"},"responses":{"201":{"description":"The created pet.","content":{"application/json":{"schema":{"$ref":"#/components/schemas/Pet"}}},"links":{"getPet":{"operationId":"getPet","parameters":{"petId":"$response.body#/id"}}}}},"callbacks":{"created":{"{$request.body#/callbackUrl}":{"post":{"responses":{"200":{"description":"Received."}}}}}}}},"/pets/{petId}":{"get":{"tags":["pets"],"summary":"Find a pet.","operationId":"getPet","responses":{"200":{"description":"The pet.","content":{"application/json":{"schema":{"$ref":"#/components/schemas/Pet"}}}}}},"put":{"tags":["pets"],"summary":"Rename a pet.","operationId":"updatePet","requestBody":{"content":{"application/x-www-form-urlencoded":{"schema":{"properties":{"name":{"type":"string"},"status":{"type":"string"}},"required":["name"],"type":"object"}}},"required":true},"responses":{"204":{"description":"Renamed."}}},"parameters":[{"name":"petId","in":"path","required":true,"schema":{"type":"integer","format":"int64"}}]}},"components":{"schemas":{"Cat":{"properties":{"indoor":{"type":"boolean"}},"type":"object"},"Dog":{"properties":{"breed":{"type":"string"}},"type":"object"},"Error":{"properties":{"code":{"type":"integer","format":"int32"},"message":{"type":"string"}},"required":["code","message"],"type":"object"},"Pet":{"properties":{"id":{"minimum":0,"exclusiveMinimum":true,"type":"integer","format":"int64","readOnly":true},"kind":{"oneOf":[{"$ref":"#/components/schemas/Cat"},{"$ref":"#/components/schemas/Dog"}]},"name":{"minLength":1,"type":"string","example":"Rex"},"tag":{"type":"string","nullable":true}},"required":["id","name"],"type":"object"}},"responses":{"Error":{"description":"An error.","content":{"application/json":{"schema":{"$ref":"#/components/schemas/Error"}}}}},"parameters":{"limit":{"name":"limit","in":"query","schema":{"maximum":100,"type":"integer","default":20}}},"requestBodies":{"Pet":{"content":{"application/json":{"schema":{"$ref":"#/components/schemas/Pet"}}},"required":true}},"securitySchemes":{"basic":{"type":"http","scheme":"basic"},"bearer":{"type":"http","scheme":"bearer"},"oauth":{"type":"oauth2","flows":{"authorizationCode":{"authorizationUrl":"https://petstore.example.com/oauth/authorize","tokenUrl":"https://petstore.example.com/oauth/token","scopes":{"write:pets":"Modify pets."}}}}}},"security":[{"oauth":["write:pets"]}]}"##;
+
+/// Serves the operations of an [`Api`]: routes each request to its operation, checks it against the document, decodes
+/// its parameters and body, then encodes the response of the operation.
+#[derive(Clone, Debug)]
+pub struct Server<A> {
+    api: A,
+    validator: Validator<'static>,
+}
+
+/// The document, parsed once for every server.
+fn document() -> &'static OpenApi {
+    static OPENAPI: OnceLock<OpenApi> = OnceLock::new();
+    OPENAPI.get_or_init(|| serde_json::from_str(DOCUMENT).expect("the document is valid"))
+}
+
+impl<A: Api> Server<A> {
+    pub fn new(api: A) -> Self {
+        Server {
+            api,
+            validator: Validator::new(document()),
+        }
+    }
+
+    pub fn api(&self) -> &A {
+        &self.api
+    }
+
+    /// The document the requests are checked against.
+    pub fn openapi(&self) -> &OpenApi {
+        self.validator.openapi()
+    }
+
+    /// Answer a request. A request which does not follow the document is answered with its errors, as a `404` when no
+    /// path matches, a `405` when the path has no operation for the method, a `415` for an unsupported media type and
+    /// a `400` otherwise.
+    pub async fn handle(&self, request: &HttpRequest) -> HttpResponse {
+        let mut checked = Request::new(&request.method, &request.path).with_body(&request.body);
+        for (name, value) in &request.headers {
+            checked = checked.with_header(name, value);
+        }
+        let extracted = match self.validator.extract_request(&checked) {
+            Ok(extracted) => extracted,
+            Err(errors) => {
+                let message = errors
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                return text_response(errors[0].status(), message);
+            }
+        };
+        self.dispatch(request, &extracted)
+            .await
+            .unwrap_or_else(|response| response)
+    }
+
+    async fn dispatch(
+        &self,
+        _request: &HttpRequest,
+        extracted: &Extracted<'_>,
+    ) -> Result<HttpResponse, HttpResponse> {
+        match (extracted.method, extracted.template) {
+            ("get", "/pets") => {
+                let params = ListPetsParams {
+                    limit: parameter(extracted, "query", "limit")?,
+                    tags: parameter(extracted, "query", "tags")?,
+                    session: parameter(extracted, "cookie", "session")?,
+                };
+                let response = self.api.list_pets(params).await;
+                Ok(response.into_response())
+            }
+            ("post", "/pets") => {
+                let body = required_body(extracted)?;
+                let response = self.api.create_pet(body).await;
+                Ok(response.into_response())
+            }
+            ("get", "/pets/{petId}") => {
+                let params = GetPetParams {
+                    pet_id: required_parameter(extracted, "path", "petId")?,
+                };
+                let response = self.api.get_pet(params).await;
+                Ok(response.into_response())
+            }
+            ("put", "/pets/{petId}") => {
+                let params = UpdatePetParams {
+                    pet_id: required_parameter(extracted, "path", "petId")?,
+                };
+                let body = required_body(extracted)?;
+                let response = self.api.update_pet(params, body).await;
+                Ok(response.into_response())
+            }
+            (method, template) => {
+                let message = format!("`{}` has no `{}` operation", template, method);
+                Err(text_response(405, message))
+            }
+        }
+    }
+}
+
+fn parameter<V: serde::de::DeserializeOwned>(
+    extracted: &Extracted<'_>,
+    location: &str,
+    name: &str,
+) -> Result<Option<V>, HttpResponse> {
+    extracted
+        .parameter(location, name)
+        .map(|value| serde_json::from_value(value.clone()))
+        .transpose()
+        .map_err(|error| {
+            let message = format!("the {} parameter `{}` is invalid: {}", location, name, error);
+            text_response(400, message)
+        })
+}
+
+fn required_parameter<V: serde::de::DeserializeOwned>(
+    extracted: &Extracted<'_>,
+    location: &str,
+    name: &str,
+) -> Result<V, HttpResponse> {
+    parameter(extracted, location, name)?.ok_or_else(|| {
+        let message = format!("the {} parameter `{}` is required", location, name);
+        text_response(400, message)
+    })
+}
+
+fn body<V: serde::de::DeserializeOwned>(extracted: &Extracted<'_>) -> Result<Option<V>, HttpResponse> {
+    extracted
+        .body
+        .clone()
+        .map(serde_json::from_value)
+        .transpose()
+        .map_err(|error| text_response(400, format!("the request body is invalid: {}", error)))
+}
+
+fn required_body<V: serde::de::DeserializeOwned>(extracted: &Extracted<'_>) -> Result<V, HttpResponse> {
+    body(extracted)?.ok_or_else(|| text_response(400, "the request body is required".to_string()))
+}
+
+fn json_response<V: serde::Serialize>(status: u16, media_type: &str, body: &V) -> HttpResponse {
+    match serde_json::to_vec(body) {
+        Ok(body) => bytes_response(status, media_type, body),
+        Err(error) => text_response(500, error.to_string()),
+    }
+}
+
+fn empty_response(status: u16) -> HttpResponse {
+    HttpResponse {
+        status,
+        headers: Vec::new(),
+        body: Vec::new(),
+    }
+}
+
+fn bytes_response(status: u16, media_type: &str, body: Vec<u8>) -> HttpResponse {
+    HttpResponse {
+        status,
+        headers: vec![("content-type".to_string(), media_type.to_string())],
+        body,
+    }
+}
+
+fn text_response(status: u16, message: String) -> HttpResponse {
+    bytes_response(status, "text/plain", message.into_bytes())
+}
@@ -0,0 +1,93 @@
+#![cfg(feature = "yaml")]
+
+use openapi_schema::loader::Loader;
+use openapi_schema::{v3, Doc, Error};
+use serde_json::Value;
+
+const CATEGORY: &str =
+    r#"{"Category": {"type": "object", "properties": {"name": {"type": "string"}}}}"#;
+
+fn bundled() -> v3::OpenApi {
+    let loader = Loader::new().with_fetcher(|url: &str| match url {
+        "https://example.com/schemas/category.json" => Ok(CATEGORY.to_string()),
+        _ => Err(Error::UnresolvedReference {
+            reference: url.to_string(),
+            message: "is not served".to_string(),
+        }),
+    });
+    let set = loader.load("./tests/multi/openapi.yaml").unwrap();
+    match set.doc().unwrap() {
+        Doc::V3(mut openapi) => {
+            openapi.bundle(&set).unwrap();
+            openapi
+        }
+        doc => panic!("expected an openapi 3.0 document, got {}", doc.version()),
+    }
+}
+
+fn references<'a>(value: &'a Value, references: &mut Vec<&'a str>) {
+    match value {
+        Value::Object(map) => {
+            if let Some(Value::String(reference)) = map.get("$ref") {
+                references.push(reference);
+            }
+            map.values()
+                .for_each(|value| self::references(value, references));
+        }
+        Value::Array(items) => items
+            .iter()
+            .for_each(|value| self::references(value, references)),
+        _ => {}
+    }
+}
+
+#[test]
+fn test_bundle() {
+    let openapi = bundled();
+    let components = openapi.components.as_ref().unwrap();
+    let schemas = components.schemas.as_ref().unwrap();
+    assert_eq!(
+        schemas.keys().collect::<Vec<_>>(),
+        vec!["Category", "Error", "Error2", "Id", "Pet", "User"]
+    );
+    assert!(components
+        .parameters
+        .as_ref()
+        .unwrap()
+        .contains_key("limit"));
+    assert!(components.responses.as_ref().unwrap().contains_key("Error"));
+
+    let operation = openapi.paths["/pets"].get.as_ref().unwrap();
+    match &operation.responses["default"] {
+        v3::RefOrObject::Ref(reference) => {
+            assert_eq!(reference.reference, "#/components/responses/Error")
+        }
+        response => panic!("unexpected response {:?}", response),
+    }
+    let error: &v3::Response = openapi
+        .resolve_reference("#/components/responses/Error")
+        .unwrap();
+    let schema = error.content.as_ref().unwrap()["application/json"]
+        .schema
+        .as_ref()
+        .unwrap();
+    assert_eq!(
+        schema,
+        &v3::RefOrObject::Ref(v3::Reference {
+            reference: "#/components/schemas/Error2".to_string()
+        })
+    );
+}
+
+#[test]
+fn test_bundle_is_self_contained() {
+    let value = serde_json::to_value(bundled()).unwrap();
+    let mut found = Vec::new();
+    references(&value, &mut found);
+    assert!(found.contains(&"#/components/schemas/Pet"));
+    for reference in found {
+        let pointer = reference.strip_prefix('#').unwrap();
+        assert!(pointer.starts_with("/components/"), "{}", reference);
+        assert!(value.pointer(pointer).is_some(), "{}", reference);
+    }
+}
@@ -0,0 +1,579 @@
+use openapi_schema::codegen::Generator;
+use openapi_schema::{v2, v3};
+use std::fs;
+
+fn openapi() -> v3::OpenApi {
+    serde_json::from_str(&fs::read_to_string("./tests/petstore-v3.json").unwrap()).unwrap()
+}
+
+#[test]
+fn test_types() {
+    let source = Generator::new(openapi()).types().unwrap();
+    assert!(source.starts_with("// Generated from an OpenAPI document"));
+    assert!(source.contains("use serde::{Deserialize, Serialize};\n"));
+    assert!(!source.contains("BTreeMap"));
+    assert!(source.contains(
+        r#"pub struct Pet {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<PetKind>,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+}"#
+    ));
+    assert!(
+        source.contains("#[serde(untagged)]\npub enum PetKind {\n    Cat(Cat),\n    Dog(Dog),\n}")
+    );
+    assert!(source.contains("pub struct Error {\n    pub code: i32,\n    pub message: String,\n}"));
+
+    // The source does not depend on anything but the document.
+    assert_eq!(Generator::new(openapi()).types().unwrap(), source);
+}
+
+#[test]
+fn test_swagger_types() {
+    let swagger: v2::Swagger =
+        serde_json::from_str(&fs::read_to_string("./tests/petstore-v2.json").unwrap()).unwrap();
+    let source = Generator::from_swagger(&swagger).types().unwrap();
+    assert!(source.contains("use std::collections::BTreeMap;\n"));
+    assert!(source.contains("    pub tags: Option<BTreeMap<String, Tag>>,\n"));
+    assert!(source.contains("pub type Tag = String;\n"));
+}
+
+#[allow(dead_code)]
+#[rustfmt::skip]
+#[path = "generated/reserved_types.rs"]
+mod reserved_types;
+#[allow(dead_code)]
+#[rustfmt::skip]
+#[path = "generated/reserved_client.rs"]
+mod reserved_client;
+#[allow(dead_code)]
+#[rustfmt::skip]
+#[path = "generated/reserved_server.rs"]
+mod reserved_server;
+
+fn reserved() -> v3::OpenApi {
+    serde_json::from_str(&fs::read_to_string("./tests/reserved.json").unwrap()).unwrap()
+}
+
+#[test]
+fn test_reserved_types() {
+    // The schemas named after the types the source refers to get a suffix, so the committed source compiles.
+    let source = Generator::new(reserved()).types().unwrap();
+    assert_eq!(
+        source,
+        fs::read_to_string("./tests/generated/reserved_types.rs").unwrap()
+    );
+
+    let value =
+        serde_json::json!({"name": "a", "items": ["b"], "option": "some", "next": {"name": "c"}});
+    let boxed: reserved_types::Box2 = serde_json::from_value(value.clone()).unwrap();
+    assert_eq!(boxed.option, Some(reserved_types::Option2::Some));
+    assert_eq!(serde_json::to_value(&boxed).unwrap(), value);
+}
+
+#[rustfmt::skip]
+#[path = "generated/client.rs"]
+mod client;
+#[rustfmt::skip]
+#[path = "generated/server.rs"]
+mod server;
+
+use client::{
+    Client, ClientError, CreatePetResponse, GetPetParams, HttpRequest, HttpResponse,
+    ListPetsParams, ListPetsResponse, Pet, Transport, UpdatePetBody, UpdatePetParams,
+    UpdatePetResponse,
+};
+use openapi_schema::http::Request;
+use std::cell::RefCell;
+use std::future::Future;
+use std::sync::Mutex;
+use std::task::{Context, Poll, Waker};
+
+/// A transport answering with the given responses, which checks the requests against the document.
+struct Memory {
+    openapi: v3::OpenApi,
+    requests: RefCell<Vec<HttpRequest>>,
+    responses: RefCell<Vec<HttpResponse>>,
+}
+
+impl Memory {
+    fn new(responses: Vec<HttpResponse>) -> Self {
+        Memory {
+            openapi: openapi(),
+            requests: RefCell::new(Vec::new()),
+            responses: RefCell::new(responses),
+        }
+    }
+}
+
+impl Transport for Memory {
+    type Error = String;
+
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse, String> {
+        let path = request
+            .url
+            .strip_prefix("https://eu.petstore.example.com")
+            .ok_or("unknown server")?;
+        let mut validated = Request::new(request.method, path);
+        for (name, value) in &request.headers {
+            validated = validated.with_header(name, value);
+        }
+        if let Some(body) = &request.body {
+            validated = validated.with_body(body);
+        }
+        let errors = self.openapi.validate_request(&validated);
+        assert!(errors.is_empty(), "{:?}", errors);
+        self.requests.borrow_mut().push(request);
+        Ok(self.responses.borrow_mut().remove(0))
+    }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = std::pin::pin!(future);
+    let mut context = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+fn response(status: u16, body: &str) -> HttpResponse {
+    HttpResponse {
+        status,
+        headers: vec![("content-type".to_string(), "application/json".to_string())],
+        body: body.as_bytes().to_vec(),
+    }
+}
+
+#[test]
+fn test_reserved_client() {
+    // The schemas named after the types of the client get a suffix, so the committed source compiles.
+    let source = Generator::new(reserved()).client().unwrap();
+    assert_eq!(
+        source,
+        fs::read_to_string("./tests/generated/reserved_client.rs").unwrap()
+    );
+
+    struct Created;
+
+    impl reserved_client::Transport for Created {
+        type Error = String;
+
+        async fn send(
+            &self,
+            request: reserved_client::HttpRequest,
+        ) -> Result<reserved_client::HttpResponse, String> {
+            assert_eq!(request.url, "/boxes");
+            Ok(reserved_client::HttpResponse {
+                status: 201,
+                headers: Vec::new(),
+                body: br#""created""#.to_vec(),
+            })
+        }
+    }
+
+    let client = reserved_client::Client::new(Created);
+    let boxed = reserved_client::Box2 {
+        counts: None,
+        items: None,
+        labels: None,
+        name: "a".to_string(),
+        next: None,
+        option: None,
+    };
+    assert_eq!(
+        block_on(client.create_box(&boxed)).unwrap(),
+        reserved_client::CreateBoxResponse::Created(reserved_client::Result2::String2(
+            "created".to_string()
+        ))
+    );
+}
+
+#[test]
+fn test_client() {
+    // The committed client is the one generated from the document.
+    let source = Generator::new(openapi()).client().unwrap();
+    assert_eq!(
+        source,
+        fs::read_to_string("./tests/generated/client.rs").unwrap()
+    );
+
+    let client = Client::new(Memory::new(vec![
+        response(200, r#"[{"id": 1, "name": "Rex"}]"#),
+        response(500, r#"{"code": 7, "message": "down"}"#),
+    ]));
+    let params = ListPetsParams {
+        limit: Some(10),
+        tags: Some(vec!["old".to_string(), "big dog".to_string()]),
+        session: Some("abc".to_string()),
+    };
+    assert_eq!(
+        block_on(client.list_pets(&params)),
+        Ok(ListPetsResponse::Ok(vec![Pet {
+            id: Some(1),
+            kind: None,
+            name: "Rex".to_string(),
+            tag: None,
+        }]))
+    );
+    let request = client.transport().requests.borrow()[0].clone();
+    assert_eq!(
+        request.url,
+        "https://eu.petstore.example.com/v1/pets?limit=10&tags=old&tags=big%20dog"
+    );
+    assert!(request
+        .headers
+        .contains(&("cookie".to_string(), "session=abc".to_string())));
+    match block_on(client.list_pets(&params)) {
+        Ok(ListPetsResponse::Default { status, body }) => {
+            assert_eq!((status, body.code), (500, 7));
+        }
+        other => panic!("{:?}", other),
+    }
+
+    let client = Client::new(Memory::new(vec![response(
+        201,
+        r#"{"id": 2, "name": "Tom"}"#,
+    )]))
+    .with_base_url("https://eu.petstore.example.com/v1/");
+    assert_eq!(client.base_url(), "https://eu.petstore.example.com/v1");
+    let pet = Pet {
+        id: None,
+        kind: None,
+        name: "Tom".to_string(),
+        tag: None,
+    };
+    match block_on(client.create_pet(&pet)) {
+        Ok(CreatePetResponse::Created(created)) => assert_eq!(created.id, Some(2)),
+        other => panic!("{:?}", other),
+    }
+    let request = client.transport().requests.borrow()[0].clone();
+    assert_eq!(request.body.unwrap(), br#"{"name":"Tom"}"#);
+
+    let client = Client::new(Memory::new(vec![
+        response(204, ""),
+        response(404, ""),
+        response(200, r#"{"name": 1}"#),
+    ]));
+    let body = UpdatePetBody {
+        name: "Rex II".to_string(),
+        status: None,
+    };
+    assert_eq!(
+        block_on(client.update_pet(&UpdatePetParams { pet_id: 1 }, &body)),
+        Ok(UpdatePetResponse::NoContent)
+    );
+    let request = client.transport().requests.borrow()[0].clone();
+    assert_eq!(request.url, "https://eu.petstore.example.com/v1/pets/1");
+    assert_eq!(request.body.unwrap(), b"name=Rex%20II");
+    assert!(matches!(
+        block_on(client.get_pet(&GetPetParams { pet_id: 1 })),
+        Err(ClientError::UnexpectedStatus(HttpResponse {
+            status: 404,
+            ..
+        }))
+    ));
+    assert!(matches!(
+        block_on(client.get_pet(&GetPetParams { pet_id: 1 })),
+        Err(ClientError::Decode { status: 200, .. })
+    ));
+}
+
+/// An implementation of the operations keeping the pets in memory.
+#[derive(Default)]
+struct Pets {
+    pets: Mutex<Vec<server::Pet>>,
+}
+
+impl server::Api for Pets {
+    async fn list_pets(&self, params: server::ListPetsParams) -> server::ListPetsResponse {
+        let pets = self.pets.lock().unwrap();
+        if pets.is_empty() {
+            return server::ListPetsResponse::Default {
+                status: 404,
+                body: server::Error {
+                    code: 1,
+                    message: "no pets yet".to_string(),
+                },
+            };
+        }
+        let limit = params.limit.unwrap_or(20) as usize;
+        server::ListPetsResponse::Ok(pets.iter().take(limit).cloned().collect())
+    }
+
+    async fn create_pet(&self, mut body: server::Pet) -> server::CreatePetResponse {
+        let mut pets = self.pets.lock().unwrap();
+        body.id = Some(pets.len() as i64 + 1);
+        pets.push(body.clone());
+        server::CreatePetResponse::Created(body)
+    }
+
+    async fn get_pet(&self, params: server::GetPetParams) -> server::GetPetResponse {
+        let pets = self.pets.lock().unwrap();
+        server::GetPetResponse::Ok(pets[params.pet_id as usize - 1].clone())
+    }
+
+    async fn update_pet(
+        &self,
+        params: server::UpdatePetParams,
+        body: server::UpdatePetBody,
+    ) -> server::UpdatePetResponse {
+        self.pets.lock().unwrap()[params.pet_id as usize - 1].name = body.name;
+        server::UpdatePetResponse::NoContent
+    }
+}
+
+/// A transport sending the requests of the client to the server.
+struct Loopback(server::Server<Pets>);
+
+impl Transport for Loopback {
+    type Error = String;
+
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse, String> {
+        let request = server::HttpRequest {
+            method: request.method.to_string(),
+            path: request.url.replace("https://eu.petstore.example.com", ""),
+            headers: request.headers,
+            body: request.body.unwrap_or_default(),
+        };
+        let response = self.0.handle(&request).await;
+        Ok(HttpResponse {
+            status: response.status,
+            headers: response.headers,
+            body: response.body,
+        })
+    }
+}
+
+#[test]
+fn test_reserved_server() {
+    // The schemas named after the types of the server get a suffix, so the committed source compiles.
+    let source = Generator::new(reserved()).server().unwrap();
+    assert_eq!(
+        source,
+        fs::read_to_string("./tests/generated/reserved_server.rs").unwrap()
+    );
+
+    struct Boxes;
+
+    impl reserved_server::Api for Boxes {
+        async fn create_box(
+            &self,
+            body: reserved_server::Box2,
+        ) -> reserved_server::CreateBoxResponse {
+            reserved_server::CreateBoxResponse::Created(reserved_server::Result2::String2(
+                body.name,
+            ))
+        }
+
+        async fn get_box(
+            &self,
+            params: reserved_server::GetBoxParams,
+        ) -> reserved_server::GetBoxResponse {
+            reserved_server::GetBoxResponse::Ok(reserved_server::Box2 {
+                counts: None,
+                items: None,
+                labels: None,
+                name: params.id,
+                next: None,
+                option: None,
+            })
+        }
+    }
+
+    let server = reserved_server::Server::new(Boxes);
+    let response = block_on(server.handle(&reserved_server::HttpRequest {
+        method: "GET".to_string(),
+        path: "/boxes/a".to_string(),
+        ..Default::default()
+    }));
+    assert_eq!(response.status, 200);
+    assert_eq!(response.body, br#"{"name":"a"}"#);
+}
+
+#[test]
+fn test_server() {
+    // The committed server is the one generated from the document.
+    let source = Generator::new(openapi()).server().unwrap();
+    assert_eq!(
+        source,
+        fs::read_to_string("./tests/generated/server.rs").unwrap()
+    );
+
+    let server = server::Server::new(Pets::default());
+    let request = |method: &str, path: &str, body: &str| server::HttpRequest {
+        method: method.to_string(),
+        path: path.to_string(),
+        headers: vec![("content-type".to_string(), "application/json".to_string())],
+        body: body.as_bytes().to_vec(),
+    };
+    assert_eq!(server.openapi().paths.len(), 2);
+    let response = block_on(server.handle(&request("GET", "/v1/pets", "")));
+    assert_eq!(response.status, 404);
+    assert_eq!(response.body, br#"{"code":1,"message":"no pets yet"}"#);
+    let response = block_on(server.handle(&request("POST", "/v1/pets", r#"{"name": "Rex"}"#)));
+    assert_eq!(response.status, 201);
+    assert_eq!(
+        response.headers,
+        [("content-type".to_string(), "application/json".to_string())]
+    );
+    assert_eq!(response.body, br#"{"id":1,"name":"Rex"}"#);
+
+    // Requests which do not follow the document never reach the operations.
+    let response = block_on(server.handle(&request("POST", "/v1/pets", r#"{"name": ""}"#)));
+    assert_eq!(response.status, 400);
+    assert!(String::from_utf8(response.body)
+        .unwrap()
+        .contains("invalid"));
+    assert_eq!(
+        block_on(server.handle(&request("GET", "/v1/pets/rex", ""))).status,
+        400
+    );
+    assert_eq!(
+        block_on(server.handle(&request("DELETE", "/v1/pets/1", ""))).status,
+        405
+    );
+    assert_eq!(
+        block_on(server.handle(&request("GET", "/v1/owners", ""))).status,
+        404
+    );
+    assert_eq!(server.api().pets.lock().unwrap().len(), 1);
+
+    // The generated client talks to the generated server.
+    let client = Client::new(Loopback(server));
+    let params = UpdatePetParams { pet_id: 1 };
+    let body = UpdatePetBody {
+        name: "Rex II".to_string(),
+        status: None,
+    };
+    assert_eq!(
+        block_on(client.update_pet(&params, &body)),
+        Ok(UpdatePetResponse::NoContent)
+    );
+    let params = ListPetsParams {
+        limit: Some(1),
+        tags: None,
+        session: None,
+    };
+    match block_on(client.list_pets(&params)) {
+        Ok(ListPetsResponse::Ok(pets)) => assert_eq!(pets[0].name, "Rex II"),
+        other => panic!("{:?}", other),
+    }
+    match block_on(client.get_pet(&GetPetParams { pet_id: 1 })) {
+        Ok(client::GetPetResponse::Ok(pet)) => assert_eq!(pet.id, Some(1)),
+        other => panic!("{:?}", other),
+    }
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn test_build() {
+    use openapi_schema::codegen::Build;
+    use openapi_schema::loader::Loader;
+    use openapi_schema::{Doc, Error};
+    use std::path::PathBuf;
+
+    let loader = || {
+        Loader::new().with_fetcher(|url: &str| match url {
+            "https://example.com/schemas/category.json" => Ok(
+                r#"{"Category": {"type": "object", "properties": {"name": {"type": "string"}}}}"#
+                    .to_string(),
+            ),
+            _ => Err(Error::UnresolvedReference {
+                reference: url.to_string(),
+                message: "is not served".to_string(),
+            }),
+        })
+    };
+    let out_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("build");
+    let build = Build::new("./tests/multi/openapi.yaml")
+        .with_loader(loader())
+        .with_out_dir(&out_dir)
+        .with_client(true)
+        .with_rustfmt(false);
+    let generated = build.generate().unwrap();
+    assert_eq!(
+        generated.files,
+        vec![out_dir.join("types.rs"), out_dir.join("client.rs")]
+    );
+
+    // the remote document is not a dependency of the build
+    let set = loader().load("./tests/multi/openapi.yaml").unwrap();
+    let dependencies: Vec<PathBuf> = set
+        .documents()
+        .map(|(document, _)| document)
+        .filter(|document| !document.starts_with("https://"))
+        .map(PathBuf::from)
+        .collect();
+    assert_eq!(generated.dependencies, dependencies);
+    assert_eq!(dependencies.len(), set.documents().count() - 1);
+    assert!(generated
+        .dependencies
+        .iter()
+        .any(|path| path.ends_with("schemas/pet.yaml")));
+
+    let Doc::V3(mut openapi) = set.doc().unwrap() else {
+        panic!("expected an openapi 3.0 document");
+    };
+    openapi.bundle(&set).unwrap();
+    let generator = Generator::new(openapi);
+    let types = fs::read_to_string(out_dir.join("types.rs")).unwrap();
+    assert_eq!(types, generator.types().unwrap());
+    assert!(types.contains("pub struct Pet {"));
+    assert!(types.contains("pub struct Category {"));
+    assert_eq!(
+        fs::read_to_string(out_dir.join("client.rs")).unwrap(),
+        generator.client().unwrap()
+    );
+
+    // a second build generates the same source and leaves the files untouched
+    let modified = fs::metadata(out_dir.join("types.rs"))
+        .unwrap()
+        .modified()
+        .unwrap();
+    assert_eq!(build.generate().unwrap(), generated);
+    assert_eq!(
+        fs::metadata(out_dir.join("types.rs"))
+            .unwrap()
+            .modified()
+            .unwrap(),
+        modified
+    );
+    assert!(!out_dir.join("server.rs").exists());
+
+    // formatted with rustfmt when it is installed
+    let formatted = Build::new("./tests/multi/openapi.yaml")
+        .with_loader(loader())
+        .with_out_dir(out_dir.join("formatted"))
+        .with_server(true)
+        .generate()
+        .unwrap();
+    assert_eq!(formatted.files[1], out_dir.join("formatted/server.rs"));
+    let server = fs::read_to_string(&formatted.files[1]).unwrap();
+    assert!(server.starts_with("// Generated from an OpenAPI document"));
+    assert!(server.contains("pub trait Api {"));
+
+    // a Swagger 2.0 document is converted and the documents it references are bundled into it
+    let v2 = Build::new("./tests/multi-v2/swagger.json")
+        .with_out_dir(out_dir.join("v2"))
+        .with_client(true)
+        .with_rustfmt(false)
+        .generate()
+        .unwrap();
+    assert_eq!(v2.dependencies.len(), 2);
+    let types = fs::read_to_string(&v2.files[0]).unwrap();
+    assert!(types.contains("pub struct Pet {"));
+    assert!(types.contains("pub struct Owner {"));
+    assert!(types.contains("pub struct Address {"));
+
+    let error = Build::new("./tests/openapi31.json")
+        .with_out_dir(&out_dir)
+        .generate()
+        .unwrap_err();
+    assert!(matches!(error, Error::UnsupportedVersion { .. }));
+}
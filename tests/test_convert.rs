@@ -0,0 +1,455 @@
+use openapi_schema::{v2, v3};
+use serde_json::{json, Value};
+use std::fs;
+
+fn swagger(path: &str) -> v2::Swagger {
+    serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+fn references<'a>(value: &'a Value, found: &mut Vec<&'a str>) {
+    match value {
+        Value::Object(map) => {
+            if let Some(Value::String(reference)) = map.get("$ref") {
+                found.push(reference);
+            }
+            map.values().for_each(|value| references(value, found));
+        }
+        Value::Array(items) => items.iter().for_each(|value| references(value, found)),
+        _ => {}
+    }
+}
+
+/// Every local reference of the converted document points into its components.
+fn assert_references_resolve(openapi: &v3::OpenApi) {
+    let value = serde_json::to_value(openapi).unwrap();
+    let mut found = Vec::new();
+    references(&value, &mut found);
+    for reference in found {
+        // `swagger.json` holds a reference which is not a pointer, it is carried over as is.
+        let Some(pointer) = reference.strip_prefix('#') else {
+            continue;
+        };
+        assert!(pointer.starts_with("/components/"), "{}", reference);
+        assert!(value.pointer(pointer).is_some(), "{}", reference);
+    }
+}
+
+#[test]
+fn test_v2_to_v3() {
+    let (openapi, notes) = swagger("./tests/petstore-v2.json").to_v3();
+    assert_eq!(notes, []);
+    assert_references_resolve(&openapi);
+    let value = serde_json::to_value(&openapi).unwrap();
+    assert_eq!(value["openapi"], "3.0.3");
+    assert_eq!(value["x-audience"], "public");
+    assert_eq!(
+        value["servers"],
+        json!([
+            {"url": "https://petstore.example.com/v1"},
+            {"url": "http://petstore.example.com/v1"}
+        ])
+    );
+
+    let list = &value["paths"]["/pets"]["get"];
+    assert_eq!(
+        list["parameters"],
+        json!([
+            {"$ref": "#/components/parameters/limit"},
+            {
+                "name": "tags",
+                "in": "query",
+                "style": "form",
+                "explode": true,
+                "schema": {"type": "array", "items": {"type": "string"}}
+            },
+            {
+                "name": "status",
+                "in": "query",
+                "style": "form",
+                "explode": false,
+                "schema": {"type": "array", "items": {"type": "string", "enum": ["available", "sold"]}}
+            }
+        ])
+    );
+    let ok = &list["responses"]["200"];
+    assert_eq!(
+        ok["content"]["application/xml"]["schema"]["items"],
+        json!({"$ref": "#/components/schemas/Pet"})
+    );
+    assert_eq!(
+        ok["content"]["application/json"]["example"][0]["name"],
+        "Rex"
+    );
+    assert_eq!(
+        ok["headers"]["x-next"],
+        json!({"description": "A link to the next page.", "schema": {"type": "string"}})
+    );
+    assert_eq!(
+        list["responses"]["default"],
+        json!({"$ref": "#/components/responses/Error"})
+    );
+
+    let create = &value["paths"]["/pets"]["post"];
+    assert!(create.get("parameters").is_none());
+    assert_eq!(
+        create["requestBody"],
+        json!({"$ref": "#/components/requestBodies/pet"})
+    );
+    assert_eq!(create["security"], json!([{"oauth": ["write:pets"]}]));
+
+    let item = &value["paths"]["/pets/{petId}"];
+    assert_eq!(item["parameters"][0]["schema"]["format"], "int64");
+    assert_eq!(item["put"]["deprecated"], true);
+    assert_eq!(
+        item["put"]["requestBody"],
+        json!({
+            "content": {
+                "application/x-www-form-urlencoded": {
+                    "schema": {
+                        "type": "object",
+                        "properties": {"name": {"type": "string"}, "status": {"type": "string"}},
+                        "required": ["name"]
+                    }
+                }
+            },
+            "required": true
+        })
+    );
+    let upload = &value["paths"]["/pets/{petId}/photo"]["post"]["requestBody"];
+    assert_eq!(
+        upload["content"]["multipart/form-data"]["schema"]["properties"]["file"],
+        json!({"type": "string", "format": "binary"})
+    );
+
+    let components = &value["components"];
+    assert_eq!(
+        components["schemas"]["Pet"]["discriminator"],
+        json!({"propertyName": "kind"})
+    );
+    assert_eq!(
+        components["schemas"]["Pet"]["properties"]["tags"]["additionalProperties"],
+        json!({"$ref": "#/components/schemas/Tag"})
+    );
+    assert_eq!(
+        components["parameters"]["limit"]["schema"],
+        json!({"type": "integer", "maximum": 100, "default": 20})
+    );
+    assert_eq!(
+        components["requestBodies"]["pet"]["content"]["application/json"]["schema"],
+        json!({"$ref": "#/components/schemas/Pet"})
+    );
+    assert_eq!(
+        components["securitySchemes"]["basic"],
+        json!({"type": "http", "scheme": "basic"})
+    );
+    assert_eq!(
+        components["securitySchemes"]["key"],
+        json!({"type": "apiKey", "name": "api_key", "in": "header"})
+    );
+    assert_eq!(
+        components["securitySchemes"]["oauth"]["flows"]["authorizationCode"]["tokenUrl"],
+        "https://petstore.example.com/oauth/token"
+    );
+
+    // The converted document is a valid OpenAPI 3.0 document.
+    let reparsed: v3::OpenApi = serde_json::from_value(value).unwrap();
+    assert_eq!(reparsed, openapi);
+}
+
+#[test]
+fn test_v2_to_v3_without_schemes() {
+    let openapi = swagger("./tests/swagger.json").to_v3().0;
+    assert_references_resolve(&openapi);
+    assert_eq!(openapi.servers.unwrap()[0].url, "//192.168.0.60:22105/");
+    let schemes = openapi.components.unwrap().security_schemes.unwrap();
+    assert_eq!(schemes.len(), 8);
+}
+
+#[test]
+fn test_v2_to_v3_notes() {
+    let swagger: v2::Swagger = serde_json::from_value(json!({
+        "swagger": "2.0",
+        "info": {"title": "Pets", "version": "1.0.0"},
+        "paths": {"/pets": {"post": {
+            "parameters": [
+                {"$ref": "other.json#/parameters/pet"},
+                {"name": "X-Tags", "in": "header", "type": "array", "items": {"type": "string"}, "collectionFormat": "ssv"},
+                {"name": "sizes", "in": "query", "type": "array", "items": {"type": "string"}, "collectionFormat": "tsv"},
+                {"name": "colors", "in": "query", "type": "array", "items": {"type": "string"}, "collectionFormat": "pipes"}
+            ],
+            "responses": {"200": {
+                "description": "The pet.",
+                "schema": {"$ref": "other.json#/definitions/Pet"}
+            }}
+        }}},
+        "definitions": {
+            "Tag": {"type": "string", "x-nullable": true, "x-order": 2}
+        }
+    }))
+    .unwrap();
+    let (openapi, notes) = swagger.to_v3();
+    let value = serde_json::to_value(&openapi).unwrap();
+    let create = &value["paths"]["/pets"]["post"];
+    assert_eq!(
+        create["parameters"][0],
+        json!({"$ref": "other.json#/parameters/pet"})
+    );
+    // the delimited styles are only allowed on query parameters
+    assert_eq!(create["parameters"][1]["style"], json!(null));
+    assert_eq!(create["parameters"][2]["style"], json!(null));
+    assert_eq!(create["parameters"][3]["style"], "pipeDelimited");
+    assert_eq!(
+        create["responses"]["200"]["content"]["application/json"]["schema"],
+        json!({"$ref": "other.json#/definitions/Pet"})
+    );
+    assert_eq!(
+        value["components"]["schemas"]["Tag"],
+        json!({"type": "string", "nullable": true, "x-order": 2})
+    );
+    let pointers: Vec<_> = notes.iter().map(|note| note.pointer.as_str()).collect();
+    assert_eq!(
+        pointers,
+        [
+            "/paths/~1pets/post/parameters/0",
+            "/paths/~1pets/post/parameters/1/collectionFormat",
+            "/paths/~1pets/post/parameters/2/collectionFormat",
+            "/paths/~1pets/post/responses/200/schema",
+        ]
+    );
+}
+
+fn openapi(path: &str) -> v3::OpenApi {
+    serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn test_v3_to_v2() {
+    let (swagger, notes) = openapi("./tests/petstore-v3.json").to_v2();
+    let value = serde_json::to_value(&swagger).unwrap();
+    assert_eq!(value["swagger"], "2.0");
+    assert_eq!(value["host"], "eu.petstore.example.com");
+    assert_eq!(value["basePath"], "/v1");
+    assert_eq!(value["schemes"], json!(["https", "http"]));
+
+    let list = &value["paths"]["/pets"]["get"];
+    assert_eq!(
+        list["parameters"],
+        json!([
+            {"$ref": "#/parameters/limit"},
+            {
+                "name": "tags",
+                "in": "query",
+                "type": "array",
+                "items": {"type": "string"},
+                "collectionFormat": "multi"
+            }
+        ])
+    );
+    assert_eq!(list["produces"], json!(["application/json"]));
+    let ok = &list["responses"]["200"];
+    assert_eq!(ok["schema"]["items"], json!({"$ref": "#/definitions/Pet"}));
+    assert_eq!(ok["examples"]["application/json"][0]["name"], "Rex");
+    assert_eq!(
+        ok["headers"]["x-next"],
+        json!({"description": "A link to the next page.", "type": "string"})
+    );
+    assert_eq!(
+        list["responses"]["default"],
+        json!({"$ref": "#/responses/Error"})
+    );
+
+    let create = &value["paths"]["/pets"]["post"];
+    assert_eq!(create["parameters"], json!([{"$ref": "#/parameters/Pet"}]));
+    assert_eq!(create["consumes"], json!(["application/json"]));
+    let update = &value["paths"]["/pets/{petId}"]["put"];
+    assert_eq!(
+        update["parameters"],
+        json!([
+            {"name": "name", "in": "formData", "type": "string", "required": true},
+            {"name": "status", "in": "formData", "type": "string"}
+        ])
+    );
+    assert_eq!(
+        value["paths"]["/pets/{petId}"]["parameters"][0],
+        json!({"name": "petId", "in": "path", "required": true, "type": "integer", "format": "int64"})
+    );
+
+    assert_eq!(
+        value["parameters"]["Pet"],
+        json!({
+            "name": "Pet",
+            "in": "body",
+            "required": true,
+            "schema": {"$ref": "#/definitions/Pet"}
+        })
+    );
+    assert_eq!(
+        value["definitions"]["Pet"]["properties"]["tag"],
+        json!({"type": "string", "x-nullable": true})
+    );
+    assert_eq!(value["securityDefinitions"]["oauth"]["flow"], "accessCode");
+    assert!(value["securityDefinitions"].get("bearer").is_none());
+
+    let pointers: Vec<_> = notes.iter().map(|note| note.pointer.as_str()).collect();
+    assert_eq!(
+        pointers,
+        [
+            "/components/schemas/Pet/properties/kind/oneOf",
+            "/components/securitySchemes/bearer",
+            "/paths/~1pets/get/parameters/2",
+            "/paths/~1pets/post/responses/201/links",
+            "/paths/~1pets/post/callbacks",
+        ]
+    );
+
+    // The converted document is a valid Swagger 2.0 document.
+    let reparsed: v2::Swagger = serde_json::from_value(value).unwrap();
+    assert_eq!(reparsed, swagger);
+}
+
+#[test]
+fn test_v3_to_v2_body_named_like_parameter() {
+    let openapi: v3::OpenApi = serde_json::from_value(json!({
+        "openapi": "3.0.3",
+        "info": {"title": "Pets", "version": "1.0.0"},
+        "paths": {"/pets": {"post": {
+            "parameters": [{"$ref": "#/components/parameters/Pet"}],
+            "requestBody": {"$ref": "#/components/requestBodies/Pet"},
+            "responses": {"204": {"description": "Created."}}
+        }}},
+        "components": {
+            "parameters": {"Pet": {"name": "pet", "in": "query", "schema": {"type": "string"}}},
+            "requestBodies": {"Pet": {"content": {"application/json": {"schema": {"type": "object"}}}}}
+        }
+    }))
+    .unwrap();
+    let (swagger, notes) = openapi.to_v2();
+    let value = serde_json::to_value(&swagger).unwrap();
+    assert_eq!(value["parameters"]["Pet"]["in"], "query");
+    assert_eq!(value["parameters"]["PetBody"]["in"], "body");
+    assert_eq!(value["parameters"]["PetBody"]["name"], "PetBody");
+    assert_eq!(
+        value["paths"]["/pets"]["post"]["parameters"],
+        json!([{"$ref": "#/parameters/Pet"}, {"$ref": "#/parameters/PetBody"}])
+    );
+    assert_eq!(notes.len(), 1);
+    assert_eq!(notes[0].pointer, "/components/requestBodies/Pet");
+}
+
+#[test]
+fn test_v3_to_v2_dropped_targets() {
+    let openapi: v3::OpenApi = serde_json::from_value(json!({
+        "openapi": "3.0.3",
+        "info": {"title": "Pets", "version": "1.0.0"},
+        "paths": {"/pets": {"post": {
+            "requestBody": {"$ref": "#/components/requestBodies/NewPet"},
+            "responses": {
+                "201": {"$ref": "#/components/responses/Created"},
+                "default": {"$ref": "errors.yaml#/Error"}
+            },
+            "security": [{"bearer": []}, {"basic": []}]
+        }}},
+        "components": {
+            "requestBodies": {
+                "NewPet": {"$ref": "#/components/requestBodies/Pet"},
+                "Pet": {"content": {"application/json": {"schema": {"type": "object"}}}}
+            },
+            "responses": {
+                "Created": {"$ref": "#/components/responses/Ok"},
+                "Ok": {"description": "Done."}
+            },
+            "securitySchemes": {
+                "basic": {"type": "http", "scheme": "basic"},
+                "bearer": {"type": "http", "scheme": "bearer"}
+            }
+        },
+        "security": [{"bearer": []}]
+    }))
+    .unwrap();
+    let (swagger, notes) = openapi.to_v2();
+    let value = serde_json::to_value(&swagger).unwrap();
+    let create = &value["paths"]["/pets"]["post"];
+    assert_eq!(create["parameters"], json!([{"$ref": "#/parameters/Pet"}]));
+    assert_eq!(
+        create["responses"],
+        json!({"201": {"$ref": "#/responses/Ok"}, "default": {"$ref": "errors.yaml#/Error"}})
+    );
+    assert_eq!(create["security"], json!([{"basic": []}]));
+    assert_eq!(value["security"], json!([]));
+    assert!(value["parameters"].get("NewPet").is_none());
+    assert!(value["responses"].get("Created").is_none());
+
+    let pointers: Vec<_> = notes.iter().map(|note| note.pointer.as_str()).collect();
+    assert_eq!(
+        pointers,
+        [
+            "/components/requestBodies/NewPet",
+            "/components/responses/Created",
+            "/components/securitySchemes/bearer",
+            "/paths/~1pets/post/responses/default",
+            "/paths/~1pets/post/security/0",
+            "/security/0",
+        ]
+    );
+}
+
+#[test]
+fn test_v2_to_v3_to_v2() {
+    let (swagger, _) = swagger("./tests/petstore-v2.json").to_v3().0.to_v2();
+    let value = serde_json::to_value(&swagger).unwrap();
+    assert_eq!(value["host"], "petstore.example.com");
+    assert_eq!(
+        value["paths"]["/pets"]["post"]["parameters"],
+        json!([{"$ref": "#/parameters/pet"}])
+    );
+    assert_eq!(
+        value["definitions"]["Pet"]["properties"]["tags"]["additionalProperties"],
+        json!({"$ref": "#/definitions/Tag"})
+    );
+    assert_eq!(value["definitions"]["Pet"]["discriminator"], "kind");
+}
+
+#[test]
+fn test_v3_to_v3_1() {
+    let (openapi, notes) = openapi("./tests/petstore-v3.json").to_v3_1().unwrap();
+    let value = serde_json::to_value(&openapi).unwrap();
+    assert_eq!(value["openapi"], "3.1.0");
+    let properties = &value["components"]["schemas"]["Pet"]["properties"];
+    assert_eq!(
+        properties["id"],
+        json!({"type": "integer", "format": "int64", "exclusiveMinimum": 0, "readOnly": true})
+    );
+    assert_eq!(
+        properties["name"],
+        json!({"type": "string", "minLength": 1, "examples": ["Rex"]})
+    );
+    assert_eq!(properties["tag"], json!({"type": ["string", "null"]}));
+    assert_eq!(
+        value["paths"]["/pets"]["get"]["responses"]["200"]["content"]["application/json"]
+            ["example"][0]["name"],
+        "Rex"
+    );
+
+    let notes: Vec<_> = notes
+        .iter()
+        .map(|note| (note.pointer.as_str(), note.message.as_str()))
+        .collect();
+    assert_eq!(
+        notes,
+        [
+            ("/openapi", "`openapi` was bumped from `3.0.3` to `3.1.0`"),
+            (
+                "/components/schemas/Pet/properties/id/exclusiveMinimum",
+                "`exclusiveMinimum: true` became `exclusiveMinimum: 0` in place of `minimum`"
+            ),
+            (
+                "/components/schemas/Pet/properties/name/example",
+                "`example` became the single item of `examples`"
+            ),
+            (
+                "/components/schemas/Pet/properties/tag/nullable",
+                "`nullable: true` became `type: [\"string\",\"null\"]`"
+            ),
+        ]
+    );
+}
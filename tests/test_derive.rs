@@ -0,0 +1,340 @@
+#![cfg(feature = "derive")]
+
+use std::collections::BTreeMap;
+
+use openapi_schema::v3::{Components, ToSchema};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+fn schema<T: ToSchema>() -> Value {
+    serde_json::to_value(T::schema()).unwrap()
+}
+
+fn schemas(components: &Components) -> Vec<&str> {
+    components
+        .schemas
+        .iter()
+        .flatten()
+        .map(|(name, _)| name.as_str())
+        .collect()
+}
+
+/// A pet of the store.
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct Pet {
+    /// The id of the pet,
+    /// unique in the store.
+    pet_id: i64,
+    #[serde(rename = "label")]
+    name: String,
+    nick_name: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(skip)]
+    #[allow(dead_code)]
+    cache: Vec<u8>,
+    owner: Owner,
+    #[serde(flatten)]
+    audit: Audit,
+}
+
+#[derive(Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename = "PetOwner")]
+struct Owner {
+    name: String,
+    address: Option<Address>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+struct Address {
+    city: String,
+}
+
+#[derive(Default, Serialize, Deserialize, ToSchema)]
+#[serde(default)]
+struct Audit {
+    created_by: String,
+    editor: Owner,
+}
+
+#[test]
+fn test_struct() {
+    assert_eq!(Pet::name().as_deref(), Some("Pet"));
+    assert_eq!(Owner::name().as_deref(), Some("PetOwner"));
+    assert_eq!(
+        schema::<Pet>(),
+        json!({
+            "type": "object",
+            "description": "A pet of the store.",
+            "properties": {
+                "petId": {"type": "integer", "format": "int64", "description": "The id of the pet,\nunique in the store."},
+                "label": {"type": "string"},
+                "nickName": {"type": "string", "nullable": true},
+                "tags": {"type": "array", "items": {"type": "string"}},
+                "owner": {"$ref": "#/components/schemas/PetOwner"},
+                "created_by": {"type": "string"},
+                "editor": {"$ref": "#/components/schemas/PetOwner"},
+            },
+            "required": ["petId", "label", "owner"],
+        })
+    );
+}
+
+#[test]
+fn test_register() {
+    let mut components = Components::default();
+    Pet::register(&mut components);
+    // the flattened struct is not registered, the types it contains are
+    assert_eq!(schemas(&components), ["Address", "Pet", "PetOwner"]);
+    let owner = serde_json::to_value(&components.schemas.as_ref().unwrap()["PetOwner"]).unwrap();
+    // a reference is wrapped to be nullable
+    assert_eq!(
+        owner["properties"]["address"],
+        json!({"allOf": [{"$ref": "#/components/schemas/Address"}], "nullable": true})
+    );
+    assert_eq!(owner["required"], json!(["name"]));
+
+    // a type already in the components is kept
+    Owner::register(&mut components);
+    <Vec<Option<Box<Pet>>>>::register(&mut components);
+    assert_eq!(schemas(&components), ["Address", "Pet", "PetOwner"]);
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+struct Account {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    roles: Vec<String>,
+    #[serde(skip_deserializing)]
+    created: u64,
+    #[serde(skip_serializing)]
+    #[allow(dead_code)]
+    password: String,
+    #[serde(skip_deserializing)]
+    owner: Owner,
+    #[serde(skip_serializing, skip_deserializing)]
+    #[allow(dead_code)]
+    session: String,
+}
+
+#[test]
+fn test_skip_serializing() {
+    assert_eq!(
+        schema::<Account>(),
+        json!({
+            "type": "object",
+            "properties": {
+                "roles": {"type": "array", "items": {"type": "string"}},
+                "created": {"type": "integer", "format": "int64", "readOnly": true},
+                "password": {"type": "string", "writeOnly": true},
+                "owner": {"allOf": [{"$ref": "#/components/schemas/PetOwner"}], "readOnly": true},
+            },
+            "required": ["created", "password", "owner"],
+        })
+    );
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+struct Tree {
+    value: i32,
+    children: Vec<Tree>,
+    parent: Option<Box<Tree>>,
+}
+
+#[test]
+fn test_recursive() {
+    let mut components = Components::default();
+    Tree::register(&mut components);
+    assert_eq!(schemas(&components), ["Tree"]);
+    assert_eq!(
+        schema::<Tree>()["properties"]["children"],
+        json!({"type": "array", "items": {"$ref": "#/components/schemas/Tree"}})
+    );
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+enum Status {
+    Available,
+    #[serde(rename = "on-hold")]
+    OnHold,
+    NotForSale,
+    #[serde(skip)]
+    #[allow(dead_code)]
+    Deleted,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+struct Id(u32);
+
+#[derive(Serialize, Deserialize, ToSchema)]
+struct Point(f64, f64);
+
+#[test]
+fn test_unit_variants_and_tuples() {
+    assert_eq!(
+        schema::<Status>(),
+        json!({"type": "string", "enum": ["AVAILABLE", "on-hold", "NOT_FOR_SALE"]})
+    );
+    assert_eq!(
+        schema::<Id>(),
+        json!({"type": "integer", "format": "int64"})
+    );
+    assert_eq!(
+        schema::<Point>(),
+        json!({
+            "type": "array",
+            "items": {"type": "number", "format": "double"},
+            "minItems": 2,
+            "maxItems": 2,
+        })
+    );
+}
+
+/// An event.
+#[derive(Serialize, Deserialize, ToSchema)]
+enum External {
+    Ping,
+    /// A message.
+    Message(String),
+    Moved {
+        x: i32,
+        y: i32,
+    },
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum Internal {
+    Ping,
+    Moved {
+        #[serde(rename = "X")]
+        x: i32,
+    },
+    Address(Address),
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(tag = "t", content = "c")]
+enum Adjacent {
+    Ping,
+    Message(String),
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+enum Untagged {
+    Number(i64),
+    Address(Address),
+    Pair(i64, String),
+}
+
+#[test]
+fn test_enums() {
+    assert_eq!(
+        schema::<External>(),
+        json!({
+            "description": "An event.",
+            "oneOf": [
+                {"type": "string", "enum": ["Ping"]},
+                {
+                    "type": "object",
+                    "description": "A message.",
+                    "properties": {"Message": {"type": "string"}},
+                    "required": ["Message"],
+                },
+                {
+                    "type": "object",
+                    "properties": {
+                        "Moved": {
+                            "type": "object",
+                            "properties": {
+                                "x": {"type": "integer", "format": "int32"},
+                                "y": {"type": "integer", "format": "int32"},
+                            },
+                            "required": ["x", "y"],
+                        },
+                    },
+                    "required": ["Moved"],
+                },
+            ],
+        })
+    );
+
+    let tag = |value: &str| json!({"type": "string", "enum": [value]});
+    assert_eq!(
+        schema::<Internal>(),
+        json!({
+            "oneOf": [
+                {"type": "object", "properties": {"type": tag("ping")}, "required": ["type"]},
+                {
+                    "type": "object",
+                    "properties": {"type": tag("moved"), "X": {"type": "integer", "format": "int32"}},
+                    "required": ["type", "X"],
+                },
+                {"allOf": [
+                    {"type": "object", "properties": {"type": tag("address")}, "required": ["type"]},
+                    {"$ref": "#/components/schemas/Address"},
+                ]},
+            ],
+            "discriminator": {"propertyName": "type"},
+        })
+    );
+
+    assert_eq!(
+        schema::<Adjacent>(),
+        json!({
+            "oneOf": [
+                {"type": "object", "properties": {"t": tag("Ping")}, "required": ["t"]},
+                {
+                    "type": "object",
+                    "properties": {"t": tag("Message"), "c": {"type": "string"}},
+                    "required": ["t", "c"],
+                },
+            ],
+            "discriminator": {"propertyName": "t"},
+        })
+    );
+
+    assert_eq!(
+        schema::<Untagged>()["oneOf"][0],
+        json!({"type": "integer", "format": "int64"})
+    );
+    assert_eq!(
+        schema::<Untagged>()["oneOf"][1],
+        json!({"allOf": [{"$ref": "#/components/schemas/Address"}]})
+    );
+    assert_eq!(schema::<Untagged>()["oneOf"][2]["maxItems"], json!(2));
+
+    let mut components = Components::default();
+    Internal::register(&mut components);
+    Untagged::register(&mut components);
+    assert_eq!(schemas(&components), ["Address", "Internal", "Untagged"]);
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+struct Page<T> {
+    items: Vec<T>,
+    next: Option<String>,
+    extra: BTreeMap<String, Value>,
+}
+
+#[test]
+fn test_generic() {
+    assert_eq!(<Page<Address>>::name(), None);
+    assert_eq!(
+        schema::<Page<Address>>(),
+        json!({
+            "type": "object",
+            "properties": {
+                "items": {"type": "array", "items": {"$ref": "#/components/schemas/Address"}},
+                "next": {"type": "string", "nullable": true},
+                "extra": {"type": "object", "additionalProperties": {}},
+            },
+            "required": ["items", "extra"],
+        })
+    );
+    let mut components = Components::default();
+    <Page<Address>>::register(&mut components);
+    assert_eq!(schemas(&components), ["Address"]);
+}
@@ -0,0 +1,28 @@
+use openapi_schema::{from_path, from_str, Error};
+use std::path::Path;
+
+#[test]
+fn test_missing_file() {
+    match from_path("./tests/missing.json") {
+        Err(Error::Io { path, source }) => {
+            assert_eq!(path.as_deref(), Some(Path::new("./tests/missing.json")));
+            assert_eq!(source.kind(), std::io::ErrorKind::NotFound);
+        }
+        other => panic!("expected an io error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_json_syntax_error() {
+    let e = from_str("{\n  \"swagger\": \"2.0\",\n  \"info\": {").unwrap_err();
+    assert!(matches!(e, Error::Syntax { .. }));
+    assert_eq!(e.location().map(|(line, _)| line), Some(3));
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn test_yaml_syntax_error() {
+    let e = from_str("openapi: 3.0.0\ninfo: [title\n").unwrap_err();
+    assert!(matches!(e, Error::Syntax { .. }));
+    assert!(e.location().is_some());
+}
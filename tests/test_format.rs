@@ -0,0 +1,46 @@
+use openapi_schema::{from_str, Doc};
+
+#[test]
+fn test_json_detected_by_content() {
+    let doc = from_str(r#"{"swagger":"2.0","info":{"title":"t"},"paths":{}}"#).unwrap();
+    assert!(matches!(doc, Doc::V2(_)));
+}
+
+#[cfg(feature = "yaml")]
+mod yaml {
+    use super::*;
+    use openapi_schema::{from_path, to_path, to_string, Format};
+    use std::path::PathBuf;
+
+    fn tmp_path(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name)
+    }
+
+    #[test]
+    fn test_yaml_detected_by_content() {
+        let yaml = "openapi: 3.0.0\ninfo:\n  title: pets\npaths: {}\n";
+        match from_str(yaml).unwrap() {
+            Doc::V3(openapi) => assert_eq!(openapi.info.title, "pets"),
+            _ => panic!("expected an openapi 3.0 document"),
+        }
+    }
+
+    #[test]
+    fn test_yaml_round_trip() {
+        let doc = from_path("./tests/openapi.json").unwrap();
+        let yaml = to_string(&doc, Format::Yaml).unwrap();
+        assert!(yaml.starts_with("openapi: 3.0.0"));
+        assert_eq!(from_str(&yaml).unwrap(), doc);
+    }
+
+    #[test]
+    fn test_yaml_path() {
+        let doc = from_path("./tests/openapi.json").unwrap();
+        let path = tmp_path("openapi.yaml");
+        to_path(&path, &doc).unwrap();
+        assert!(std::fs::read_to_string(&path)
+            .unwrap()
+            .starts_with("openapi: 3.0.0"));
+        assert_eq!(from_path(&path).unwrap(), doc);
+    }
+}
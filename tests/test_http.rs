@@ -0,0 +1,329 @@
+use openapi_schema::http::{Request, RequestError, Response, ResponseError, Validator};
+use openapi_schema::v3;
+use serde_json::json;
+use std::fs;
+
+fn openapi() -> v3::OpenApi {
+    serde_json::from_str(&fs::read_to_string("./tests/petstore-v3.json").unwrap()).unwrap()
+}
+
+/// The variant of each error, with the name of the parameter it is about.
+fn kinds(errors: &[RequestError]) -> Vec<String> {
+    errors
+        .iter()
+        .map(|error| match error {
+            RequestError::NotFound { .. } => "not-found".to_string(),
+            RequestError::MethodNotAllowed { .. } => "method-not-allowed".to_string(),
+            RequestError::MissingParameter { name, .. } => format!("missing {}", name),
+            RequestError::MalformedParameter { name, .. } => format!("malformed {}", name),
+            RequestError::InvalidParameter { name, .. } => format!("invalid {}", name),
+            RequestError::MissingBody => "missing-body".to_string(),
+            RequestError::UnsupportedMediaType { .. } => "unsupported-media-type".to_string(),
+            RequestError::MalformedBody { .. } => "malformed-body".to_string(),
+            RequestError::InvalidBody { .. } => "invalid-body".to_string(),
+            RequestError::UnresolvedReference { reference } => format!("unresolved {}", reference),
+        })
+        .collect()
+}
+
+#[test]
+fn test_route() {
+    let openapi = openapi();
+    assert_eq!(openapi.validate_request(&Request::new("GET", "/pets")), []);
+    assert_eq!(
+        openapi.validate_request(&Request::new("get", "/v1/pets/1")),
+        []
+    );
+    assert_eq!(
+        kinds(&openapi.validate_request(&Request::new("GET", "/owners"))),
+        ["not-found"]
+    );
+    assert_eq!(
+        openapi.validate_request(&Request::new("DELETE", "/pets/1")),
+        [RequestError::MethodNotAllowed {
+            method: "DELETE".to_string(),
+            template: "/pets/{petId}".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_parameters() {
+    let openapi = openapi();
+    let request = Request::new("GET", "/pets?limit=10&tags=cat&tags=dog")
+        .with_header("Cookie", "session=abc; theme=dark");
+    assert_eq!(openapi.validate_request(&request), []);
+
+    let errors = openapi.validate_request(&Request::new("GET", "/pets?limit=500"));
+    let [RequestError::InvalidParameter {
+        name,
+        location,
+        errors,
+    }] = errors.as_slice()
+    else {
+        panic!("unexpected errors {:?}", errors);
+    };
+    assert_eq!((name.as_str(), location.as_str()), ("limit", "query"));
+    assert_eq!(
+        errors[0].schema_path,
+        "#/components/parameters/limit/schema/maximum"
+    );
+
+    let errors = openapi.validate_request(&Request::new("GET", "/pets/rex"));
+    assert_eq!(kinds(&errors), ["invalid petId"]);
+    let RequestError::InvalidParameter { errors, .. } = &errors[0] else {
+        unreachable!()
+    };
+    assert_eq!(
+        errors[0].schema_path,
+        "#/paths/~1pets~1{petId}/parameters/0/schema/type"
+    );
+}
+
+#[test]
+fn test_body() {
+    let openapi = openapi();
+    let create = |content_type, body: &'static [u8]| {
+        let request = Request::new("POST", "/pets")
+            .with_header("content-type", content_type)
+            .with_body(body);
+        openapi.validate_request(&request)
+    };
+    assert_eq!(
+        create("application/json; charset=utf-8", br#"{"name": "Rex"}"#),
+        []
+    );
+    assert_eq!(kinds(&create("application/json", b"{")), ["malformed-body"]);
+    assert_eq!(
+        kinds(&create("text/plain", b"Rex")),
+        ["unsupported-media-type"]
+    );
+    assert_eq!(
+        kinds(&openapi.validate_request(&Request::new("POST", "/pets"))),
+        ["missing-body"]
+    );
+
+    let errors = create("application/json", br#"{"id": 1, "name": ""}"#);
+    let [RequestError::InvalidBody { media_type, errors }] = errors.as_slice() else {
+        panic!("unexpected errors {:?}", errors);
+    };
+    assert_eq!(media_type, "application/json");
+    let paths: Vec<_> = errors
+        .iter()
+        .map(|error| (error.instance_path.as_str(), error.schema_path.as_str()))
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            ("/id", "#/components/schemas/Pet/properties/id/readOnly"),
+            (
+                "/name",
+                "#/components/schemas/Pet/properties/name/minLength"
+            ),
+        ]
+    );
+
+    let rename = |body: &'static [u8]| {
+        let request = Request::new("PUT", "/pets/1")
+            .with_header("Content-Type", "application/x-www-form-urlencoded")
+            .with_body(body);
+        openapi.validate_request(&request)
+    };
+    assert_eq!(rename(b"name=Rex+the+Dog&status=ok"), []);
+    assert_eq!(kinds(&rename(b"status=ok")), ["invalid-body"]);
+}
+
+#[test]
+fn test_extract() {
+    let openapi = openapi();
+    let request = Request::new("PUT", "/v1/pets/42")
+        .with_header("Content-Type", "application/x-www-form-urlencoded")
+        .with_body(b"name=Rex");
+    let extracted = openapi.extract_request(&request).unwrap();
+    assert_eq!(
+        (extracted.template, extracted.method),
+        ("/pets/{petId}", "put")
+    );
+    assert_eq!(extracted.parameter("path", "petId"), Some(&json!(42)));
+    assert_eq!(extracted.body, Some(json!({"name": "Rex"})));
+
+    let request =
+        Request::new("GET", "/pets?limit=10&tags=cat").with_header("Cookie", "session=abc");
+    let extracted = openapi.extract_request(&request).unwrap();
+    assert_eq!(extracted.parameter("query", "limit"), Some(&json!(10)));
+    assert_eq!(extracted.parameter("query", "tags"), Some(&json!(["cat"])));
+    assert_eq!(
+        extracted.parameter("cookie", "session"),
+        Some(&json!("abc"))
+    );
+    assert_eq!(extracted.body, None);
+
+    let request = Request::new("GET", "/pets?tags=a%2Cb&tags=c+d");
+    let extracted = openapi.extract_request(&request).unwrap();
+    assert_eq!(
+        extracted.parameter("query", "tags"),
+        Some(&json!(["a,b", "c d"]))
+    );
+
+    let errors = openapi
+        .extract_request(&Request::new("DELETE", "/pets/1"))
+        .unwrap_err();
+    assert_eq!(errors[0].status(), 405);
+    let errors = openapi
+        .extract_request(&Request::new("GET", "/pets?limit=500"))
+        .unwrap_err();
+    assert_eq!(errors[0].status(), 400);
+
+    // a parameter or a body which cannot be resolved is not valid
+    let openapi: v3::OpenApi = serde_json::from_value(json!({
+        "openapi": "3.0.3",
+        "info": {"title": "Pets", "version": "1.0.0"},
+        "paths": {"/pets": {
+            "parameters": [{"$ref": "#/components/parameters/Missing"}],
+            "post": {
+                "requestBody": {"$ref": "#/components/requestBodies/Missing"},
+                "responses": {"201": {"description": "Created."}}
+            }
+        }}
+    }))
+    .unwrap();
+    let errors = openapi
+        .extract_request(&Request::new("POST", "/pets").with_body(b"{}"))
+        .unwrap_err();
+    assert_eq!(
+        kinds(&errors),
+        [
+            "unresolved #/components/parameters/Missing",
+            "unresolved #/components/requestBodies/Missing"
+        ]
+    );
+    assert_eq!(errors[0].status(), 500);
+}
+
+#[test]
+fn test_response() {
+    let openapi = openapi();
+    let list = Request::new("GET", "/pets");
+    let response = Response::new(200)
+        .with_header("X-Rate-Limit", "10")
+        .with_header("Content-Type", "application/json")
+        .with_body(br#"[{"id": 1, "name": "Rex"}]"#);
+    assert_eq!(openapi.validate_response(&list, &response), []);
+
+    let response = Response::new(200)
+        .with_header("x-rate-limit", "-1")
+        .with_header("Content-Type", "application/json")
+        .with_body(br#"[{"name": "Rex", "secret": true}]"#);
+    let errors = openapi.validate_response(&list, &response);
+    let [ResponseError::InvalidHeader {
+        name,
+        errors: header,
+    }, ResponseError::InvalidBody { errors: body, .. }] = errors.as_slice()
+    else {
+        panic!("unexpected errors {:?}", errors);
+    };
+    assert_eq!(name, "x-rate-limit");
+    assert_eq!(
+        header[0].schema_path,
+        "#/paths/~1pets/get/responses/200/headers/x-rate-limit/schema/minimum"
+    );
+    assert_eq!(body[0].instance_path, "/0");
+
+    // Statuses without their own response are described by `default`.
+    let response = Response::new(500)
+        .with_header("Content-Type", "application/json")
+        .with_body(br#"{"code": 500}"#);
+    let errors = openapi.validate_response(&list, &response);
+    let [ResponseError::InvalidBody { errors, .. }] = errors.as_slice() else {
+        panic!("unexpected errors {:?}", errors);
+    };
+    assert_eq!(errors[0].schema_path, "#/components/schemas/Error/required");
+
+    assert_eq!(
+        openapi.validate_response(&list, &Response::new(500)),
+        [ResponseError::MissingBody]
+    );
+    assert_eq!(
+        openapi.validate_response(&Request::new("GET", "/pets/1"), &Response::new(404)),
+        [ResponseError::UndocumentedStatus { status: 404 }]
+    );
+    let rename = Request::new("PUT", "/pets/1");
+    assert_eq!(openapi.validate_response(&rename, &Response::new(204)), []);
+    assert_eq!(
+        openapi.validate_response(&rename, &Response::new(204).with_body(b"ok")),
+        [ResponseError::UnexpectedBody]
+    );
+
+    // a response which cannot be resolved is not valid
+    let openapi: v3::OpenApi = serde_json::from_value(json!({
+        "openapi": "3.0.3",
+        "info": {"title": "Pets", "version": "1.0.0"},
+        "paths": {"/pets": {"get": {
+            "responses": {"200": {"$ref": "#/components/responses/Missing"}}
+        }}}
+    }))
+    .unwrap();
+    assert_eq!(
+        openapi.validate_response(&list, &Response::new(200)),
+        [ResponseError::UnresolvedResponse {
+            reference: "#/components/responses/Missing".to_string()
+        }]
+    );
+}
+
+#[test]
+fn test_validator() {
+    // a validator checks many requests with the router it compiled once, as the document does
+    let openapi = openapi();
+    let validator = Validator::new(&openapi);
+    for request in [
+        Request::new("GET", "/v1/pets?limit=10"),
+        Request::new("GET", "/pets?limit=500"),
+        Request::new("DELETE", "/pets/1"),
+        Request::new("GET", "/owners"),
+    ] {
+        assert_eq!(
+            validator.validate_request(&request),
+            openapi.validate_request(&request)
+        );
+    }
+    let extracted = validator
+        .extract_request(&Request::new("GET", "/pets/42"))
+        .unwrap();
+    assert_eq!(extracted.parameter("path", "petId"), Some(&json!(42)));
+
+    let rename = Request::new("PUT", "/pets/1");
+    assert_eq!(
+        validator.validate_response(&rename, &Response::new(204)),
+        []
+    );
+    assert_eq!(
+        validator.validate_response(&Request::new("GET", "/owners"), &Response::new(200)),
+        openapi.validate_response(&Request::new("GET", "/owners"), &Response::new(200))
+    );
+}
+
+#[test]
+fn test_cookie_array() {
+    let openapi: v3::OpenApi = serde_json::from_value(json!({
+        "openapi": "3.0.3",
+        "info": {"title": "Cookies", "version": "1.0.0"},
+        "paths": {"/items": {"get": {
+            "parameters": [
+                {"name": "ids", "in": "cookie", "explode": false, "schema": {"type": "array", "items": {"type": "integer"}}},
+                {"name": "theme", "in": "cookie", "schema": {"type": "string"}}
+            ],
+            "responses": {"204": {"description": "Listed."}}
+        }}}
+    }))
+    .unwrap();
+    let request = Request::new("GET", "/items")
+        .with_header("Cookie", "ids=3,4,5")
+        .with_header("Cookie", "theme=dark");
+    let extracted = openapi.extract_request(&request).unwrap();
+    assert_eq!(
+        extracted.parameter("cookie", "ids"),
+        Some(&json!([3, 4, 5]))
+    );
+    assert_eq!(extracted.parameter("cookie", "theme"), Some(&json!("dark")));
+}
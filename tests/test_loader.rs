@@ -0,0 +1,114 @@
+#![cfg(feature = "yaml")]
+
+use openapi_schema::loader::Loader;
+use openapi_schema::{v3, Doc, Error};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+const CATEGORY: &str =
+    r#"{"Category": {"type": "object", "properties": {"name": {"type": "string"}}}}"#;
+
+fn loader() -> (Loader, Rc<RefCell<Vec<String>>>) {
+    let fetched = Rc::new(RefCell::new(Vec::new()));
+    let log = fetched.clone();
+    let loader = Loader::new().with_fetcher(move |url: &str| {
+        log.borrow_mut().push(url.to_string());
+        match url {
+            "https://example.com/schemas/category.json" => Ok(CATEGORY.to_string()),
+            _ => Err(Error::UnresolvedReference {
+                reference: url.to_string(),
+                message: "is not served".to_string(),
+            }),
+        }
+    });
+    (loader, fetched)
+}
+
+#[test]
+fn test_load() {
+    let (loader, fetched) = loader();
+    let set = loader.load("./tests/multi/openapi.yaml").unwrap();
+    assert_eq!(set.documents().count(), 7);
+    assert_eq!(
+        *fetched.borrow(),
+        vec!["https://example.com/schemas/category.json"]
+    );
+    assert!(set.root().ends_with("openapi.yaml"));
+    match set.doc().unwrap() {
+        Doc::V3(openapi) => assert_eq!(openapi.info.title, "Pet store"),
+        doc => panic!("expected an openapi 3.0 document, got {}", doc.version()),
+    }
+}
+
+#[test]
+fn test_resolve_across_documents() {
+    let (loader, _) = loader();
+    let set = loader.load("./tests/multi/openapi.yaml").unwrap();
+
+    let limit: v3::Parameter = set.resolve(set.root(), "./parameters.yaml#/limit").unwrap();
+    assert_eq!(limit.name, "limit");
+
+    let pet = set
+        .resolve_value(set.root(), "./schemas/pet.yaml#/Pet")
+        .unwrap();
+    assert!(pet.document.ends_with("pet.yaml"));
+    assert_eq!(pet.pointer, "/Pet");
+
+    // References inside a document are relative to it.
+    let owner = set
+        .resolve_value(pet.document, "./user.yaml#/User")
+        .unwrap();
+    assert!(owner.document.ends_with("user.yaml"));
+    let id: v3::Schema = set.resolve(pet.document, "#/Id").unwrap();
+    assert_eq!(id.format.as_deref(), Some("int64"));
+    let category = set
+        .resolve_value(
+            pet.document,
+            "https://example.com/schemas/category.json#/Category",
+        )
+        .unwrap();
+    assert_eq!(category.value["type"], "object");
+
+    // A chain of references is followed to the object.
+    let error: v3::Response = set.resolve(set.root(), "./responses.json#/Alias").unwrap();
+    assert_eq!(error.description, "An unexpected error.");
+}
+
+#[test]
+fn test_resolve_errors() {
+    let (loader, _) = loader();
+    let set = loader.load("./tests/multi/openapi.yaml").unwrap();
+    match set.resolve_value(set.root(), "./parameters.yaml#/offset") {
+        Err(Error::UnresolvedReference { reference, .. }) => {
+            assert_eq!(reference, "./parameters.yaml#/offset")
+        }
+        result => panic!("unexpected result {:?}", result),
+    }
+    match set.resolve::<v3::Parameter>(set.root(), "./schemas/pet.yaml#/Pet") {
+        Err(Error::Structure { path, pointer, .. }) => {
+            assert!(path.unwrap().ends_with("pet.yaml"));
+            assert_eq!(pointer, "/Pet/required");
+        }
+        result => panic!("unexpected result {:?}", result),
+    }
+}
+
+#[test]
+fn test_remote_without_fetcher() {
+    match Loader::new().load("./tests/multi/openapi.yaml") {
+        Err(Error::UnresolvedReference { reference, .. }) => {
+            assert_eq!(reference, "https://example.com/schemas/category.json")
+        }
+        result => panic!("unexpected result {:?}", result),
+    }
+}
+
+#[test]
+fn test_missing_file() {
+    match Loader::new().load("./tests/multi/missing.yaml") {
+        Err(Error::Io { path, .. }) => {
+            assert_eq!(path.unwrap().to_str(), Some("./tests/multi/missing.yaml"))
+        }
+        result => panic!("unexpected result {:?}", result),
+    }
+}
@@ -0,0 +1,57 @@
+use openapi_schema::router::RouteError;
+use openapi_schema::{v2, v3};
+use std::fs;
+
+#[test]
+fn test_openapi_router() {
+    let openapi: v3::OpenApi =
+        serde_json::from_str(&fs::read_to_string("./tests/petstore-v3.json").unwrap()).unwrap();
+    let router = openapi.router();
+    let found = router.route("get", "/v1/pets/42").unwrap();
+    assert_eq!((found.template, found.method), ("/pets/{petId}", "get"));
+    assert_eq!(found.operation.operation_id.as_deref(), Some("getPet"));
+    assert_eq!(found.parameters["petId"], "42");
+    assert_eq!(router.find("/pets").unwrap().template, "/pets");
+    assert_eq!(
+        router.route("DELETE", "/pets/42"),
+        Err(RouteError::MethodNotAllowed {
+            method: "DELETE".to_string(),
+            template: "/pets/{petId}".to_string(),
+            allowed: vec!["get", "put"],
+        })
+    );
+}
+
+#[test]
+fn test_swagger_router() {
+    let swagger: v2::Swagger =
+        serde_json::from_str(&fs::read_to_string("./tests/petstore-v2.json").unwrap()).unwrap();
+    let router = swagger.router();
+    let found = router.route("POST", "/v1/pets/7/photo").unwrap();
+    assert_eq!(found.template, "/pets/{petId}/photo");
+    assert_eq!(found.parameters["petId"], "7");
+    assert!(matches!(
+        router.route("GET", "/v1/owners"),
+        Err(RouteError::NotFound { .. })
+    ));
+}
+
+#[test]
+fn test_match_server() {
+    let openapi: v3::OpenApi =
+        serde_json::from_str(&fs::read_to_string("./tests/petstore-v3.json").unwrap()).unwrap();
+    let found = openapi
+        .match_server("https://us.petstore.example.com/v1/pets/42")
+        .unwrap();
+    assert_eq!(found.server.url, "https://{region}.petstore.example.com/v1");
+    assert_eq!(found.variables["region"], "us");
+    assert_eq!(found.path, "/pets/42");
+    let found = openapi
+        .match_server("http://eu.petstore.example.com/v1/pets")
+        .unwrap();
+    assert_eq!(found.server.url, "http://eu.petstore.example.com/v1");
+    assert!(openapi
+        .match_server("https://asia.petstore.example.com/v1/pets")
+        .is_none());
+    assert!(openapi.router().find("/v1/pets").is_some());
+}
@@ -0,0 +1,32 @@
+use openapi_schema::{from_path, v3_1, Doc};
+use serde_json::Value;
+use std::fs;
+
+#[test]
+fn test_round_trip() {
+    let content = fs::read_to_string("./tests/openapi31.json").unwrap();
+    let openapi: v3_1::OpenApi = serde_json::from_str(&content).unwrap();
+    assert_eq!(
+        serde_json::to_value(&openapi).unwrap(),
+        serde_json::from_str::<Value>(&content).unwrap()
+    );
+}
+
+#[test]
+fn test_dispatch() {
+    match from_path("./tests/openapi31.json").unwrap() {
+        Doc::V31(openapi) => {
+            assert!(openapi.webhooks.unwrap().contains_key("newPet"));
+            let components = openapi.components.unwrap();
+            assert!(components.path_items.unwrap().contains_key("Pet"));
+            match &components.schemas.unwrap()["Pet"] {
+                v3_1::Schema::Object(pet) => {
+                    assert!(pet.defs.as_ref().unwrap().contains_key("Owner"));
+                    assert_eq!(pet.r#else, Some(v3_1::Schema::Bool(true)));
+                }
+                schema => panic!("unexpected schema {:?}", schema),
+            }
+        }
+        doc => panic!("expected an openapi 3.1 document, got {}", doc.version()),
+    }
+}
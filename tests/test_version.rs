@@ -0,0 +1,84 @@
+use openapi_schema::{from_path, from_str, Doc, Error};
+
+#[test]
+fn test_dispatch_by_version() {
+    let doc = from_path("./tests/openapi.json").unwrap();
+    assert!(matches!(doc, Doc::V3(_)));
+    assert_eq!(doc.version(), "3.0.0");
+
+    let doc = from_path("./tests/swagger.json").unwrap();
+    assert!(matches!(doc, Doc::V2(_)));
+    assert_eq!(doc.version(), "2.0");
+}
+
+#[test]
+fn test_unsupported_version() {
+    let e = from_str(r#"{"openapi":"4.0.0","info":{"title":"t"},"paths":{}}"#).unwrap_err();
+    assert!(matches!(e, Error::UnsupportedVersion { ref version, .. } if version == "4.0.0"));
+
+    let e = from_str(r#"{"swagger":"1.2","info":{"title":"t"},"paths":{}}"#).unwrap_err();
+    assert!(matches!(e, Error::UnsupportedVersion { ref version, .. } if version == "1.2"));
+}
+
+#[test]
+fn test_missing_version() {
+    let e = from_str(r#"{"info":{"title":"t"},"paths":{}}"#).unwrap_err();
+    assert!(matches!(e, Error::Structure { .. }));
+}
+
+#[test]
+fn test_field_error_pointer() {
+    let json = r#"{
+        "openapi": "3.0.3",
+        "info": {"title": "pets"},
+        "paths": {
+            "/pets": {
+                "get": {
+                    "responses": {
+                        "200": {"description": 5}
+                    }
+                }
+            }
+        }
+    }"#;
+    match from_str(json).unwrap_err() {
+        Error::Structure {
+            pointer, message, ..
+        } => {
+            assert_eq!(pointer, "/paths/~1pets/get/responses/200/description");
+            assert!(
+                message.starts_with("invalid type: integer `5`"),
+                "{}",
+                message
+            );
+        }
+        e => panic!("expected a structural error, got {:?}", e),
+    }
+
+    let e = serde_json::from_str::<Doc>(json).unwrap_err();
+    assert!(e
+        .to_string()
+        .starts_with("/paths/~1pets/get/responses/200/description: invalid type"));
+}
+
+#[test]
+fn test_field_error_pointer_in_parameter() {
+    let json = r#"{
+        "swagger": "2.0",
+        "info": {"title": "pets"},
+        "paths": {
+            "/pets": {
+                "get": {
+                    "parameters": [{"name": "limit", "in": "cookie"}],
+                    "responses": {}
+                }
+            }
+        }
+    }"#;
+    match from_str(json).unwrap_err() {
+        Error::Structure { pointer, .. } => {
+            assert_eq!(pointer, "/paths/~1pets/get/parameters/0/in")
+        }
+        e => panic!("expected a structural error, got {:?}", e),
+    }
+}